@@ -253,6 +253,15 @@ pub struct VerifiedUnaggregatedAttestation<T: BeaconChainTypes> {
     indexed_attestation: IndexedAttestation<T::EthSpec>,
 }
 
+/// Wraps an `Attestation` that has passed all gossip checks other than signature verification.
+///
+/// Only exists so that the signatures of many attestations may be verified as a batch.
+struct IndexedUnaggregatedAttestation<T: BeaconChainTypes> {
+    attestation: Attestation<T::EthSpec>,
+    indexed_attestation: IndexedAttestation<T::EthSpec>,
+    validator_index: u64,
+}
+
 /// Custom `Clone` implementation is to avoid the restrictive trait bounds applied by the usual derive
 /// macro.
 impl<T: BeaconChainTypes> Clone for VerifiedUnaggregatedAttestation<T> {
@@ -414,13 +423,13 @@ impl<T: BeaconChainTypes> VerifiedAggregatedAttestation<T> {
     }
 }
 
-impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
-    /// Returns `Ok(Self)` if the `attestation` is valid to be (re)published on the gossip
-    /// network.
+impl<T: BeaconChainTypes> IndexedUnaggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `attestation` passes all gossip checks except for signature
+    /// verification.
     ///
     /// `subnet_id` is the subnet from which we received this attestation. This function will
     /// verify that it was received on the correct subnet.
-    pub fn verify(
+    fn verify(
         attestation: Attestation<T::EthSpec>,
         subnet_id: SubnetId,
         chain: &BeaconChain<T>,
@@ -482,8 +491,44 @@ impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
             });
         }
 
+        Ok(Self {
+            attestation,
+            indexed_attestation,
+            validator_index,
+        })
+    }
+}
+
+impl<T: BeaconChainTypes> VerifiedUnaggregatedAttestation<T> {
+    /// Returns `Ok(Self)` if the `attestation` is valid to be (re)published on the gossip
+    /// network.
+    ///
+    /// `subnet_id` is the subnet from which we received this attestation. This function will
+    /// verify that it was received on the correct subnet.
+    pub fn verify(
+        attestation: Attestation<T::EthSpec>,
+        subnet_id: SubnetId,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let indexed = IndexedUnaggregatedAttestation::verify(attestation, subnet_id, chain)?;
+
         // The aggregate signature of the attestation is valid.
-        verify_attestation_signature(chain, &indexed_attestation)?;
+        verify_attestation_signature(chain, &indexed.indexed_attestation)?;
+
+        Self::from_signature_verified(indexed, chain)
+    }
+
+    /// Completes the verification of an attestation with a valid signature by observing its
+    /// attester.
+    fn from_signature_verified(
+        indexed: IndexedUnaggregatedAttestation<T>,
+        chain: &BeaconChain<T>,
+    ) -> Result<Self, Error> {
+        let IndexedUnaggregatedAttestation {
+            attestation,
+            indexed_attestation,
+            validator_index,
+        } = indexed;
 
         // Now that the attestation has been fully verified, store that we have received a valid
         // attestation from this validator.
@@ -642,6 +687,98 @@ pub fn verify_attestation_signature<T: BeaconChainTypes>(
     }
 }
 
+/// Verifies each of the `attestations` as per `VerifiedUnaggregatedAttestation::verify`, returning
+/// a result for each in the same order.
+///
+/// The signatures of all attestations which pass the other gossip checks are verified as a single
+/// BLS batch. Only if the batch is invalid is each signature verified individually, to determine
+/// which attestations are at fault.
+///
+/// Returns `Err` only if there was an error which affects the entire batch.
+pub fn batch_verify_unaggregated_attestations<T: BeaconChainTypes>(
+    attestations: Vec<(Attestation<T::EthSpec>, SubnetId)>,
+    chain: &BeaconChain<T>,
+) -> Result<Vec<Result<VerifiedUnaggregatedAttestation<T>, Error>>, Error> {
+    let indexed_results = attestations
+        .into_iter()
+        .map(|(attestation, subnet_id)| {
+            IndexedUnaggregatedAttestation::verify(attestation, subnet_id, chain)
+        })
+        .collect::<Vec<_>>();
+
+    // `None` for each attestation which failed before signature verification.
+    let signature_results = {
+        let signature_setup_timer =
+            metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SIGNATURE_SETUP_TIMES);
+
+        let pubkey_cache = chain
+            .validator_pubkey_cache
+            .try_read_for(VALIDATOR_PUBKEY_CACHE_LOCK_TIMEOUT)
+            .ok_or_else(|| BeaconChainError::ValidatorPubkeyCacheLockTimeout)?;
+
+        let fork = chain
+            .canonical_head
+            .try_read_for(HEAD_LOCK_TIMEOUT)
+            .ok_or_else(|| BeaconChainError::CanonicalHeadLockTimeout)
+            .map(|head| head.beacon_state.fork)?;
+
+        let signature_sets = indexed_results
+            .iter()
+            .map(|result| {
+                result.as_ref().ok().map(|indexed| {
+                    indexed_attestation_signature_set_from_pubkeys(
+                        |validator_index| pubkey_cache.get(validator_index).map(Cow::Borrowed),
+                        &indexed.indexed_attestation.signature,
+                        &indexed.indexed_attestation,
+                        &fork,
+                        chain.genesis_validators_root,
+                        &chain.spec,
+                    )
+                })
+            })
+            .collect::<Vec<_>>();
+
+        metrics::stop_timer(signature_setup_timer);
+
+        let _signature_verification_timer =
+            metrics::start_timer(&metrics::ATTESTATION_PROCESSING_SIGNATURE_TIMES);
+
+        let batch = signature_sets
+            .iter()
+            .filter_map(|set| set.as_ref()?.as_ref().ok())
+            .collect::<Vec<_>>();
+        let batch_is_valid = verify_signature_sets(batch.into_iter());
+
+        signature_sets
+            .into_iter()
+            .map(|set| {
+                set.map(|set| match set {
+                    Ok(set) => {
+                        if batch_is_valid || set.verify() {
+                            Ok(())
+                        } else {
+                            Err(Error::InvalidSignature)
+                        }
+                    }
+                    Err(e) => Err(BeaconChainError::SignatureSetError(e).into()),
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    Ok(indexed_results
+        .into_iter()
+        .zip(signature_results)
+        .map(|(indexed_result, signature_result)| {
+            let indexed = indexed_result?;
+            if let Some(signature_result) = signature_result {
+                signature_result?;
+            }
+            VerifiedUnaggregatedAttestation::from_signature_verified(indexed, chain)
+        })
+        .collect())
+}
+
 /// Verifies all the signatures in a `SignedAggregateAndProof` using BLS batch verification. This
 /// includes three signatures:
 ///
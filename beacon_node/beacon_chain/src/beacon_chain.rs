@@ -1,6 +1,6 @@
 use crate::attestation_verification::{
-    Error as AttestationError, SignatureVerifiedAttestation, VerifiedAggregatedAttestation,
-    VerifiedUnaggregatedAttestation,
+    batch_verify_unaggregated_attestations, Error as AttestationError,
+    SignatureVerifiedAttestation, VerifiedAggregatedAttestation, VerifiedUnaggregatedAttestation,
 };
 use crate::block_verification::{
     check_block_is_finalized_descendant, check_block_relevancy, get_block_root,
@@ -143,6 +143,7 @@ pub struct HeadInfo {
     pub block_root: Hash256,
     pub state_root: Hash256,
     pub current_justified_checkpoint: types::Checkpoint,
+    pub previous_justified_checkpoint: types::Checkpoint,
     pub finalized_checkpoint: types::Checkpoint,
     pub fork: Fork,
     pub genesis_time: u64,
@@ -459,6 +460,49 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Returns `true` if the block with the given `block_root` and `slot` is in the canonical chain
+    /// and no later than the finalized checkpoint.
+    ///
+    /// The canonical root at `slot` is first sought by iterating backwards from the head. If that
+    /// is unable to map `slot` (e.g., during a pruning race), the forwards iterator (which may read
+    /// from the freezer) is consulted instead. A block is only deemed non-finalized if it is after
+    /// the finalized checkpoint or a different root is found at its slot.
+    ///
+    /// ## Errors
+    ///
+    /// May return a database error.
+    pub fn is_finalized_block(&self, block_root: &Hash256, slot: Slot) -> Result<bool, Error> {
+        let finalized_checkpoint = self.head_info()?.finalized_checkpoint;
+
+        if *block_root == finalized_checkpoint.root {
+            return Ok(true);
+        }
+
+        let finalized_slot = finalized_checkpoint
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+        if slot > finalized_slot {
+            return Ok(false);
+        }
+
+        let root_at_slot = process_results(self.rev_iter_block_roots()?, |iter| {
+            iter.take_while(|(_, this_slot)| *this_slot >= slot)
+                .find(|(_, this_slot)| *this_slot == slot)
+                .map(|(root, _)| root)
+        })?;
+
+        let root_at_slot = match root_at_slot {
+            Some(root) => Some(root),
+            None => process_results(self.forwards_iter_block_roots(slot)?, |iter| {
+                iter.take_while(|(_, this_slot)| *this_slot <= slot)
+                    .find(|(_, this_slot)| *this_slot == slot)
+                    .map(|(root, _)| root)
+            })?,
+        };
+
+        Ok(root_at_slot.map_or(false, |root| root == *block_root))
+    }
+
     /// Returns the block at the given root, if any.
     ///
     /// ## Errors
@@ -516,6 +560,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                 block_root: head.beacon_block_root,
                 state_root: head.beacon_state_root,
                 current_justified_checkpoint: head.beacon_state.current_justified_checkpoint,
+                previous_justified_checkpoint: head.beacon_state.previous_justified_checkpoint,
                 finalized_checkpoint: head.beacon_state.finalized_checkpoint,
                 fork: head.beacon_state.fork,
                 genesis_time: head.beacon_state.genesis_time,
@@ -544,7 +589,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         slot: Slot,
         config: StateSkipConfig,
     ) -> Result<BeaconState<T::EthSpec>, Error> {
-        let head_state = self.head()?.beacon_state;
+        let head = self.head()?;
+        let head_state_root = head.beacon_state_root;
+        let head_state = head.beacon_state;
 
         match slot.cmp(&head_state.slot) {
             Ordering::Equal => Ok(head_state),
@@ -583,9 +630,15 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
                         });
                     }
 
-                    // Note: supplying some `state_root` when it is known would be a cheap and easy
-                    // optimization.
-                    match per_slot_processing(&mut state, skip_state_root, &self.spec) {
+                    // The root of the head state is always known. Supplying it ensures that the
+                    // block roots of the skipped slots are correct, even without state roots.
+                    let state_root = if state.slot == head_state_slot {
+                        Some(head_state_root)
+                    } else {
+                        skip_state_root
+                    };
+
+                    match per_slot_processing(&mut state, state_root, &self.spec) {
                         Ok(_) => (),
                         Err(e) => {
                             warn!(
@@ -696,9 +749,20 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let epoch = |slot: Slot| slot.epoch(T::EthSpec::slots_per_epoch());
         let head_state = &self.head()?.beacon_state;
 
+        if let Ok(current_epoch) = self.epoch() {
+            metrics::set_gauge(
+                &metrics::BLOCK_PROPOSER_HEAD_EPOCH_DISTANCE,
+                current_epoch
+                    .as_u64()
+                    .saturating_sub(epoch(head_state.slot).as_u64()) as i64,
+            );
+        }
+
         let mut state = if epoch(slot) == epoch(head_state.slot) {
+            metrics::inc_counter(&metrics::BLOCK_PROPOSER_HEAD_STATE_HITS);
             self.head()?.beacon_state
         } else {
+            metrics::inc_counter(&metrics::BLOCK_PROPOSER_HEAD_STATE_MISSES);
             // The block proposer shuffling is not affected by the state roots, so we don't need to
             // calculate them.
             self.state_at_slot(slot, StateSkipConfig::WithoutStateRoots)?
@@ -910,6 +974,32 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         })
     }
 
+    /// As per `verify_unaggregated_attestation_for_gossip`, but verifies the signatures of all
+    /// `attestations` as a batch, returning a result for each attestation in the same order.
+    ///
+    /// Returns `Err` only if there was an error which prevented the verification of the entire
+    /// batch.
+    pub fn batch_verify_unaggregated_attestations_for_gossip(
+        &self,
+        attestations: Vec<(Attestation<T::EthSpec>, SubnetId)>,
+    ) -> Result<Vec<Result<VerifiedUnaggregatedAttestation<T>, AttestationError>>, AttestationError>
+    {
+        metrics::inc_counter_by(
+            &metrics::UNAGGREGATED_ATTESTATION_PROCESSING_REQUESTS,
+            attestations.len() as i64,
+        );
+        let _timer =
+            metrics::start_timer(&metrics::UNAGGREGATED_ATTESTATION_GOSSIP_VERIFICATION_TIMES);
+
+        let results = batch_verify_unaggregated_attestations(attestations, self)?;
+        metrics::inc_counter_by(
+            &metrics::UNAGGREGATED_ATTESTATION_PROCESSING_SUCCESSES,
+            results.iter().filter(|result| result.is_ok()).count() as i64,
+        );
+
+        Ok(results)
+    }
+
     /// Accepts some `SignedAggregateAndProof` from the network and attempts to verify it,
     /// returning `Ok(_)` if it is valid to be (re)broadcast on the gossip network.
     pub fn verify_aggregated_attestation_for_gossip(
@@ -1610,6 +1700,49 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         self.produce_block_on_state(state, slot, randao_reveal, validator_graffiti)
     }
 
+    /// Produce a block for some `slot` upon the block with the given `parent_root`, instead of
+    /// upon the head.
+    ///
+    /// This is useful for testing fork choice or for building away from a bad head. The parent
+    /// must be known, prior to `slot` and must not conflict with finalization.
+    pub fn produce_block_with_parent(
+        &self,
+        randao_reveal: Signature,
+        slot: Slot,
+        validator_graffiti: Option<Graffiti>,
+        parent_root: Hash256,
+    ) -> Result<BeaconBlockAndState<T::EthSpec>, BlockProductionError> {
+        let parent = self
+            .get_block(&parent_root)?
+            .ok_or_else(|| BlockProductionError::UnknownParentBlock(parent_root))?;
+
+        if parent.slot() >= slot {
+            return Err(BlockProductionError::UnableToProduceAtSlot(slot));
+        }
+
+        let finalized_checkpoint = self.head_info()?.finalized_checkpoint;
+        let finalized_slot = finalized_checkpoint
+            .epoch
+            .start_slot(T::EthSpec::slots_per_epoch());
+
+        if finalized_checkpoint.epoch > 0
+            && parent_root != finalized_checkpoint.root
+            && parent.slot() <= finalized_slot
+        {
+            return Err(BlockProductionError::ParentIsPreFinalization {
+                parent_root,
+                parent_slot: parent.slot(),
+                finalized_slot,
+            });
+        }
+
+        let state = self
+            .get_state(&parent.state_root(), Some(parent.slot()))?
+            .ok_or_else(|| Error::MissingBeaconState(parent.state_root()))?;
+
+        self.produce_block_on_state(state, slot, randao_reveal, validator_graffiti)
+    }
+
     /// Produce a block for some `slot` upon the given `state`.
     ///
     /// Typically the `self.produce_block()` function should be used, instead of calling this
@@ -1886,6 +2019,9 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             self.persist_head_and_fork_choice()?;
         }
 
+        let new_head_slot = new_head.beacon_block.slot();
+        let new_head_state_root = new_head.beacon_state_root;
+
         let update_head_timer = metrics::start_timer(&metrics::UPDATE_HEAD_TIMES);
 
         // Update the snapshot that stores the head of the chain at the time it received the
@@ -1923,6 +2059,10 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
             reorg: is_reorg,
             previous_head_beacon_block_root: current_head.block_root,
             current_head_beacon_block_root: beacon_block_root,
+            current_head_slot: new_head_slot,
+            current_head_state_root: new_head_state_root,
+            epoch_transition: current_head.slot.epoch(T::EthSpec::slots_per_epoch())
+                != new_head_slot.epoch(T::EthSpec::slots_per_epoch()),
         });
 
         Ok(())
@@ -1938,6 +2078,134 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         }
     }
 
+    /// Returns the root of the block which determines the attester shuffling for `epoch`.
+    ///
+    /// This is the block at the last slot of `epoch - 2`, so any two states which share this block
+    /// also share the shuffling for `epoch`. Unless it is the genesis state, `state` must be from a
+    /// later slot.
+    pub fn attester_shuffling_decision_root(
+        &self,
+        state: &BeaconState<T::EthSpec>,
+        epoch: Epoch,
+    ) -> Result<Hash256, Error> {
+        let decision_slot = epoch
+            .saturating_sub(1_u64)
+            .start_slot(T::EthSpec::slots_per_epoch())
+            .saturating_sub(1_u64);
+
+        if decision_slot == self.spec.genesis_slot {
+            Ok(self.genesis_block_root)
+        } else {
+            Ok(*state.get_block_root(decision_slot)?)
+        }
+    }
+
+    /// Builds the committee cache of `state` for `relative_epoch`, reading it from the shuffling
+    /// cache if it is there (e.g., because it was computed by `Self::warm_committee_cache`) and
+    /// adding it to the shuffling cache otherwise.
+    ///
+    /// Returns `true` if the committee cache was read from the shuffling cache.
+    pub fn build_committee_cache(
+        &self,
+        state: &mut BeaconState<T::EthSpec>,
+        relative_epoch: RelativeEpoch,
+    ) -> Result<bool, Error> {
+        let epoch = relative_epoch.into_epoch(state.current_epoch());
+        let decision_root = self.attester_shuffling_decision_root(state, epoch)?;
+
+        let cached = self
+            .shuffling_cache
+            .try_write_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+            .ok_or_else(|| Error::AttestationCacheLockTimeout)?
+            .get(epoch, decision_root)
+            .cloned();
+
+        if let Some(committee_cache) = cached {
+            state.set_committee_cache(relative_epoch, committee_cache)?;
+            return Ok(true);
+        }
+
+        if state.committee_cache(relative_epoch).is_err() {
+            metrics::inc_counter(&metrics::COMMITTEE_CACHE_WARM_MISSES);
+            state.build_committee_cache(relative_epoch, &self.spec)?;
+        }
+
+        self.shuffling_cache
+            .try_write_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+            .ok_or_else(|| Error::AttestationCacheLockTimeout)?
+            .insert(epoch, decision_root, state.committee_cache(relative_epoch)?);
+
+        Ok(false)
+    }
+
+    /// Once the wall-clock slot is within `self.config.committee_cache_warm_slots` of the next
+    /// epoch boundary, computes the shufflings for the current and next epochs of the canonical
+    /// head and adds them to the shuffling cache. This avoids the committee and duties requests
+    /// made at the start of each epoch all waiting on the shuffling to be computed.
+    ///
+    /// Shufflings which are already in the cache are not recomputed. Since the cache is keyed by
+    /// decision root, calling this each slot will compute the new shuffling if a re-org changes
+    /// the decision root before the boundary.
+    pub fn warm_committee_cache(&self) -> Result<(), Error> {
+        let slots_per_epoch = T::EthSpec::slots_per_epoch();
+        let current_slot = self.slot()?;
+        let current_epoch = current_slot.epoch(slots_per_epoch);
+        let next_epoch_start_slot = (current_epoch + 1).start_slot(slots_per_epoch);
+
+        if current_slot + self.config.committee_cache_warm_slots < next_epoch_start_slot {
+            return Ok(());
+        }
+
+        // Clone the head state (or skip it to the current epoch) so that the head lock is not held
+        // whilst the shufflings are computed.
+        let head_epoch = self.head_info()?.slot.epoch(slots_per_epoch);
+        let mut state = if head_epoch == current_epoch {
+            self.head()?.beacon_state
+        } else if head_epoch + 1 == current_epoch {
+            self.state_at_slot(
+                current_epoch.start_slot(slots_per_epoch),
+                StateSkipConfig::WithoutStateRoots,
+            )?
+        } else {
+            // The head is too far behind the wall-clock (e.g., whilst syncing) for its shufflings
+            // to be useful.
+            return Ok(());
+        };
+
+        for &relative_epoch in &[RelativeEpoch::Current, RelativeEpoch::Next] {
+            let epoch = relative_epoch.into_epoch(state.current_epoch());
+            let decision_root = self.attester_shuffling_decision_root(&state, epoch)?;
+
+            let is_cached = self
+                .shuffling_cache
+                .try_read_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+                .ok_or_else(|| Error::AttestationCacheLockTimeout)?
+                .contains(epoch, decision_root);
+
+            if is_cached {
+                continue;
+            }
+
+            state.build_committee_cache(relative_epoch, &self.spec)?;
+
+            self.shuffling_cache
+                .try_write_for(ATTESTATION_CACHE_LOCK_TIMEOUT)
+                .ok_or_else(|| Error::AttestationCacheLockTimeout)?
+                .insert(epoch, decision_root, state.committee_cache(relative_epoch)?);
+
+            metrics::inc_counter(&metrics::COMMITTEE_CACHE_WARMED);
+
+            debug!(
+                self.log,
+                "Warmed committee cache";
+                "epoch" => epoch,
+                "decision_root" => format!("{:?}", decision_root),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Called after `self` has had a new block finalized.
     ///
     /// Performs pruning and finality-based optimizations.
@@ -1987,6 +2255,7 @@ impl<T: BeaconChainTypes> BeaconChain<T> {
         let _ = self.event_handler.register(EventKind::BeaconFinalization {
             epoch: new_finalized_checkpoint.epoch,
             root: new_finalized_checkpoint.root,
+            state_root: new_finalized_state_root,
         });
 
         Ok(())
@@ -2,6 +2,8 @@ use serde_derive::{Deserialize, Serialize};
 
 /// There is a 693 block skip in the current canonical Medalla chain, we use 700 to be safe.
 pub const DEFAULT_IMPORT_BLOCK_MAX_SKIP_SLOTS: u64 = 700;
+/// Warm the committee cache two slots before each epoch boundary.
+pub const DEFAULT_COMMITTEE_CACHE_WARM_SLOTS: u64 = 2;
 
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct ChainConfig {
@@ -10,12 +12,18 @@ pub struct ChainConfig {
     ///
     /// If `None`, there is no limit.
     pub import_max_skip_slots: Option<u64>,
+    /// Number of slots before each epoch boundary at which the shuffling for the next epoch is
+    /// computed and added to the shuffling cache.
+    ///
+    /// If `0`, the shuffling is not computed ahead of time.
+    pub committee_cache_warm_slots: u64,
 }
 
 impl Default for ChainConfig {
     fn default() -> Self {
         Self {
             import_max_skip_slots: Some(DEFAULT_IMPORT_BLOCK_MAX_SKIP_SLOTS),
+            committee_cache_warm_slots: DEFAULT_COMMITTEE_CACHE_WARM_SLOTS,
         }
     }
 }
@@ -113,9 +113,19 @@ pub enum BlockProductionError {
     /// The `BeaconChain` was explicitly configured _without_ a connection to eth1, therefore it
     /// cannot produce blocks.
     NoEth1ChainConnection,
+    /// The requested parent block is not known to the `BeaconChain`.
+    UnknownParentBlock(Hash256),
+    /// The requested parent block conflicts with finalization.
+    ParentIsPreFinalization {
+        parent_root: Hash256,
+        parent_slot: Slot,
+        finalized_slot: Slot,
+    },
+    BeaconChainError(BeaconChainError),
 }
 
 easy_from_to!(BlockProcessingError, BlockProductionError);
 easy_from_to!(BeaconStateError, BlockProductionError);
 easy_from_to!(SlotProcessingError, BlockProductionError);
 easy_from_to!(Eth1ChainError, BlockProductionError);
+easy_from_to!(BeaconChainError, BlockProductionError);
@@ -3,10 +3,16 @@ use parking_lot::Mutex;
 use serde_derive::{Deserialize, Serialize};
 use slog::{error, Logger};
 use std::marker::PhantomData;
+use std::str::FromStr;
 use std::sync::Arc;
-use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHash};
+use tokio::sync::broadcast;
+use types::{Attestation, Epoch, EthSpec, Hash256, SignedBeaconBlock, SignedBeaconBlockHash, Slot};
 pub use websocket_server::WebSocketSender;
 
+/// The number of standard events which may be queued for each subscriber. A subscriber which falls
+/// further behind than this misses events, rather than holding up the beacon chain.
+pub const STANDARD_EVENTS_CAPACITY: usize = 16;
+
 pub trait EventHandler<T: EthSpec>: Sized + Send + Sync {
     fn register(&self, kind: EventKind<T>) -> Result<(), String>;
 }
@@ -26,21 +32,38 @@ pub struct ServerSentEvents<T: EthSpec> {
     // Bus<> is itself Sync + Send.  We use Mutex<> here only because of the surrounding code does
     // not enforce mutability statically (i.e. relies on interior mutability).
     head_changed_queue: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    /// Publishes events in the format of the standard Eth2 API. Sending never blocks.
+    standard_events: broadcast::Sender<StandardEvent>,
     log: Logger,
     _phantom: PhantomData<T>,
 }
 
 impl<T: EthSpec> ServerSentEvents<T> {
-    pub fn new(log: Logger) -> (Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>) {
+    #[allow(clippy::type_complexity)]
+    pub fn new(
+        log: Logger,
+    ) -> (
+        Self,
+        Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+        broadcast::Sender<StandardEvent>,
+    ) {
         let bus = Bus::new(T::slots_per_epoch() as usize);
         let mutex = Mutex::new(bus);
         let arc = Arc::new(mutex);
+        let (standard_events, _) = broadcast::channel(STANDARD_EVENTS_CAPACITY);
         let this = Self {
             head_changed_queue: arc.clone(),
+            standard_events: standard_events.clone(),
             log,
             _phantom: PhantomData,
         };
-        (this, arc)
+        (this, arc, standard_events)
+    }
+
+    /// Publishes `event` to all current subscribers of the standard events.
+    fn publish_standard_event(&self, event: StandardEvent) {
+        // An error only indicates that there are no subscribers.
+        let _ = self.standard_events.send(event);
     }
 }
 
@@ -49,6 +72,9 @@ impl<T: EthSpec> EventHandler<T> for ServerSentEvents<T> {
         match kind {
             EventKind::BeaconHeadChanged {
                 current_head_beacon_block_root,
+                current_head_slot,
+                current_head_state_root,
+                epoch_transition,
                 ..
             } => {
                 let mut guard = self.head_changed_queue.lock();
@@ -62,6 +88,35 @@ impl<T: EthSpec> EventHandler<T> for ServerSentEvents<T> {
                         "dropped_change" => format!("{}", current_head_beacon_block_root),
                     );
                 }
+                drop(guard);
+
+                self.publish_standard_event(StandardEvent::Head(SseHead {
+                    slot: current_head_slot,
+                    block: current_head_beacon_block_root,
+                    state: current_head_state_root,
+                    epoch_transition,
+                }));
+                Ok(())
+            }
+            EventKind::BeaconBlockImported { block_root, block } => {
+                self.publish_standard_event(StandardEvent::Block(SseBlock {
+                    slot: block.slot(),
+                    block: block_root,
+                }));
+                Ok(())
+            }
+            EventKind::BeaconFinalization {
+                epoch,
+                root,
+                state_root,
+            } => {
+                self.publish_standard_event(StandardEvent::FinalizedCheckpoint(
+                    SseFinalizedCheckpoint {
+                        block: root,
+                        state: state_root,
+                        epoch,
+                    },
+                ));
                 Ok(())
             }
             _ => Ok(()),
@@ -82,13 +137,20 @@ impl<E: EthSpec> TeeEventHandler<E> {
     pub fn new(
         log: Logger,
         websockets_handler: WebSocketSender<E>,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
-        let (sse_handler, bus) = ServerSentEvents::new(log);
+    ) -> Result<
+        (
+            Self,
+            Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+            broadcast::Sender<StandardEvent>,
+        ),
+        String,
+    > {
+        let (sse_handler, bus, standard_events) = ServerSentEvents::new(log);
         let result = Self {
             websockets_handler,
             sse_handler,
         };
-        Ok((result, bus))
+        Ok((result, bus, standard_events))
     }
 }
 
@@ -124,10 +186,15 @@ pub enum EventKind<T: EthSpec> {
         reorg: bool,
         current_head_beacon_block_root: Hash256,
         previous_head_beacon_block_root: Hash256,
+        current_head_slot: Slot,
+        current_head_state_root: Hash256,
+        /// True if the new head is in a later epoch than the previous head.
+        epoch_transition: bool,
     },
     BeaconFinalization {
         epoch: Epoch,
         root: Hash256,
+        state_root: Hash256,
     },
     BeaconBlockImported {
         block_root: Hash256,
@@ -145,3 +212,80 @@ pub enum EventKind<T: EthSpec> {
         attestation: Box<Attestation<T>>,
     },
 }
+
+/// A topic of the standard Eth2 API event stream.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventTopic {
+    Head,
+    Block,
+    FinalizedCheckpoint,
+}
+
+impl EventTopic {
+    /// The name of the topic, as used in the `topics` query parameter and the `event` field of
+    /// each server-sent event.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventTopic::Head => "head",
+            EventTopic::Block => "block",
+            EventTopic::FinalizedCheckpoint => "finalized_checkpoint",
+        }
+    }
+}
+
+impl FromStr for EventTopic {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(EventTopic::Head),
+            "block" => Ok(EventTopic::Block),
+            "finalized_checkpoint" => Ok(EventTopic::FinalizedCheckpoint),
+            _ => Err(format!("Unknown event topic: {}", s)),
+        }
+    }
+}
+
+/// An event in the format of the standard Eth2 API event stream.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StandardEvent {
+    Head(SseHead),
+    Block(SseBlock),
+    FinalizedCheckpoint(SseFinalizedCheckpoint),
+}
+
+impl StandardEvent {
+    pub fn topic(&self) -> EventTopic {
+        match self {
+            StandardEvent::Head(_) => EventTopic::Head,
+            StandardEvent::Block(_) => EventTopic::Block,
+            StandardEvent::FinalizedCheckpoint(_) => EventTopic::FinalizedCheckpoint,
+        }
+    }
+}
+
+/// The data of a `head` event, published when the head of the chain changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseHead {
+    pub slot: Slot,
+    pub block: Hash256,
+    pub state: Hash256,
+    pub epoch_transition: bool,
+}
+
+/// The data of a `block` event, published when a block is imported.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseBlock {
+    pub slot: Slot,
+    pub block: Hash256,
+}
+
+/// The data of a `finalized_checkpoint` event, published when the finalized checkpoint changes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SseFinalizedCheckpoint {
+    pub block: Hash256,
+    pub state: Hash256,
+    pub epoch: Epoch,
+}
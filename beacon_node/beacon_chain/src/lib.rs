@@ -44,7 +44,9 @@ pub use beacon_fork_choice_store::{BeaconForkChoiceStore, Error as ForkChoiceSto
 pub use block_verification::{BlockError, GossipVerifiedBlock};
 pub use eth1_chain::{Eth1Chain, Eth1ChainBackend};
 pub use events::EventHandler;
+pub use fork_choice::InvalidAttestation as InvalidForkChoiceAttestation;
 pub use metrics::scrape_for_metrics;
+pub use naive_aggregation_pool::Error as NaiveAggregationError;
 pub use parking_lot;
 pub use slot_clock;
 pub use state_processing::per_block_processing::errors::{
@@ -179,6 +179,30 @@ lazy_static! {
         try_create_int_counter("beacon_shuffling_cache_hits_total", "Count of times shuffling cache fulfils request");
     pub static ref SHUFFLING_CACHE_MISSES: Result<IntCounter> =
         try_create_int_counter("beacon_shuffling_cache_misses_total", "Count of times shuffling cache fulfils request");
+    pub static ref COMMITTEE_CACHE_WARMED: Result<IntCounter> = try_create_int_counter(
+        "beacon_committee_cache_warmed_total",
+        "Count of shufflings computed ahead of time by the committee cache warming task"
+    );
+    pub static ref COMMITTEE_CACHE_WARM_MISSES: Result<IntCounter> = try_create_int_counter(
+        "beacon_committee_cache_warm_misses_total",
+        "Count of committee cache requests which had to compute a shuffling that was not warmed"
+    );
+
+    /*
+     * Block proposer lookups
+     */
+    pub static ref BLOCK_PROPOSER_HEAD_STATE_HITS: Result<IntCounter> = try_create_int_counter(
+        "beacon_block_proposer_head_state_hits_total",
+        "Count of block proposer lookups served from the head state"
+    );
+    pub static ref BLOCK_PROPOSER_HEAD_STATE_MISSES: Result<IntCounter> = try_create_int_counter(
+        "beacon_block_proposer_head_state_misses_total",
+        "Count of block proposer lookups which required loading and skipping a state"
+    );
+    pub static ref BLOCK_PROPOSER_HEAD_EPOCH_DISTANCE: Result<IntGauge> = try_create_int_gauge(
+        "beacon_block_proposer_head_epoch_distance",
+        "Number of epochs the head state was behind the wall-clock epoch at the last proposer lookup"
+    );
 
     /*
      * Attestation Production
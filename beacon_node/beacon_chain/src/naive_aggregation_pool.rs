@@ -233,6 +233,11 @@ impl<E: EthSpec> NaiveAggregationPool<E> {
         self.maps.iter().map(|(_slot, map)| map.iter()).flatten()
     }
 
+    /// Returns the total number of attestations stored in `self`.
+    pub fn num_attestations(&self) -> usize {
+        self.maps.values().map(AggregatedAttestationMap::len).sum()
+    }
+
     /// Removes any attestations with a slot lower than `current_slot` and bars any future
     /// attestations with a slot lower than `current_slot - SLOTS_RETAINED`.
     pub fn prune(&mut self, current_slot: Slot) {
@@ -36,6 +36,13 @@ impl ShufflingCache {
         opt
     }
 
+    /// Returns `true` if the cache contains a committee cache for `epoch` and `root`.
+    ///
+    /// Unlike `Self::get`, this does not update the hit/miss metrics.
+    pub fn contains(&self, epoch: Epoch, root: Hash256) -> bool {
+        self.cache.contains(&(epoch, root))
+    }
+
     pub fn insert(&mut self, epoch: Epoch, root: Hash256, committee_cache: &CommitteeCache) {
         let key = (epoch, root);
 
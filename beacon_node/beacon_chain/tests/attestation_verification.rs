@@ -834,3 +834,123 @@ fn attestation_that_skips_epochs() {
         .verify_unaggregated_attestation_for_gossip(attestation, subnet_id)
         .expect("should gossip verify attestation that skips slots");
 }
+
+/// Ensures that an attestation which is still valid for gossip, but is for a slot that has been
+/// pruned from the naive aggregation pool, does not cause an error when added to the pool.
+#[test]
+fn unaggregated_attestation_past_aggregation_window() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        MainnetEthSpec::slots_per_epoch() as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+
+    let (attestation, _, _, _, subnet_id) = get_valid_unaggregated_attestation(&harness.chain);
+
+    // Advance just past the slots retained by the naive aggregation pool, whilst remaining well
+    // within the gossip propagation range.
+    for _ in 0..4 {
+        harness.advance_slot();
+    }
+    harness.chain.per_slot_task();
+
+    let verified_attestation = harness
+        .chain
+        .verify_unaggregated_attestation_for_gossip(attestation, subnet_id)
+        .expect("late attestation should still be valid for gossip");
+
+    harness
+        .chain
+        .add_to_naive_aggregation_pool(verified_attestation)
+        .expect("late attestation should not error when added to the naive aggregation pool");
+}
+
+/// Verifies a batch of attestations with one invalid signature both individually and as a batch,
+/// ensuring the outcomes are identical and reporting the time taken by each.
+#[test]
+fn batch_unaggregated_gossip_verification() {
+    let num_attestations = 128;
+    let corrupted_index = 64;
+
+    let build_harness = || {
+        let mut harness = get_harness(VALIDATOR_COUNT);
+        harness.extend_chain(
+            MainnetEthSpec::slots_per_epoch() as usize,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::SomeValidators(vec![]),
+        );
+        harness
+    };
+
+    // Both chains are built deterministically and are therefore identical.
+    let mut individual_harness = build_harness();
+    let mut batch_harness = build_harness();
+
+    // Produce attestations from all validators for each of the most recent slots, extending the
+    // chain one block at a time.
+    let mut attestations = vec![];
+    while attestations.len() < num_attestations {
+        for harness in vec![&mut individual_harness, &mut batch_harness] {
+            harness.advance_slot();
+            harness.extend_chain(
+                1,
+                BlockStrategy::OnCanonicalHead,
+                AttestationStrategy::SomeValidators(vec![]),
+            );
+        }
+
+        let head = individual_harness.chain.head().expect("should get head");
+        attestations.extend(
+            individual_harness
+                .make_unaggregated_attestations(
+                    &individual_harness.get_all_validators(),
+                    &head.beacon_state,
+                    head.beacon_block_root.into(),
+                    head.beacon_block.slot(),
+                )
+                .into_iter()
+                .flatten(),
+        );
+    }
+    attestations.truncate(num_attestations);
+
+    // Replace one signature with a valid signature over a different message.
+    let other_signature = attestations[corrupted_index + 1].0.signature.clone();
+    attestations[corrupted_index].0.signature = other_signature;
+
+    let individual_results = attestations
+        .clone()
+        .into_iter()
+        .map(|(attestation, subnet_id)| {
+            individual_harness
+                .chain
+                .verify_unaggregated_attestation_for_gossip(attestation, subnet_id)
+        })
+        .collect::<Vec<_>>();
+
+    let batch_results = batch_harness
+        .chain
+        .batch_verify_unaggregated_attestations_for_gossip(attestations)
+        .expect("should verify batch");
+
+    assert_eq!(batch_results.len(), num_attestations);
+    for (i, (individual, batch)) in individual_results.iter().zip(&batch_results).enumerate() {
+        assert_eq!(
+            individual.as_ref().err().map(|e| format!("{:?}", e)),
+            batch.as_ref().err().map(|e| format!("{:?}", e)),
+            "attestation {} should have the same outcome",
+            i
+        );
+
+        if i == corrupted_index {
+            assert!(
+                matches!(batch, Err(AttnError::InvalidSignature)),
+                "corrupted attestation should have an invalid signature"
+            );
+        } else {
+            assert!(batch.is_ok(), "attestation {} should be valid", i);
+        }
+    }
+}
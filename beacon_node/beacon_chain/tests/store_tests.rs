@@ -41,10 +41,16 @@ type E = MinimalEthSpec;
 type TestHarness = BeaconChainHarness<BlockingMigratorDiskHarnessType<E>>;
 
 fn get_store(db_path: &TempDir) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
+    get_store_with_config(db_path, StoreConfig::default())
+}
+
+fn get_store_with_config(
+    db_path: &TempDir,
+    config: StoreConfig,
+) -> Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>> {
     let spec = MinimalEthSpec::default_spec();
     let hot_path = db_path.path().join("hot_db");
     let cold_path = db_path.path().join("cold_db");
-    let config = StoreConfig::default();
 
     let decorator = slog_term::PlainDecorator::new(slog_term::TestStdoutWriter);
     let drain = slog_term::FullFormat::new(decorator).build();
@@ -88,6 +94,164 @@ fn full_participation_no_skips() {
     check_iterators(&harness);
 }
 
+#[test]
+fn cold_balances_match_cold_states() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > 0, "some states should be frozen");
+
+    for slot in (0..split_slot.as_u64()).map(Slot::new) {
+        let balances = store
+            .load_cold_balances_by_slot(slot, u64::max_value())
+            .expect("should load cold balances");
+        let state = store
+            .load_cold_state_by_slot(slot)
+            .expect("should load cold state");
+        assert_eq!(balances, state.balances.to_vec(), "slot {}", slot);
+    }
+
+    // Slots which require replaying more than the limit should be refused.
+    assert!(store.load_cold_balances_by_slot(Slot::new(0), 0).is_ok());
+    assert!(store.load_cold_balances_by_slot(Slot::new(1), 0).is_err());
+
+    // Non-frozen slots should be refused.
+    assert!(store
+        .load_cold_balances_by_slot(split_slot, u64::max_value())
+        .is_err());
+}
+
+#[test]
+fn cold_roots_match_iterators() {
+    let num_blocks_produced = E::slots_per_epoch() * 6;
+    let db_path = tempdir().unwrap();
+    let store = get_store_with_config(
+        &db_path,
+        StoreConfig {
+            slots_per_restore_point: 2 * E::slots_per_epoch(),
+            ..StoreConfig::default()
+        },
+    );
+    let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let split_slot = store.get_split_slot();
+    let restore_point_slot = store.get_latest_restore_point_slot();
+    assert!(
+        restore_point_slot > 0,
+        "some restore points should be frozen"
+    );
+    assert!(
+        restore_point_slot + 1 < split_slot,
+        "some frozen slots should lie after the latest restore point"
+    );
+
+    let block_roots = harness
+        .chain
+        .rev_iter_block_roots()
+        .unwrap()
+        .map(|result| result.map(|(root, slot)| (slot, root)))
+        .collect::<Result<HashMap<_, _>, _>>()
+        .unwrap();
+    let state_roots = harness
+        .chain
+        .rev_iter_state_roots()
+        .unwrap()
+        .map(|result| result.map(|(root, slot)| (slot, root)))
+        .collect::<Result<HashMap<_, _>, _>>()
+        .unwrap();
+
+    for slot in (0..split_slot.as_u64()).map(Slot::new) {
+        // The freezer tables only cover slots prior to the latest restore point.
+        let (expected_block_root, expected_state_root) = if slot < restore_point_slot {
+            (Some(block_roots[&slot]), Some(state_roots[&slot]))
+        } else {
+            (None, None)
+        };
+
+        assert_eq!(
+            store.load_cold_block_root(slot).unwrap(),
+            expected_block_root,
+            "slot {}",
+            slot
+        );
+        assert_eq!(
+            store.load_cold_state_root(slot).unwrap(),
+            expected_state_root,
+            "slot {}",
+            slot
+        );
+    }
+
+    // Non-frozen slots should never be read from the freezer.
+    assert_eq!(store.load_cold_block_root(split_slot).unwrap(), None);
+    assert_eq!(store.load_cold_state_root(split_slot).unwrap(), None);
+}
+
+#[test]
+fn finalized_blocks_are_canonical() {
+    let num_blocks_produced = E::slots_per_epoch() * 5;
+    let db_path = tempdir().unwrap();
+    let store = get_store(&db_path);
+    let mut harness = get_harness(store.clone(), LOW_VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        num_blocks_produced as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let split_slot = store.get_split_slot();
+    assert!(split_slot > 0, "some blocks should be frozen");
+
+    let finalized_slot = harness
+        .chain
+        .head_info()
+        .unwrap()
+        .finalized_checkpoint
+        .epoch
+        .start_slot(E::slots_per_epoch());
+
+    let block_roots = harness
+        .chain
+        .rev_iter_block_roots()
+        .unwrap()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    for (block_root, slot) in block_roots {
+        assert_eq!(
+            harness
+                .chain
+                .is_finalized_block(&block_root, slot)
+                .expect("should determine finality"),
+            slot <= finalized_slot,
+            "slot {}",
+            slot
+        );
+    }
+
+    // Deeply finalized slots should not be reported as finalized for an unknown root.
+    assert!(!harness
+        .chain
+        .is_finalized_block(&Hash256::repeat_byte(42), Slot::new(1))
+        .unwrap());
+}
+
 #[test]
 fn randomised_skips() {
     let num_slots = E::slots_per_epoch() * 5;
@@ -9,13 +9,16 @@ use beacon_chain::{
         AttestationStrategy, BeaconChainHarness, BlockStrategy, NullMigratorEphemeralHarnessType,
         OP_POOL_DB_KEY,
     },
+    BlockProductionError,
 };
 use operation_pool::PersistedOperationPool;
 use state_processing::{
     per_slot_processing, per_slot_processing::Error as SlotProcessingError, EpochProcessingError,
 };
 use store::config::StoreConfig;
-use types::{BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Slot};
+use types::{
+    BeaconStateError, EthSpec, Hash256, Keypair, MinimalEthSpec, RelativeEpoch, Signature, Slot,
+};
 
 // Should ideally be divisible by 3.
 pub const VALIDATOR_COUNT: usize = 24;
@@ -189,6 +192,151 @@ fn chooses_fork() {
     );
 }
 
+#[test]
+fn fork_choice_weights_of_forked_heads() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let delay = MinimalEthSpec::default_spec().min_attestation_inclusion_delay as usize;
+
+    let honest_validators: Vec<usize> = (0..two_thirds).collect();
+    let faulty_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+
+    harness.extend_chain(
+        delay + 1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let (honest_head, faulty_head) = harness.generate_two_forks_by_skipping_a_block(
+        &honest_validators,
+        &faulty_validators,
+        delay + 1,
+        delay + 2,
+    );
+
+    let heads = harness.chain.heads();
+    assert_eq!(heads.len(), 2, "there should be two heads");
+
+    let fork_choice = harness.chain.fork_choice.read();
+    let proto_array = fork_choice.proto_array().core_proto_array();
+    let node = |root: &Hash256| {
+        proto_array
+            .indices
+            .get(root)
+            .and_then(|&index| proto_array.nodes.get(index))
+            .expect("head should be known to fork choice")
+    };
+
+    let honest_node = node(&honest_head);
+    let faulty_node = node(&faulty_head);
+
+    assert!(
+        proto_array.node_is_viable_for_head(honest_node),
+        "honest head should be viable"
+    );
+    assert!(
+        proto_array.node_is_viable_for_head(faulty_node),
+        "faulty head should be viable"
+    );
+
+    assert!(faulty_node.weight() > 0, "faulty head should have votes");
+    assert!(
+        honest_node.weight() > faulty_node.weight(),
+        "honest head should outweigh the faulty head"
+    );
+
+    let total_balance = VALIDATOR_COUNT as u64 * harness.spec.max_effective_balance;
+    assert!(
+        honest_node.weight() + faulty_node.weight() <= total_balance,
+        "the weight of disjoint heads cannot exceed the total balance"
+    );
+}
+
+#[test]
+fn produces_blocks_on_explicit_parents() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    let two_thirds = (VALIDATOR_COUNT / 3) * 2;
+    let honest_validators: Vec<usize> = (0..two_thirds).collect();
+    let faulty_validators: Vec<usize> = (two_thirds..VALIDATOR_COUNT).collect();
+
+    harness.extend_chain(
+        2,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let (honest_head, faulty_head) = harness.generate_two_forks_by_skipping_a_block(
+        &honest_validators,
+        &faulty_validators,
+        2,
+        3,
+    );
+
+    let head_slot = |root| {
+        harness
+            .chain
+            .get_block(&root)
+            .expect("should read block")
+            .expect("block should exist")
+            .slot()
+    };
+    let slot = std::cmp::max(head_slot(honest_head), head_slot(faulty_head)) + 1;
+
+    for &parent_root in &[honest_head, faulty_head] {
+        let (block, state) = harness
+            .chain
+            .produce_block_with_parent(Signature::empty(), slot, None, parent_root)
+            .expect("should produce block upon either branch");
+
+        assert_eq!(block.parent_root, parent_root);
+        assert_eq!(block.slot, slot);
+        assert_eq!(state.slot, slot);
+    }
+
+    assert!(matches!(
+        harness.chain.produce_block_with_parent(
+            Signature::empty(),
+            slot,
+            None,
+            Hash256::repeat_byte(42)
+        ),
+        Err(BlockProductionError::UnknownParentBlock(_))
+    ));
+}
+
+#[test]
+fn does_not_produce_blocks_on_pre_finalization_parents() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+
+    harness.extend_chain(
+        (MinimalEthSpec::slots_per_epoch() * 5) as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let head = harness.chain.head().expect("should get head");
+    assert!(head.beacon_state.finalized_checkpoint.epoch > 0);
+
+    let parent_root = harness
+        .chain
+        .block_at_slot(Slot::new(1))
+        .expect("should read block")
+        .expect("block should exist")
+        .canonical_root();
+
+    assert!(matches!(
+        harness.chain.produce_block_with_parent(
+            Signature::empty(),
+            head.beacon_block.slot() + 1,
+            None,
+            parent_root
+        ),
+        Err(BlockProductionError::ParentIsPreFinalization { .. })
+    ));
+}
+
 #[test]
 fn finalizes_with_full_participation() {
     let num_blocks_produced = MinimalEthSpec::slots_per_epoch() * 5;
@@ -619,3 +767,152 @@ fn produces_and_processes_with_genesis_skip_slots() {
         run_skip_slot_test(i)
     }
 }
+
+#[test]
+fn committee_cache_warmed_before_epoch_boundary() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+    let spec = &MinimalEthSpec::default_spec();
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+    let warm_slots = harness.chain.config.committee_cache_warm_slots;
+    let boundary_slot = 3 * slots_per_epoch;
+
+    // Build the chain up to the first slot in which the committee cache is warmed.
+    harness.extend_chain(
+        (boundary_slot - warm_slots - 1) as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness.advance_slot();
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+    harness
+        .chain
+        .warm_committee_cache()
+        .expect("should warm committee cache");
+
+    // Cross the epoch boundary.
+    while harness.get_current_slot() + 1 < boundary_slot {
+        harness.advance_slot();
+    }
+    harness.advance_slot();
+    harness.extend_chain(
+        1,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::AllValidators,
+    );
+
+    let mut state = harness.chain.head().expect("should get head").beacon_state;
+    assert_eq!(state.slot, boundary_slot);
+
+    assert!(
+        harness
+            .chain
+            .build_committee_cache(&mut state, RelativeEpoch::Current)
+            .expect("should build committee cache"),
+        "the first request after the boundary should hit the cache"
+    );
+
+    let mut expected = state.clone();
+    expected
+        .force_build_committee_cache(RelativeEpoch::Current, spec)
+        .expect("should build committee cache");
+    assert_eq!(
+        state.committee_cache(RelativeEpoch::Current),
+        expected.committee_cache(RelativeEpoch::Current),
+        "the warmed committee cache should match one built from the head state"
+    );
+
+    // The shuffling for the epoch after the boundary was not yet known, so it was not warmed.
+    assert!(!harness
+        .chain
+        .build_committee_cache(&mut state, RelativeEpoch::Next)
+        .expect("should build committee cache"));
+    assert!(harness
+        .chain
+        .build_committee_cache(&mut state, RelativeEpoch::Next)
+        .expect("should build committee cache"));
+}
+
+/// Returns the value of the counter or gauge called `name`, or zero if it is not yet registered.
+fn metric_value(name: &str) -> f64 {
+    lighthouse_metrics::gather()
+        .into_iter()
+        .find(|family| family.get_name() == name)
+        .and_then(|family| family.get_metric().first().cloned())
+        .map_or(0.0, |metric| {
+            if metric.has_counter() {
+                metric.get_counter().get_value()
+            } else {
+                metric.get_gauge().get_value()
+            }
+        })
+}
+
+#[test]
+fn block_proposer_head_state_metrics() {
+    let mut harness = get_harness(VALIDATOR_COUNT);
+    let spec = &MinimalEthSpec::default_spec();
+    let slots_per_epoch = MinimalEthSpec::slots_per_epoch();
+
+    harness.extend_chain(
+        slots_per_epoch as usize,
+        BlockStrategy::OnCanonicalHead,
+        AttestationStrategy::SomeValidators(vec![]),
+    );
+
+    let head_state = harness.chain.head().expect("should get head").beacon_state;
+    let head_epoch = head_state.slot.epoch(slots_per_epoch);
+
+    let hits = "beacon_block_proposer_head_state_hits_total";
+    let misses = "beacon_block_proposer_head_state_misses_total";
+    let distance = "beacon_block_proposer_head_epoch_distance";
+
+    // A slot in the head epoch is served from the head state.
+    let (hits_before, misses_before) = (metric_value(hits), metric_value(misses));
+    harness
+        .chain
+        .block_proposer(head_state.slot)
+        .expect("should get proposer in the head epoch");
+    assert_eq!(metric_value(hits), hits_before + 1.0);
+    assert_eq!(metric_value(misses), misses_before);
+
+    // Move the clock two epochs past the head, without producing blocks.
+    for _ in 0..slots_per_epoch * 2 {
+        harness.advance_slot();
+    }
+    let current_epoch = harness.chain.epoch().expect("should read epoch");
+    assert!(current_epoch > head_epoch);
+
+    // A slot in a later epoch requires skipping the head state.
+    let slot = (head_epoch + 1).start_slot(slots_per_epoch);
+    let (hits_before, misses_before) = (metric_value(hits), metric_value(misses));
+    let proposer = harness
+        .chain
+        .block_proposer(slot)
+        .expect("should get proposer in a later epoch");
+    assert_eq!(metric_value(hits), hits_before);
+    assert_eq!(metric_value(misses), misses_before + 1.0);
+    assert_eq!(
+        metric_value(distance),
+        (current_epoch - head_epoch).as_u64() as f64,
+        "should record how far the head is behind the clock"
+    );
+
+    let mut state = head_state;
+    while state.slot < slot {
+        per_slot_processing(&mut state, None, spec).expect("should process slot");
+    }
+    state
+        .build_committee_cache(RelativeEpoch::Current, spec)
+        .expect("should build committee cache");
+    assert_eq!(
+        proposer,
+        state
+            .get_beacon_proposer_index(slot, spec)
+            .expect("should get proposer index"),
+        "the proposer from a skipped state should match"
+    );
+}
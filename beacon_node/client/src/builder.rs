@@ -1,7 +1,7 @@
 use crate::config::{ClientGenesis, Config as ClientConfig};
 use crate::notifier::spawn_notifier;
 use crate::Client;
-use beacon_chain::events::TeeEventHandler;
+use beacon_chain::events::{StandardEvent, TeeEventHandler};
 use beacon_chain::{
     builder::{BeaconChainBuilder, Witness},
     eth1_chain::{CachingEth1Backend, Eth1Chain},
@@ -25,7 +25,7 @@ use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
 use timer::spawn_timer;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{broadcast, mpsc::UnboundedSender};
 use types::{
     test_utils::generate_deterministic_keypairs, BeaconState, ChainSpec, EthSpec,
     SignedBeaconBlockHash,
@@ -61,7 +61,7 @@ pub struct ClientBuilder<T: BeaconChainTypes> {
     event_handler: Option<T::EventHandler>,
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
     network_send: Option<UnboundedSender<NetworkMessage<T::EthSpec>>>,
-    http_listen_addr: Option<SocketAddr>,
+    http_listen_addrs: Vec<SocketAddr>,
     websocket_listen_addr: Option<SocketAddr>,
     eth_spec_instance: T::EthSpec,
 }
@@ -103,7 +103,7 @@ where
             event_handler: None,
             network_globals: None,
             network_send: None,
-            http_listen_addr: None,
+            http_listen_addrs: vec![],
             websocket_listen_addr: None,
             eth_spec_instance,
         }
@@ -286,6 +286,7 @@ where
         client_config: &ClientConfig,
         eth2_config: &Eth2Config,
         events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+        standard_events: broadcast::Sender<StandardEvent>,
     ) -> Result<Self, String> {
         let beacon_chain = self
             .beacon_chain
@@ -310,7 +311,7 @@ where
             network_chan: network_send,
         };
 
-        let listening_addr = rest_api::start_server(
+        let listening_addrs = rest_api::start_server(
             context.executor,
             &client_config.rest_api,
             beacon_chain,
@@ -323,10 +324,13 @@ where
                 .map_err(|_| "unable to read freezer DB dir")?,
             eth2_config.clone(),
             events,
+            standard_events,
+            self.eth1_service.clone(),
+            client_config.eth1.clone(),
         )
-        .map_err(|e| format!("Failed to start HTTP API: {:?}", e))?;
+        .map_err(|e| format!("Failed to start HTTP API: {}", e))?;
 
-        self.http_listen_addr = Some(listening_addr);
+        self.http_listen_addrs = listening_addrs;
 
         Ok(self)
     }
@@ -383,7 +387,7 @@ where
         Client {
             beacon_chain: self.beacon_chain,
             network_globals: self.network_globals,
-            http_listen_addr: self.http_listen_addr,
+            http_listen_addrs: self.http_listen_addrs,
             websocket_listen_addr: self.websocket_listen_addr,
         }
     }
@@ -461,7 +465,14 @@ where
     pub fn tee_event_handler(
         mut self,
         config: WebSocketConfig,
-    ) -> Result<(Self, Arc<Mutex<Bus<SignedBeaconBlockHash>>>), String> {
+    ) -> Result<
+        (
+            Self,
+            Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+            broadcast::Sender<StandardEvent>,
+        ),
+        String,
+    > {
         let context = self
             .runtime_context
             .as_ref()
@@ -478,9 +489,9 @@ where
         };
 
         self.websocket_listen_addr = listening_addr;
-        let (tee_event_handler, bus) = TeeEventHandler::new(log, sender)?;
+        let (tee_event_handler, bus, standard_events) = TeeEventHandler::new(log, sender)?;
         self.event_handler = Some(tee_event_handler);
-        Ok((self, bus))
+        Ok((self, bus, standard_events))
     }
 }
 
@@ -644,7 +655,8 @@ where
                 })?
         };
 
-        self.eth1_service = None;
+        // Retain a handle to the service so that its caches can be inspected via the HTTP API.
+        self.eth1_service = Some(backend.core.clone());
 
         // Starts the service that connects to an eth1 node and periodically updates caches.
         backend.start(context.executor);
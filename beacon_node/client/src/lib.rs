@@ -23,7 +23,7 @@ pub use eth2_config::Eth2Config;
 pub struct Client<T: BeaconChainTypes> {
     beacon_chain: Option<Arc<BeaconChain<T>>>,
     network_globals: Option<Arc<NetworkGlobals<T::EthSpec>>>,
-    http_listen_addr: Option<SocketAddr>,
+    http_listen_addrs: Vec<SocketAddr>,
     websocket_listen_addr: Option<SocketAddr>,
 }
 
@@ -33,9 +33,15 @@ impl<T: BeaconChainTypes> Client<T> {
         self.beacon_chain.clone()
     }
 
-    /// Returns the address of the client's HTTP API server, if it was started.
+    /// Returns the first address of the client's HTTP API server, if it was started.
     pub fn http_listen_addr(&self) -> Option<SocketAddr> {
-        self.http_listen_addr
+        self.http_listen_addrs.first().copied()
+    }
+
+    /// Returns every address of the client's HTTP API server, which is empty if it was not
+    /// started.
+    pub fn http_listen_addrs(&self) -> &[SocketAddr] {
+        &self.http_listen_addrs
     }
 
     /// Returns the address of the client's WebSocket API server, if it was started.
@@ -278,6 +278,11 @@ impl DepositCache {
         }
     }
 
+    /// Returns the root of the deposit tree containing all deposits in the cache.
+    pub fn deposit_root(&self) -> Hash256 {
+        self.deposit_tree.root()
+    }
+
     /// Gets the deposit root at block height = block_number.
     ///
     /// Fetches the `deposit_count` on or just before the queried `block_number`
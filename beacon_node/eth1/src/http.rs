@@ -55,6 +55,16 @@ impl FromStr for Eth1NetworkId {
     }
 }
 
+impl From<Eth1NetworkId> for u64 {
+    fn from(network_id: Eth1NetworkId) -> u64 {
+        match network_id {
+            Eth1NetworkId::Mainnet => 1,
+            Eth1NetworkId::Goerli => 5,
+            Eth1NetworkId::Custom(network_id) => network_id,
+        }
+    }
+}
+
 /// Get the eth1 network id of the given endpoint.
 pub async fn get_network_id(endpoint: &str, timeout: Duration) -> Result<Eth1NetworkId, String> {
     let response_body = send_rpc_request(endpoint, "net_version", json!([]), timeout).await?;
@@ -96,6 +96,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             .expect("Local ENR must have a fork id");
 
         let meta_data = load_or_build_metadata(&net_conf.network_dir, &log);
+        *network_globals.local_metadata.write() = meta_data.clone();
 
         let gossipsub = Gossipsub::new(MessageAuthenticity::Anonymous, net_conf.gs_config.clone())
             .map_err(|e| format!("Could not construct gossipsub: {:?}", e))?;
@@ -414,6 +415,7 @@ impl<TSpec: EthSpec> Behaviour<TSpec> {
             .local_enr()
             .bitfield::<TSpec>()
             .expect("Local discovery must have bitfield");
+        *self.network_globals.local_metadata.write() = self.meta_data.clone();
         // Save the updated metadata to disk
         save_metadata_to_disk(&self.network_dir, self.meta_data.clone(), &self.log);
     }
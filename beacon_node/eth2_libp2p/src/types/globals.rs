@@ -1,6 +1,7 @@
 //! A collection of variables that are accessible outside of the network thread itself.
 use crate::peer_manager::PeerDB;
-use crate::types::SyncState;
+use crate::rpc::MetaData;
+use crate::types::{EnrBitfield, SyncState};
 use crate::Client;
 use crate::EnrExt;
 use crate::{Enr, GossipTopic, Multiaddr, PeerId};
@@ -26,6 +27,8 @@ pub struct NetworkGlobals<TSpec: EthSpec> {
     pub gossipsub_subscriptions: RwLock<HashSet<GossipTopic>>,
     /// The current sync status of the node.
     pub sync_state: RwLock<SyncState>,
+    /// The current metadata of the node, as sent to peers.
+    pub local_metadata: RwLock<MetaData<TSpec>>,
 }
 
 impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
@@ -39,6 +42,10 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
             peers: RwLock::new(PeerDB::new(log)),
             gossipsub_subscriptions: RwLock::new(HashSet::new()),
             sync_state: RwLock::new(SyncState::Stalled),
+            local_metadata: RwLock::new(MetaData {
+                seq_number: 0,
+                attnets: EnrBitfield::<TSpec>::default(),
+            }),
         }
     }
 
@@ -53,6 +60,11 @@ impl<TSpec: EthSpec> NetworkGlobals<TSpec> {
         self.peer_id.read().clone()
     }
 
+    /// Returns the current metadata of the node, as sent to peers.
+    pub fn local_metadata(&self) -> MetaData<TSpec> {
+        self.local_metadata.read().clone()
+    }
+
     /// Returns the list of `Multiaddr` that the underlying libp2p instance is listening on.
     pub fn listen_multiaddrs(&self) -> Vec<Multiaddr> {
         self.listen_multiaddrs.read().clone()
@@ -15,13 +15,13 @@ use state_processing::per_block_processing::{
     verify_exit, VerifySignatures,
 };
 use state_processing::SigVerifiedOp;
-use std::collections::{hash_map, HashMap, HashSet};
+use std::collections::{hash_map, BTreeMap, HashMap, HashSet};
 use std::marker::PhantomData;
 use std::ptr;
 use types::{
     typenum::Unsigned, Attestation, AttesterSlashing, BeaconState, BeaconStateError, ChainSpec,
     EthSpec, Fork, ForkVersion, Hash256, ProposerSlashing, RelativeEpoch, SignedVoluntaryExit,
-    Validator,
+    Slot, Validator,
 };
 
 #[derive(Default, Debug)]
@@ -95,6 +95,15 @@ impl<T: EthSpec> OperationPool<T> {
         self.attestations.read().values().map(Vec::len).sum()
     }
 
+    /// Number of attestations in the pool for each slot, without cloning any attestations.
+    pub fn num_attestations_by_slot(&self) -> BTreeMap<Slot, usize> {
+        let mut counts = BTreeMap::new();
+        for attestation in self.attestations.read().values().flatten() {
+            *counts.entry(attestation.data.slot).or_insert(0) += 1;
+        }
+        counts
+    }
+
     /// Get a list of attestations for inclusion in a block.
     ///
     /// The `validity_filter` is a closure that provides extra filtering of the attestations
@@ -280,6 +289,37 @@ impl<T: EthSpec> OperationPool<T> {
             });
     }
 
+    /// Returns all attester slashings in the pool for which `filter` returns `true`.
+    ///
+    /// Only matching slashings are cloned.
+    pub fn get_all_attester_slashings(
+        &self,
+        filter: impl Fn(&AttesterSlashing<T>) -> bool,
+    ) -> Vec<AttesterSlashing<T>> {
+        self.attester_slashings
+            .read()
+            .iter()
+            .map(|(slashing, _)| slashing)
+            .filter(|slashing| filter(slashing))
+            .cloned()
+            .collect()
+    }
+
+    /// Returns all proposer slashings in the pool for which `filter` returns `true`.
+    ///
+    /// Only matching slashings are cloned.
+    pub fn get_all_proposer_slashings(
+        &self,
+        filter: impl Fn(&ProposerSlashing) -> bool,
+    ) -> Vec<ProposerSlashing> {
+        self.proposer_slashings
+            .read()
+            .values()
+            .filter(|slashing| filter(slashing))
+            .cloned()
+            .collect()
+    }
+
     /// Total number of attester slashings in the pool.
     pub fn num_attester_slashings(&self) -> usize {
         self.attester_slashings.read().len()
@@ -959,4 +999,63 @@ mod release_tests {
         op_pool.prune_attester_slashings(state, state.fork);
         assert_eq!(op_pool.get_slashings(state).1, vec![slashing]);
     }
+
+    /// Filter attester slashings with overlapping sets of indices.
+    #[test]
+    fn get_all_attester_slashings_filtered() {
+        let ctxt = TestContext::new();
+        let (op_pool, state, spec) = (&ctxt.op_pool, &ctxt.state, &ctxt.spec);
+        let slashing1 = ctxt.attester_slashing(&[1, 2, 3]);
+        let slashing2 = ctxt.attester_slashing(&[3, 4]);
+        for slashing in &[&slashing1, &slashing2] {
+            op_pool.insert_attester_slashing(
+                (*slashing).clone().validate(state, spec).unwrap(),
+                state.fork,
+            );
+        }
+
+        let slashings_for = |validator_index: u64| {
+            op_pool.get_all_attester_slashings(|slashing| {
+                slashing
+                    .attestation_1
+                    .attesting_indices
+                    .iter()
+                    .chain(slashing.attestation_2.attesting_indices.iter())
+                    .any(|&index| index == validator_index)
+            })
+        };
+
+        assert_eq!(slashings_for(1), vec![slashing1.clone()]);
+        assert_eq!(slashings_for(4), vec![slashing2.clone()]);
+        assert!(slashings_for(5).is_empty());
+
+        let overlapping = slashings_for(3);
+        assert_eq!(overlapping.len(), 2);
+        assert!(overlapping.contains(&slashing1));
+        assert!(overlapping.contains(&slashing2));
+
+        assert_eq!(op_pool.get_all_attester_slashings(|_| true).len(), 2);
+    }
+
+    /// Filter proposer slashings by proposer index.
+    #[test]
+    fn get_all_proposer_slashings_filtered() {
+        let ctxt = TestContext::new();
+        let (op_pool, state, spec) = (&ctxt.op_pool, &ctxt.state, &ctxt.spec);
+        let slashing1 = ctxt.proposer_slashing(1);
+        let slashing2 = ctxt.proposer_slashing(2);
+        op_pool.insert_proposer_slashing(slashing1.clone().validate(state, spec).unwrap());
+        op_pool.insert_proposer_slashing(slashing2.clone().validate(state, spec).unwrap());
+
+        let slashings_for = |proposer_index: u64| {
+            op_pool.get_all_proposer_slashings(|slashing| {
+                slashing.signed_header_1.message.proposer_index == proposer_index
+            })
+        };
+
+        assert_eq!(slashings_for(1), vec![slashing1]);
+        assert_eq!(slashings_for(2), vec![slashing2]);
+        assert!(slashings_for(3).is_empty());
+        assert_eq!(op_pool.get_all_proposer_slashings(|_| true).len(), 2);
+    }
 }
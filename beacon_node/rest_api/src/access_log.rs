@@ -0,0 +1,174 @@
+//! An optional, machine-readable log of HTTP API requests, written as one JSON object per line.
+//!
+//! The access log is independent of the node's main logger so it may be shipped elsewhere (e.g.,
+//! to Loki or Elasticsearch) without changing the node's log format.
+
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// A single entry in the access log.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    pub method: String,
+    /// The path of the matched route (e.g., `/eth/v1/beacon/blocks/{block_id}`), or the request
+    /// path if no route matched.
+    pub path: String,
+    pub status: u16,
+    pub duration_ms: u64,
+    /// The address of the remote peer, if known.
+    pub remote: Option<String>,
+    pub request_id: String,
+    pub user_agent: Option<String>,
+}
+
+/// Writes `AccessLogEntry`s to a file or, if no path is given, to stdout.
+pub struct AccessLog {
+    path: Option<PathBuf>,
+    file: Mutex<Option<File>>,
+}
+
+impl AccessLog {
+    /// Opens the access log at `path` (appending to any existing file), or on stdout if `path` is
+    /// `None`.
+    pub fn new(path: Option<PathBuf>) -> Result<Self, String> {
+        let file = path.as_deref().map(open).transpose()?;
+
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+        })
+    }
+
+    /// Closes and reopens the access log file, so that a log which has been moved aside (e.g., by
+    /// `logrotate`) is replaced by a new file at the configured path.
+    ///
+    /// Has no effect when logging to stdout.
+    pub fn reopen(&self) -> Result<(), String> {
+        if let Some(path) = &self.path {
+            *self.file.lock() = Some(open(path)?);
+        }
+        Ok(())
+    }
+
+    /// Writes `entry` to the log as a single line of JSON.
+    ///
+    /// If the log file has been removed since it was opened, it is recreated first.
+    pub fn write(&self, entry: &AccessLogEntry) -> Result<(), String> {
+        let mut line = serde_json::to_vec(entry)
+            .map_err(|e| format!("Unable to serialize access log entry: {:?}", e))?;
+        line.push(b'\n');
+
+        match &self.path {
+            Some(path) => {
+                let mut file = self.file.lock();
+                if !path.exists() {
+                    *file = Some(open(path)?);
+                }
+                match file.as_mut() {
+                    Some(file) => write_line(file, &line),
+                    None => Ok(()),
+                }
+            }
+            None => write_line(&mut io::stdout().lock(), &line),
+        }
+    }
+}
+
+fn open(path: &Path) -> Result<File, String> {
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("Unable to open access log {:?}: {:?}", path, e))
+}
+
+fn write_line<W: Write>(writer: &mut W, line: &[u8]) -> Result<(), String> {
+    writer
+        .write_all(line)
+        .map_err(|e| format!("Unable to write access log: {:?}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempdir::TempDir;
+
+    fn entry(request_id: &str) -> AccessLogEntry {
+        AccessLogEntry {
+            method: "GET".to_string(),
+            path: "/node/version".to_string(),
+            status: 200,
+            duration_ms: 3,
+            remote: Some("127.0.0.1:52000".to_string()),
+            request_id: request_id.to_string(),
+            user_agent: Some("curl/7.68.0".to_string()),
+        }
+    }
+
+    fn read_entries(path: &Path) -> Vec<AccessLogEntry> {
+        fs::read_to_string(path)
+            .expect("should read access log")
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("each line should be JSON"))
+            .collect()
+    }
+
+    #[test]
+    fn writes_json_lines() {
+        let dir = TempDir::new("access_log").expect("should create temp dir");
+        let path = dir.path().join("access.log");
+        let log = AccessLog::new(Some(path.clone())).expect("should open log");
+
+        log.write(&entry("a")).expect("should write");
+        log.write(&entry("b")).expect("should write");
+
+        assert_eq!(read_entries(&path), vec![entry("a"), entry("b")]);
+
+        let line = fs::read_to_string(&path).unwrap();
+        let value: serde_json::Value = serde_json::from_str(line.lines().next().unwrap()).unwrap();
+        for field in &[
+            "method",
+            "path",
+            "status",
+            "duration_ms",
+            "remote",
+            "request_id",
+            "user_agent",
+        ] {
+            assert!(value.get(field).is_some(), "should contain {}", field);
+        }
+    }
+
+    #[test]
+    fn recreates_removed_file() {
+        let dir = TempDir::new("access_log").expect("should create temp dir");
+        let path = dir.path().join("access.log");
+        let log = AccessLog::new(Some(path.clone())).expect("should open log");
+
+        log.write(&entry("a")).expect("should write");
+        fs::remove_file(&path).expect("should remove log");
+        log.write(&entry("b")).expect("should write after removal");
+
+        assert_eq!(read_entries(&path), vec![entry("b")]);
+    }
+
+    #[test]
+    fn reopen_after_rename() {
+        let dir = TempDir::new("access_log").expect("should create temp dir");
+        let path = dir.path().join("access.log");
+        let rotated = dir.path().join("access.log.1");
+        let log = AccessLog::new(Some(path.clone())).expect("should open log");
+
+        log.write(&entry("a")).expect("should write");
+        fs::rename(&path, &rotated).expect("should rename log");
+        log.reopen().expect("should reopen");
+        log.write(&entry("b")).expect("should write");
+
+        assert_eq!(read_entries(&rotated), vec![entry("a")]);
+        assert_eq!(read_entries(&path), vec![entry("b")]);
+    }
+}
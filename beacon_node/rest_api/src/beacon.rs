@@ -1,66 +1,94 @@
 use crate::helpers::*;
+use crate::metrics::{self, SubmissionOutcome, SubmittedObject};
 use crate::validator::get_state_for_epoch;
 use crate::Context;
 use crate::{ApiError, UrlQuery};
 use beacon_chain::{
-    observed_operations::ObservationOutcome, BeaconChain, BeaconChainTypes, StateSkipConfig,
+    events::{EventTopic, StandardEvent},
+    observed_operations::ObservationOutcome,
+    BeaconChain, BeaconChainTypes, StateSkipConfig,
 };
+use eth2_libp2p::PubsubMessage;
 use futures::executor::block_on;
+use futures::stream;
 use hyper::body::Bytes;
 use hyper::{Body, Request};
+use itertools::process_results;
 use rest_types::{
-    BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
-    ValidatorRequest, ValidatorResponse,
+    BlockHeaderData, BlockId, BlockResponse, CanonicalHeadResponse, Committee, GenericResponse,
+    HeadBeaconBlock, PaginatedResponse, PaginationMetaData, PublishOperationResponse, RootData,
+    StateResponse, ValidatorBalanceData, ValidatorData, ValidatorListing, ValidatorRequest,
+    ValidatorResponse, ValidatorStatus,
 };
+use ssz::Encode;
+use std::collections::BTreeSet;
 use std::io::Write;
+use std::ops::Range;
+use std::str::FromStr;
 use std::sync::Arc;
+use store::hot_cold_store::HotColdDBError;
+use tokio::sync::broadcast::RecvError;
 
-use slog::error;
+use slog::{debug, error};
 use types::{
-    AttesterSlashing, BeaconState, EthSpec, Hash256, ProposerSlashing, PublicKeyBytes,
-    RelativeEpoch, SignedBeaconBlockHash, Slot,
+    Attestation, AttesterSlashing, BeaconState, Epoch, EthSpec, Hash256, ProposerSlashing,
+    PublicKeyBytes, RelativeEpoch, SignedBeaconBlock, SignedBeaconBlockHash,
+    SignedBeaconBlockHeader, SignedVoluntaryExit, Slot,
 };
 
+/// The maximum number of slots that `get_validator_balances` will replay on top of a freezer
+/// restore point.
+pub const MAX_BALANCES_REPLAY_SLOTS: u64 = 256;
+
 /// Returns a summary of the head of the beacon chain.
+///
+/// All values are read from a single `HeadInfo` snapshot, avoiding a clone of the head state.
 pub fn get_head<T: BeaconChainTypes>(
     ctx: Arc<Context<T>>,
 ) -> Result<CanonicalHeadResponse, ApiError> {
-    let beacon_chain = &ctx.beacon_chain;
-    let chain_head = beacon_chain.head()?;
+    let head_info = ctx.beacon_chain.head_info()?;
+    let epoch_start_slot = |epoch: Epoch| epoch.start_slot(T::EthSpec::slots_per_epoch());
 
     Ok(CanonicalHeadResponse {
-        slot: chain_head.beacon_state.slot,
-        block_root: chain_head.beacon_block_root,
-        state_root: chain_head.beacon_state_root,
-        finalized_slot: chain_head
-            .beacon_state
-            .finalized_checkpoint
-            .epoch
-            .start_slot(T::EthSpec::slots_per_epoch()),
-        finalized_block_root: chain_head.beacon_state.finalized_checkpoint.root,
-        justified_slot: chain_head
-            .beacon_state
-            .current_justified_checkpoint
-            .epoch
-            .start_slot(T::EthSpec::slots_per_epoch()),
-        justified_block_root: chain_head.beacon_state.current_justified_checkpoint.root,
-        previous_justified_slot: chain_head
-            .beacon_state
-            .previous_justified_checkpoint
-            .epoch
-            .start_slot(T::EthSpec::slots_per_epoch()),
-        previous_justified_block_root: chain_head.beacon_state.previous_justified_checkpoint.root,
+        slot: head_info.slot,
+        block_root: head_info.block_root,
+        state_root: head_info.state_root,
+        finalized_slot: epoch_start_slot(head_info.finalized_checkpoint.epoch),
+        finalized_block_root: head_info.finalized_checkpoint.root,
+        justified_slot: epoch_start_slot(head_info.current_justified_checkpoint.epoch),
+        justified_block_root: head_info.current_justified_checkpoint.root,
+        previous_justified_slot: epoch_start_slot(head_info.previous_justified_checkpoint.epoch),
+        previous_justified_block_root: head_info.previous_justified_checkpoint.root,
     })
 }
 
-/// Return the list of heads of the beacon chain.
+/// Return the list of heads of the beacon chain, along with their fork choice weight, viability
+/// and checkpoint epochs.
+///
+/// All heads are read from fork choice under a single lock. A head which is unknown to fork
+/// choice (e.g., one which has just been pruned) is returned with `None` for these fields.
 pub fn get_heads<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Vec<HeadBeaconBlock> {
-    ctx.beacon_chain
-        .heads()
+    let heads = ctx.beacon_chain.heads();
+
+    let fork_choice = ctx.beacon_chain.fork_choice.read();
+    let proto_array = fork_choice.proto_array().core_proto_array();
+
+    heads
         .into_iter()
-        .map(|(beacon_block_root, beacon_block_slot)| HeadBeaconBlock {
-            beacon_block_root,
-            beacon_block_slot,
+        .map(|(beacon_block_root, beacon_block_slot)| {
+            let node = proto_array
+                .indices
+                .get(&beacon_block_root)
+                .and_then(|&index| proto_array.nodes.get(index));
+
+            HeadBeaconBlock {
+                beacon_block_root,
+                beacon_block_slot,
+                weight: node.map(|node| node.weight()),
+                viable: node.map(|node| proto_array.node_is_viable_for_head(node)),
+                justified_epoch: node.map(|node| node.justified_epoch),
+                finalized_epoch: node.map(|node| node.finalized_epoch),
+            }
         })
         .collect()
 }
@@ -70,9 +98,26 @@ pub fn get_block<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<BlockResponse<T::EthSpec>, ApiError> {
-    let beacon_chain = &ctx.beacon_chain;
+    let (block_root, block) = block_from_query(&req, &ctx.beacon_chain)?;
+    let finalized = ctx
+        .beacon_chain
+        .is_finalized_block(&block_root, block.message.slot)?;
+
+    Ok(BlockResponse {
+        root: block_root,
+        beacon_block: block,
+        finalized,
+    })
+}
+
+/// Returns the `SignedBeaconBlock` (and its root) identified by the `root` or `slot` query
+/// parameter of `req`.
+pub fn block_from_query<T: BeaconChainTypes>(
+    req: &Request<Vec<u8>>,
+    beacon_chain: &BeaconChain<T>,
+) -> Result<(Hash256, SignedBeaconBlock<T::EthSpec>), ApiError> {
     let query_params = ["root", "slot"];
-    let (key, value) = UrlQuery::from_request(&req)?.first_of(&query_params)?;
+    let (key, value) = UrlQuery::from_request(req)?.first_of(&query_params)?;
 
     let block_root = match (key.as_ref(), value) {
         ("slot", value) => {
@@ -96,12 +141,251 @@ pub fn get_block<T: BeaconChainTypes>(
         ))
     })?;
 
-    Ok(BlockResponse {
-        root: block_root,
-        beacon_block: block,
+    Ok((block_root, block))
+}
+
+/// HTTP handler to return the block given by the `block_id` path parameter, as per the standard
+/// Eth2 API. See `BlockId` for the accepted values.
+pub fn get_block_by_id<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<SignedBeaconBlock<T::EthSpec>>, ApiError> {
+    let block_id = parse_block_id(req.uri().path().rsplit('/').next().unwrap_or(""))?;
+    let (_root, block) = block_from_id(&ctx.beacon_chain, block_id)?;
+
+    Ok(GenericResponse { data: block })
+}
+
+/// HTTP handler to return the root of the block given by the `block_id` path parameter, as per
+/// the standard Eth2 API.
+pub fn get_block_root_by_id<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<RootData>, ApiError> {
+    let block_id = parse_block_id(req.uri().path().rsplit('/').nth(1).unwrap_or(""))?;
+    let (root, _block) = block_from_id(&ctx.beacon_chain, block_id)?;
+
+    Ok(GenericResponse {
+        data: RootData { root },
+    })
+}
+
+/// HTTP handler to return the header of the block given by the `block_id` path parameter, as per
+/// the standard Eth2 API.
+pub fn get_block_header_by_id<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<BlockHeaderData>, ApiError> {
+    let block_id = parse_block_id(req.uri().path().rsplit('/').next().unwrap_or(""))?;
+    let (root, block) = block_from_id(&ctx.beacon_chain, block_id)?;
+
+    Ok(GenericResponse {
+        data: block_header_data(&ctx.beacon_chain, root, block)?,
+    })
+}
+
+/// HTTP handler to return block headers, as per the standard Eth2 API.
+///
+/// If the `slot` query parameter is supplied, the headers of all known blocks at that slot are
+/// returned, including blocks on forks other than the canonical chain. Likewise, if the
+/// `parent_root` query parameter is supplied, the headers of all known children of that block are
+/// returned. If both are supplied, only blocks matching both are returned. The canonical block, if
+/// any, is first.
+///
+/// If neither parameter is supplied, the header of the canonical head block is returned.
+pub fn get_block_headers<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<Vec<BlockHeaderData>>, ApiError> {
+    let beacon_chain = &ctx.beacon_chain;
+    let query = UrlQuery::from_request(&req)?;
+    let slot_opt = query
+        .first_of_opt(&["slot"])
+        .map(|(_key, value)| parse_slot(&value))
+        .transpose()?;
+    let parent_root_opt = query
+        .first_of_opt(&["parent_root"])
+        .map(|(_key, value)| parse_root(&value))
+        .transpose()?;
+
+    let mut roots = match (slot_opt, parent_root_opt) {
+        (None, None) => {
+            let (root, block) = block_from_id(beacon_chain, BlockId::Head)?;
+            let data = block_header_data(beacon_chain, root, block)?;
+            return Ok(GenericResponse { data: vec![data] });
+        }
+        (_, Some(parent_root)) => block_children(beacon_chain, parent_root)?,
+        (Some(slot), None) => {
+            // Fork choice knows of every block since finalization, on every fork.
+            let mut roots = beacon_chain
+                .fork_choice
+                .read()
+                .proto_array()
+                .core_proto_array()
+                .nodes
+                .iter()
+                .filter(|node| node.slot == slot)
+                .map(|node| node.root)
+                .collect::<Vec<_>>();
+
+            // A finalized block may have been pruned from fork choice. If `slot` was skipped, this
+            // is the root of an earlier block, which is filtered out below.
+            if let Some(root) = block_root_at_slot(beacon_chain, slot)? {
+                roots.push(root);
+            }
+
+            roots
+        }
+    };
+    roots.sort();
+    roots.dedup();
+
+    let mut data = vec![];
+    for root in roots {
+        if let Some(block) = beacon_chain.store.get_block(&root)? {
+            let slot_matches = slot_opt.map_or(true, |slot| block.slot() == slot);
+            let parent_matches =
+                parent_root_opt.map_or(true, |parent| block.parent_root() == parent);
+
+            if slot_matches && parent_matches {
+                data.push(block_header_data(beacon_chain, root, block)?);
+            }
+        }
+    }
+    data.sort_by_key(|header| (!header.canonical, header.root));
+
+    Ok(GenericResponse { data })
+}
+
+/// Returns the roots of all known children of the block with the given `parent_root`, on all
+/// forks.
+///
+/// If the parent has been pruned from fork choice (i.e., it is finalized) then only its canonical
+/// child can be known.
+fn block_children<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    parent_root: Hash256,
+) -> Result<Vec<Hash256>, ApiError> {
+    {
+        let fork_choice = beacon_chain.fork_choice.read();
+        let proto_array = fork_choice.proto_array().core_proto_array();
+
+        if let Some(&parent_index) = proto_array.indices.get(&parent_root) {
+            // Children always follow their parent in the proto-array.
+            return Ok(proto_array
+                .nodes
+                .iter()
+                .skip(parent_index + 1)
+                .filter(|node| node.parent == Some(parent_index))
+                .map(|node| node.root)
+                .collect());
+        }
+    }
+
+    let parent_slot = match beacon_chain.store.get_block(&parent_root)? {
+        Some(parent) => parent.slot(),
+        None => return Ok(vec![]),
+    };
+    if block_root_at_slot(beacon_chain, parent_slot)? != Some(parent_root) {
+        return Ok(vec![]);
+    }
+
+    // The canonical child is the first block after the parent, skipping over any skipped slots
+    // (which repeat the parent root).
+    let child = process_results(
+        beacon_chain.forwards_iter_block_roots(parent_slot + 1)?,
+        |mut iter| iter.find(|(root, _)| *root != parent_root),
+    )?;
+
+    Ok(child.map(|(root, _)| root).into_iter().collect())
+}
+
+/// Returns the `BlockHeaderData` of `block`, which has the given `root`.
+fn block_header_data<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    root: Hash256,
+    block: SignedBeaconBlock<T::EthSpec>,
+) -> Result<BlockHeaderData, ApiError> {
+    let canonical = block_root_at_slot(beacon_chain, block.slot())? == Some(root);
+    let finalized = canonical && beacon_chain.is_finalized_block(&root, block.slot())?;
+
+    Ok(BlockHeaderData {
+        root,
+        canonical,
+        finalized,
+        header: SignedBeaconBlockHeader {
+            message: block.message.block_header(),
+            signature: block.signature,
+        },
+    })
+}
+
+/// HTTP handler to return the attestations included in the block given by the `block_id` path
+/// parameter, as per the standard Eth2 API.
+pub fn get_block_attestations_by_id<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<Vec<Attestation<T::EthSpec>>>, ApiError> {
+    let block_id = parse_block_id(req.uri().path().rsplit('/').nth(1).unwrap_or(""))?;
+    let (_root, block) = block_from_id(&ctx.beacon_chain, block_id)?;
+
+    Ok(GenericResponse {
+        data: block.message.body.attestations.to_vec(),
     })
 }
 
+/// Returns the `SignedBeaconBlock` (and its root) identified by `block_id`.
+///
+/// The `finalized` and `justified` blocks are those of the checkpoints of the canonical head.
+pub fn block_from_id<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<(Hash256, SignedBeaconBlock<T::EthSpec>), ApiError> {
+    find_block_by_id(beacon_chain, block_id).map_err(|e| e.context("block_from_id"))
+}
+
+fn find_block_by_id<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    block_id: BlockId,
+) -> Result<(Hash256, SignedBeaconBlock<T::EthSpec>), ApiError> {
+    // A checkpoint root is zero until the first justification or finalization, in which case the
+    // checkpoint block is the genesis block.
+    let checkpoint_block_root = |root: Hash256| {
+        if root == Hash256::zero() {
+            beacon_chain.genesis_block_root
+        } else {
+            root
+        }
+    };
+
+    let block_root = match block_id {
+        BlockId::Head => beacon_chain.head_info()?.block_root,
+        BlockId::Genesis => beacon_chain.genesis_block_root,
+        BlockId::Finalized => {
+            checkpoint_block_root(beacon_chain.head_info()?.finalized_checkpoint.root)
+        }
+        BlockId::Justified => {
+            checkpoint_block_root(beacon_chain.head_info()?.current_justified_checkpoint.root)
+        }
+        BlockId::Slot(slot) => block_root_at_slot(beacon_chain, slot)?.ok_or_else(|| {
+            ApiError::NotFound(format!(
+                "Unable to find SignedBeaconBlock for slot {:?}",
+                slot
+            ))
+        })?,
+        BlockId::Root(root) => root,
+    };
+
+    let block = beacon_chain.store.get_block(&block_root)?.ok_or_else(|| {
+        ApiError::NotFound(format!(
+            "Unable to find SignedBeaconBlock for root {:?}",
+            block_root
+        ))
+    })?;
+
+    Ok((block_root, block))
+}
+
 /// HTTP handler to return a `SignedBeaconBlock` root at a given `slot`.
 pub fn get_block_root<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
@@ -152,15 +436,76 @@ pub fn stream_forks<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Body, A
     Ok(body)
 }
 
+/// Returns a server-sent event stream of the standard Eth2 API events whose topics are listed in
+/// the comma-separated `topics` query parameter.
+///
+/// A subscriber which falls too far behind is disconnected rather than delaying the chain. The
+/// subscription is dropped along with the response body when the client disconnects.
+pub fn stream_events<T: BeaconChainTypes>(
+    req: Request<()>,
+    ctx: Arc<Context<T>>,
+) -> Result<Body, ApiError> {
+    let topics = UrlQuery::from_request(&req)?
+        .all_of("topics")?
+        .iter()
+        .flat_map(|topics| topics.split(','))
+        .map(|topic| EventTopic::from_str(topic.trim()).map_err(ApiError::BadRequest))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if topics.is_empty() {
+        return Err(ApiError::BadRequest(
+            "At least one topic is required in the topics query parameter".to_string(),
+        ));
+    }
+
+    let receiver = ctx.standard_events.subscribe();
+    let log = request_log(&req, &ctx).clone();
+
+    let stream = stream::unfold(
+        (receiver, topics, log),
+        |(mut receiver, topics, log)| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if topics.contains(&event.topic()) => {
+                        let chunk = make_event_chunk(&event);
+                        return Some((chunk, (receiver, topics, log)));
+                    }
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(missed)) => {
+                        debug!(
+                            log,
+                            "Disconnecting slow event stream subscriber";
+                            "missed_events" => missed
+                        );
+                        return None;
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        },
+    );
+
+    Ok(Body::wrap_stream(stream))
+}
+
+/// Encodes `event` as a server-sent event, with its topic as the event type.
+fn make_event_chunk(event: &StandardEvent) -> Result<Bytes, serde_json::Error> {
+    let data = serde_json::to_string(event)?;
+    Ok(format!("event: {}\ndata: {}\n\n", event.topic().as_str(), data).into())
+}
+
 /// HTTP handler to which accepts a query string of a list of validator pubkeys and maps it to a
 /// `ValidatorResponse`.
 ///
 /// This method is limited to as many `pubkeys` that can fit in a URL. See `post_validators` for
 /// doing bulk requests.
+///
+/// The `fields` query parameter may be used to select fields of each response, see
+/// `ValidatorListing`.
 pub fn get_validators<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<Vec<ValidatorResponse>, ApiError> {
+) -> Result<ValidatorListing, ApiError> {
     let query = UrlQuery::from_request(&req)?;
 
     let validator_pubkeys = query
@@ -175,14 +520,60 @@ pub fn get_validators<T: BeaconChainTypes>(
         None
     };
 
-    validator_responses_by_pubkey(&ctx.beacon_chain, state_root_opt, validator_pubkeys)
+    Ok(ValidatorListing {
+        validators: validator_responses_by_pubkey(&ctx, state_root_opt, validator_pubkeys)?,
+        fields: query.validator_fields()?,
+    })
+}
+
+/// HTTP handler to return the balances of all validators in the canonical state at the given
+/// `slot`, indexed by validator index.
+///
+/// Balances for finalized slots are loaded from the freezer database without cloning the rest of
+/// the state. Returns `400 Bad Request` if this would require replaying more than
+/// `MAX_BALANCES_REPLAY_SLOTS` slots.
+///
+/// The response may be paginated, see `validator_index_range`.
+pub fn get_validator_balances<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<u64>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let slot = query.slot()?;
+    let beacon_chain = &ctx.beacon_chain;
+
+    let balances = if slot < beacon_chain.store.get_split_slot() {
+        beacon_chain
+            .store
+            .load_cold_balances_by_slot(slot, MAX_BALANCES_REPLAY_SLOTS)
+            .map_err(|e| match e {
+                store::Error::HotColdDBError(HotColdDBError::ReplayLimitExceeded {
+                    replay_slots,
+                    max_replay_slots,
+                    ..
+                }) => ApiError::BadRequest(format!(
+                    "Loading balances for slot {} requires replaying {} slots, the maximum is {}",
+                    slot, replay_slots, max_replay_slots
+                )),
+                e => e.into(),
+            })?
+    } else {
+        let (_root, state) = ctx.state_cache.state_at_slot(beacon_chain, slot)?;
+        state.balances.into()
+    };
+
+    let range = validator_index_range(&ctx, query, balances.len(), balances.len())?;
+    Ok(balances[range].to_vec())
 }
 
 /// HTTP handler to return all validators, each as a `ValidatorResponse`.
+///
+/// The response may be paginated, see `validator_index_range`, and the `fields` query parameter
+/// may be used to select fields of each response, see `ValidatorListing`.
 pub fn get_all_validators<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<Vec<ValidatorResponse>, ApiError> {
+) -> Result<ValidatorListing, ApiError> {
     let query = UrlQuery::from_request(&req)?;
 
     let state_root_opt = if let Some((_key, value)) = query.first_of_opt(&["state_root"]) {
@@ -191,21 +582,28 @@ pub fn get_all_validators<T: BeaconChainTypes>(
         None
     };
 
-    let mut state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
+    let fields = query.validator_fields()?;
+    let mut state = get_state_from_root_opt(&ctx, state_root_opt)?;
+    let num_validators = state.validators.len();
+    let range = validator_index_range(&ctx, query, num_validators, num_validators)?;
     state.update_pubkey_cache()?;
 
-    state
-        .validators
+    let validators = state.validators[range]
         .iter()
         .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone()))
-        .collect::<Result<Vec<_>, _>>()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ValidatorListing { validators, fields })
 }
 
 /// HTTP handler to return all active validators, each as a `ValidatorResponse`.
+///
+/// The response may be paginated, see `validator_index_range`, and the `fields` query parameter
+/// may be used to select fields of each response, see `ValidatorListing`.
 pub fn get_active_validators<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<Vec<ValidatorResponse>, ApiError> {
+) -> Result<ValidatorListing, ApiError> {
     let query = UrlQuery::from_request(&req)?;
 
     let state_root_opt = if let Some((_key, value)) = query.first_of_opt(&["state_root"]) {
@@ -214,15 +612,225 @@ pub fn get_active_validators<T: BeaconChainTypes>(
         None
     };
 
-    let mut state = get_state_from_root_opt(&ctx.beacon_chain, state_root_opt)?;
+    let fields = query.validator_fields()?;
+    let mut state = get_state_from_root_opt(&ctx, state_root_opt)?;
+    let current_epoch = state.current_epoch();
+    let num_active = state
+        .validators
+        .iter()
+        .filter(|validator| validator.is_active_at(current_epoch))
+        .count();
+    let range = validator_index_range(&ctx, query, state.validators.len(), num_active)?;
     state.update_pubkey_cache()?;
 
-    state
-        .validators
+    let validators = state.validators[range]
         .iter()
-        .filter(|validator| validator.is_active_at(state.current_epoch()))
+        .filter(|validator| validator.is_active_at(current_epoch))
         .map(|validator| validator_response_by_pubkey(&state, validator.pubkey.clone()))
-        .collect::<Result<Vec<_>, _>>()
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(ValidatorListing { validators, fields })
+}
+
+/// HTTP handler to return the validators in the registry of the state given by the `state_id`
+/// path parameter, as per the standard Eth2 API. See `state_from_id` for the accepted values.
+///
+/// The `id` query parameter restricts the response to the given validators, see
+/// `validator_indices_by_id`. The `status` query parameter, in the same format, restricts the
+/// response to validators with any of the given statuses or status categories, see
+/// `ValidatorStatus`.
+///
+/// The response may be paginated, see `paginate`.
+pub fn get_state_validators<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<PaginatedResponse<ValidatorData>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let state_id = req.uri().path().rsplit('/').nth(1).unwrap_or("");
+    let mut state = state_from_id(&ctx, state_id)?;
+
+    let status_filters = query
+        .all_of("status")?
+        .iter()
+        .flat_map(|statuses| statuses.split(','))
+        .map(|status| ValidatorStatus::from_str(status.trim()).map_err(ApiError::BadRequest))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let epoch = state.current_epoch();
+    let far_future_epoch = ctx.beacon_chain.spec.far_future_epoch;
+    let status_of = |state: &BeaconState<T::EthSpec>, index: usize| {
+        ValidatorStatus::from_validator(&state.validators[index], epoch, far_future_epoch)
+    };
+
+    let filtered = has_id_filter(query) || !status_filters.is_empty();
+    let indices = validator_indices_by_id(&mut state, query)?
+        .into_iter()
+        .filter(|index| {
+            let status = status_of(&state, *index);
+            status_filters.is_empty() || status_filters.iter().any(|filter| status.matches(*filter))
+        })
+        .collect::<Vec<_>>();
+
+    paginate(&ctx, query, indices, filtered, |index| {
+        Ok(ValidatorData {
+            index: index as u64,
+            balance: validator_balance(&state, index)?,
+            status: status_of(&state, index),
+            validator: state.validators[index].clone(),
+        })
+    })
+}
+
+/// HTTP handler to return the balances of the validators in the registry of the state given by
+/// the `state_id` path parameter, as per the standard Eth2 API.
+///
+/// The `id` query parameter restricts the response to the given validators, see
+/// `validator_indices_by_id`, and the response may be paginated, see `paginate`.
+pub fn get_state_validator_balances<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<PaginatedResponse<ValidatorBalanceData>, ApiError> {
+    let query = UrlQuery::from_request(&req)?;
+    let state_id = req.uri().path().rsplit('/').nth(1).unwrap_or("");
+    let mut state = state_from_id(&ctx, state_id)?;
+
+    let filtered = has_id_filter(query);
+    let indices = validator_indices_by_id(&mut state, query)?;
+
+    paginate(&ctx, query, indices, filtered, |index| {
+        Ok(ValidatorBalanceData {
+            index: index as u64,
+            balance: validator_balance(&state, index)?,
+        })
+    })
+}
+
+/// Returns `true` if the request has an `id` query parameter, see `validator_indices_by_id`.
+fn has_id_filter(query: UrlQuery) -> bool {
+    query.first_of_opt(&["id"]).is_some()
+}
+
+/// Returns the ascending, de-duplicated indices of the validators in `state` given by the `id`
+/// query parameter, or of all validators if there is no such parameter.
+///
+/// The parameter may be repeated or hold a comma-separated list of validator indices and/or
+/// `0x`-prefixed pubkeys. Ids which are not in the registry are ignored.
+fn validator_indices_by_id<E: EthSpec>(
+    state: &mut BeaconState<E>,
+    query: UrlQuery,
+) -> Result<Vec<usize>, ApiError> {
+    let ids = query
+        .all_of("id")?
+        .iter()
+        .flat_map(|ids| ids.split(','))
+        .map(|id| id.trim().to_string())
+        .collect::<Vec<_>>();
+
+    if ids.is_empty() {
+        return Ok((0..state.validators.len()).collect());
+    }
+
+    // Only pubkey ids need the pubkey cache, which is slow to build for a fresh state.
+    if ids.iter().any(|id| id.starts_with("0x")) {
+        state.update_pubkey_cache()?;
+    }
+
+    let mut indices = BTreeSet::new();
+    for id in &ids {
+        let index_opt = if id.starts_with("0x") {
+            state
+                .get_validator_index(&parse_pubkey_bytes(id)?)
+                .map_err(|e| {
+                    ApiError::ServerError(format!("Unable to read pubkey cache: {:?}", e))
+                })?
+        } else {
+            Some(parse_validator_index(id)? as usize)
+        };
+
+        if let Some(index) = index_opt.filter(|index| *index < state.validators.len()) {
+            indices.insert(index);
+        }
+    }
+
+    Ok(indices.into_iter().collect())
+}
+
+/// Maps the page of `items` selected by the `offset` and `limit` query parameters with `f`, see
+/// `validator_index_range`.
+///
+/// If the `items` were `filtered` by the request (e.g., by id or status), they are exempt from
+/// the `max_unfiltered_results` limit.
+///
+/// The total number of `items` is included in the response, so that clients can iterate over the
+/// pages.
+fn paginate<T, I, U, F>(
+    ctx: &Context<T>,
+    query: UrlQuery,
+    items: Vec<I>,
+    filtered: bool,
+    f: F,
+) -> Result<PaginatedResponse<U>, ApiError>
+where
+    T: BeaconChainTypes,
+    F: Fn(I) -> Result<U, ApiError>,
+{
+    let total = items.len();
+    let range = if filtered && query.pagination()?.is_none() {
+        0..total
+    } else {
+        validator_index_range(ctx, query, total, total)?
+    };
+
+    let data = items
+        .into_iter()
+        .skip(range.start)
+        .take(range.len())
+        .map(f)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(PaginatedResponse {
+        data,
+        meta: PaginationMetaData { total },
+    })
+}
+
+/// Returns the balance of the validator at `index` in `state`.
+fn validator_balance<E: EthSpec>(state: &BeaconState<E>, index: usize) -> Result<u64, ApiError> {
+    state
+        .balances
+        .get(index)
+        .copied()
+        .ok_or_else(|| ApiError::ServerError(format!("Invalid balances index: {:?}", index)))
+}
+
+/// Returns the state identified by `state_id`, which is one of `head`, `genesis`, `finalized`,
+/// `justified`, a slot or a `0x`-prefixed state root.
+///
+/// The `finalized` and `justified` states are those at the first slot of the respective
+/// checkpoint epoch in the canonical chain.
+fn state_from_id<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    state_id: &str,
+) -> Result<BeaconState<T::EthSpec>, ApiError> {
+    let beacon_chain = &ctx.beacon_chain;
+    let epoch_start_slot = |epoch: Epoch| epoch.start_slot(T::EthSpec::slots_per_epoch());
+
+    let slot = match state_id {
+        "head" => return Ok(beacon_chain.head()?.beacon_state),
+        "genesis" => Slot::new(0),
+        "finalized" => epoch_start_slot(beacon_chain.head_info()?.finalized_checkpoint.epoch),
+        "justified" => {
+            epoch_start_slot(beacon_chain.head_info()?.current_justified_checkpoint.epoch)
+        }
+        root if root.starts_with("0x") => {
+            return get_state_from_root_opt(ctx, Some(parse_root(root)?))
+        }
+        slot => parse_slot(slot)?,
+    };
+
+    ctx.state_cache
+        .state_at_slot(beacon_chain, slot)
+        .map(|(_root, state)| state)
 }
 
 /// HTTP handler to which accepts a `ValidatorRequest` and returns a `ValidatorResponse` for
@@ -242,43 +850,64 @@ pub fn post_validators<T: BeaconChainTypes>(
             ))
         })
         .and_then(|bulk_request| {
-            validator_responses_by_pubkey(
-                &ctx.beacon_chain,
-                bulk_request.state_root,
-                bulk_request.pubkeys,
-            )
+            validator_responses_by_pubkey(&ctx, bulk_request.state_root, bulk_request.pubkeys)
         })
 }
 
+/// Returns the range of validator indices to include in a response about a registry of
+/// `num_validators` validators, which would contain `num_results` entries if unfiltered.
+///
+/// If the `offset` or `limit` query parameters are supplied, the page they select is returned.
+/// Otherwise the whole registry is selected, unless `num_results` exceeds the configured
+/// `max_unfiltered_results`, in which case `400 Bad Request` is returned.
+pub(crate) fn validator_index_range<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    query: UrlQuery,
+    num_validators: usize,
+    num_results: usize,
+) -> Result<Range<usize>, ApiError> {
+    match query.pagination()? {
+        Some((offset, limit)) => {
+            let start = std::cmp::min(offset, num_validators);
+            let end = limit.map_or(num_validators, |limit| {
+                std::cmp::min(start.saturating_add(limit), num_validators)
+            });
+            Ok(start..end)
+        }
+        None => match ctx.config.max_unfiltered_results {
+            Some(max) if num_results > max => Err(ApiError::BadRequest(format!(
+                "Response would contain {} entries, more than the maximum of {}. \
+                 Use the offset and limit query parameters to paginate.",
+                num_results, max
+            ))),
+            _ => Ok(0..num_validators),
+        },
+    }
+}
+
 /// Returns either the state given by `state_root_opt`, or the canonical head state if it is
 /// `None`.
 fn get_state_from_root_opt<T: BeaconChainTypes>(
-    beacon_chain: &BeaconChain<T>,
+    ctx: &Context<T>,
     state_root_opt: Option<Hash256>,
 ) -> Result<BeaconState<T::EthSpec>, ApiError> {
     if let Some(state_root) = state_root_opt {
-        beacon_chain
-            .get_state(&state_root, None)
-            .map_err(|e| {
-                ApiError::ServerError(format!(
-                    "Database error when reading state root {}: {:?}",
-                    state_root, e
-                ))
-            })?
+        ctx.state_cache
+            .state_by_root(&ctx.beacon_chain, state_root)?
             .ok_or_else(|| ApiError::NotFound(format!("No state exists with root: {}", state_root)))
     } else {
-        Ok(beacon_chain.head()?.beacon_state)
+        Ok(ctx.beacon_chain.head()?.beacon_state)
     }
 }
 
 /// Maps a vec of `validator_pubkey` to a vec of `ValidatorResponse`, using the state at the given
 /// `state_root`. If `state_root.is_none()`, uses the canonial head state.
 fn validator_responses_by_pubkey<T: BeaconChainTypes>(
-    beacon_chain: &BeaconChain<T>,
+    ctx: &Context<T>,
     state_root_opt: Option<Hash256>,
     validator_pubkeys: Vec<PublicKeyBytes>,
 ) -> Result<Vec<ValidatorResponse>, ApiError> {
-    let mut state = get_state_from_root_opt(beacon_chain, state_root_opt)?;
+    let mut state = get_state_from_root_opt(ctx, state_root_opt)?;
     state.update_pubkey_cache()?;
 
     validator_pubkeys
@@ -327,7 +956,15 @@ fn validator_response_by_pubkey<E: EthSpec>(
     }
 }
 
-/// HTTP handler
+/// HTTP handler to return the committees at the given `epoch`.
+///
+/// The optional `slot` and `index` query parameters restrict the response to matching
+/// committees. If `include_pubkeys=true` is supplied then each committee also lists the public
+/// key of each member.
+///
+/// Committees of finalized epochs are computed from a state reconstructed in the freezer database.
+/// Returns `400 Bad Request` if this would require replaying more than
+/// `Config::max_committee_replay_slots` slots.
 pub fn get_committees<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
@@ -335,28 +972,82 @@ pub fn get_committees<T: BeaconChainTypes>(
     let query = UrlQuery::from_request(&req)?;
 
     let epoch = query.epoch()?;
+    let slot_filter = query
+        .first_of_opt(&["slot"])
+        .map(|(_, value)| parse_slot(&value))
+        .transpose()?;
+    let index_filter = query
+        .first_of_opt(&["index"])
+        .map(|(_, value)| parse_committee_index(&value))
+        .transpose()?;
+    let include_pubkeys = query
+        .first_of_opt(&["include_pubkeys"])
+        .map(|(_, value)| {
+            value.parse::<bool>().map_err(|e| {
+                ApiError::BadRequest(format!("Unable to parse include_pubkeys: {:?}", e))
+            })
+        })
+        .transpose()?
+        .unwrap_or(false);
 
-    let mut state =
-        get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+    // Committees for epochs prior to the previous epoch cannot be computed from the head state, so
+    // they are computed from the state at the start of the epoch.
+    let mut state = if epoch + 1 < ctx.beacon_chain.epoch()? {
+        state_at_epoch_start(
+            &ctx.beacon_chain,
+            &ctx.state_cache,
+            epoch,
+            ctx.config.max_committee_replay_slots,
+        )?
+    } else {
+        get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?
+    };
 
     let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch).map_err(|e| {
         ApiError::ServerError(format!("Failed to get state suitable for epoch: {:?}", e))
     })?;
 
-    state
-        .build_committee_cache(relative_epoch, &ctx.beacon_chain.spec)
+    ctx.beacon_chain
+        .build_committee_cache(&mut state, relative_epoch)
         .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
 
-    Ok(state
+    state
         .get_beacon_committees_at_epoch(relative_epoch)
         .map_err(|e| ApiError::ServerError(format!("Unable to get all committees: {:?}", e)))?
         .into_iter()
-        .map(|c| Committee {
-            slot: c.slot,
-            index: c.index,
-            committee: c.committee.to_vec(),
+        .filter(|c| slot_filter.map_or(true, |slot| c.slot == slot))
+        .filter(|c| index_filter.map_or(true, |index| c.index == index))
+        .map(|c| {
+            let pubkeys = if include_pubkeys {
+                let pubkeys = c
+                    .committee
+                    .iter()
+                    .map(|&validator_index| {
+                        state
+                            .validators
+                            .get(validator_index)
+                            .map(|validator| validator.pubkey.clone())
+                            .ok_or_else(|| {
+                                ApiError::ServerError(format!(
+                                    "Unknown validator in committee: {}",
+                                    validator_index
+                                ))
+                            })
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                Some(pubkeys)
+            } else {
+                None
+            };
+
+            Ok(Committee {
+                slot: c.slot,
+                index: c.index,
+                committee: c.committee.to_vec(),
+                pubkeys,
+            })
         })
-        .collect::<Vec<_>>())
+        .collect()
 }
 
 /// HTTP handler to return a `BeaconState` at a given `root` or `slot`.
@@ -385,17 +1076,18 @@ pub fn get_state<T: BeaconChainTypes>(
     };
 
     let (root, state): (Hash256, BeaconState<T::EthSpec>) = match (key.as_ref(), value) {
-        ("slot", value) => state_at_slot(&ctx.beacon_chain, parse_slot(&value)?)?,
+        ("slot", value) => ctx
+            .state_cache
+            .state_at_slot(&ctx.beacon_chain, parse_slot(&value)?)?,
         ("root", value) => {
-            let root = &parse_root(&value)?;
+            let root = parse_root(&value)?;
 
             let state = ctx
-                .beacon_chain
-                .store
-                .get_state(root, None)?
+                .state_cache
+                .state_by_root(&ctx.beacon_chain, root)?
                 .ok_or_else(|| ApiError::NotFound(format!("No state for root: {:?}", root)))?;
 
-            (*root, state)
+            (root, state)
         }
         _ => return Err(ApiError::ServerError("Unexpected query parameter".into())),
     };
@@ -427,74 +1119,258 @@ pub fn get_state_root<T: BeaconChainTypes>(
 pub fn get_genesis_state<T: BeaconChainTypes>(
     ctx: Arc<Context<T>>,
 ) -> Result<BeaconState<T::EthSpec>, ApiError> {
-    state_at_slot(&ctx.beacon_chain, Slot::new(0)).map(|(_root, state)| state)
+    ctx.state_cache
+        .state_at_slot(&ctx.beacon_chain, Slot::new(0))
+        .map(|(_root, state)| state)
+}
+
+/// Returns the optional `validator_index` query parameter of `req`.
+fn validator_index_filter(req: &Request<Vec<u8>>) -> Result<Option<u64>, ApiError> {
+    UrlQuery::from_request(req)?
+        .first_of_opt(&["validator_index"])
+        .map(|(_key, value)| parse_validator_index(&value))
+        .transpose()
+}
+
+/// Sorts operation pool `items` by `key`, breaking ties by their SSZ encoding so that the order
+/// never depends on the iteration order of the pool.
+///
+/// Returns the page of sorted items selected by the `offset` and `limit` query parameters of
+/// `req`, or all of them if neither is present.
+fn sorted_pool_page<I, K, F>(
+    req: &Request<Vec<u8>>,
+    mut items: Vec<I>,
+    key: F,
+) -> Result<Vec<I>, ApiError>
+where
+    I: Encode,
+    K: Ord,
+    F: Fn(&I) -> K,
+{
+    items.sort_by(|a, b| {
+        key(a)
+            .cmp(&key(b))
+            .then_with(|| a.as_ssz_bytes().cmp(&b.as_ssz_bytes()))
+    });
+
+    match UrlQuery::from_request(req)?.pagination()? {
+        Some((offset, limit)) => Ok(items
+            .into_iter()
+            .skip(offset)
+            .take(limit.unwrap_or(usize::max_value()))
+            .collect()),
+        None => Ok(items),
+    }
+}
+
+/// HTTP handler to return the attester slashings in the operation pool, ordered by the target
+/// and source epochs and attesting indices of their first attestation.
+///
+/// If the `validator_index` query parameter is supplied, only slashings where that validator is
+/// in the attesting indices of either attestation are returned. The response may be paginated
+/// with the `offset` and `limit` query parameters.
+pub fn get_pool_attester_slashings<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<AttesterSlashing<T::EthSpec>>, ApiError> {
+    let validator_index = validator_index_filter(&req)?;
+
+    let slashings = ctx
+        .beacon_chain
+        .op_pool
+        .get_all_attester_slashings(|slashing| {
+            validator_index.map_or(true, |validator_index| {
+                slashing
+                    .attestation_1
+                    .attesting_indices
+                    .iter()
+                    .chain(slashing.attestation_2.attesting_indices.iter())
+                    .any(|&index| index == validator_index)
+            })
+        });
+
+    sorted_pool_page(&req, slashings, |slashing| {
+        let attestation = &slashing.attestation_1;
+        (
+            attestation.data.target.epoch,
+            attestation.data.source.epoch,
+            attestation.attesting_indices.to_vec(),
+        )
+    })
+}
+
+/// HTTP handler to return the proposer slashings in the operation pool, ordered by proposer
+/// index and slot.
+///
+/// If the `validator_index` query parameter is supplied, only the slashing for that proposer is
+/// returned. The response may be paginated with the `offset` and `limit` query parameters.
+pub fn get_pool_proposer_slashings<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Vec<ProposerSlashing>, ApiError> {
+    let validator_index = validator_index_filter(&req)?;
+
+    let slashings = ctx
+        .beacon_chain
+        .op_pool
+        .get_all_proposer_slashings(|slashing| {
+            validator_index.map_or(true, |validator_index| {
+                slashing.signed_header_1.message.proposer_index == validator_index
+            })
+        });
+
+    sorted_pool_page(&req, slashings, |slashing| {
+        let header = &slashing.signed_header_1.message;
+        (header.proposer_index, header.slot)
+    })
+}
+
+/// Returns an error if `ctx` has no eth1 chain, in which case the operation pool is unused.
+fn require_eth1_chain<T: BeaconChainTypes>(
+    ctx: &Context<T>,
+    operation: &str,
+) -> Result<(), ApiError> {
+    if ctx.beacon_chain.eth1_chain.is_some() {
+        Ok(())
+    } else {
+        Err(ApiError::BadRequest(format!(
+            "Cannot insert {} on node without Eth1 connection.",
+            operation
+        )))
+    }
 }
 
+/// Verifies an operation submitted to the HTTP API with `verify` and, if it is new to the node,
+/// imports it with `import` and publishes the message returned by `import` to the network.
+///
+/// `name` describes the operation in errors, and the outcome is counted as a submission of
+/// `object`.
+fn publish_operation<T, V, E>(
+    ctx: &Context<T>,
+    object: SubmittedObject,
+    name: &str,
+    verify: impl FnOnce(&BeaconChain<T>) -> Result<ObservationOutcome<V>, E>,
+    import: impl FnOnce(&BeaconChain<T>, V) -> Result<PubsubMessage<T::EthSpec>, ApiError>,
+) -> Result<PublishOperationResponse, ApiError>
+where
+    T: BeaconChainTypes,
+    E: std::fmt::Debug,
+{
+    require_eth1_chain(ctx, name)?;
+
+    let outcome = verify(&ctx.beacon_chain).map_err(|e| {
+        metrics::inc_submitted_object(object, SubmissionOutcome::Invalid);
+        ApiError::BadRequest(format!("Error while verifying {}: {:?}", name, e))
+    })?;
+
+    match outcome {
+        ObservationOutcome::New(verified) => {
+            let message = import(&ctx.beacon_chain, verified).map_err(|e| {
+                metrics::inc_submitted_object(object, SubmissionOutcome::Invalid);
+                e
+            })?;
+            metrics::inc_submitted_object(object, SubmissionOutcome::Imported);
+            publish_operation_to_network::<T>(&ctx.network_chan, message)?;
+
+            Ok(PublishOperationResponse {
+                broadcast: true,
+                already_known: false,
+            })
+        }
+        ObservationOutcome::AlreadyKnown => {
+            metrics::inc_submitted_object(object, SubmissionOutcome::AlreadyKnown);
+            Ok(PublishOperationResponse {
+                broadcast: false,
+                already_known: true,
+            })
+        }
+    }
+}
+
+/// HTTP handler to verify, import and publish a `ProposerSlashing`.
+///
+/// Submitting a slashing that the node has already seen is not an error, it is reported via
+/// `PublishOperationResponse::already_known`.
 pub fn proposer_slashing<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<bool, ApiError> {
-    let body = req.into_body();
-
-    serde_json::from_slice::<ProposerSlashing>(&body)
-        .map_err(|e| format!("Unable to parse JSON into ProposerSlashing: {:?}", e))
-        .and_then(move |proposer_slashing| {
-            if ctx.beacon_chain.eth1_chain.is_some() {
-                let obs_outcome = ctx
-                    .beacon_chain
-                    .verify_proposer_slashing_for_gossip(proposer_slashing)
-                    .map_err(|e| format!("Error while verifying proposer slashing: {:?}", e))?;
-                if let ObservationOutcome::New(verified_proposer_slashing) = obs_outcome {
-                    ctx.beacon_chain
-                        .import_proposer_slashing(verified_proposer_slashing);
-                    Ok(())
-                } else {
-                    Err("Proposer slashing for that validator index already known".into())
-                }
-            } else {
-                Err("Cannot insert proposer slashing on node without Eth1 connection.".to_string())
-            }
-        })
-        .map_err(ApiError::BadRequest)?;
+) -> Result<PublishOperationResponse, ApiError> {
+    let proposer_slashing =
+        serde_json::from_slice::<ProposerSlashing>(&req.into_body()).map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into ProposerSlashing: {:?}",
+                e
+            ))
+        })?;
 
-    Ok(true)
+    publish_operation(
+        &ctx,
+        SubmittedObject::ProposerSlashing,
+        "proposer slashing",
+        |chain| chain.verify_proposer_slashing_for_gossip(proposer_slashing),
+        |chain, verified| {
+            let message = PubsubMessage::ProposerSlashing(Box::new(verified.as_inner().clone()));
+            chain.import_proposer_slashing(verified);
+            Ok(message)
+        },
+    )
 }
 
+/// HTTP handler to verify, import and publish an `AttesterSlashing`.
+///
+/// Submitting a slashing that only covers validators the node has already seen slashings for is
+/// not an error, it is reported via `PublishOperationResponse::already_known`.
 pub fn attester_slashing<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<bool, ApiError> {
-    let body = req.into_body();
-    serde_json::from_slice::<AttesterSlashing<T::EthSpec>>(&body)
-        .map_err(|e| {
+) -> Result<PublishOperationResponse, ApiError> {
+    let attester_slashing =
+        serde_json::from_slice::<AttesterSlashing<T::EthSpec>>(&req.into_body()).map_err(|e| {
             ApiError::BadRequest(format!(
                 "Unable to parse JSON into AttesterSlashing: {:?}",
                 e
             ))
-        })
-        .and_then(move |attester_slashing| {
-            if ctx.beacon_chain.eth1_chain.is_some() {
-                ctx.beacon_chain
-                    .verify_attester_slashing_for_gossip(attester_slashing)
-                    .map_err(|e| format!("Error while verifying attester slashing: {:?}", e))
-                    .and_then(|outcome| {
-                        if let ObservationOutcome::New(verified_attester_slashing) = outcome {
-                            ctx.beacon_chain
-                                .import_attester_slashing(verified_attester_slashing)
-                                .map_err(|e| {
-                                    format!("Error while importing attester slashing: {:?}", e)
-                                })
-                        } else {
-                            Err("Attester slashing only covers already slashed indices".to_string())
-                        }
-                    })
-                    .map_err(ApiError::BadRequest)
-            } else {
-                Err(ApiError::BadRequest(
-                    "Cannot insert attester slashing on node without Eth1 connection.".to_string(),
-                ))
-            }
         })?;
 
-    Ok(true)
+    publish_operation(
+        &ctx,
+        SubmittedObject::AttesterSlashing,
+        "attester slashing",
+        |chain| chain.verify_attester_slashing_for_gossip(attester_slashing),
+        |chain, verified| {
+            let message = PubsubMessage::AttesterSlashing(Box::new(verified.as_inner().clone()));
+            chain.import_attester_slashing(verified).map_err(|e| {
+                ApiError::BadRequest(format!("Error while importing attester slashing: {:?}", e))
+            })?;
+            Ok(message)
+        },
+    )
+}
+
+/// HTTP handler to verify, import and publish a `SignedVoluntaryExit`.
+///
+/// Submitting an exit for a validator that the node has already seen an exit for is not an
+/// error, it is reported via `PublishOperationResponse::already_known`.
+pub fn voluntary_exit<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<PublishOperationResponse, ApiError> {
+    let exit = serde_json::from_slice::<SignedVoluntaryExit>(&req.into_body()).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into SignedVoluntaryExit: {:?}",
+            e
+        ))
+    })?;
+
+    publish_operation(
+        &ctx,
+        SubmittedObject::VoluntaryExit,
+        "voluntary exit",
+        |chain| chain.verify_voluntary_exit_for_gossip(exit),
+        |chain, verified| {
+            let message = PubsubMessage::VoluntaryExit(Box::new(verified.as_inner().clone()));
+            chain.import_voluntary_exit(verified);
+            Ok(message)
+        },
+    )
 }
@@ -0,0 +1,175 @@
+//! Compresses HTTP API responses with gzip, if the client accepts it.
+
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use futures::stream::{self, StreamExt};
+use hyper::body::{Bytes, HttpBody};
+use hyper::header::{self, HeaderMap, HeaderValue};
+use hyper::{Body, Response};
+use std::io::{self, Write};
+use std::mem;
+
+/// Responses smaller than this are not worth compressing.
+const MIN_COMPRESSED_SIZE: u64 = 1024;
+
+/// The media types of responses which are compressed. Binary formats (e.g., SSZ) are already
+/// compact, and server-sent events must not be buffered by an encoder.
+const COMPRESSIBLE_MEDIA_TYPES: &[&str] = &["application/json", "application/yaml", "text/plain"];
+
+/// Returns `true` if the `Accept-Encoding` header in `headers` permits a gzip encoded response.
+pub fn accepts_gzip(headers: &HeaderMap) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|coding| {
+            let mut parts = coding.split(';').map(str::trim);
+            let name = parts.next().unwrap_or("");
+            let quality = parts
+                .find(|param| param.starts_with("q="))
+                .and_then(|param| param[2..].parse::<f32>().ok())
+                .unwrap_or(1.0);
+
+            (name.eq_ignore_ascii_case("gzip") || name == "*") && quality > 0.0
+        })
+}
+
+/// Compresses the body of `response` with gzip, unless it is small, empty, already encoded or
+/// not of a compressible media type.
+///
+/// The body is compressed as it is streamed, so a streamed response is never buffered in full.
+pub fn gzip_response(mut response: Response<Body>) -> Response<Body> {
+    let compressible = response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map_or(false, |media_type| {
+            COMPRESSIBLE_MEDIA_TYPES.contains(&media_type.trim())
+        });
+    let size_hint = response.body().size_hint();
+    let large = size_hint
+        .upper()
+        .map_or(true, |size| size >= MIN_COMPRESSED_SIZE);
+
+    if !compressible || !large || response.headers().contains_key(header::CONTENT_ENCODING) {
+        return response;
+    }
+
+    let headers = response.headers_mut();
+    headers.remove(header::CONTENT_LENGTH);
+    headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+    headers.append(header::VARY, HeaderValue::from_static("Accept-Encoding"));
+
+    let (parts, body) = response.into_parts();
+    Response::from_parts(parts, gzip_body(body))
+}
+
+/// Returns a body which yields the gzip compressed bytes of `body`.
+fn gzip_body(body: Body) -> Body {
+    let encoder = GzEncoder::new(vec![], Compression::default());
+
+    let compressed = stream::unfold(Some((body, encoder)), |state| async move {
+        let (mut body, mut encoder) = state?;
+
+        loop {
+            match body.next().await {
+                Some(Ok(chunk)) => {
+                    if let Err(e) = encoder.write_all(&chunk) {
+                        return Some((Err(e), None));
+                    }
+
+                    // The encoder only outputs once it has accumulated enough input.
+                    let output = mem::take(encoder.get_mut());
+                    if !output.is_empty() {
+                        return Some((Ok(Bytes::from(output)), Some((body, encoder))));
+                    }
+                }
+                Some(Err(e)) => return Some((Err(io::Error::new(io::ErrorKind::Other, e)), None)),
+                None => return Some((encoder.finish().map(Bytes::from), None)),
+            }
+        }
+    });
+
+    Body::wrap_stream(compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::read::GzDecoder;
+    use std::io::Read;
+
+    fn headers(accept_encoding: &[&str]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for value in accept_encoding {
+            headers.append(
+                header::ACCEPT_ENCODING,
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        headers
+    }
+
+    fn response(content_type: &str, body: Vec<u8>) -> Response<Body> {
+        Response::builder()
+            .header(header::CONTENT_TYPE, content_type)
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    fn body_bytes(response: Response<Body>) -> Vec<u8> {
+        futures::executor::block_on(hyper::body::to_bytes(response.into_body()))
+            .unwrap()
+            .to_vec()
+    }
+
+    #[test]
+    fn accept_encoding() {
+        assert!(accepts_gzip(&headers(&["gzip"])));
+        assert!(accepts_gzip(&headers(&["deflate, GZIP;q=0.5"])));
+        assert!(accepts_gzip(&headers(&["br", "gzip"])));
+        assert!(accepts_gzip(&headers(&["*"])));
+
+        assert!(!accepts_gzip(&headers(&[])));
+        assert!(!accepts_gzip(&headers(&["deflate, br"])));
+        assert!(!accepts_gzip(&headers(&["gzip;q=0"])));
+        assert!(!accepts_gzip(&headers(&["identity"])));
+    }
+
+    #[test]
+    fn compresses_large_json() {
+        let json = serde_json::to_vec(&vec![42_u64; 1024]).unwrap();
+
+        let compressed = gzip_response(response("application/json", json.clone()));
+        assert_eq!(
+            compressed.headers().get(header::CONTENT_ENCODING),
+            Some(&HeaderValue::from_static("gzip"))
+        );
+
+        let compressed_bytes = body_bytes(compressed);
+        assert!(compressed_bytes.len() < json.len());
+
+        let mut decompressed = vec![];
+        GzDecoder::new(&compressed_bytes[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, json);
+    }
+
+    #[test]
+    fn skips_small_and_binary_responses() {
+        let small = gzip_response(response("application/json", b"[1,2,3]".to_vec()));
+        assert!(!small.headers().contains_key(header::CONTENT_ENCODING));
+
+        let ssz = gzip_response(response("application/ssz", vec![0; 4096]));
+        assert!(!ssz.headers().contains_key(header::CONTENT_ENCODING));
+        assert_eq!(body_bytes(ssz), vec![0; 4096]);
+
+        let octet_stream = gzip_response(response("application/octet-stream", vec![0; 4096]));
+        assert!(!octet_stream
+            .headers()
+            .contains_key(header::CONTENT_ENCODING));
+    }
+}
@@ -1,5 +1,36 @@
+use crate::router::path_matches;
 use serde::{Deserialize, Serialize};
-use std::net::Ipv4Addr;
+use std::net::{IpAddr, Ipv4Addr};
+use std::path::PathBuf;
+use std::time::Duration;
+use url::Url;
+
+/// Endpoints which may need to load or regenerate a (possibly historical) `BeaconState`, as route
+/// paths in which a `{name}` segment matches any single segment.
+const STATE_QUERY_PATHS: &[&str] = &[
+    "/beacon/state",
+    "/beacon/state_root",
+    "/beacon/validators",
+    "/beacon/validators/all",
+    "/beacon/validators/active",
+    "/beacon/validator_balances",
+    "/beacon/committees",
+    "/validator/duties/all",
+    "/validator/duties/active",
+    "/eth/v1/beacon/states/{state_id}/validators",
+    "/eth/v1/beacon/states/{state_id}/validator_balances",
+    "/eth/v1/validator/duties/attester/{epoch}",
+];
+
+/// Prefixes of endpoints which are intended for debugging.
+const DEBUG_PATH_PREFIXES: &[&str] = &["/advanced/", "/lighthouse/"];
+
+/// The default maximum size of a request body.
+///
+/// A `SignedBeaconBlock` with every operation list full is a few hundred KiB of SSZ and several
+/// times that as JSON, so this leaves ample room for any legal block, or a large list of
+/// attestations or public keys.
+pub const DEFAULT_MAX_REQUEST_BODY_SIZE: u64 = 8 * 1024 * 1024;
 
 /// Defines the encoding for the API.
 #[derive(Clone, Serialize, Deserialize, Copy)]
@@ -29,27 +60,323 @@ impl From<&str> for ApiEncodingFormat {
     }
 }
 
+/// A limit on the rate of requests from a single IP address.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// The sustained number of requests permitted each second.
+    pub requests_per_second: u32,
+    /// The number of requests which may be made at once, after a period of inactivity.
+    pub burst: u32,
+}
+
 /// HTTP REST API Configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
     /// Enable the REST API server.
     pub enabled: bool,
-    /// The IPv4 address the REST API HTTP server will listen on.
-    pub listen_address: Ipv4Addr,
+    /// The IPv4 or IPv6 addresses the REST API HTTP server will listen on, each with `port`.
+    pub listen_addresses: Vec<IpAddr>,
     /// The port the REST API HTTP server will listen on.
     pub port: u16,
-    /// If something else than "", a 'Access-Control-Allow-Origin' header will be present in
-    /// responses.  Put *, to allow any origin.
-    pub allow_origin: String,
+    /// If set, the REST API HTTP server will also listen on a Unix domain socket at this path,
+    /// which only the current user may connect to. `listen_addresses` may be empty to only listen
+    /// on this socket.
+    pub listen_unix_socket: Option<PathBuf>,
+    /// The origins (e.g., `http://localhost:3000`) permitted to make cross-origin requests. If a
+    /// request's `Origin` header matches one of these, it is echoed in an
+    /// 'Access-Control-Allow-Origin' header. Use a single `*` to allow any origin.
+    pub allow_origins: Vec<String>,
+    /// If set, admin endpoints (e.g., `/lighthouse/op_pool/prune`) are enabled and require an
+    /// `Authorization: Bearer <admin_token>` header. If `None`, admin endpoints are disabled.
+    pub admin_token: Option<String>,
+    /// The maximum time to spend producing the response to a request, unless overridden below.
+    /// Requests which exceed it fail with a `504 Gateway Timeout` error. It does not apply to
+    /// streaming a response body (e.g., server-sent events). If `None`, there is no limit.
+    pub default_request_timeout: Option<Duration>,
+    /// The maximum time to spend on a request to a debug endpoint (e.g., `/advanced/*`). If
+    /// `None`, there is no limit.
+    pub debug_request_timeout: Option<Duration>,
+    /// The maximum time to spend on a request that reads a `BeaconState` (e.g., `/beacon/state`).
+    /// If `None`, there is no limit.
+    pub state_request_timeout: Option<Duration>,
+    /// The maximum number of slots the head may be behind the wall clock for
+    /// `/lighthouse/readiness` to report the node as ready.
+    pub readiness_max_head_distance: u64,
+    /// The minimum number of connected peers for `/lighthouse/readiness` to report the node as
+    /// ready.
+    pub readiness_min_peers: usize,
+    /// The maximum number of entries returned by a registry-wide endpoint (e.g.,
+    /// `/beacon/validators/all`) when no `offset` or `limit` is supplied. If `None`, there is no
+    /// limit.
+    pub max_unfiltered_results: Option<usize>,
+    /// If `true`, JSON, YAML and plain text responses are compressed with gzip for clients which
+    /// accept it.
+    pub compression: bool,
+    /// The maximum number of recently requested states (other than the head) to keep in memory.
+    /// If zero, states are not cached.
+    pub state_cache_size: usize,
+    /// The maximum total size in bytes (as SSZ) of the states in the state cache.
+    pub state_cache_max_bytes: usize,
+    /// The maximum number of slots which may be replayed on top of a freezer restore point to
+    /// compute the committees of a finalized epoch. Requests which would exceed it are rejected
+    /// with a `400 Bad Request` error.
+    pub max_committee_replay_slots: u64,
+    /// The maximum number of requests which may run on the blocking thread pool at once. Further
+    /// requests queue until one completes, or until their timeout expires.
+    pub max_blocking_tasks: usize,
+    /// The maximum size in bytes of a request body. Larger requests are rejected with a
+    /// `413 Payload Too Large` error.
+    pub max_request_body_size: u64,
+    /// Limits the rate of requests from each IP address, except to validator endpoints. If `None`,
+    /// these requests are not limited.
+    pub rate_limit: Option<RateLimit>,
+    /// Limits the rate of requests to validator endpoints (e.g., `/validator/duties`) from each IP
+    /// address, separately from `rate_limit`. If `None`, these requests are not limited.
+    pub validator_rate_limit: Option<RateLimit>,
+    /// A header (e.g., `X-Forwarded-For`) set by a trusted reverse proxy, from which the client IP
+    /// address is read for rate limiting. If `None`, the address of the connection is used.
+    pub trusted_proxy_header: Option<String>,
+    /// Requests which take longer than this to respond to are logged at `WARN`, rather than
+    /// `DEBUG`. If `None`, requests are never logged as slow.
+    pub slow_request_threshold: Option<Duration>,
+    /// If `true`, write an entry for each request to a JSON access log.
+    pub json_access_log: bool,
+    /// The file to write the JSON access log to. If `None`, the access log is written to stdout.
+    pub json_access_log_path: Option<PathBuf>,
+    /// A PEM file containing the TLS certificate chain. If both this and `tls_key_path` are set,
+    /// the server is served over HTTPS.
+    pub tls_cert_path: Option<PathBuf>,
+    /// A PEM file containing the (PKCS#8 or RSA) private key for `tls_cert_path`.
+    pub tls_key_path: Option<PathBuf>,
 }
 
 impl Default for Config {
     fn default() -> Self {
         Config {
             enabled: false,
-            listen_address: Ipv4Addr::new(127, 0, 0, 1),
+            listen_addresses: vec![IpAddr::V4(Ipv4Addr::new(127, 0, 0, 1))],
             port: 5052,
-            allow_origin: "".to_string(),
+            listen_unix_socket: None,
+            allow_origins: vec![],
+            admin_token: None,
+            default_request_timeout: Some(Duration::from_secs(30)),
+            debug_request_timeout: Some(Duration::from_secs(30)),
+            state_request_timeout: Some(Duration::from_secs(10)),
+            readiness_max_head_distance: 2,
+            readiness_min_peers: 1,
+            max_unfiltered_results: Some(16_384),
+            compression: true,
+            state_cache_size: 4,
+            state_cache_max_bytes: 256 * 1024 * 1024,
+            max_committee_replay_slots: 1024,
+            max_blocking_tasks: 32,
+            max_request_body_size: DEFAULT_MAX_REQUEST_BODY_SIZE,
+            rate_limit: None,
+            validator_rate_limit: None,
+            trusted_proxy_header: None,
+            slow_request_threshold: Some(Duration::from_secs(1)),
+            json_access_log: false,
+            json_access_log_path: None,
+            tls_cert_path: None,
+            tls_key_path: None,
+        }
+    }
+}
+
+/// The origins permitted to make cross-origin requests, validated from `Config::allow_origins`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AllowOrigin {
+    /// No 'Access-Control-Allow-Origin' header is sent.
+    None,
+    /// Any origin is allowed.
+    Any,
+    /// Only these origins, in their serialized form (e.g., `https://example.com:8080`), are
+    /// allowed.
+    List(Vec<String>),
+}
+
+impl AllowOrigin {
+    /// Validates `origins`, returning an error naming the first invalid entry.
+    ///
+    /// Each entry must either be `*` (which may not be mixed with other entries) or consist of
+    /// only a scheme, host and optional port.
+    pub fn from_config(origins: &[String]) -> Result<Self, String> {
+        if origins.is_empty() {
+            return Ok(AllowOrigin::None);
+        }
+
+        if origins.iter().any(|origin| origin == "*") {
+            return if origins.len() == 1 {
+                Ok(AllowOrigin::Any)
+            } else {
+                Err("Invalid allow-origin: * may not be combined with other origins".to_string())
+            };
+        }
+
+        origins
+            .iter()
+            .map(|origin| parse_origin(origin))
+            .collect::<Result<_, _>>()
+            .map(AllowOrigin::List)
+    }
+
+    /// Returns the value of the 'Access-Control-Allow-Origin' header for a request with the given
+    /// `Origin` header, if any.
+    pub fn header_value<'a>(&'a self, request_origin: Option<&'a str>) -> Option<&'a str> {
+        match self {
+            AllowOrigin::None => None,
+            AllowOrigin::Any => Some("*"),
+            AllowOrigin::List(origins) => {
+                request_origin.filter(|origin| origins.iter().any(|allowed| allowed == origin))
+            }
+        }
+    }
+}
+
+/// Parses `origin`, returning its serialized form (as would be sent by a browser in an `Origin`
+/// header).
+fn parse_origin(origin: &str) -> Result<String, String> {
+    let invalid = |reason: &str| format!("Invalid allow-origin {:?}: {}", origin, reason);
+
+    let url = Url::parse(origin).map_err(|e| invalid(&e.to_string()))?;
+
+    if url.scheme() != "http" && url.scheme() != "https" {
+        return Err(invalid("scheme must be http or https"));
+    }
+    if !url.username().is_empty() || url.password().is_some() {
+        return Err(invalid("must not contain credentials"));
+    }
+    if origin.ends_with('/') || url.path() != "/" || url.query().is_some() {
+        return Err(invalid("must not contain a path or query"));
+    }
+    if url.fragment().is_some() {
+        return Err(invalid("must not contain a fragment"));
+    }
+
+    Ok(url.origin().ascii_serialization())
+}
+
+impl Config {
+    /// Returns the maximum time to spend on a request to `path`, if any.
+    pub fn request_timeout(&self, path: &str) -> Option<Duration> {
+        if STATE_QUERY_PATHS
+            .iter()
+            .any(|template| path_matches(template, path))
+        {
+            self.state_request_timeout
+        } else if DEBUG_PATH_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            self.debug_request_timeout
+        } else {
+            self.default_request_timeout
         }
     }
+
+    /// Returns `true` if a request which took `duration` should be logged as slow.
+    pub fn is_slow_request(&self, duration: Duration) -> bool {
+        self.slow_request_threshold
+            .map_or(false, |threshold| duration > threshold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn origins(origins: &[&str]) -> Vec<String> {
+        origins.iter().map(|origin| origin.to_string()).collect()
+    }
+
+    #[test]
+    fn allow_origin_none() {
+        assert_eq!(AllowOrigin::from_config(&[]), Ok(AllowOrigin::None));
+        assert_eq!(AllowOrigin::None.header_value(Some("http://a.com")), None);
+    }
+
+    #[test]
+    fn allow_origin_any() {
+        let allow = AllowOrigin::from_config(&origins(&["*"])).unwrap();
+        assert_eq!(allow, AllowOrigin::Any);
+        assert_eq!(allow.header_value(Some("http://a.com")), Some("*"));
+        assert_eq!(allow.header_value(None), Some("*"));
+    }
+
+    #[test]
+    fn allow_origin_list() {
+        let allow = AllowOrigin::from_config(&origins(&["http://localhost:3000", "https://b.org"]))
+            .unwrap();
+
+        assert_eq!(
+            allow.header_value(Some("http://localhost:3000")),
+            Some("http://localhost:3000")
+        );
+        assert_eq!(
+            allow.header_value(Some("https://b.org")),
+            Some("https://b.org")
+        );
+        assert_eq!(allow.header_value(Some("https://c.org")), None);
+        assert_eq!(allow.header_value(Some("http://localhost:3001")), None);
+        assert_eq!(allow.header_value(None), None);
+    }
+
+    #[test]
+    fn request_timeouts() {
+        let config = Config {
+            default_request_timeout: Some(Duration::from_secs(1)),
+            debug_request_timeout: Some(Duration::from_secs(2)),
+            state_request_timeout: None,
+            ..Config::default()
+        };
+
+        assert_eq!(
+            config.request_timeout("/node/version"),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            config.request_timeout("/advanced/fork_choice"),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(config.request_timeout("/beacon/state"), None);
+        assert_eq!(
+            config.request_timeout("/eth/v1/beacon/states/head/validators"),
+            None
+        );
+        assert_eq!(
+            config.request_timeout("/eth/v1/beacon/states/head/validators/extra"),
+            Some(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn slow_request() {
+        let mut config = Config::default();
+        config.slow_request_threshold = Some(Duration::from_millis(500));
+        assert!(!config.is_slow_request(Duration::from_millis(500)));
+        assert!(config.is_slow_request(Duration::from_millis(501)));
+
+        config.slow_request_threshold = None;
+        assert!(!config.is_slow_request(Duration::from_secs(3600)));
+    }
+
+    #[test]
+    fn allow_origin_invalid() {
+        for invalid in &[
+            vec!["*", "https://b.org"],
+            vec!["https://b.org", "https://b.org/path"],
+            vec!["https://b.org/"],
+            vec!["https://b.org?q=1"],
+            vec!["https://user@b.org"],
+            vec!["ftp://b.org"],
+            vec!["b.org"],
+        ] {
+            let result = AllowOrigin::from_config(&origins(invalid));
+            assert!(result.is_err(), "{:?} should be invalid", invalid);
+        }
+
+        let err =
+            AllowOrigin::from_config(&origins(&["https://a.org", "https://b.org/x"])).unwrap_err();
+        assert!(err.contains("https://b.org/x"), "should name the entry");
+    }
 }
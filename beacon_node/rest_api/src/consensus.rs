@@ -1,4 +1,3 @@
-use crate::helpers::*;
 use crate::{ApiError, Context, UrlQuery};
 use beacon_chain::BeaconChainTypes;
 use hyper::Request;
@@ -58,7 +57,9 @@ pub fn get_vote_count<T: BeaconChainTypes>(
     // This is the last slot of the given epoch (one prior to the first slot of the next epoch).
     let target_slot = (epoch + 1).start_slot(T::EthSpec::slots_per_epoch()) - 1;
 
-    let (_root, state) = state_at_slot(&ctx.beacon_chain, target_slot)?;
+    let (_root, state) = ctx
+        .state_cache
+        .state_at_slot(&ctx.beacon_chain, target_slot)?;
     let spec = &ctx.beacon_chain.spec;
 
     let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
@@ -86,7 +87,9 @@ pub fn post_individual_votes<T: BeaconChainTypes>(
             // This is the last slot of the given epoch (one prior to the first slot of the next epoch).
             let target_slot = (epoch + 1).start_slot(T::EthSpec::slots_per_epoch()) - 1;
 
-            let (_root, mut state) = state_at_slot(&ctx.beacon_chain, target_slot)?;
+            let (_root, mut state) = ctx
+                .state_cache
+                .state_at_slot(&ctx.beacon_chain, target_slot)?;
             let spec = &ctx.beacon_chain.spec;
 
             let mut validator_statuses = ValidatorStatuses::new(&state, spec)?;
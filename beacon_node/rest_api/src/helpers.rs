@@ -1,15 +1,31 @@
-use crate::{ApiError, NetworkChannel};
+use crate::state_cache::StateCache;
+use crate::{ApiError, Context, NetworkChannel};
 use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
 use bls::PublicKeyBytes;
 use eth2_libp2p::PubsubMessage;
+use hyper::Request;
 use itertools::process_results;
 use network::NetworkMessage;
+use rest_types::BlockId;
+use slog::Logger;
 use ssz::Decode;
+use std::collections::BTreeMap;
+use store::hot_cold_store::HotColdDBError;
 use store::iter::AncestorIter;
 use types::{
     BeaconState, CommitteeIndex, Epoch, EthSpec, Hash256, RelativeEpoch, SignedBeaconBlock, Slot,
+    YamlConfig,
 };
 
+/// Returns the logger of `req`, which includes its `request_id`, or the logger of `ctx` if the
+/// request has none.
+pub fn request_log<'a, T: BeaconChainTypes, B>(
+    req: &'a Request<B>,
+    ctx: &'a Context<T>,
+) -> &'a Logger {
+    req.extensions().get::<Logger>().unwrap_or(&ctx.log)
+}
+
 /// Parse a slot.
 ///
 /// E.g., `"1234"`
@@ -39,6 +55,22 @@ pub fn parse_committee_index(string: &str) -> Result<CommitteeIndex, ApiError> {
         .map_err(|e| ApiError::BadRequest(format!("Unable to parse committee index: {:?}", e)))
 }
 
+/// Parse a validator index.
+///
+/// E.g., `"42"`
+pub fn parse_validator_index(string: &str) -> Result<u64, ApiError> {
+    string
+        .parse::<u64>()
+        .map_err(|e| ApiError::BadRequest(format!("Unable to parse validator index: {:?}", e)))
+}
+
+/// Parse a block identifier, see `BlockId`.
+///
+/// E.g., `"finalized"`
+pub fn parse_block_id(string: &str) -> Result<BlockId, ApiError> {
+    string.parse().map_err(ApiError::BadRequest)
+}
+
 /// Parse an SSZ object from some hex-encoded bytes.
 ///
 /// E.g., A signature is `"0x0000000000000000000000000000000000000000000000000000000000000000"`
@@ -93,14 +125,47 @@ pub fn parse_pubkey_bytes(string: &str) -> Result<PublicKeyBytes, ApiError> {
     }
 }
 
+/// Converts `config` into the flat map used by the standard Eth2 API for the spec, where every
+/// value is a string (e.g., `"SECONDS_PER_SLOT": "12"`).
+///
+/// Hex values such as the domain types keep their `0x` prefix. Fields which are specific to
+/// Lighthouse are included under their upper-snake-case names.
+pub fn config_to_string_map(config: &YamlConfig) -> Result<BTreeMap<String, String>, ApiError> {
+    let value = serde_json::to_value(config)
+        .map_err(|e| ApiError::ServerError(format!("Unable to serialize config: {:?}", e)))?;
+
+    match value {
+        serde_json::Value::Object(fields) => Ok(fields
+            .into_iter()
+            .map(|(key, value)| match value {
+                serde_json::Value::String(string) => (key, string),
+                other => (key, other.to_string()),
+            })
+            .collect()),
+        other => Err(ApiError::ServerError(format!(
+            "Config is not an object: {}",
+            other
+        ))),
+    }
+}
+
 /// Returns the root of the `SignedBeaconBlock` in the canonical chain of `beacon_chain` at the given
 /// `slot`, if possible.
 ///
 /// May return a root for a previous slot, in the case of skip slots.
+///
+/// Finalized slots are read straight from the freezer database where possible, rather than
+/// iterating backwards from the head.
 pub fn block_root_at_slot<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     target: Slot,
 ) -> Result<Option<Hash256>, ApiError> {
+    if target < beacon_chain.store.get_split_slot() {
+        if let Some(root) = beacon_chain.store.load_cold_block_root(target)? {
+            return Ok(Some(root));
+        }
+    }
+
     Ok(process_results(
         beacon_chain.rev_iter_block_roots()?,
         |iter| {
@@ -111,28 +176,62 @@ pub fn block_root_at_slot<T: BeaconChainTypes>(
     )?)
 }
 
-/// Returns a `BeaconState` and it's root in the canonical chain of `beacon_chain` at the given
-/// `slot`, if possible.
+/// Loads the `BeaconState` with the given `root` in the canonical chain at `slot` from the
+/// database.
 ///
-/// Will not return a state if the request slot is in the future. Will return states higher than
-/// the current head by skipping slots.
-pub fn state_at_slot<T: BeaconChainTypes>(
+/// States prior to the split slot are loaded straight from the freezer database, all others from
+/// the hot database.
+pub fn load_state_at_slot<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
+    root: Hash256,
     slot: Slot,
-) -> Result<(Hash256, BeaconState<T::EthSpec>), ApiError> {
-    let head = beacon_chain.head()?;
-
-    if head.beacon_state.slot == slot {
-        Ok((head.beacon_state_root, head.beacon_state))
+) -> Result<BeaconState<T::EthSpec>, ApiError> {
+    if slot < beacon_chain.store.get_split_slot() {
+        // The canonical state at a frozen slot is unique, so there is no need to look up the
+        // slot of `root` in the freezer before loading it.
+        Ok(beacon_chain.store.load_cold_state_by_slot(slot)?)
     } else {
-        let root = state_root_at_slot(beacon_chain, slot, StateSkipConfig::WithStateRoots)?;
-
-        let state: BeaconState<T::EthSpec> = beacon_chain
+        beacon_chain
             .store
             .get_state(&root, Some(slot))?
-            .ok_or_else(|| ApiError::NotFound(format!("Unable to find state at root {}", root)))?;
+            .ok_or_else(|| ApiError::NotFound(format!("Unable to find state at root {}", root)))
+    }
+}
+
+/// Returns the `BeaconState` in the canonical chain of `beacon_chain` at the start of `epoch`, from
+/// which the committees of `epoch` may be computed.
+///
+/// Frozen states are reconstructed from the nearest restore point, which is refused with a
+/// `400 Bad Request` error if it would require replaying more than `max_replay_slots` slots. Later
+/// states are loaded via the `state_cache`.
+pub fn state_at_epoch_start<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    state_cache: &StateCache<T::EthSpec>,
+    epoch: Epoch,
+    max_replay_slots: u64,
+) -> Result<BeaconState<T::EthSpec>, ApiError> {
+    let slot = epoch.start_slot(T::EthSpec::slots_per_epoch());
 
-        Ok((root, state))
+    if slot < beacon_chain.store.get_split_slot() {
+        beacon_chain
+            .store
+            .load_cold_state_by_slot_with_limit(slot, max_replay_slots)
+            .map_err(|e| match e {
+                store::Error::HotColdDBError(HotColdDBError::ReplayLimitExceeded {
+                    replay_slots,
+                    max_replay_slots,
+                    ..
+                }) => ApiError::BadRequest(format!(
+                    "Loading the state at the start of epoch {} requires replaying {} slots, the \
+                     maximum is {}",
+                    epoch, replay_slots, max_replay_slots
+                )),
+                e => e.into(),
+            })
+    } else {
+        state_cache
+            .state_at_slot(beacon_chain, slot)
+            .map(|(_, state)| state)
     }
 }
 
@@ -174,8 +273,15 @@ pub fn state_root_at_slot<T: BeaconChainTypes>(
     } else if head_state.slot > slot {
         // 3. The request slot is prior to the head slot.
         //
-        // Iterate through the state roots on the head state to find the root for that
-        // slot. Once the root is found, load it from the database.
+        // If the slot is finalized, read the root straight from the freezer database where
+        // possible. Otherwise, iterate through the state roots on the head state to find the
+        // root for that slot.
+        if slot < beacon_chain.store.get_split_slot() {
+            if let Some(root) = beacon_chain.store.load_cold_state_root(slot)? {
+                return Ok(root);
+            }
+        }
+
         process_results(
             head_state
                 .try_iter_ancestor_roots(beacon_chain.store.clone())
@@ -229,9 +335,173 @@ pub fn publish_beacon_block_to_network<T: BeaconChainTypes + 'static>(
     Ok(())
 }
 
+/// Publishes an operation (e.g., a slashing or voluntary exit) to the p2p network via gossipsub.
+pub fn publish_operation_to_network<T: BeaconChainTypes + 'static>(
+    chan: &NetworkChannel<T::EthSpec>,
+    message: PubsubMessage<T::EthSpec>,
+) -> Result<(), ApiError> {
+    if let Err(e) = chan.send(NetworkMessage::Publish {
+        messages: vec![message],
+    }) {
+        return Err(ApiError::ServerError(format!(
+            "Unable to send operation to network: {:?}",
+            e
+        )));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy, BlockingMigratorDiskHarnessType,
+    };
+    use std::sync::Arc;
+    use store::{HotColdDB, LevelDB, StoreConfig};
+    use tempdir::TempDir;
+    use types::{test_utils::generate_deterministic_keypairs, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    /// Returns a harness whose database has frozen a restore point, along with some later
+    /// states which can only be reconstructed from it.
+    fn get_migrated_harness(
+        db_path: &TempDir,
+    ) -> (
+        BeaconChainHarness<BlockingMigratorDiskHarnessType<E>>,
+        Arc<HotColdDB<E, LevelDB<E>, LevelDB<E>>>,
+    ) {
+        let config = StoreConfig {
+            slots_per_restore_point: 2 * E::slots_per_epoch(),
+            ..StoreConfig::default()
+        };
+        let log = slog::Logger::root(slog::Discard, slog::o!());
+        let store = Arc::new(
+            HotColdDB::open(
+                &db_path.path().join("hot_db"),
+                &db_path.path().join("cold_db"),
+                config,
+                E::default_spec(),
+                log,
+            )
+            .expect("disk store should initialize"),
+        );
+
+        let mut harness = BeaconChainHarness::new_with_disk_store(
+            MinimalEthSpec,
+            store.clone(),
+            generate_deterministic_keypairs(24),
+        );
+        harness.advance_slot();
+        harness.extend_chain(
+            E::slots_per_epoch() as usize * 6,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+
+        (harness, store)
+    }
+
+    /// Returns the root at `slot` from a reverse roots iterator.
+    fn find_root(
+        iter: impl Iterator<Item = Result<(Hash256, Slot), beacon_chain::BeaconChainError>>,
+        slot: Slot,
+    ) -> Hash256 {
+        process_results(iter, |mut iter| {
+            iter.find(|(_, root_slot)| *root_slot == slot)
+                .map(|(root, _)| root)
+        })
+        .expect("should iterate roots")
+        .expect("should find root")
+    }
+
+    #[test]
+    fn finalized_slots_resolve_around_split() {
+        let db_path = TempDir::new("finalized_slots").expect("should create temp dir");
+        let (harness, store) = get_migrated_harness(&db_path);
+        let chain = &harness.chain;
+
+        let split_slot = store.get_split_slot();
+        let restore_point_slot = store.get_latest_restore_point_slot();
+        assert!(restore_point_slot > 0, "a restore point should be frozen");
+        assert!(restore_point_slot + 1 < split_slot);
+
+        let slots = vec![
+            // Resolved entirely from the freezer tables.
+            restore_point_slot - 1,
+            // Exactly at a restore point.
+            restore_point_slot,
+            // Frozen, but only reconstructible by replaying blocks on the restore point.
+            restore_point_slot + 1,
+            split_slot - 1,
+            // The split state itself lives in the hot database.
+            split_slot,
+            split_slot + 1,
+        ];
+
+        for slot in slots {
+            let expected_state_root = find_root(chain.rev_iter_state_roots().unwrap(), slot);
+            let expected_block_root = find_root(chain.rev_iter_block_roots().unwrap(), slot);
+
+            let state_root = state_root_at_slot(chain, slot, StateSkipConfig::WithStateRoots)
+                .expect("should get state root");
+            let state = load_state_at_slot(chain, state_root, slot).expect("should get state");
+            assert_eq!(state_root, expected_state_root, "slot {}", slot);
+            assert_eq!(state.slot, slot, "slot {}", slot);
+            assert_eq!(state.canonical_root(), state_root, "slot {}", slot);
+
+            assert_eq!(
+                block_root_at_slot(chain, slot).expect("should get block root"),
+                Some(expected_block_root),
+                "slot {}",
+                slot
+            );
+        }
+    }
+
+    #[test]
+    fn epoch_start_states_are_replayed_within_limit() {
+        let db_path = TempDir::new("epoch_start_states").expect("should create temp dir");
+        let (harness, store) = get_migrated_harness(&db_path);
+        let chain = &harness.chain;
+        let state_cache = StateCache::new(0, 0);
+        let slots_per_epoch = E::slots_per_epoch();
+
+        // Restore points are every two epochs, so the start of epoch 1 is reconstructed by
+        // replaying a whole epoch on the restore point at genesis.
+        let epoch = Epoch::new(1);
+        assert!(epoch.end_slot(slots_per_epoch) < store.get_split_slot());
+
+        let state = state_at_epoch_start(chain, &state_cache, epoch, slots_per_epoch)
+            .expect("should replay within the limit");
+        let expected_root = find_root(
+            chain.rev_iter_state_roots().unwrap(),
+            epoch.start_slot(slots_per_epoch),
+        );
+        assert_eq!(state.canonical_root(), expected_root);
+
+        match state_at_epoch_start(chain, &state_cache, epoch, slots_per_epoch - 1) {
+            Err(ApiError::BadRequest(_)) => (),
+            other => panic!(
+                "should exceed the replay limit, got {:?}",
+                other.map(|s| s.slot)
+            ),
+        }
+
+        // Restore points and unfinalized states never need to be replayed.
+        let restore_point_epoch = Epoch::new(2);
+        let state = state_at_epoch_start(chain, &state_cache, restore_point_epoch, 0)
+            .expect("should load restore point");
+        assert_eq!(state.slot, restore_point_epoch.start_slot(slots_per_epoch));
+
+        let head_epoch = chain.head_info().unwrap().slot.epoch(slots_per_epoch);
+        assert!(head_epoch.start_slot(slots_per_epoch) >= store.get_split_slot());
+        let state = state_at_epoch_start(chain, &state_cache, head_epoch, 0)
+            .expect("should load unfinalized state");
+        assert_eq!(state.slot, head_epoch.start_slot(slots_per_epoch));
+    }
 
     #[test]
     fn parse_root_works() {
@@ -3,33 +3,48 @@ extern crate lazy_static;
 mod router;
 extern crate network as client_network;
 
+pub mod access_log;
 mod beacon;
+mod compression;
 pub mod config;
 mod consensus;
 mod helpers;
 mod lighthouse;
 mod metrics;
 mod node;
+mod rate_limit;
+mod state_cache;
+mod tls;
+#[cfg(unix)]
+mod unix_socket;
 mod url_query;
 mod validator;
 
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use access_log::AccessLog;
+use beacon_chain::{events::StandardEvent, BeaconChain, BeaconChainTypes};
 use bus::Bus;
 use client_network::NetworkMessage;
-pub use config::ApiEncodingFormat;
+pub use config::{AllowOrigin, ApiEncodingFormat, RateLimit};
 use eth2_config::Eth2Config;
 use eth2_libp2p::NetworkGlobals;
-use futures::future::TryFutureExt;
+use futures::future::{BoxFuture, FutureExt, TryFutureExt};
+use hyper::server::accept;
 use hyper::server::conn::AddrStream;
 use hyper::service::{make_service_fn, service_fn};
 use hyper::{Body, Request, Server};
 use parking_lot::Mutex;
+use rate_limit::RateLimiter;
 use rest_types::ApiError;
 use slog::{info, warn};
+use socket2::{Domain, Protocol, Socket, Type};
+use state_cache::StateCache;
+use std::io;
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::sync::mpsc;
+use tokio::net::TcpStream;
+use tokio::sync::{broadcast, mpsc, Semaphore};
+use tokio_rustls::server::TlsStream;
 use types::SignedBeaconBlockHash;
 use url_query::UrlQuery;
 
@@ -55,13 +70,34 @@ pub fn start_server<T: BeaconChainTypes>(
     freezer_db_path: PathBuf,
     eth2_config: Eth2Config,
     events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
-) -> Result<SocketAddr, hyper::Error> {
+    standard_events: broadcast::Sender<StandardEvent>,
+    eth1_service: Option<eth1::Service>,
+    eth1_config: eth1::Config,
+) -> Result<Vec<SocketAddr>, String> {
     let log = executor.log();
     let eth2_config = Arc::new(eth2_config);
+    let allow_origin = AllowOrigin::from_config(&config.allow_origins)?;
+    let access_log = if config.json_access_log {
+        Some(AccessLog::new(config.json_access_log_path.clone())?)
+    } else {
+        None
+    };
+
+    let rate_limiter = RateLimiter::from_config(config)?;
+    if config.max_blocking_tasks == 0 {
+        return Err("The HTTP server must allow at least one blocking task".to_string());
+    }
+
+    let tls_acceptor = match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => Some(tls::tls_acceptor(cert_path, key_path)?),
+        (None, None) => None,
+        _ => return Err("Both a TLS certificate and a TLS private key are required".to_string()),
+    };
 
     let context = Arc::new(Context {
         executor: executor.clone(),
         config: config.clone(),
+        allow_origin,
         beacon_chain,
         network_globals: network_info.network_globals.clone(),
         network_chan: network_info.network_chan,
@@ -70,58 +106,158 @@ pub fn start_server<T: BeaconChainTypes>(
         db_path,
         freezer_db_path,
         events,
+        standard_events,
+        op_pool_prune_lock: Mutex::new(()),
+        access_log,
+        rate_limiter,
+        blocking_permits: Arc::new(Semaphore::new(config.max_blocking_tasks)),
+        state_cache: StateCache::new(config.state_cache_size, config.state_cache_max_bytes),
+        eth1_service,
+        eth1_config,
     });
 
-    // Define the function that will build the request handler.
-    let make_service = make_service_fn(move |_socket: &AddrStream| {
-        let ctx = context.clone();
+    if config.listen_addresses.is_empty() && config.listen_unix_socket.is_none() {
+        return Err("At least one HTTP listen address or Unix socket is required".to_string());
+    }
+
+    // Bind every address before starting any server, so a failure to bind one address does not
+    // leave the others running.
+    let listeners = config
+        .listen_addresses
+        .iter()
+        .map(|&ip| bind(SocketAddr::new(ip, config.port)))
+        .collect::<Result<Vec<_>, _>>()?;
 
-        async move {
-            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
-                router::on_http_request(req, ctx.clone())
-            }))
+    #[cfg(unix)]
+    let unix_listener = config
+        .listen_unix_socket
+        .as_ref()
+        .map(|path| unix_socket::bind(path).map(|listener| (listener, path.clone())))
+        .transpose()?;
+    #[cfg(not(unix))]
+    {
+        if config.listen_unix_socket.is_some() {
+            return Err("Unix sockets are not supported on this platform".to_string());
         }
-    });
+    }
+
+    let mut listen_addrs = Vec::with_capacity(listeners.len());
+    for listener in listeners {
+        // The address the server is actually listening on may be different to the bind address if
+        // the bind port was 0 (this allows the OS to choose a free port).
+        let listen_addr = listener
+            .local_addr()
+            .map_err(|e| format!("Unable to read HTTP server address: {}", e))?;
+
+        // Build a channel to kill the HTTP server.
+        let exit = executor.exit();
+        let inner_log = log.clone();
+        let server_exit = async move {
+            let _ = exit.await;
+            info!(inner_log, "HTTP service shutdown"; "address" => listen_addr.to_string());
+        };
+
+        // Build the server, serving requests over TLS if it is configured. Either way, the server
+        // is configured to gracefully shutdown when the shutdown channel is triggered.
+        let server: BoxFuture<'static, Result<(), hyper::Error>> = if let Some(tls_acceptor) =
+            &tls_acceptor
+        {
+            let listener = tokio::net::TcpListener::from_std(listener)
+                .map_err(|e| format!("Unable to start HTTPS server on {}: {}", listen_addr, e))?;
+
+            let ctx = context.clone();
+            let make_service = make_service_fn(move |stream: &TlsStream<TcpStream>| {
+                let ctx = ctx.clone();
+                let remote_addr = stream.get_ref().0.peer_addr().ok();
+
+                async move {
+                    Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                        router::on_http_request(req, remote_addr, ctx.clone())
+                    }))
+                }
+            });
+            let incoming = tls::incoming(listener, tls_acceptor.clone(), log.clone());
+
+            Server::builder(accept::from_stream(incoming))
+                .serve(make_service)
+                .with_graceful_shutdown(server_exit)
+                .boxed()
+        } else {
+            // Define the function that will build the request handler.
+            let ctx = context.clone();
+            let make_service = make_service_fn(move |socket: &AddrStream| {
+                let ctx = ctx.clone();
+                let remote_addr = Some(socket.remote_addr());
 
-    let bind_addr = (config.listen_address, config.port).into();
-    let server = Server::bind(&bind_addr).serve(make_service);
-
-    // Determine the address the server is actually listening on.
-    //
-    // This may be different to `bind_addr` if bind port was 0 (this allows the OS to choose a free
-    // port).
-    let actual_listen_addr = server.local_addr();
-
-    // Build a channel to kill the HTTP server.
-    let exit = executor.exit();
-    let inner_log = log.clone();
-    let server_exit = async move {
-        let _ = exit.await;
-        info!(inner_log, "HTTP service shutdown");
+                async move {
+                    Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                        router::on_http_request(req, remote_addr, ctx.clone())
+                    }))
+                }
+            });
+
+            Server::from_tcp(listener)
+                .map_err(|e| format!("Unable to start HTTP server on {}: {}", listen_addr, e))?
+                .serve(make_service)
+                .with_graceful_shutdown(server_exit)
+                .boxed()
+        };
+
+        let inner_log = log.clone();
+        let server_future = server
+            .map_err(move |e| {
+                warn!(
+                inner_log,
+                "HTTP server failed to start, Unable to bind"; "address" => format!("{:?}", e)
+                )
+            })
+            .unwrap_or_else(|_| ());
+
+        info!(
+            log,
+            "HTTP API started";
+            "address" => format!("{}", listen_addr.ip()),
+            "port" => listen_addr.port(),
+            "tls" => tls_acceptor.is_some(),
+        );
+
+        executor.spawn_without_exit(server_future, "http");
+
+        listen_addrs.push(listen_addr);
+    }
+
+    #[cfg(unix)]
+    {
+        if let Some((listener, path)) = unix_listener {
+            unix_socket::serve(listener, path, context, &executor, log);
+        }
+    }
+
+    Ok(listen_addrs)
+}
+
+/// Binds a TCP listener to `addr`.
+///
+/// IPv6 listeners only accept IPv6 connections, so that `::` and `0.0.0.0` may both be bound on a
+/// dual-stack host.
+fn bind(addr: SocketAddr) -> Result<std::net::TcpListener, String> {
+    let bind = || -> io::Result<std::net::TcpListener> {
+        let domain = if addr.is_ipv6() {
+            Domain::ipv6()
+        } else {
+            Domain::ipv4()
+        };
+        let socket = Socket::new(domain, Type::stream(), Some(Protocol::tcp()))?;
+        if addr.is_ipv6() {
+            socket.set_only_v6(true)?;
+        }
+        // Matches `std::net::TcpListener::bind`, allowing a quick restart of the node.
+        #[cfg(unix)]
+        socket.set_reuse_address(true)?;
+        socket.bind(&addr.into())?;
+        socket.listen(1024)?;
+        Ok(socket.into_tcp_listener())
     };
 
-    // Configure the `hyper` server to gracefully shutdown when the shutdown channel is triggered.
-    let inner_log = log.clone();
-    let server_future = server
-        .with_graceful_shutdown(async {
-            server_exit.await;
-        })
-        .map_err(move |e| {
-            warn!(
-            inner_log,
-            "HTTP server failed to start, Unable to bind"; "address" => format!("{:?}", e)
-            )
-        })
-        .unwrap_or_else(|_| ());
-
-    info!(
-        log,
-        "HTTP API started";
-        "address" => format!("{}", actual_listen_addr.ip()),
-        "port" => actual_listen_addr.port(),
-    );
-
-    executor.spawn_without_exit(server_future, "http");
-
-    Ok(actual_listen_addr)
+    bind().map_err(|e| format!("Unable to bind HTTP server to {}: {}", addr, e))
 }
@@ -1,11 +1,25 @@
 //! This contains a collection of lighthouse specific HTTP endpoints.
 
-use crate::{ApiError, Context};
+use crate::beacon::{block_from_id, validator_index_range};
+use crate::helpers::parse_block_id;
+use crate::{ApiError, Context, UrlQuery};
 use beacon_chain::BeaconChainTypes;
 use eth2_libp2p::PeerInfo;
+use hyper::{header::AUTHORIZATION, Request};
+use rest_types::{
+    BlockBodySummary, Eth1DepositCacheSnapshot, Eth1DepositLog, OpPoolPruneResponse, OpPoolStats,
+    Readiness, SlotAttestationCount,
+};
 use serde::Serialize;
+use std::ops::Range;
 use std::sync::Arc;
-use types::EthSpec;
+use types::{EthSpec, Hash256};
+
+/// The number of most recent slots for which `op_pool_stats` reports attestation counts.
+pub const OP_POOL_STATS_SLOTS: usize = 8;
+
+/// The prefix of the `Authorization` header value expected by admin endpoints.
+const BEARER_PREFIX: &str = "Bearer ";
 
 /// Returns all known peers and corresponding information
 pub fn peers<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Vec<Peer<T::EthSpec>>, ApiError> {
@@ -37,6 +51,265 @@ pub fn connected_peers<T: BeaconChainTypes>(
         .collect())
 }
 
+/// Returns `Ok` if the node is ready to serve validators: genesis has occurred, the head is no
+/// more than `readiness_max_head_distance` slots behind the wall clock and at least
+/// `readiness_min_peers` peers are connected.
+///
+/// Otherwise, returns `ApiError::ServiceUnavailable` describing why the node is not ready.
+pub fn readiness<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<Readiness, ApiError> {
+    let current_slot = ctx
+        .beacon_chain
+        .slot()
+        .map_err(|_| ApiError::ServiceUnavailable("Genesis has not yet occurred".to_string()))?;
+    let head_slot = ctx
+        .beacon_chain
+        .head_info()
+        .map_err(|e| ApiError::ServiceUnavailable(format!("Unable to read head: {:?}", e)))?
+        .slot;
+    let connected_peers = ctx.network_globals.connected_peers();
+
+    let max_distance = ctx.config.readiness_max_head_distance;
+    if head_slot + max_distance < current_slot {
+        return Err(ApiError::ServiceUnavailable(format!(
+            "Head slot {} is more than {} slots behind the current slot {}",
+            head_slot, max_distance, current_slot
+        )));
+    }
+
+    let min_peers = ctx.config.readiness_min_peers;
+    if connected_peers < min_peers {
+        return Err(ApiError::ServiceUnavailable(format!(
+            "Connected to {} peers, at least {} are required",
+            connected_peers, min_peers
+        )));
+    }
+
+    Ok(Readiness {
+        head_slot,
+        current_slot,
+        connected_peers,
+    })
+}
+
+/// Returns the number of each type of operation in the operation pool, without cloning the
+/// pool contents.
+pub fn op_pool_stats<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<OpPoolStats, ApiError> {
+    let op_pool = &ctx.beacon_chain.op_pool;
+    let attestations_by_slot = op_pool.num_attestations_by_slot();
+
+    let mut attestations_per_slot = attestations_by_slot
+        .into_iter()
+        .rev()
+        .take(OP_POOL_STATS_SLOTS)
+        .map(|(slot, count)| SlotAttestationCount { slot, count })
+        .collect::<Vec<_>>();
+    attestations_per_slot.reverse();
+
+    Ok(OpPoolStats {
+        attestations: op_pool.num_attestations(),
+        attestations_per_slot,
+        attester_slashings: op_pool.num_attester_slashings(),
+        proposer_slashings: op_pool.num_proposer_slashings(),
+        voluntary_exits: op_pool.num_voluntary_exits(),
+        naive_aggregation_pool: ctx
+            .beacon_chain
+            .naive_aggregation_pool
+            .read()
+            .num_attestations(),
+    })
+}
+
+/// Returns a summary of the body of the block given by the `block_id` path parameter, omitting
+/// the attestations. See `BlockId` for the accepted values.
+pub fn get_block_body_summary<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<BlockBodySummary<T::EthSpec>, ApiError> {
+    let block_id = parse_block_id(req.uri().path().rsplit('/').nth(1).unwrap_or(""))?;
+    let (block_root, block) = block_from_id(&ctx.beacon_chain, block_id)?;
+
+    Ok(BlockBodySummary::from_block(block_root, &block))
+}
+
+/// Returns a summary of the eth1 deposit cache.
+///
+/// The deposit logs themselves are only included if the `include_deposits` query parameter is
+/// `true`, in which case they may be paginated with the `offset` and `limit` query parameters.
+pub fn eth1_deposit_cache<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<Eth1DepositCacheSnapshot, ApiError> {
+    let eth1_service = ctx.eth1_service.as_ref().ok_or_else(|| {
+        ApiError::ServiceUnavailable("The eth1 service is not enabled".to_string())
+    })?;
+
+    let query = UrlQuery::from_request(&req)?;
+    let include_deposits = query
+        .first_of_opt(&["include_deposits"])
+        .map(|(_, value)| {
+            value.parse::<bool>().map_err(|e| {
+                ApiError::BadRequest(format!("Unable to parse include_deposits: {:?}", e))
+            })
+        })
+        .transpose()?
+        .unwrap_or(false);
+
+    let range = if include_deposits {
+        let num_deposits = eth1_service.deposits().read().cache.len();
+        Some(validator_index_range(
+            &ctx,
+            query,
+            num_deposits,
+            num_deposits,
+        )?)
+    } else {
+        None
+    };
+
+    Ok(deposit_cache_snapshot(eth1_service, range))
+}
+
+/// Reads the deposit cache of `eth1_service`, including the deposit logs in `range` if it is
+/// `Some`.
+fn deposit_cache_snapshot(
+    eth1_service: &eth1::Service,
+    range: Option<Range<usize>>,
+) -> Eth1DepositCacheSnapshot {
+    let deposits = eth1_service.deposits().read();
+
+    Eth1DepositCacheSnapshot {
+        deposit_count: deposits.cache.len() as u64,
+        last_processed_block: deposits.last_processed_block,
+        deposit_root: deposits.cache.deposit_root(),
+        deposits: range.map(|range| {
+            deposits
+                .cache
+                .iter()
+                .skip(range.start)
+                .take(range.len())
+                .map(|log| Eth1DepositLog {
+                    deposit_data: log.deposit_data.clone(),
+                    block_number: log.block_number,
+                    index: log.index,
+                    signature_is_valid: log.signature_is_valid,
+                })
+                .collect()
+        }),
+    }
+}
+
+/// Removes all operations from the operation pool which are no longer valid with respect to the
+/// latest finalized state, returning the number of each type of operation that was removed.
+///
+/// Requires the admin token. Only one prune may run at a time; concurrent requests receive a
+/// `409 Conflict`.
+pub fn prune_op_pool<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<OpPoolPruneResponse, ApiError> {
+    check_admin_token(&req, &ctx)?;
+
+    let _prune_guard = ctx
+        .op_pool_prune_lock
+        .try_lock()
+        .ok_or_else(|| ApiError::Conflict("Op pool prune already in progress".to_string()))?;
+
+    let chain = &ctx.beacon_chain;
+    let head_info = chain.head_info()?;
+
+    // The finalized checkpoint root is zero until the first finalization, in which case the
+    // genesis block is the finalized block.
+    let finalized_block_root = if head_info.finalized_checkpoint.root == Hash256::zero() {
+        chain.genesis_block_root
+    } else {
+        head_info.finalized_checkpoint.root
+    };
+    let finalized_block = chain.get_block(&finalized_block_root)?.ok_or_else(|| {
+        ApiError::ServerError(format!(
+            "Unable to find finalized block {:?}",
+            finalized_block_root
+        ))
+    })?;
+    let finalized_state = chain
+        .get_state(&finalized_block.state_root(), Some(finalized_block.slot()))?
+        .ok_or_else(|| {
+            ApiError::ServerError(format!(
+                "Unable to find finalized state {:?}",
+                finalized_block.state_root()
+            ))
+        })?;
+
+    // The pool is only locked for the duration of `prune_all`, so operations may be inserted by
+    // other tasks whilst we prune. Saturate to avoid reporting spurious counts in that case.
+    let op_pool = &chain.op_pool;
+    let attestations = op_pool.num_attestations();
+    let attester_slashings = op_pool.num_attester_slashings();
+    let proposer_slashings = op_pool.num_proposer_slashings();
+    let voluntary_exits = op_pool.num_voluntary_exits();
+
+    op_pool.prune_all(&finalized_state, head_info.fork);
+
+    Ok(OpPoolPruneResponse {
+        attestations: attestations.saturating_sub(op_pool.num_attestations()),
+        attester_slashings: attester_slashings.saturating_sub(op_pool.num_attester_slashings()),
+        proposer_slashings: proposer_slashings.saturating_sub(op_pool.num_proposer_slashings()),
+        voluntary_exits: voluntary_exits.saturating_sub(op_pool.num_voluntary_exits()),
+    })
+}
+
+/// Reopens the JSON access log file, e.g., after it has been rotated.
+///
+/// Requires the admin token. Returns `false` if the access log is disabled.
+pub fn reopen_access_log<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<bool, ApiError> {
+    check_admin_token(&req, &ctx)?;
+
+    match &ctx.access_log {
+        Some(access_log) => access_log
+            .reopen()
+            .map(|()| true)
+            .map_err(ApiError::ServerError),
+        None => Ok(false),
+    }
+}
+
+/// Returns an error unless `req` carries an `Authorization: Bearer <token>` header matching the
+/// configured admin token.
+///
+/// Admin endpoints are disabled (and always return an error) if no admin token is configured.
+pub(crate) fn check_admin_token<T: BeaconChainTypes>(
+    req: &Request<Vec<u8>>,
+    ctx: &Context<T>,
+) -> Result<(), ApiError> {
+    let admin_token = ctx.config.admin_token.as_ref().ok_or_else(|| {
+        ApiError::NotFound("Admin endpoints are disabled, no admin token is set".to_string())
+    })?;
+
+    let provided = req
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| value.starts_with(BEARER_PREFIX))
+        .map(|value| &value[BEARER_PREFIX.len()..])
+        .ok_or_else(|| ApiError::Unauthorized("Missing admin token".to_string()))?;
+
+    // Compare without short-circuiting so the response time does not leak the token.
+    let matches = provided.len() == admin_token.len()
+        && provided
+            .bytes()
+            .zip(admin_token.bytes())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0;
+
+    if matches {
+        Ok(())
+    } else {
+        Err(ApiError::Unauthorized("Invalid admin token".to_string()))
+    }
+}
+
 /// Information returned by `peers` and `connected_peers`.
 #[derive(Clone, Debug, Serialize)]
 #[serde(bound = "T: EthSpec")]
@@ -46,3 +319,71 @@ pub struct Peer<T: EthSpec> {
     /// The PeerInfo associated with the peer.
     peer_info: PeerInfo<T>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eth1::DepositLog;
+    use slog::{o, Logger};
+    use types::{DepositData, MainnetEthSpec, PublicKeyBytes, SignatureBytes};
+
+    fn deposit_log(index: u64) -> DepositLog {
+        DepositLog {
+            deposit_data: DepositData {
+                pubkey: PublicKeyBytes::empty(),
+                withdrawal_credentials: Hash256::from_low_u64_be(index),
+                amount: 32_000_000_000,
+                signature: SignatureBytes::empty(),
+            },
+            block_number: 100 + index,
+            index,
+            signature_is_valid: index % 2 == 0,
+        }
+    }
+
+    #[test]
+    fn deposit_cache_snapshot_counts() {
+        let service = eth1::Service::new(
+            eth1::Config::default(),
+            Logger::root(slog::Discard, o!()),
+            MainnetEthSpec::default_spec(),
+        );
+
+        let empty = deposit_cache_snapshot(&service, Some(0..0));
+        assert_eq!(empty.deposit_count, 0);
+        assert_eq!(empty.last_processed_block, None);
+        assert_eq!(empty.deposits, Some(vec![]));
+
+        {
+            let mut deposits = service.deposits().write();
+            for index in 0..5 {
+                deposits
+                    .cache
+                    .insert_log(deposit_log(index))
+                    .expect("should insert log");
+            }
+            deposits.last_processed_block = Some(104);
+        }
+
+        let summary = deposit_cache_snapshot(&service, None);
+        assert_eq!(summary.deposit_count, 5);
+        assert_eq!(summary.last_processed_block, Some(104));
+        assert_eq!(
+            summary.deposit_root,
+            service.deposits().read().cache.deposit_root()
+        );
+        assert_ne!(summary.deposit_root, empty.deposit_root);
+        assert_eq!(summary.deposits, None);
+
+        let page = deposit_cache_snapshot(&service, Some(1..3))
+            .deposits
+            .expect("should include deposits");
+        assert_eq!(
+            page.iter().map(|log| log.index).collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(page[0].block_number, 101);
+        assert!(!page[0].signature_is_valid);
+        assert!(page[1].signature_is_valid);
+    }
+}
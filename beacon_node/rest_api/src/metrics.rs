@@ -24,11 +24,33 @@ lazy_static! {
         "Count of HTTP that did not return 200 OK",
         &["endpoint"]
     );
+    pub static ref BEACON_HTTP_API_RATE_LIMITED_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_http_api_rate_limited_total",
+            "Count of HTTP requests rejected for exceeding a rate limit",
+            &["endpoint"]
+        );
+    pub static ref BEACON_HTTP_API_STATE_CACHE_HITS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_state_cache_hits_total",
+            "Count of states served from the HTTP API state cache"
+        );
+    pub static ref BEACON_HTTP_API_STATE_CACHE_MISSES_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_state_cache_misses_total",
+            "Count of states which were not in the HTTP API state cache"
+        );
     pub static ref BEACON_HTTP_API_TIMES_TOTAL: Result<HistogramVec> = try_create_histogram_vec(
         "beacon_http_api_times_total",
         "Duration to process HTTP requests",
         &["endpoint"]
     );
+    pub static ref BEACON_HTTP_API_SUBMITTED_OBJECTS_TOTAL: Result<IntCounterVec> =
+        try_create_int_counter_vec(
+            "beacon_http_api_submitted_objects_total",
+            "Count of objects submitted via the HTTP API, by type and outcome",
+            &["object", "outcome"]
+        );
     pub static ref REQUEST_RESPONSE_TIME: Result<Histogram> = try_create_histogram(
         "http_server_request_duration_seconds",
         "Time taken to build a response to a HTTP request"
@@ -67,6 +89,64 @@ lazy_static! {
         try_create_float_gauge("system_loadavg_15", "Loadavg over 15 minutes");
 }
 
+/// The type of an object submitted via the HTTP API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubmittedObject {
+    Block,
+    Attestation,
+    AggregateAndProof,
+    ProposerSlashing,
+    AttesterSlashing,
+    VoluntaryExit,
+}
+
+impl SubmittedObject {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SubmittedObject::Block => "block",
+            SubmittedObject::Attestation => "attestation",
+            SubmittedObject::AggregateAndProof => "aggregate_and_proof",
+            SubmittedObject::ProposerSlashing => "proposer_slashing",
+            SubmittedObject::AttesterSlashing => "attester_slashing",
+            SubmittedObject::VoluntaryExit => "voluntary_exit",
+        }
+    }
+}
+
+/// The outcome of processing an object submitted via the HTTP API.
+///
+/// Internal errors are not counted, since they say nothing about the submitted object.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SubmissionOutcome {
+    /// The object was published and imported.
+    Imported,
+    /// The object was published, but could not be imported (e.g., into fork choice or a pool).
+    BroadcastWithoutImport,
+    /// The object was rejected and not published.
+    Invalid,
+    /// The object had already been seen and was not published again.
+    AlreadyKnown,
+}
+
+impl SubmissionOutcome {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SubmissionOutcome::Imported => "imported",
+            SubmissionOutcome::BroadcastWithoutImport => "broadcast_without_import",
+            SubmissionOutcome::Invalid => "invalid",
+            SubmissionOutcome::AlreadyKnown => "already_known",
+        }
+    }
+}
+
+/// Increments the count of `object`s submitted via the HTTP API with the given `outcome`.
+pub fn inc_submitted_object(object: SubmittedObject, outcome: SubmissionOutcome) {
+    inc_counter_vec(
+        &BEACON_HTTP_API_SUBMITTED_OBJECTS_TOTAL,
+        &[object.as_str(), outcome.as_str()],
+    );
+}
+
 /// Returns the full set of Prometheus metrics for the Beacon Node application.
 ///
 /// # Note
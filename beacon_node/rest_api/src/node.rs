@@ -1,9 +1,307 @@
-use crate::{ApiError, Context};
+use crate::{lighthouse::check_admin_token, router::ROUTES, ApiError, Context, UrlQuery};
 use beacon_chain::BeaconChainTypes;
+use eth1::http::Eth1NetworkId;
 use eth2_libp2p::types::SyncState;
-use rest_types::{SyncingResponse, SyncingStatus};
+use eth2_libp2p::{multiaddr::Protocol, EnrExt, PeerConnectionStatus, PeerId, PeerInfo};
+use hyper::{Request, StatusCode};
+use lighthouse_version::version_with_platform;
+use rest_types::{
+    ApiIndex, ApiRoute, DepositContractData, GenericResponse, IdentityData, IdentityMetaData,
+    PeerCountData, PeerData, PeerDirection, PeerMetaData, PeerResponse, PeerState, PeersMetaData,
+    PeersResponse, SyncingResponse, SyncingStatus,
+};
+use std::str::FromStr;
 use std::sync::Arc;
-use types::Slot;
+use types::{Address, EthSpec, Slot};
+
+/// HTTP handler to return the eth1 deposit contract of the network, as per the standard Eth2 API.
+///
+/// The chain id is the configured eth1 network id. If eth1 is disabled there is no such
+/// configuration, so the chain id is instead inferred from the name of the network.
+pub fn get_deposit_contract<T: BeaconChainTypes>(
+    _req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<DepositContractData>, ApiError> {
+    let network_id = match &ctx.eth1_service {
+        Some(eth1_service) => eth1_service.config().network_id.clone(),
+        None => eth1_network_id_for_config(ctx.eth2_config.config_name()),
+    };
+    let address = ctx
+        .eth1_config
+        .deposit_contract_address
+        .trim_start_matches("0x")
+        .parse::<Address>()
+        .map_err(|e| ApiError::ServerError(format!("Invalid deposit contract address: {:?}", e)))?;
+
+    Ok(GenericResponse {
+        data: DepositContractData {
+            chain_id: network_id.into(),
+            address,
+        },
+    })
+}
+
+/// Returns the eth1 network where the deposit contract of the named Eth2 network is deployed.
+///
+/// Only mainnet uses the eth1 mainnet; all other networks (i.e., testnets) use Goerli.
+fn eth1_network_id_for_config(config_name: &str) -> Eth1NetworkId {
+    match config_name {
+        "mainnet" => Eth1NetworkId::Mainnet,
+        _ => eth1::DEFAULT_NETWORK_ID,
+    }
+}
+
+/// Returns the name and version of the server, along with every route it serves beneath the
+/// request path (e.g., only the `/eth/v1` routes for `/eth/v1`).
+///
+/// Routes which require the admin token are only listed if `req` carries a valid admin token.
+pub fn get_api_index<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<ApiIndex, ApiError> {
+    let include_admin = check_admin_token(&req, &ctx).is_ok();
+    let prefix = format!("{}/", req.uri().path().trim_end_matches('/'));
+
+    let mut routes: Vec<ApiRoute> = Vec::with_capacity(ROUTES.len());
+    for route in ROUTES
+        .iter()
+        .filter(|route| include_admin || !route.admin)
+        .filter(|route| route.path == req.uri().path() || route.path.starts_with(&prefix))
+    {
+        // A route may be matched by several arms, e.g., to select an encoding.
+        if !routes
+            .iter()
+            .any(|known| known.method == route.method && known.path == route.path)
+        {
+            routes.push(ApiRoute {
+                method: route.method.to_string(),
+                path: route.path.to_string(),
+            });
+        }
+    }
+
+    Ok(ApiIndex {
+        name: "Lighthouse".to_string(),
+        version: version_with_platform(),
+        routes,
+    })
+}
+
+/// Returns the identity of the node on the p2p network, in the format of the standard Eth2 API.
+pub fn get_identity<T: BeaconChainTypes>(
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<IdentityData<T::EthSpec>>, ApiError> {
+    let network_globals = &ctx.network_globals;
+    let peer_id = network_globals.local_peer_id();
+    let enr = network_globals.local_enr();
+    let metadata = network_globals.local_metadata();
+
+    let p2p_addresses = network_globals
+        .listen_multiaddrs()
+        .into_iter()
+        .map(|mut multiaddr| {
+            multiaddr.push(Protocol::P2p(peer_id.clone().into()));
+            multiaddr.to_string()
+        })
+        .collect();
+
+    let discovery_addresses = enr
+        .multiaddr_p2p()
+        .into_iter()
+        .filter(|multiaddr| {
+            multiaddr
+                .iter()
+                .any(|protocol| matches!(protocol, Protocol::Udp(_)))
+        })
+        .map(|multiaddr| multiaddr.to_string())
+        .collect();
+
+    Ok(GenericResponse {
+        data: IdentityData {
+            peer_id: peer_id.to_base58(),
+            enr: enr.to_base64(),
+            p2p_addresses,
+            discovery_addresses,
+            metadata: IdentityMetaData {
+                seq_number: metadata.seq_number,
+                attnets: metadata.attnets,
+            },
+        },
+    })
+}
+
+/// Returns the peers known to the node, in the format of the standard Eth2 API.
+///
+/// The peers may be filtered by the comma-separated `state` and `direction` query parameters.
+pub fn get_peers<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<PeersResponse, ApiError> {
+    let states = parse_peer_filter::<PeerState>(&req, "state")?;
+    let directions = parse_peer_filter::<PeerDirection>(&req, "direction")?;
+
+    let data: Vec<PeerData> = ctx
+        .network_globals
+        .peers
+        .read()
+        .peers()
+        .map(|(peer_id, info)| peer_data(peer_id, info))
+        .filter(|peer| states.is_empty() || states.contains(&peer.state))
+        .filter(|peer| {
+            directions.is_empty()
+                || peer
+                    .direction
+                    .map_or(false, |direction| directions.contains(&direction))
+        })
+        .collect();
+
+    Ok(PeersResponse {
+        meta: PeersMetaData { count: data.len() },
+        data,
+    })
+}
+
+/// Returns the peer with the base58 encoded peer ID in the last segment of the request path, in
+/// the format of the standard Eth2 API.
+///
+/// The `meta` of the response contains Lighthouse-specific information about the peer.
+pub fn get_peer<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<PeerResponse, ApiError> {
+    let peer_id_str = req.uri().path().rsplit('/').next().unwrap_or("");
+    let peer_id = PeerId::from_str(peer_id_str)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid peer ID {}: {:?}", peer_id_str, e)))?;
+
+    let peers = ctx.network_globals.peers.read();
+    let info = peers
+        .peer_info(&peer_id)
+        .ok_or_else(|| ApiError::NotFound(format!("Unknown peer: {}", peer_id)))?;
+
+    Ok(PeerResponse {
+        data: peer_data(&peer_id, info),
+        meta: PeerMetaData {
+            score: info.score.score(),
+            client: info.client.to_string(),
+            agent_string: info.client.agent_string.clone(),
+        },
+    })
+}
+
+/// Returns the standard API representation of the peer with `peer_id`.
+fn peer_data<E: EthSpec>(peer_id: &PeerId, info: &PeerInfo<E>) -> PeerData {
+    let (state, direction) = peer_state_and_direction(&info.connection_status);
+
+    PeerData {
+        peer_id: peer_id.to_base58(),
+        // The peer database does not record the ENRs of peers.
+        enr: None,
+        last_seen_p2p_address: info
+            .listening_addresses
+            .last()
+            .map(|multiaddr| multiaddr.to_string()),
+        state,
+        direction,
+    }
+}
+
+/// Returns the number of known peers in each connection state, in the format of the standard Eth2
+/// API.
+pub fn get_peer_count<T: BeaconChainTypes>(
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<PeerCountData>, ApiError> {
+    let mut counts = PeerCountData::default();
+
+    for (_, info) in ctx.network_globals.peers.read().peers() {
+        let count = match peer_state_and_direction(&info.connection_status).0 {
+            PeerState::Disconnected => &mut counts.disconnected,
+            PeerState::Connecting => &mut counts.connecting,
+            PeerState::Connected => &mut counts.connected,
+            PeerState::Disconnecting => &mut counts.disconnecting,
+        };
+        *count += 1;
+    }
+
+    Ok(GenericResponse { data: counts })
+}
+
+/// Parses all values of the query parameter `key`, each of which may be a comma-separated list.
+///
+/// Returns an empty `Vec` if `key` is not present.
+fn parse_peer_filter<F: FromStr<Err = String>>(
+    req: &Request<Vec<u8>>,
+    key: &str,
+) -> Result<Vec<F>, ApiError> {
+    UrlQuery::from_request(req)?
+        .all_of(key)?
+        .iter()
+        .flat_map(|values| values.split(','))
+        .map(|value| F::from_str(value.trim()).map_err(ApiError::BadRequest))
+        .collect()
+}
+
+/// Maps the connection status of a peer to its state and, where it is known, the direction of
+/// its connection.
+///
+/// A peer with both inbound and outbound connections is reported as outbound.
+fn peer_state_and_direction(status: &PeerConnectionStatus) -> (PeerState, Option<PeerDirection>) {
+    match status {
+        PeerConnectionStatus::Connected { n_out, .. } if *n_out > 0 => {
+            (PeerState::Connected, Some(PeerDirection::Outbound))
+        }
+        PeerConnectionStatus::Connected { .. } => {
+            (PeerState::Connected, Some(PeerDirection::Inbound))
+        }
+        PeerConnectionStatus::Dialing { .. } => {
+            (PeerState::Connecting, Some(PeerDirection::Outbound))
+        }
+        PeerConnectionStatus::Disconnected { .. }
+        | PeerConnectionStatus::Banned { .. }
+        | PeerConnectionStatus::Unknown => (PeerState::Disconnected, None),
+    }
+}
+
+/// The health of the node, as reported by the status code of /eth/v1/node/health.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NodeHealth {
+    /// The node is synced.
+    Ready,
+    /// The node is syncing, but can serve requests with possibly incomplete data.
+    Syncing,
+    /// Genesis has not occurred or the head of the chain cannot be read.
+    NotInitialized,
+}
+
+impl NodeHealth {
+    /// Derives the health of the node from whether the slot clock has started (i.e., genesis has
+    /// occurred), whether the head of the chain can be read and the sync state.
+    pub fn new(slot_clock_started: bool, head_available: bool, sync_state: &SyncState) -> Self {
+        if !slot_clock_started || !head_available {
+            NodeHealth::NotInitialized
+        } else if sync_state.is_synced() {
+            NodeHealth::Ready
+        } else {
+            NodeHealth::Syncing
+        }
+    }
+
+    pub fn status_code(self) -> StatusCode {
+        match self {
+            NodeHealth::Ready => StatusCode::OK,
+            NodeHealth::Syncing => StatusCode::PARTIAL_CONTENT,
+            NodeHealth::NotInitialized => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+}
+
+/// Returns the health of the node, to be reported by the status code of /eth/v1/node/health.
+pub fn get_health<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> NodeHealth {
+    NodeHealth::new(
+        ctx.beacon_chain.slot().is_ok(),
+        ctx.beacon_chain.head_info().is_ok(),
+        &ctx.network_globals.sync_state(),
+    )
+}
 
 /// Returns a syncing status.
 pub fn syncing<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<SyncingResponse, ApiError> {
@@ -37,3 +335,54 @@ pub fn syncing<T: BeaconChainTypes>(ctx: Arc<Context<T>>) -> Result<SyncingRespo
         sync_status,
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::Hash256;
+
+    #[test]
+    fn node_health() {
+        let syncing = SyncState::SyncingHead {
+            start_slot: Slot::new(0),
+            head_slot: Slot::new(64),
+        };
+        let syncing_finalized = SyncState::SyncingFinalized {
+            start_slot: Slot::new(0),
+            head_slot: Slot::new(64),
+            head_root: Hash256::zero(),
+        };
+
+        assert_eq!(
+            NodeHealth::new(true, true, &SyncState::Synced),
+            NodeHealth::Ready
+        );
+        assert_eq!(NodeHealth::new(true, true, &syncing), NodeHealth::Syncing);
+        assert_eq!(
+            NodeHealth::new(true, true, &syncing_finalized),
+            NodeHealth::Syncing
+        );
+        assert_eq!(
+            NodeHealth::new(true, true, &SyncState::Stalled),
+            NodeHealth::Syncing
+        );
+        assert_eq!(
+            NodeHealth::new(false, true, &SyncState::Synced),
+            NodeHealth::NotInitialized
+        );
+        assert_eq!(
+            NodeHealth::new(true, false, &SyncState::Synced),
+            NodeHealth::NotInitialized
+        );
+
+        assert_eq!(NodeHealth::Ready.status_code(), StatusCode::OK);
+        assert_eq!(
+            NodeHealth::Syncing.status_code(),
+            StatusCode::PARTIAL_CONTENT
+        );
+        assert_eq!(
+            NodeHealth::NotInitialized.status_code(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+    }
+}
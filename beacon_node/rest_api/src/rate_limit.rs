@@ -0,0 +1,231 @@
+//! Limits the rate of requests from each client IP address.
+
+use crate::config::{Config, RateLimit};
+use hyper::header::HeaderName;
+use hyper::{Body, Request};
+use lru::LruCache;
+use parking_lot::Mutex;
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, Instant};
+
+/// The maximum number of addresses tracked, beyond which the least recently seen is discarded.
+const MAX_TRACKED_ADDRESSES: usize = 65_536;
+
+/// Path prefixes of the endpoints used by validator clients, which have a separate limit.
+const VALIDATOR_PATH_PREFIXES: &[&str] = &["/validator/", "/eth/v1/validator/"];
+
+/// Limits the rate of requests from each IP address with a token bucket per address.
+///
+/// Requests to validator endpoints draw from separate buckets, so that other requests cannot
+/// starve a validator client of its duties.
+pub struct RateLimiter {
+    default: Option<Buckets>,
+    validator: Option<Buckets>,
+    trusted_proxy_header: Option<HeaderName>,
+}
+
+impl RateLimiter {
+    /// Returns a `RateLimiter` for the limits in `config`, or `None` if no requests are limited.
+    pub fn from_config(config: &Config) -> Result<Option<Self>, String> {
+        if config.rate_limit.is_none() && config.validator_rate_limit.is_none() {
+            return Ok(None);
+        }
+
+        let trusted_proxy_header = config
+            .trusted_proxy_header
+            .as_ref()
+            .map(|name| {
+                HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|e| format!("Invalid trusted proxy header {:?}: {}", name, e))
+            })
+            .transpose()?;
+
+        Ok(Some(Self {
+            default: config.rate_limit.map(Buckets::new).transpose()?,
+            validator: config.validator_rate_limit.map(Buckets::new).transpose()?,
+            trusted_proxy_header,
+        }))
+    }
+
+    /// Takes a token for `req` from the bucket of its client, returning the time until a token
+    /// will be available if there are none.
+    ///
+    /// Requests from an unknown address (e.g., over a Unix socket) are not limited.
+    pub fn check(
+        &self,
+        req: &Request<Body>,
+        remote_addr: Option<SocketAddr>,
+    ) -> Result<(), Duration> {
+        let path = req.uri().path();
+        let buckets = if VALIDATOR_PATH_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+        {
+            &self.validator
+        } else {
+            &self.default
+        };
+
+        match (buckets, self.client_ip(req, remote_addr)) {
+            (Some(buckets), Some(ip)) => buckets.take(ip, Instant::now()),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the IP address of the client which made `req`.
+    ///
+    /// If a trusted proxy header is configured and present, the last address in it (i.e., the one
+    /// added by the trusted proxy) is used. Otherwise, the address of the connection is used.
+    fn client_ip(&self, req: &Request<Body>, remote_addr: Option<SocketAddr>) -> Option<IpAddr> {
+        self.trusted_proxy_header
+            .as_ref()
+            .and_then(|name| req.headers().get(name))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.rsplit(',').next())
+            .and_then(|ip| ip.trim().parse().ok())
+            .or_else(|| remote_addr.map(|addr| addr.ip()))
+    }
+}
+
+/// The token buckets of each IP address, which all share a limit.
+struct Buckets {
+    limit: RateLimit,
+    buckets: Mutex<LruCache<IpAddr, Bucket>>,
+}
+
+impl Buckets {
+    fn new(limit: RateLimit) -> Result<Self, String> {
+        if limit.requests_per_second == 0 || limit.burst == 0 {
+            return Err("HTTP rate limits must allow at least one request".to_string());
+        }
+
+        Ok(Self {
+            limit,
+            buckets: Mutex::new(LruCache::new(MAX_TRACKED_ADDRESSES)),
+        })
+    }
+
+    /// Takes a token from the bucket for `ip` at time `now`, returning the time until a token
+    /// will be available if there are none.
+    fn take(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let limit = self.limit;
+        let mut buckets = self.buckets.lock();
+
+        // A new address starts with a full bucket.
+        let mut tokens = buckets.get(&ip).map_or(f64::from(limit.burst), |bucket| {
+            bucket.tokens_at(limit, now)
+        });
+
+        let result = if tokens >= 1.0 {
+            tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - tokens) / f64::from(limit.requests_per_second),
+            ))
+        };
+
+        // Once the cache is full, adding an address evicts the least recently seen one.
+        buckets.put(
+            ip,
+            Bucket {
+                tokens,
+                updated: now,
+            },
+        );
+
+        result
+    }
+}
+
+struct Bucket {
+    /// The number of tokens in the bucket at `updated`.
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    /// Returns the number of tokens in the bucket at `now`.
+    fn tokens_at(&self, limit: RateLimit, now: Instant) -> f64 {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        (self.tokens + elapsed * f64::from(limit.requests_per_second)).min(f64::from(limit.burst))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buckets(requests_per_second: u32, burst: u32) -> Buckets {
+        Buckets::new(RateLimit {
+            requests_per_second,
+            burst,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn burst_then_refill() {
+        let buckets = buckets(2, 3);
+        let ip = "1.2.3.4".parse().unwrap();
+        let now = Instant::now();
+
+        for _ in 0..3 {
+            assert_eq!(buckets.take(ip, now), Ok(()));
+        }
+        assert_eq!(buckets.take(ip, now), Err(Duration::from_millis(500)));
+
+        // Half a second refills one token.
+        let later = now + Duration::from_millis(500);
+        assert_eq!(buckets.take(ip, later), Ok(()));
+        assert!(buckets.take(ip, later).is_err());
+
+        // The bucket never holds more than the burst.
+        let much_later = later + Duration::from_secs(60);
+        for _ in 0..3 {
+            assert_eq!(buckets.take(ip, much_later), Ok(()));
+        }
+        assert!(buckets.take(ip, much_later).is_err());
+    }
+
+    #[test]
+    fn addresses_are_independent() {
+        let buckets = buckets(1, 1);
+        let now = Instant::now();
+
+        assert_eq!(buckets.take("1.2.3.4".parse().unwrap(), now), Ok(()));
+        assert!(buckets.take("1.2.3.4".parse().unwrap(), now).is_err());
+        assert_eq!(buckets.take("::1".parse().unwrap(), now), Ok(()));
+    }
+
+    #[test]
+    fn tracked_addresses_are_capped() {
+        let buckets = buckets(1, 1);
+        let now = Instant::now();
+        let first: IpAddr = "1.2.3.4".parse().unwrap();
+
+        assert_eq!(buckets.take(first, now), Ok(()));
+        assert!(buckets.take(first, now).is_err());
+
+        // Each address is seen once, so none of their buckets is full.
+        for i in 0..MAX_TRACKED_ADDRESSES as u32 {
+            let ip = IpAddr::from((0x0a00_0000 + i).to_be_bytes());
+            assert_eq!(buckets.take(ip, now), Ok(()));
+            assert!(buckets.buckets.lock().len() <= MAX_TRACKED_ADDRESSES);
+        }
+        assert_eq!(buckets.buckets.lock().len(), MAX_TRACKED_ADDRESSES);
+
+        // The first address was the least recently seen, so its bucket was discarded.
+        assert_eq!(buckets.take(first, now), Ok(()));
+        assert_eq!(buckets.buckets.lock().len(), MAX_TRACKED_ADDRESSES);
+    }
+
+    #[test]
+    fn zero_limit_is_invalid() {
+        let zero = RateLimit {
+            requests_per_second: 0,
+            burst: 1,
+        };
+        assert!(Buckets::new(zero).is_err());
+    }
+}
@@ -1,26 +1,48 @@
 use crate::{
-    beacon, config::Config, consensus, lighthouse, metrics, node, validator, NetworkChannel,
+    access_log::{AccessLog, AccessLogEntry},
+    beacon, compression,
+    config::{AllowOrigin, Config},
+    consensus, helpers, lighthouse, metrics, node,
+    rate_limit::RateLimiter,
+    state_cache::StateCache,
+    validator, NetworkChannel, UrlQuery,
 };
-use beacon_chain::{BeaconChain, BeaconChainTypes};
+use beacon_chain::{events::StandardEvent, BeaconChain, BeaconChainTypes};
 use bus::Bus;
 use environment::TaskExecutor;
 use eth2_config::Eth2Config;
 use eth2_libp2p::{NetworkGlobals, PeerId};
 use hyper::header::HeaderValue;
-use hyper::{Body, Method, Request, Response};
+use hyper::{Body, Method, Request, Response, StatusCode};
 use lighthouse_version::version_with_platform;
 use operation_pool::PersistedOperationPool;
 use parking_lot::Mutex;
-use rest_types::{ApiError, Handler, Health};
-use slog::debug;
+use rest_types::{
+    ApiEncodingFormat, ApiError, GenericResponse, Handler, Health, VersionData,
+    OCTET_STREAM_CONTENT_TYPE, PRETTY_JSON_CONTENT_TYPE,
+};
+use slog::{debug, o, warn};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
-use std::time::Instant;
-use types::{EthSpec, SignedBeaconBlockHash};
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, Semaphore};
+use types::{EthSpec, SignedBeaconBlockHash, YamlConfig};
+
+/// The header used to correlate a HTTP request with the logs of the beacon node.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Request IDs supplied by the client which are longer than this are replaced.
+const MAX_REQUEST_ID_LEN: usize = 128;
+/// The methods which cross-origin requests may use.
+const CORS_ALLOW_METHODS: &str = "GET, POST";
+/// The headers which cross-origin requests may set (e.g., for the admin token).
+const CORS_ALLOW_HEADERS: &str = "Content-Type, Authorization, X-Request-Id";
 
 pub struct Context<T: BeaconChainTypes> {
     pub executor: TaskExecutor,
     pub config: Config,
+    /// The validated form of `config.allow_origins`.
+    pub allow_origin: AllowOrigin,
     pub beacon_chain: Arc<BeaconChain<T>>,
     pub network_globals: Arc<NetworkGlobals<T::EthSpec>>,
     pub network_chan: NetworkChannel<T::EthSpec>,
@@ -29,94 +51,392 @@ pub struct Context<T: BeaconChainTypes> {
     pub db_path: PathBuf,
     pub freezer_db_path: PathBuf,
     pub events: Arc<Mutex<Bus<SignedBeaconBlockHash>>>,
+    /// Publishes events in the format of the standard Eth2 API, for `/eth/v1/events`.
+    pub standard_events: broadcast::Sender<StandardEvent>,
+    /// Held whilst an admin-triggered op pool prune is running, so only one may run at a time.
+    pub op_pool_prune_lock: Mutex<()>,
+    /// If enabled, receives a JSON entry for every request.
+    pub access_log: Option<AccessLog>,
+    /// Bounds the number of requests running on the blocking thread pool at once.
+    pub blocking_permits: Arc<Semaphore>,
+    /// Recently requested states, other than the head.
+    pub state_cache: StateCache<T::EthSpec>,
+    /// If enabled, limits the rate of requests from each client.
+    pub rate_limiter: Option<RateLimiter>,
+    /// The service which caches eth1 blocks and deposit logs, if it is running.
+    pub eth1_service: Option<eth1::Service>,
+    /// The eth1 config of the node. Its `network_id` is only meaningful if `eth1_service` is
+    /// running.
+    pub eth1_config: eth1::Config,
 }
 
 pub async fn on_http_request<T: BeaconChainTypes>(
     req: Request<Body>,
+    remote_addr: Option<SocketAddr>,
     ctx: Arc<Context<T>>,
 ) -> Result<Response<Body>, ApiError> {
     let path = req.uri().path().to_string();
+    let metrics_path = route_path(req.method(), &path).to_string();
 
-    let _timer = metrics::start_timer_vec(&metrics::BEACON_HTTP_API_TIMES_TOTAL, &[&path]);
-    metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_REQUESTS_TOTAL, &[&path]);
+    let _timer = metrics::start_timer_vec(&metrics::BEACON_HTTP_API_TIMES_TOTAL, &[&metrics_path]);
+    metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_REQUESTS_TOTAL, &[&metrics_path]);
 
     let received_instant = Instant::now();
-    let log = ctx.log.clone();
-    let allow_origin = ctx.config.allow_origin.clone();
-
-    match route(req, ctx).await {
-        Ok(mut response) => {
-            metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_SUCCESS_TOTAL, &[&path]);
-
-            if allow_origin != "" {
-                let headers = response.headers_mut();
-                headers.insert(
-                    hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN,
-                    HeaderValue::from_str(&allow_origin)?,
-                );
-                headers.insert(hyper::header::VARY, HeaderValue::from_static("Origin"));
-            }
+    let method = req.method().to_string();
+    let user_agent = req
+        .headers()
+        .get(hyper::header::USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(String::from);
+    let request_id = request_id(&req);
+    let request_id_header = HeaderValue::from_str(&request_id)?;
+    let log = ctx.log.new(o!("request_id" => request_id.clone()));
+    let allow_origin = ctx
+        .allow_origin
+        .header_value(
+            req.headers()
+                .get(hyper::header::ORIGIN)
+                .and_then(|origin| origin.to_str().ok()),
+        )
+        .map(HeaderValue::from_str)
+        .transpose()?;
+    let vary_origin = ctx.allow_origin != AllowOrigin::None;
+    let gzip = ctx.config.compression && compression::accepts_gzip(req.headers());
+    let json_errors = accepts_json(&req);
+
+    let rate_limit = ctx
+        .rate_limiter
+        .as_ref()
+        .map_or(Ok(()), |limiter| limiter.check(&req, remote_addr));
 
-            debug!(
-                log,
-                "HTTP API request successful";
-                "path" => path,
-                "duration_ms" => Instant::now().duration_since(received_instant).as_millis()
-            );
-            Ok(response)
+    // CORS preflight and rate-limited requests are answered here, without reaching any handler.
+    let result = if vary_origin && req.method() == Method::OPTIONS {
+        cors_preflight_response()
+    } else if let Err(retry_after) = rate_limit {
+        metrics::inc_counter_vec(
+            &metrics::BEACON_HTTP_API_RATE_LIMITED_TOTAL,
+            &[&metrics_path],
+        );
+        too_many_requests_response(retry_after)
+    } else {
+        route(req, ctx.clone(), log.clone()).await
+    };
+
+    let mut response = match result {
+        Ok(response) => {
+            metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_SUCCESS_TOTAL, &[&metrics_path]);
+            response
         }
 
         Err(error) => {
-            metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_ERROR_TOTAL, &[&path]);
-
-            debug!(
-                log,
-                "HTTP API request failure";
-                "path" => path,
-                "duration_ms" => Instant::now().duration_since(received_instant).as_millis()
-            );
-            Ok(error.into())
+            metrics::inc_counter_vec(&metrics::BEACON_HTTP_API_ERROR_TOTAL, &[&metrics_path]);
+            error.into_response(json_errors)
+        }
+    };
+
+    // Log every request, including those rejected by a handler. The request body is never
+    // logged, since it may contain an entire block.
+    let duration = Instant::now().duration_since(received_instant);
+    let remote = remote_addr.map(|addr| addr.to_string());
+    let status = response.status().as_u16();
+    if ctx.config.is_slow_request(duration) {
+        warn!(
+            log,
+            "Slow HTTP API request";
+            "method" => &method,
+            "path" => &path,
+            "remote" => &remote,
+            "status" => status,
+            "duration_ms" => duration.as_millis()
+        );
+    } else {
+        debug!(
+            log,
+            "HTTP API request";
+            "method" => &method,
+            "path" => &path,
+            "remote" => &remote,
+            "status" => status,
+            "duration_ms" => duration.as_millis()
+        );
+    }
+
+    let headers = response.headers_mut();
+    headers.insert(REQUEST_ID_HEADER, request_id_header);
+    if let Some(allow_origin) = allow_origin {
+        headers.insert(hyper::header::ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+    }
+    if vary_origin {
+        headers.insert(hyper::header::VARY, HeaderValue::from_static("Origin"));
+    }
+    if gzip {
+        response = compression::gzip_response(response);
+    }
+
+    if let Some(access_log) = &ctx.access_log {
+        let entry = AccessLogEntry {
+            method,
+            path: metrics_path,
+            status,
+            duration_ms: duration.as_millis() as u64,
+            remote,
+            request_id,
+            user_agent,
+        };
+        if let Err(e) = access_log.write(&entry) {
+            warn!(log, "Unable to write HTTP access log"; "error" => e);
         }
     }
+
+    Ok(response)
+}
+
+/// Returns the response to a CORS preflight (`OPTIONS`) request.
+///
+/// The 'Access-Control-Allow-Origin' header is added by the caller, as for any other request.
+fn cors_preflight_response() -> Result<Response<Body>, ApiError> {
+    Response::builder()
+        .status(StatusCode::NO_CONTENT)
+        .header(
+            hyper::header::ACCESS_CONTROL_ALLOW_METHODS,
+            CORS_ALLOW_METHODS,
+        )
+        .header(
+            hyper::header::ACCESS_CONTROL_ALLOW_HEADERS,
+            CORS_ALLOW_HEADERS,
+        )
+        .body(Body::empty())
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
+
+/// Returns the response to a request which exceeded its rate limit, where a request may be made
+/// again after `retry_after`.
+fn too_many_requests_response(retry_after: Duration) -> Result<Response<Body>, ApiError> {
+    // `Retry-After` is in whole seconds, so round up to avoid an early retry.
+    let retry_after_secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+
+    Response::builder()
+        .status(StatusCode::TOO_MANY_REQUESTS)
+        .header(hyper::header::RETRY_AFTER, retry_after_secs.max(1))
+        .body(Body::from("Too many requests, please slow down"))
+        .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+}
+
+/// Returns the ID supplied in the `X-Request-Id` header of `req`, or a newly generated ID if the
+/// header is absent, empty, too long or not visible ASCII.
+fn request_id(req: &Request<Body>) -> String {
+    req.headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|id| !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN)
+        .map(String::from)
+        .unwrap_or_else(|| hex::encode(rand::random::<[u8; 16]>()))
+}
+
+/// Returns `true` if the `Accept` header of `req` lists a JSON media type, in which case errors are
+/// returned as a JSON `ErrorMessage` rather than plain text.
+fn accepts_json(req: &Request<Body>) -> bool {
+    req.headers()
+        .get(hyper::header::ACCEPT)
+        .and_then(|value| value.to_str().ok())
+        .map_or(false, |accept| {
+            accept
+                .split(',')
+                .filter_map(|media_type| media_type.split(';').next())
+                .map(str::trim)
+                .any(|media_type| {
+                    media_type == ApiEncodingFormat::JSON.get_content_type()
+                        || media_type == PRETTY_JSON_CONTENT_TYPE
+                })
+        })
 }
 
 async fn route<T: BeaconChainTypes>(
-    req: Request<Body>,
+    mut req: Request<Body>,
     ctx: Arc<Context<T>>,
+    log: slog::Logger,
 ) -> Result<Response<Body>, ApiError> {
+    // Handlers find the logger of the request, with its `request_id`, via `request_log`.
+    req.extensions_mut().insert(log.clone());
+
     let path = req.uri().path().to_string();
     let ctx = ctx.clone();
     let method = req.method().clone();
     let executor = ctx.executor.clone();
-    let handler = Handler::new(req, ctx, executor)?;
+    let timeout = ctx.config.request_timeout(&path);
+    let max_body_size = ctx.config.max_request_body_size;
+    let blocking_permits = ctx.blocking_permits.clone();
+    let format_query = UrlQuery::from_request(&req)?
+        .first_of_opt(&["format"])
+        .map(|(_, value)| value);
+    let pretty = UrlQuery::from_request(&req)?
+        .first_of_opt(&["pretty"])
+        .map_or(false, |(_, value)| value == "true");
+    let handler = Handler::new(req, ctx, executor)?
+        .with_timeout(timeout)
+        .with_max_body_size(Some(max_body_size))
+        .with_blocking_permits(blocking_permits)
+        .pretty_json(pretty)
+        .with_log(log);
+
+    dispatch(method, &path, handler, format_query).await
+}
+
+/// A route served by the HTTP API.
+pub struct Route {
+    pub method: &'static str,
+    pub path: &'static str,
+    /// True if the route requires the admin token.
+    pub admin: bool,
+}
+
+/// Returns the path of the route which serves a `method` request to `path`, so that requests to
+/// a route with path parameters share its metrics. Returns `path` if no route serves it.
+fn route_path<'a>(method: &Method, path: &'a str) -> &'a str {
+    ROUTES
+        .iter()
+        .find(|route| route.method == method.as_str() && path_matches(route.path, path))
+        .map_or(path, |route| route.path)
+}
 
-    match (method, path.as_ref()) {
-        (Method::GET, "/node/version") => handler
+/// Returns `true` if `path` matches the route path `template`, in which a `{name}` segment
+/// matches any single segment of `path`.
+pub(crate) fn path_matches(template: &str, path: &str) -> bool {
+    let mut template_segments = template.split('/');
+    let mut path_segments = path.split('/');
+
+    loop {
+        match (template_segments.next(), path_segments.next()) {
+            (Some(template_segment), Some(path_segment)) => {
+                let is_param = template_segment.starts_with('{') && template_segment.ends_with('}');
+                if !is_param && template_segment != path_segment {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Defines `ROUTES` and the function which dispatches requests to them from a single table, so
+/// the routes listed at the API root are always those which are actually served.
+///
+/// Each route is `(METHOD, "/path")`, or `(METHOD, "/path", admin)` if it requires the admin
+/// token, optionally followed by a match guard. A `{name}` segment of a path matches any single
+/// segment of the request path.
+macro_rules! router {
+    (@admin) => {
+        false
+    };
+    (@admin admin) => {
+        true
+    };
+    (
+        async fn $fn_name:ident($method:ident, $path:ident, $handler:ident, $format_query:ident) {
+            $(
+                ($route_method:ident, $route_path:literal $(, $admin:ident)?)
+                $(if $guard:expr)? => $body:expr,
+            )*
+        }
+    ) => {
+        /// All routes served by the HTTP API, in the order in which they are matched.
+        pub const ROUTES: &[Route] = &[
+            $(
+                Route {
+                    method: stringify!($route_method),
+                    path: $route_path,
+                    admin: router!(@admin $($admin)?),
+                },
+            )*
+        ];
+
+        async fn $fn_name<T: BeaconChainTypes>(
+            $method: Method,
+            $path: &str,
+            $handler: Handler<Arc<Context<T>>>,
+            $format_query: Option<String>,
+        ) -> Result<Response<Body>, ApiError> {
+            match ($method, $path) {
+                $(
+                    (Method::$route_method, _)
+                        if path_matches($route_path, $path) $(&& $guard)? => $body,
+                )*
+                _ => Err(ApiError::NotFound(
+                    "Request path and/or method not found.".to_owned(),
+                )),
+            }
+        }
+    };
+}
+
+router! {
+    async fn dispatch(method, path, handler, format_query) {
+        (GET, "/") => handler
+            .in_core_task(node::get_api_index)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1") => handler
+            .in_core_task(node::get_api_index)
+            .await?
+            .serde_encodings(),
+        (GET, "/node/version") => handler
             .static_value(version_with_platform())
             .await?
             .serde_encodings(),
-        (Method::GET, "/node/health") => handler
+        // The version in the response format of the standard Eth2 API, for clients which use it to
+        // identify the beacon node.
+        (GET, "/eth/v1/node/version") => handler
+            .static_value(GenericResponse {
+                data: VersionData {
+                    version: version_with_platform(),
+                },
+            })
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/node/identity") => handler
+            .in_core_task(|_, ctx| node::get_identity(ctx))
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/node/peers") => handler
+            .in_core_task(node::get_peers)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/node/peers/{peer_id}") => handler
+            .in_core_task(node::get_peer)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/node/peer_count") => handler
+            .in_core_task(|_, ctx| node::get_peer_count(ctx))
+            .await?
+            .serde_encodings(),
+        // Reports the health of the node solely by the status code, for load balancers.
+        (GET, "/eth/v1/node/health") => handler
+            .in_core_task(|_, ctx| Ok(node::get_health(ctx).status_code()))
+            .await?
+            .status_encoding(),
+        (GET, "/node/health") => handler
             .static_value(Health::observe().map_err(ApiError::ServerError)?)
             .await?
             .serde_encodings(),
-        (Method::GET, "/node/syncing") => handler
+        (GET, "/node/syncing") => handler
             .allow_body()
             .in_blocking_task(|_, ctx| node::syncing(ctx))
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/enr") => handler
+        (GET, "/network/enr") => handler
             .in_core_task(|_, ctx| Ok(ctx.network_globals.local_enr().to_base64()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/peer_count") => handler
+        (GET, "/network/peer_count") => handler
             .in_core_task(|_, ctx| Ok(ctx.network_globals.connected_peers()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/peer_id") => handler
+        (GET, "/network/peer_id") => handler
             .in_core_task(|_, ctx| Ok(ctx.network_globals.local_peer_id().to_base58()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/peers") => handler
+        (GET, "/network/peers") => handler
             .in_blocking_task(|_, ctx| {
                 Ok(ctx
                     .network_globals
@@ -128,158 +448,291 @@ async fn route<T: BeaconChainTypes>(
             })
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/listen_port") => handler
+        (GET, "/network/listen_port") => handler
             .in_core_task(|_, ctx| Ok(ctx.network_globals.listen_port_tcp()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/network/listen_addresses") => handler
+        (GET, "/network/listen_addresses") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.network_globals.listen_multiaddrs()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/beacon/head") => handler
+        (GET, "/beacon/head") => handler
             .in_blocking_task(|_, ctx| beacon::get_head(ctx))
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/heads") => handler
+        (GET, "/beacon/heads") => handler
             .in_blocking_task(|_, ctx| Ok(beacon::get_heads(ctx)))
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/block") => handler
+        // An `application/octet-stream` request returns the SSZ bytes of just the block.
+        (GET, "/beacon/block") if handler.prefers(OCTET_STREAM_CONTENT_TYPE) => handler
+            .in_blocking_task(|req, ctx| Ok(beacon::block_from_query(&req, &ctx.beacon_chain)?.1))
+            .await?
+            .octet_stream_encoding(),
+        (GET, "/beacon/block") => handler
+            .require_accept(&[
+                ApiEncodingFormat::JSON.get_content_type(),
+                PRETTY_JSON_CONTENT_TYPE,
+                ApiEncodingFormat::YAML.get_content_type(),
+                ApiEncodingFormat::SSZ.get_content_type(),
+                OCTET_STREAM_CONTENT_TYPE,
+            ])?
             .in_blocking_task(beacon::get_block)
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/block_root") => handler
+        // As for `/beacon/block`, `application/octet-stream` returns the SSZ bytes of the block.
+        (GET, "/eth/v1/beacon/blocks/{block_id}")
+            if handler.prefers(OCTET_STREAM_CONTENT_TYPE) =>
+        {
+            handler
+                .in_blocking_task(|req, ctx| Ok(beacon::get_block_by_id(req, ctx)?.data))
+                .await?
+                .octet_stream_encoding()
+        }
+        (GET, "/eth/v1/beacon/blocks/{block_id}") => handler
+            .require_accept(&[
+                ApiEncodingFormat::JSON.get_content_type(),
+                PRETTY_JSON_CONTENT_TYPE,
+                ApiEncodingFormat::YAML.get_content_type(),
+                OCTET_STREAM_CONTENT_TYPE,
+            ])?
+            .in_blocking_task(beacon::get_block_by_id)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/beacon/blocks/{block_id}/root") => handler
+            .in_blocking_task(beacon::get_block_root_by_id)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/beacon/blocks/{block_id}/attestations") => handler
+            .in_blocking_task(beacon::get_block_attestations_by_id)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/beacon/headers") => handler
+            .in_blocking_task(beacon::get_block_headers)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/beacon/headers/{block_id}") => handler
+            .in_blocking_task(beacon::get_block_header_by_id)
+            .await?
+            .serde_encodings(),
+        (GET, "/beacon/block_root") => handler
             .in_blocking_task(beacon::get_block_root)
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/fork") => handler
+        (GET, "/beacon/fork") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.fork))
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/fork/stream") => {
+        (GET, "/beacon/fork/stream") => {
             handler.sse_stream(|_, ctx| beacon::stream_forks(ctx)).await
-        }
-        (Method::GET, "/beacon/genesis_time") => handler
+        },
+        (GET, "/eth/v1/events") => handler.sse_stream(beacon::stream_events).await,
+        (GET, "/beacon/genesis_time") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.genesis_time))
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/genesis_validators_root") => handler
+        (GET, "/beacon/genesis_block_root") => handler
+            .in_core_task(|_, ctx| Ok(ctx.beacon_chain.genesis_block_root))
+            .await?
+            .serde_encodings(),
+        (GET, "/beacon/genesis_validators_root") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.head_info()?.genesis_validators_root))
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/validators") => handler
+        (GET, "/beacon/validators") => handler
             .in_blocking_task(beacon::get_validators)
             .await?
             .all_encodings(),
-        (Method::POST, "/beacon/validators") => handler
+        (POST, "/beacon/validators") => handler
             .allow_body()
             .in_blocking_task(beacon::post_validators)
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/validators/all") => handler
+        (GET, "/beacon/validator_balances") => handler
+            .in_blocking_task(beacon::get_validator_balances)
+            .await?
+            .streaming_serde_encodings(),
+        (GET, "/eth/v1/beacon/states/{state_id}/validators") => handler
+            .in_blocking_task(beacon::get_state_validators)
+            .await?
+            .streaming_serde_encodings(),
+        (GET, "/eth/v1/beacon/states/{state_id}/validator_balances") => handler
+            .in_blocking_task(beacon::get_state_validator_balances)
+            .await?
+            .streaming_serde_encodings(),
+        (GET, "/beacon/validators/all") => handler
             .in_blocking_task(beacon::get_all_validators)
             .await?
-            .all_encodings(),
-        (Method::GET, "/beacon/validators/active") => handler
+            .streaming_all_encodings(),
+        (GET, "/beacon/validators/active") => handler
             .in_blocking_task(beacon::get_active_validators)
             .await?
-            .all_encodings(),
-        (Method::GET, "/beacon/state") => handler
+            .streaming_all_encodings(),
+        // An `application/octet-stream` request returns the SSZ bytes of just the state.
+        (GET, "/beacon/state") if handler.prefers(OCTET_STREAM_CONTENT_TYPE) => handler
+            .in_blocking_task(|req, ctx| Ok(beacon::get_state(req, ctx)?.beacon_state))
+            .await?
+            .octet_stream_encoding(),
+        (GET, "/beacon/state") => handler
             .in_blocking_task(beacon::get_state)
             .await?
-            .all_encodings(),
-        (Method::GET, "/beacon/state_root") => handler
+            .streaming_all_encodings(),
+        (GET, "/beacon/state_root") => handler
             .in_blocking_task(beacon::get_state_root)
             .await?
             .all_encodings(),
-        (Method::GET, "/beacon/state/genesis") => handler
+        (GET, "/beacon/state/genesis") => handler
             .in_blocking_task(|_, ctx| beacon::get_genesis_state(ctx))
             .await?
-            .all_encodings(),
-        (Method::GET, "/beacon/committees") => handler
+            .streaming_all_encodings(),
+        (GET, "/beacon/committees") => handler
             .in_blocking_task(beacon::get_committees)
             .await?
             .all_encodings(),
-        (Method::POST, "/beacon/proposer_slashing") => handler
+        (GET, "/beacon/pool/attester_slashings") => handler
+            .in_blocking_task(beacon::get_pool_attester_slashings)
+            .await?
+            .serde_encodings(),
+        (GET, "/beacon/pool/proposer_slashings") => handler
+            .in_blocking_task(beacon::get_pool_proposer_slashings)
+            .await?
+            .serde_encodings(),
+        (POST, "/beacon/proposer_slashing") => handler
             .allow_body()
             .in_blocking_task(beacon::proposer_slashing)
             .await?
             .serde_encodings(),
-        (Method::POST, "/beacon/attester_slashing") => handler
+        (POST, "/beacon/attester_slashing") => handler
             .allow_body()
             .in_blocking_task(beacon::attester_slashing)
             .await?
             .serde_encodings(),
-        (Method::POST, "/validator/duties") => handler
+        (POST, "/beacon/voluntary_exit") => handler
+            .allow_body()
+            .in_blocking_task(beacon::voluntary_exit)
+            .await?
+            .serde_encodings(),
+        (POST, "/validator/duties") => handler
             .allow_body()
             .in_blocking_task(validator::post_validator_duties)
             .await?
             .serde_encodings(),
-        (Method::POST, "/validator/subscribe") => handler
+        (POST, "/eth/v1/validator/duties/attester/{epoch}") => handler
+            .allow_body()
+            .in_blocking_task(validator::post_attester_duties)
+            .await?
+            .serde_encodings(),
+        (POST, "/eth/v1/validator/beacon_committee_subscriptions") => handler
+            .allow_body()
+            .in_blocking_task(validator::post_beacon_committee_subscriptions)
+            .await?
+            .status_encoding(),
+        (POST, "/validator/subscribe") => handler
             .allow_body()
             .in_blocking_task(validator::post_validator_subscriptions)
             .await?
             .serde_encodings(),
-        (Method::GET, "/validator/duties/all") => handler
+        (GET, "/validator/duties/all") => handler
             .in_blocking_task(validator::get_all_validator_duties)
             .await?
             .serde_encodings(),
-        (Method::GET, "/validator/duties/active") => handler
+        (GET, "/validator/duties/active") => handler
             .in_blocking_task(validator::get_active_validator_duties)
             .await?
             .serde_encodings(),
-        (Method::GET, "/validator/block") => handler
+        (GET, "/validator/block") => handler
             .in_blocking_task(validator::get_new_beacon_block)
             .await?
             .serde_encodings(),
-        (Method::POST, "/validator/block") => handler
+        (POST, "/validator/block") => handler
             .allow_body()
             .in_blocking_task(validator::publish_beacon_block)
             .await?
             .serde_encodings(),
-        (Method::GET, "/validator/attestation") => handler
+        (GET, "/validator/attestation") => handler
             .in_blocking_task(validator::get_new_attestation)
             .await?
             .serde_encodings(),
-        (Method::GET, "/validator/aggregate_attestation") => handler
+        (GET, "/validator/aggregate_attestation") => handler
             .in_blocking_task(validator::get_aggregate_attestation)
             .await?
             .serde_encodings(),
-        (Method::POST, "/validator/attestations") => handler
+        (POST, "/validator/attestations") => handler
             .allow_body()
             .in_blocking_task(validator::publish_attestations)
             .await?
             .serde_encodings(),
-        (Method::POST, "/validator/aggregate_and_proofs") => handler
+        (POST, "/validator/aggregate_and_proofs") => handler
             .allow_body()
             .in_blocking_task(validator::publish_aggregate_and_proofs)
             .await?
             .serde_encodings(),
-        (Method::GET, "/consensus/global_votes") => handler
+        (GET, "/consensus/global_votes") => handler
             .allow_body()
             .in_blocking_task(consensus::get_vote_count)
             .await?
             .serde_encodings(),
-        (Method::POST, "/consensus/individual_votes") => handler
+        (POST, "/consensus/individual_votes") => handler
             .allow_body()
             .in_blocking_task(consensus::post_individual_votes)
             .await?
             .serde_encodings(),
-        (Method::GET, "/spec") => handler
+        // A YAML request returns the spec in the canonical `config.yaml` format.
+        (GET, "/spec")
+            if format_query.as_deref() == Some("yaml")
+                || matches!(handler.encoding(), ApiEncodingFormat::YAML) =>
+        {
+            handler
+                .with_encoding(ApiEncodingFormat::YAML)
+                .in_blocking_task(|_, ctx| {
+                    let mut config = YamlConfig::from_spec::<T::EthSpec>(&ctx.beacon_chain.spec);
+                    config.config_name = ctx.eth2_config.config_name().to_string();
+                    Ok(config)
+                })
+                .await?
+                .serde_encodings()
+        },
+        (GET, "/spec") => handler
             // TODO: this clone is not ideal.
             .in_blocking_task(|_, ctx| Ok(ctx.beacon_chain.spec.clone()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/spec/slots_per_epoch") => handler
+        (GET, "/eth/v1/config/spec") => handler
+            .in_blocking_task(|_, ctx| {
+                let mut config = YamlConfig::from_spec::<T::EthSpec>(&ctx.beacon_chain.spec);
+                config.config_name = ctx.eth2_config.config_name().to_string();
+                Ok(GenericResponse {
+                    data: helpers::config_to_string_map(&config)?,
+                })
+            })
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/config/deposit_contract") => handler
+            .in_core_task(node::get_deposit_contract)
+            .await?
+            .serde_encodings(),
+        (GET, "/eth/v1/config/fork_schedule") => handler
+            .in_core_task(|_, ctx| {
+                Ok(GenericResponse {
+                    data: ctx.beacon_chain.spec.fork_schedule::<T::EthSpec>(),
+                })
+            })
+            .await?
+            .serde_encodings(),
+        (GET, "/spec/slots_per_epoch") => handler
             .static_value(T::EthSpec::slots_per_epoch())
             .await?
             .serde_encodings(),
-        (Method::GET, "/spec/eth2_config") => handler
+        (GET, "/spec/config_name") => handler
+            .in_core_task(|_, ctx| Ok(ctx.eth2_config.config_name().to_string()))
+            .await?
+            .serde_encodings(),
+        (GET, "/spec/eth2_config") => handler
             // TODO: this clone is not ideal.
             .in_blocking_task(|_, ctx| Ok(ctx.eth2_config.as_ref().clone()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/advanced/fork_choice") => handler
+        (GET, "/advanced/fork_choice") => handler
             .in_blocking_task(|_, ctx| {
                 Ok(ctx
                     .beacon_chain
@@ -291,7 +744,7 @@ async fn route<T: BeaconChainTypes>(
             })
             .await?
             .serde_encodings(),
-        (Method::GET, "/advanced/operation_pool") => handler
+        (GET, "/advanced/operation_pool") => handler
             .in_blocking_task(|_, ctx| {
                 Ok(PersistedOperationPool::from_operation_pool(
                     &ctx.beacon_chain.op_pool,
@@ -299,24 +752,83 @@ async fn route<T: BeaconChainTypes>(
             })
             .await?
             .serde_encodings(),
-        (Method::GET, "/metrics") => handler
+        (GET, "/metrics") => handler
             .in_blocking_task(|_, ctx| metrics::get_prometheus(ctx))
             .await?
             .text_encoding(),
-        (Method::GET, "/lighthouse/syncing") => handler
+        (GET, "/lighthouse/liveness") => {
+            handler.static_value(true).await?.serde_encodings()
+        },
+        (GET, "/lighthouse/readiness") => handler
+            .in_core_task(|_, ctx| lighthouse::readiness(ctx))
+            .await?
+            .serde_encodings(),
+        (GET, "/lighthouse/syncing") => handler
             .in_blocking_task(|_, ctx| Ok(ctx.network_globals.sync_state()))
             .await?
             .serde_encodings(),
-        (Method::GET, "/lighthouse/peers") => handler
+        (GET, "/lighthouse/peers") => handler
             .in_blocking_task(|_, ctx| lighthouse::peers(ctx))
             .await?
             .serde_encodings(),
-        (Method::GET, "/lighthouse/connected_peers") => handler
+        (GET, "/lighthouse/connected_peers") => handler
             .in_blocking_task(|_, ctx| lighthouse::connected_peers(ctx))
             .await?
             .serde_encodings(),
-        _ => Err(ApiError::NotFound(
-            "Request path and/or method not found.".to_owned(),
-        )),
+        (GET, "/lighthouse/op_pool") => handler
+            .in_blocking_task(|_, ctx| lighthouse::op_pool_stats(ctx))
+            .await?
+            .serde_encodings(),
+        (GET, "/lighthouse/beacon/blocks/{block_id}/body_summary") => handler
+            .in_blocking_task(lighthouse::get_block_body_summary)
+            .await?
+            .serde_encodings(),
+        (GET, "/lighthouse/eth1/deposit_cache") => handler
+            .in_blocking_task(lighthouse::eth1_deposit_cache)
+            .await?
+            .serde_encodings(),
+        (POST, "/lighthouse/op_pool/prune", admin) => handler
+            .in_blocking_task(lighthouse::prune_op_pool)
+            .await?
+            .serde_encodings(),
+        (POST, "/lighthouse/access_log/reopen", admin) => handler
+            .in_blocking_task(lighthouse::reopen_access_log)
+            .await?
+            .serde_encodings(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn path_matches_segments() {
+        assert!(path_matches("/node/version", "/node/version"));
+        assert!(!path_matches("/node/version", "/node/versions"));
+        assert!(!path_matches("/node/version", "/node/version/"));
+        assert!(!path_matches("/node/version", "/node"));
+
+        let template = "/eth/v1/node/peers/{peer_id}";
+        assert!(path_matches(template, "/eth/v1/node/peers/16Uiu2HAm"));
+        assert!(path_matches(template, "/eth/v1/node/peers/"));
+        assert!(!path_matches(template, "/eth/v1/node/peers"));
+        assert!(!path_matches(
+            template,
+            "/eth/v1/node/peers/16Uiu2HAm/extra"
+        ));
+    }
+
+    #[test]
+    fn route_path_uses_template() {
+        assert_eq!(
+            route_path(&Method::GET, "/eth/v1/node/peers/16Uiu2HAm"),
+            "/eth/v1/node/peers/{peer_id}"
+        );
+        assert_eq!(
+            route_path(&Method::GET, "/eth/v1/node/peers"),
+            "/eth/v1/node/peers"
+        );
+        assert_eq!(route_path(&Method::POST, "/unknown"), "/unknown");
     }
 }
@@ -0,0 +1,221 @@
+//! Caches recently requested `BeaconState`s, so that repeated requests for the same historical
+//! state (e.g., the finalized state) do not each load it from the database.
+
+use crate::helpers::{load_state_at_slot, state_root_at_slot};
+use crate::metrics;
+use beacon_chain::{BeaconChain, BeaconChainTypes, StateSkipConfig};
+use lru::LruCache;
+use parking_lot::Mutex;
+use rest_types::ApiError;
+use ssz::Encode;
+use types::{BeaconState, EthSpec, Hash256, Slot};
+
+/// A cache of `BeaconState`s keyed by state root, bounded by both its number of entries and their
+/// total size.
+///
+/// The head state is never cached, since it is already held in memory by the `BeaconChain`.
+pub struct StateCache<E: EthSpec> {
+    max_bytes: usize,
+    /// `None` if the cache is disabled.
+    inner: Option<Mutex<Inner<E>>>,
+}
+
+struct Inner<E: EthSpec> {
+    /// Each state, with its size in bytes.
+    states: LruCache<Hash256, (BeaconState<E>, usize)>,
+    total_bytes: usize,
+}
+
+impl<E: EthSpec> StateCache<E> {
+    /// Creates a cache of at most `max_entries` states, with a total size of at most `max_bytes`.
+    /// If either is zero, the cache is disabled.
+    pub fn new(max_entries: usize, max_bytes: usize) -> Self {
+        let inner = if max_entries > 0 && max_bytes > 0 {
+            Some(Mutex::new(Inner {
+                states: LruCache::new(max_entries),
+                total_bytes: 0,
+            }))
+        } else {
+            None
+        };
+
+        Self { max_bytes, inner }
+    }
+
+    /// Returns a `BeaconState` and its root in the canonical chain of `beacon_chain` at the given
+    /// `slot`, if possible.
+    ///
+    /// Will not return a state if the request slot is in the future. Will return states higher
+    /// than the current head by skipping slots.
+    pub fn state_at_slot<T: BeaconChainTypes<EthSpec = E>>(
+        &self,
+        beacon_chain: &BeaconChain<T>,
+        slot: Slot,
+    ) -> Result<(Hash256, BeaconState<E>), ApiError> {
+        if beacon_chain.head_info()?.slot == slot {
+            let head = beacon_chain.head()?;
+            if head.beacon_state.slot == slot {
+                return Ok((head.beacon_state_root, head.beacon_state));
+            }
+        }
+
+        let root = state_root_at_slot(beacon_chain, slot, StateSkipConfig::WithStateRoots)?;
+        let state = self
+            .get_or_load(root, || {
+                load_state_at_slot(beacon_chain, root, slot).map(Some)
+            })?
+            .ok_or_else(|| ApiError::NotFound(format!("Unable to find state at root {}", root)))?;
+
+        Ok((root, state))
+    }
+
+    /// Returns the state with the given `root`, if it exists.
+    pub fn state_by_root<T: BeaconChainTypes<EthSpec = E>>(
+        &self,
+        beacon_chain: &BeaconChain<T>,
+        root: Hash256,
+    ) -> Result<Option<BeaconState<E>>, ApiError> {
+        if beacon_chain.head_info()?.state_root == root {
+            let head = beacon_chain.head()?;
+            if head.beacon_state_root == root {
+                return Ok(Some(head.beacon_state));
+            }
+        }
+
+        self.get_or_load(root, || {
+            beacon_chain.get_state(&root, None).map_err(|e| {
+                ApiError::ServerError(format!(
+                    "Database error when reading state root {}: {:?}",
+                    root, e
+                ))
+            })
+        })
+    }
+
+    /// Returns the state with the given `root` from the cache, or from `load` if it is not
+    /// cached. A loaded state is added to the cache.
+    fn get_or_load<F>(&self, root: Hash256, load: F) -> Result<Option<BeaconState<E>>, ApiError>
+    where
+        F: FnOnce() -> Result<Option<BeaconState<E>>, ApiError>,
+    {
+        let inner = match &self.inner {
+            Some(inner) => inner,
+            None => return load(),
+        };
+
+        if let Some((state, _)) = inner.lock().states.get(&root) {
+            metrics::inc_counter(&metrics::BEACON_HTTP_API_STATE_CACHE_HITS_TOTAL);
+            return Ok(Some(state.clone()));
+        }
+        metrics::inc_counter(&metrics::BEACON_HTTP_API_STATE_CACHE_MISSES_TOTAL);
+
+        // The state is loaded without holding the lock, so other requests are not delayed.
+        let state = load()?;
+        if let Some(state) = &state {
+            self.insert(&mut inner.lock(), root, state.clone());
+        }
+
+        Ok(state)
+    }
+
+    /// Adds `state` to the cache, evicting the least recently used states until it fits.
+    fn insert(&self, inner: &mut Inner<E>, root: Hash256, state: BeaconState<E>) {
+        let size = state.ssz_bytes_len();
+        if size > self.max_bytes {
+            return;
+        }
+
+        if let Some((_, replaced_size)) = inner.states.pop(&root) {
+            inner.total_bytes -= replaced_size;
+        }
+        while inner.total_bytes + size > self.max_bytes || inner.states.len() == inner.states.cap()
+        {
+            match inner.states.pop_lru() {
+                Some((_, (_, evicted_size))) => inner.total_bytes -= evicted_size,
+                None => break,
+            }
+        }
+
+        inner.states.put(root, (state, size));
+        inner.total_bytes += size;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use types::{Eth1Data, MinimalEthSpec};
+
+    type E = MinimalEthSpec;
+
+    fn state(slot: u64) -> BeaconState<E> {
+        let mut state = BeaconState::new(0, Eth1Data::default(), &E::default_spec());
+        state.slot = Slot::new(slot);
+        state
+    }
+
+    fn root(i: u64) -> Hash256 {
+        Hash256::from_low_u64_be(i)
+    }
+
+    fn cached_slot(cache: &StateCache<E>, root: Hash256) -> Option<u64> {
+        cache
+            .get_or_load(root, || Ok(None))
+            .unwrap()
+            .map(|state| state.slot.as_u64())
+    }
+
+    #[test]
+    fn hit_after_load() {
+        let cache = StateCache::new(2, usize::max_value());
+
+        let loaded = cache.get_or_load(root(1), || Ok(Some(state(1)))).unwrap();
+        assert_eq!(loaded.map(|state| state.slot), Some(Slot::new(1)));
+
+        let cached = cache
+            .get_or_load(root(1), || panic!("should not load a cached state"))
+            .unwrap();
+        assert_eq!(cached.map(|state| state.slot), Some(Slot::new(1)));
+    }
+
+    #[test]
+    fn evicts_least_recently_used() {
+        let cache = StateCache::new(2, usize::max_value());
+
+        for i in 1..=2 {
+            cache.get_or_load(root(i), || Ok(Some(state(i)))).unwrap();
+        }
+        // Use the first state, so the second is the least recently used.
+        assert_eq!(cached_slot(&cache, root(1)), Some(1));
+        cache.get_or_load(root(3), || Ok(Some(state(3)))).unwrap();
+
+        assert_eq!(cached_slot(&cache, root(1)), Some(1));
+        assert_eq!(cached_slot(&cache, root(2)), None);
+        assert_eq!(cached_slot(&cache, root(3)), Some(3));
+    }
+
+    #[test]
+    fn bounded_in_bytes() {
+        let size = state(1).ssz_bytes_len();
+        let cache = StateCache::new(10, size * 2);
+
+        for i in 1..=3 {
+            cache.get_or_load(root(i), || Ok(Some(state(i)))).unwrap();
+        }
+        assert_eq!(cached_slot(&cache, root(1)), None);
+        assert_eq!(cached_slot(&cache, root(2)), Some(2));
+        assert_eq!(cached_slot(&cache, root(3)), Some(3));
+
+        // A state larger than the cache is never stored.
+        let tiny = StateCache::new(10, size - 1);
+        tiny.get_or_load(root(1), || Ok(Some(state(1)))).unwrap();
+        assert_eq!(cached_slot(&tiny, root(1)), None);
+    }
+
+    #[test]
+    fn disabled() {
+        let cache = StateCache::new(0, usize::max_value());
+        cache.get_or_load(root(1), || Ok(Some(state(1)))).unwrap();
+        assert_eq!(cached_slot(&cache, root(1)), None);
+    }
+}
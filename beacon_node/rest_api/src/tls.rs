@@ -0,0 +1,131 @@
+//! Serves the HTTP API over TLS (i.e., HTTPS).
+
+use futures::future;
+use futures::stream::{self, Stream, StreamExt};
+use slog::{debug, Logger};
+use std::fs::File;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{TcpListener, TcpStream};
+use tokio_rustls::rustls::internal::pemfile::{certs, pkcs8_private_keys, rsa_private_keys};
+use tokio_rustls::rustls::{NoClientAuth, ServerConfig};
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+
+/// The maximum time a client may take to complete the TLS handshake.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+/// The maximum number of TLS handshakes that may be in progress at once.
+const MAX_CONCURRENT_HANDSHAKES: usize = 64;
+/// The time to wait before accepting again after failing to accept a TCP connection (e.g., due to
+/// running out of file descriptors).
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_secs(1);
+
+/// Builds a `TlsAcceptor` from the PEM encoded certificate chain and private key at the given
+/// paths.
+pub fn tls_acceptor(cert_path: &Path, key_path: &Path) -> Result<TlsAcceptor, String> {
+    let open = |path: &Path| {
+        File::open(path)
+            .map(BufReader::new)
+            .map_err(|e| format!("Unable to open {:?}: {}", path, e))
+    };
+
+    let cert_chain = certs(&mut open(cert_path)?)
+        .map_err(|()| format!("Unable to parse TLS certificates in {:?}", cert_path))?;
+    if cert_chain.is_empty() {
+        return Err(format!("No TLS certificates found in {:?}", cert_path));
+    }
+
+    let invalid_key = |()| format!("Unable to parse TLS private key in {:?}", key_path);
+    let mut keys = pkcs8_private_keys(&mut open(key_path)?).map_err(invalid_key)?;
+    if keys.is_empty() {
+        keys = rsa_private_keys(&mut open(key_path)?).map_err(invalid_key)?;
+    }
+    let key = keys
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("No TLS private key found in {:?}", key_path))?;
+
+    let mut config = ServerConfig::new(NoClientAuth::new());
+    config
+        .set_single_cert(cert_chain, key)
+        .map_err(|e| format!("Invalid TLS certificate or private key: {}", e))?;
+
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Returns a stream of the TLS connections accepted on `listener`.
+///
+/// Handshakes are performed concurrently, so a slow client does not delay others. Connections
+/// which fail to be accepted or to complete the handshake are logged and dropped, rather than
+/// stopping the server.
+pub fn incoming(
+    listener: TcpListener,
+    acceptor: TlsAcceptor,
+    log: Logger,
+) -> impl Stream<Item = Result<TlsStream<TcpStream>, io::Error>> {
+    stream::unfold(listener, |mut listener| async move {
+        let result = listener.accept().await;
+        if result.is_err() {
+            tokio::time::delay_for(ACCEPT_ERROR_DELAY).await;
+        }
+        Some((result, listener))
+    })
+    .map(move |result| {
+        let acceptor = acceptor.clone();
+        async move {
+            let (tcp_stream, _) = result?;
+            tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(tcp_stream))
+                .await
+                .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "TLS handshake timed out"))?
+        }
+    })
+    .buffer_unordered(MAX_CONCURRENT_HANDSHAKES)
+    .filter_map(move |result| {
+        let connection = match result {
+            Ok(tls_stream) => Some(Ok(tls_stream)),
+            Err(e) => {
+                debug!(log, "Failed to accept HTTPS connection"; "error" => e.to_string());
+                None
+            }
+        };
+        future::ready(connection)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn tls_dir() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/tls")
+    }
+
+    #[test]
+    fn valid_cert_and_key() {
+        let dir = tls_dir();
+        assert!(tls_acceptor(&dir.join("cert.pem"), &dir.join("key.pem")).is_ok());
+    }
+
+    #[test]
+    fn missing_file() {
+        let dir = tls_dir();
+        let missing = dir.join("missing.pem");
+
+        let err = tls_acceptor(&missing, &dir.join("key.pem")).err().unwrap();
+        assert!(err.contains("Unable to open"), "{}", err);
+        assert!(err.contains("missing.pem"), "{}", err);
+    }
+
+    #[test]
+    fn swapped_cert_and_key() {
+        let dir = tls_dir();
+
+        let err = tls_acceptor(&dir.join("key.pem"), &dir.join("cert.pem"))
+            .err()
+            .unwrap();
+        assert!(err.contains("No TLS certificates found"), "{}", err);
+    }
+}
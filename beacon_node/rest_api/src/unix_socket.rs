@@ -0,0 +1,120 @@
+//! Serves the HTTP API on a Unix domain socket.
+
+use crate::{router, Context};
+use beacon_chain::BeaconChainTypes;
+use environment::TaskExecutor;
+use futures::future::TryFutureExt;
+use futures::stream::{self, Stream};
+use hyper::server::accept;
+use hyper::service::{make_service_fn, service_fn};
+use hyper::{Body, Request, Server};
+use slog::{debug, info, warn, Logger};
+use std::fs;
+use std::io;
+use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::{UnixListener, UnixStream};
+
+/// The time to wait before accepting again after failing to accept a connection (e.g., due to
+/// running out of file descriptors).
+const ACCEPT_ERROR_DELAY: Duration = Duration::from_secs(1);
+
+/// Binds a Unix domain socket at `path`, which only the current user may connect to.
+///
+/// A stale socket at `path` (e.g., left by a node which did not shut down cleanly) is replaced,
+/// but any other kind of file is left untouched.
+pub fn bind(path: &Path) -> Result<UnixListener, String> {
+    let error = |e: io::Error| format!("Unable to bind HTTP server to {:?}: {}", path, e);
+
+    match fs::symlink_metadata(path) {
+        Ok(metadata) if metadata.file_type().is_socket() => fs::remove_file(path).map_err(error)?,
+        Ok(_) => {
+            return Err(format!(
+                "Unable to bind HTTP server to {:?}: file exists and is not a socket",
+                path
+            ))
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => (),
+        Err(e) => return Err(error(e)),
+    }
+
+    let listener = UnixListener::bind(path).map_err(error)?;
+    fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(error)?;
+
+    Ok(listener)
+}
+
+/// Serves the HTTP API to the connections accepted on `listener`, which is bound to `path`.
+///
+/// The socket file is removed when the server is shutdown.
+pub fn serve<T: BeaconChainTypes>(
+    listener: UnixListener,
+    path: PathBuf,
+    context: Arc<Context<T>>,
+    executor: &TaskExecutor,
+    log: Logger,
+) {
+    // Build a channel to kill the HTTP server.
+    let exit = executor.exit();
+    let inner_log = log.clone();
+    let inner_path = path.clone();
+    let server_exit = async move {
+        let _ = exit.await;
+        if let Err(e) = fs::remove_file(&inner_path) {
+            warn!(inner_log, "Unable to remove HTTP Unix socket"; "error" => e.to_string());
+        }
+        info!(inner_log, "HTTP service shutdown"; "path" => format!("{:?}", inner_path));
+    };
+
+    let make_service = make_service_fn(move |_: &UnixStream| {
+        let ctx = context.clone();
+
+        async move {
+            Ok::<_, hyper::Error>(service_fn(move |req: Request<Body>| {
+                router::on_http_request(req, None, ctx.clone())
+            }))
+        }
+    });
+
+    let inner_log = log.clone();
+    let server_future = Server::builder(accept::from_stream(incoming(listener, log.clone())))
+        .serve(make_service)
+        .with_graceful_shutdown(server_exit)
+        .map_err(move |e| {
+            warn!(inner_log, "HTTP server failed"; "error" => format!("{:?}", e));
+        })
+        .unwrap_or_else(|_| ());
+
+    info!(log, "HTTP API started"; "path" => format!("{:?}", path));
+
+    executor.spawn_without_exit(server_future, "http_unix_socket");
+}
+
+/// Returns a stream of the connections accepted on `listener`.
+///
+/// Failures to accept a connection are logged, rather than stopping the server.
+fn incoming(
+    listener: UnixListener,
+    log: Logger,
+) -> impl Stream<Item = Result<UnixStream, io::Error>> {
+    stream::unfold(listener, move |mut listener| {
+        let log = log.clone();
+        async move {
+            loop {
+                match listener.accept().await {
+                    Ok((stream, _)) => return Some((Ok(stream), listener)),
+                    Err(e) => {
+                        debug!(
+                            log,
+                            "Failed to accept HTTP Unix socket connection";
+                            "error" => e.to_string()
+                        );
+                        tokio::time::delay_for(ACCEPT_ERROR_DELAY).await;
+                    }
+                }
+            }
+        }
+    })
+}
@@ -1,6 +1,7 @@
 use crate::helpers::{parse_committee_index, parse_epoch, parse_hex_ssz_bytes, parse_slot};
 use crate::ApiError;
 use hyper::Request;
+use rest_types::ValidatorField;
 use types::{AttestationData, CommitteeIndex, Epoch, Signature, Slot};
 
 /// Provides handy functions for parsing the query parameters of a URL.
@@ -85,6 +86,36 @@ impl<'a> UrlQuery<'a> {
         Ok(queries)
     }
 
+    /// Returns the `offset` and `limit` pagination parameters, if either is present.
+    ///
+    /// A missing `offset` is `0` and a missing `limit` is unlimited.
+    pub fn pagination(self) -> Result<Option<(usize, Option<usize>)>, ApiError> {
+        let parse = |key: &str| {
+            self.first_of_opt(&[key])
+                .map(|(_key, value)| {
+                    value.parse::<usize>().map_err(|e| {
+                        ApiError::BadRequest(format!("Invalid {} {:?}: {:?}", key, value, e))
+                    })
+                })
+                .transpose()
+        };
+        let offset = parse("offset")?;
+        let limit = parse("limit")?;
+
+        if offset.is_none() && limit.is_none() {
+            Ok(None)
+        } else {
+            Ok(Some((offset.unwrap_or(0), limit)))
+        }
+    }
+
+    /// Returns the validator fields selected with the `fields` key, if present.
+    pub fn validator_fields(self) -> Result<Option<Vec<ValidatorField>>, ApiError> {
+        self.first_of_opt(&["fields"])
+            .map(|(_key, value)| ValidatorField::parse_list(&value).map_err(ApiError::BadRequest))
+            .transpose()
+    }
+
     /// Returns the value of the first occurrence of the `epoch` key.
     pub fn epoch(self) -> Result<Epoch, ApiError> {
         self.first_of(&["epoch"])
@@ -163,4 +194,20 @@ mod test {
         );
         assert!(get_query().first_of(&["nothing"]).is_err());
     }
+
+    #[test]
+    fn pagination() {
+        let get_result =
+            |addr: &str| UrlQuery(url::Url::parse(addr).unwrap().query_pairs()).pagination();
+
+        assert_eq!(get_result("http://cat.io/?slot=1"), Ok(None));
+        assert_eq!(get_result("http://cat.io/?offset=4"), Ok(Some((4, None))));
+        assert_eq!(get_result("http://cat.io/?limit=2"), Ok(Some((0, Some(2)))));
+        assert_eq!(
+            get_result("http://cat.io/?limit=2&offset=4"),
+            Ok(Some((4, Some(2))))
+        );
+        assert!(get_result("http://cat.io/?limit=-1").is_err());
+        assert!(get_result("http://cat.io/?offset=cat").is_err());
+    }
 }
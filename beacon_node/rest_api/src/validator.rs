@@ -1,15 +1,25 @@
-use crate::helpers::{parse_hex_ssz_bytes, publish_beacon_block_to_network};
+use crate::helpers::{
+    parse_hex_ssz_bytes, parse_root, publish_beacon_block_to_network, request_log,
+};
+use crate::metrics::{self, SubmissionOutcome, SubmittedObject};
 use crate::{ApiError, Context, NetworkChannel, UrlQuery};
 use beacon_chain::{
-    attestation_verification::Error as AttnError, BeaconChain, BeaconChainError, BeaconChainTypes,
-    BlockError, ForkChoiceError, StateSkipConfig,
+    attestation_verification::{Error as AttnError, VerifiedUnaggregatedAttestation},
+    BeaconChain, BeaconChainError, BeaconChainTypes, BlockError, BlockProductionError,
+    ForkChoiceError, InvalidForkChoiceAttestation, NaiveAggregationError, StateSkipConfig,
 };
 use bls::PublicKeyBytes;
 use eth2_libp2p::PubsubMessage;
-use hyper::Request;
+use hyper::{header, Request, StatusCode};
 use network::NetworkMessage;
-use rest_types::{ValidatorDutiesRequest, ValidatorDutyBytes, ValidatorSubscription};
-use slog::{error, info, trace, warn, Logger};
+use rest_types::{
+    AggregateAndProofStatus, AttesterData, BeaconCommitteeSubscription, Failure, GenericResponse,
+    IndexedErrorMessage, ValidatorDutiesRequest, ValidatorDutyBytes, ValidatorIndices,
+    ValidatorSubscription, OCTET_STREAM_CONTENT_TYPE,
+};
+use slog::{debug, error, info, trace, warn, Logger};
+use ssz::Decode;
+use std::collections::HashSet;
 use std::sync::Arc;
 use types::beacon_state::EthSpec;
 use types::{
@@ -34,14 +44,101 @@ pub fn post_validator_duties<T: BeaconChainTypes>(
             ))
         })
         .and_then(|bulk_request| {
+            check_duties_epoch(&ctx.beacon_chain, bulk_request.epoch)?;
+
+            let state = get_state_for_epoch(
+                &ctx.beacon_chain,
+                bulk_request.epoch,
+                StateSkipConfig::WithoutStateRoots,
+            )?;
+
             return_validator_duties(
-                &ctx.beacon_chain.clone(),
+                &ctx.beacon_chain,
+                state,
                 bulk_request.epoch,
                 bulk_request.pubkeys.into_iter().map(Into::into).collect(),
             )
         })
 }
 
+/// HTTP Handler to retrieve the attester duties, during the epoch in the last segment of the
+/// request path, of the validators whose indices are listed in the JSON request body.
+///
+/// Unlike the query string of a GET request, the body is not limited in length. Indices which are
+/// not in the validator registry, or whose validators have no duties in the epoch, are omitted.
+/// Duties are listed in the order of the requested indices.
+pub fn post_attester_duties<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<GenericResponse<Vec<AttesterData>>, ApiError> {
+    let epoch_str = req.uri().path().rsplit('/').next().unwrap_or("");
+    let epoch = epoch_str
+        .parse::<u64>()
+        .map(Epoch::new)
+        .map_err(|e| ApiError::BadRequest(format!("Invalid epoch {}: {:?}", epoch_str, e)))?;
+
+    let indices: ValidatorIndices = serde_json::from_slice(req.body()).map_err(|e| {
+        ApiError::BadRequest(format!(
+            "Unable to parse JSON into a list of validator indices: {:?}",
+            e
+        ))
+    })?;
+
+    check_duties_epoch(&ctx.beacon_chain, epoch)?;
+
+    let mut state =
+        get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
+
+    let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+        .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
+
+    ctx.beacon_chain
+        .build_committee_cache(&mut state, relative_epoch)
+        .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
+
+    // Find the duties of all the validators at once, rather than searching the committees for
+    // each validator in turn.
+    let requested_indices = indices
+        .0
+        .iter()
+        .map(|&validator_index| validator_index as usize)
+        .collect::<HashSet<_>>();
+    let attestation_duties = state
+        .get_attestation_duties_for_indices(&requested_indices, relative_epoch)
+        .map_err(|e| {
+            ApiError::ServerError(format!("Unable to obtain attestation duties: {:?}", e))
+        })?;
+
+    let mut duties = Vec::with_capacity(attestation_duties.len());
+    for validator_index in indices.0 {
+        let validator = match state.validators.get(validator_index as usize) {
+            Some(validator) => validator,
+            None => continue,
+        };
+
+        let duty = match attestation_duties.get(&(validator_index as usize)) {
+            Some(duty) => duty,
+            None => continue,
+        };
+
+        let committees_at_slot = state.get_committee_count_at_slot(duty.slot).map_err(|e| {
+            ApiError::ServerError(format!("Unable to find committee count at slot: {:?}", e))
+        })?;
+
+        duties.push(AttesterData {
+            pubkey: validator.pubkey.clone(),
+            validator_index,
+            committees_at_slot,
+            committee_index: duty.index,
+            committee_length: duty.committee_len as u64,
+            validator_committee_index: duty.committee_position as u64,
+            slot: duty.slot.as_u64(),
+        });
+    }
+
+    Ok(GenericResponse { data: duties })
+}
+
 /// HTTP Handler to retrieve subscriptions for a set of validators. This allows the node to
 /// organise peer discovery and topic subscription for known validators.
 pub fn post_validator_subscriptions<T: BeaconChainTypes>(
@@ -70,6 +167,76 @@ pub fn post_validator_subscriptions<T: BeaconChainTypes>(
         })
 }
 
+/// HTTP Handler to subscribe to the attestation subnets of beacon committees, as per the standard
+/// Eth2 API.
+///
+/// Valid subscriptions are forwarded to the network even if others in the request are invalid, in
+/// which case the invalid subscriptions are reported by their index in the request.
+pub fn post_beacon_committee_subscriptions<T: BeaconChainTypes>(
+    req: Request<Vec<u8>>,
+    ctx: Arc<Context<T>>,
+) -> Result<StatusCode, ApiError> {
+    let subscriptions: Vec<BeaconCommitteeSubscription> = serde_json::from_slice(req.body())
+        .map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into BeaconCommitteeSubscriptions: {:?}",
+                e
+            ))
+        })?;
+
+    let current_slot = ctx
+        .beacon_chain
+        .slot()
+        .map_err(|e| ApiError::ServerError(format!("Unable to read slot clock: {:?}", e)))?;
+
+    let mut failures = vec![];
+    let mut valid_subscriptions = Vec::with_capacity(subscriptions.len());
+    for (index, subscription) in subscriptions.into_iter().enumerate() {
+        let message = if subscription.slot < current_slot.as_u64() {
+            format!(
+                "Subscription slot {} is prior to the current slot {}",
+                subscription.slot, current_slot
+            )
+        } else if subscription.committee_index >= subscription.committees_at_slot {
+            format!(
+                "Committee index {} is not less than the committee count {}",
+                subscription.committee_index, subscription.committees_at_slot
+            )
+        } else {
+            valid_subscriptions.push(subscription.into());
+            continue;
+        };
+
+        failures.push(Failure {
+            index: index as u64,
+            message,
+        });
+    }
+
+    if !valid_subscriptions.is_empty() {
+        ctx.network_chan
+            .send(NetworkMessage::Subscribe {
+                subscriptions: valid_subscriptions,
+            })
+            .map_err(|e| {
+                ApiError::ServerError(format!(
+                    "Unable to send subscriptions to the network: {:?}",
+                    e
+                ))
+            })?;
+    }
+
+    if failures.is_empty() {
+        Ok(StatusCode::OK)
+    } else {
+        Err(ApiError::IndexedBadRequest(IndexedErrorMessage {
+            code: StatusCode::BAD_REQUEST.as_u16(),
+            message: "Some subscriptions were invalid".to_string(),
+            failures,
+        }))
+    }
+}
+
 /// HTTP Handler to retrieve all validator duties for the given epoch.
 pub fn get_all_validator_duties<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
@@ -78,6 +245,7 @@ pub fn get_all_validator_duties<T: BeaconChainTypes>(
     let query = UrlQuery::from_request(&req)?;
 
     let epoch = query.epoch()?;
+    check_duties_epoch(&ctx.beacon_chain, epoch)?;
 
     let state = get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
 
@@ -87,7 +255,7 @@ pub fn get_all_validator_duties<T: BeaconChainTypes>(
         .map(|validator| validator.pubkey.clone())
         .collect();
 
-    return_validator_duties(&ctx.beacon_chain, epoch, validator_pubkeys)
+    return_validator_duties(&ctx.beacon_chain, state, epoch, validator_pubkeys)
 }
 
 /// HTTP Handler to retrieve all active validator duties for the given epoch.
@@ -98,20 +266,54 @@ pub fn get_active_validator_duties<T: BeaconChainTypes>(
     let query = UrlQuery::from_request(&req)?;
 
     let epoch = query.epoch()?;
+    check_duties_epoch(&ctx.beacon_chain, epoch)?;
 
     let state = get_state_for_epoch(&ctx.beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
 
+    // The state may be in the epoch prior to `epoch`, so the validators which are active in
+    // `epoch` itself are selected.
     let validator_pubkeys = state
         .validators
         .iter()
-        .filter(|validator| validator.is_active_at(state.current_epoch()))
+        .filter(|validator| validator.is_active_at(epoch))
         .map(|validator| validator.pubkey.clone())
         .collect();
 
-    return_validator_duties(&ctx.beacon_chain, epoch, validator_pubkeys)
+    return_validator_duties(&ctx.beacon_chain, state, epoch, validator_pubkeys)
+}
+
+/// Returns an error if `epoch` is beyond the next epoch of `beacon_chain`.
+///
+/// The shuffling for any later epoch is not yet known, so duties cannot be determined for it.
+fn check_duties_epoch<T: BeaconChainTypes>(
+    beacon_chain: &BeaconChain<T>,
+    epoch: Epoch,
+) -> Result<(), ApiError> {
+    let current_epoch = beacon_chain.epoch()?;
+    let next_epoch = current_epoch + 1;
+
+    if epoch > next_epoch {
+        Err(ApiError::BadRequest(format!(
+            "Duties are only available up to the next epoch ({}), not epoch {}",
+            next_epoch, epoch
+        )))
+    } else {
+        Ok(())
+    }
 }
 
 /// Helper function to return the state that can be used to determine the duties for some `epoch`.
+///
+/// Committees are determined an epoch in advance and a state retains those of its previous epoch,
+/// so a state in `epoch` or either epoch adjacent to it will do for attestation duties. However,
+/// block proposers are only known from a state in `epoch` itself, so one is returned unless
+/// `epoch` is in the past, or later than both the wall clock and the head (when its proposers
+/// cannot be known yet).
+///
+/// The head state is used if it is suitable. Its epoch is compared directly, rather than assuming
+/// it is the wall clock epoch, since the head may lag the wall clock (e.g., after skipped slots) or
+/// be slightly ahead of it around an epoch boundary. Otherwise, the canonical state at the start of
+/// the epoch is loaded, or the head state is advanced to it with skipped slots.
 pub fn get_state_for_epoch<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     epoch: Epoch,
@@ -122,39 +324,42 @@ pub fn get_state_for_epoch<T: BeaconChainTypes>(
     let current_epoch = beacon_chain.epoch()?;
     let head_epoch = head.beacon_state.current_epoch();
 
-    if head_epoch == current_epoch && RelativeEpoch::from_epoch(current_epoch, epoch).is_ok() {
+    let head_is_suitable = epoch == head_epoch
+        || (epoch + 1 == head_epoch && epoch < current_epoch)
+        || (epoch == head_epoch + 1 && epoch > current_epoch);
+
+    if head_is_suitable {
         Ok(head.beacon_state)
     } else {
-        // If epoch is ahead of current epoch, then it should be a "next epoch" request for
-        // attestation duties. So, go to the start slot of the epoch prior to that,
-        // which should be just the next wall-clock epoch.
-        let slot = if epoch > current_epoch {
-            (epoch - 1).start_slot(slots_per_epoch)
-        }
-        // Otherwise, go to the start of the request epoch.
-        else {
-            epoch.start_slot(slots_per_epoch)
+        // An `epoch` later than the current epoch is at least 1, so the subtraction (which
+        // saturates) cannot underflow for an epoch 0 request at genesis.
+        let state_epoch = if epoch > std::cmp::max(current_epoch, head_epoch) {
+            epoch - 1
+        } else {
+            epoch
         };
 
-        beacon_chain.state_at_slot(slot, config).map_err(|e| {
-            ApiError::ServerError(format!("Unable to load state for epoch {}: {:?}", epoch, e))
-        })
+        beacon_chain
+            .state_at_slot(state_epoch.start_slot(slots_per_epoch), config)
+            .map_err(|e| {
+                ApiError::ServerError(format!("Unable to load state for epoch {}: {:?}", epoch, e))
+            })
     }
 }
 
-/// Helper function to get the duties for some `validator_pubkeys` in some `epoch`.
+/// Helper function to get the duties for some `validator_pubkeys` in some `epoch`, from a `state`
+/// returned by `get_state_for_epoch`.
 fn return_validator_duties<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
+    mut state: BeaconState<T::EthSpec>,
     epoch: Epoch,
     validator_pubkeys: Vec<PublicKeyBytes>,
 ) -> Result<Vec<ValidatorDutyBytes>, ApiError> {
-    let mut state = get_state_for_epoch(&beacon_chain, epoch, StateSkipConfig::WithoutStateRoots)?;
-
     let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
         .map_err(|_| ApiError::ServerError(String::from("Loaded state is in the wrong epoch")))?;
 
-    state
-        .build_committee_cache(relative_epoch, &beacon_chain.spec)
+    beacon_chain
+        .build_committee_cache(&mut state, relative_epoch)
         .map_err(|e| ApiError::ServerError(format!("Unable to build committee cache: {:?}", e)))?;
     state
         .update_pubkey_cache()
@@ -263,6 +468,7 @@ pub fn get_new_beacon_block<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<BeaconBlock<T::EthSpec>, ApiError> {
+    let log = request_log(&req, &ctx);
     let query = UrlQuery::from_request(&req)?;
 
     let slot = query.slot()?;
@@ -274,12 +480,36 @@ pub fn get_new_beacon_block<T: BeaconChainTypes>(
         None
     };
 
-    let (new_block, _state) = ctx
-        .beacon_chain
-        .produce_block(randao_reveal, slot, validator_graffiti)
-        .map_err(|e| {
+    let parent_root = query
+        .first_of_opt(&["parent_root"])
+        .map(|(_, value)| parse_root(&value))
+        .transpose()?;
+
+    let result = if let Some(parent_root) = parent_root {
+        ctx.beacon_chain.produce_block_with_parent(
+            randao_reveal,
+            slot,
+            validator_graffiti,
+            parent_root,
+        )
+    } else {
+        ctx.beacon_chain
+            .produce_block(randao_reveal, slot, validator_graffiti)
+    };
+
+    let (new_block, _state) = result.map_err(|e| match e {
+        BlockProductionError::UnknownParentBlock(root) => {
+            ApiError::NotFound(format!("Unknown parent block: {:?}", root))
+        }
+        BlockProductionError::ParentIsPreFinalization { .. } => {
+            ApiError::BadRequest(format!("Parent block conflicts with finalization: {:?}", e))
+        }
+        BlockProductionError::UnableToProduceAtSlot(_) if parent_root.is_some() => {
+            ApiError::BadRequest(format!("Parent block must be prior to slot {}", slot))
+        }
+        e => {
             error!(
-                ctx.log,
+                log,
                 "Error whilst producing block";
                 "error" => format!("{:?}", e)
             );
@@ -288,93 +518,130 @@ pub fn get_new_beacon_block<T: BeaconChainTypes>(
                 "Beacon node is not able to produce a block: {:?}",
                 e
             ))
-        })?;
+        }
+    })?;
 
     Ok(new_block)
 }
 
+/// Decodes the body of `req` as a `SignedBeaconBlock`, from SSZ if the `Content-Type` is
+/// `application/octet-stream` and from JSON otherwise.
+fn signed_block_from_body<E: EthSpec>(
+    req: &Request<Vec<u8>>,
+) -> Result<SignedBeaconBlock<E>, ApiError> {
+    let is_ssz = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map_or(false, |content_type| {
+            content_type == OCTET_STREAM_CONTENT_TYPE
+        });
+
+    if is_ssz {
+        SignedBeaconBlock::from_ssz_bytes(req.body()).map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse SSZ into SignedBeaconBlock: {:?}",
+                e
+            ))
+        })
+    } else {
+        serde_json::from_slice(req.body()).map_err(|e| {
+            ApiError::BadRequest(format!(
+                "Unable to parse JSON into SignedBeaconBlock: {:?}",
+                e
+            ))
+        })
+    }
+}
+
 /// HTTP Handler to publish a SignedBeaconBlock, which has been signed by a validator.
+///
+/// The block is read as SSZ if the `Content-Type` is `application/octet-stream`, or JSON otherwise.
 pub fn publish_beacon_block<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<(), ApiError> {
-    let body = req.into_body();
+    let log = request_log(&req, &ctx);
 
-    serde_json::from_slice(&body).map_err(|e| {
-                    ApiError::BadRequest(format!("Unable to parse JSON into SignedBeaconBlock: {:?}", e))
-                })
-            .and_then(move |block: SignedBeaconBlock<T::EthSpec>| {
-                let slot = block.slot();
-                match ctx.beacon_chain.process_block(block.clone()) {
-                    Ok(block_root) => {
-                        // Block was processed, publish via gossipsub
-                        info!(
-                            ctx.log,
-                            "Block from local validator";
-                            "block_root" => format!("{}", block_root),
-                            "block_slot" => slot,
-                        );
+    signed_block_from_body(&req).and_then(|block: SignedBeaconBlock<T::EthSpec>| {
+        let slot = block.slot();
+        match ctx.beacon_chain.process_block(block.clone()) {
+            Ok(block_root) => {
+                metrics::inc_submitted_object(SubmittedObject::Block, SubmissionOutcome::Imported);
 
-                        publish_beacon_block_to_network::<T>(&ctx.network_chan, block)?;
-
-                        // Run the fork choice algorithm and enshrine a new canonical head, if
-                        // found.
-                        //
-                        // The new head may or may not be the block we just received.
-                        if let Err(e) = ctx.beacon_chain.fork_choice() {
-                            error!(
-                                ctx.log,
-                                "Failed to find beacon chain head";
-                                "error" => format!("{:?}", e)
-                            );
-                        } else {
-                            // In the best case, validators should produce blocks that become the
-                            // head.
-                            //
-                            // Potential reasons this may not be the case:
-                            //
-                            // - A quick re-org between block produce and publish.
-                            // - Excessive time between block produce and publish.
-                            // - A validator is using another beacon node to produce blocks and
-                            // submitting them here.
-                            if ctx.beacon_chain.head()?.beacon_block_root != block_root {
-                                warn!(
-                                    ctx.log,
-                                    "Block from validator is not head";
-                                    "desc" => "potential re-org",
-                                );
-
-                            }
-                        }
-
-                        Ok(())
-                    }
-                    Err(BlockError::BeaconChainError(e)) => {
-                        error!(
-                            ctx.log,
-                            "Error whilst processing block";
-                            "error" => format!("{:?}", e)
-                        );
+                // Block was processed, publish via gossipsub
+                info!(
+                    log,
+                    "Block from local validator";
+                    "block_root" => format!("{}", block_root),
+                    "block_slot" => slot,
+                );
 
-                        Err(ApiError::ServerError(format!(
-                            "Error while processing block: {:?}",
-                            e
-                        )))
-                    }
-                    Err(other) => {
+                publish_beacon_block_to_network::<T>(&ctx.network_chan, block)?;
+
+                // Run the fork choice algorithm and enshrine a new canonical head, if
+                // found.
+                //
+                // The new head may or may not be the block we just received.
+                if let Err(e) = ctx.beacon_chain.fork_choice() {
+                    error!(
+                        log,
+                        "Failed to find beacon chain head";
+                        "error" => format!("{:?}", e)
+                    );
+                } else {
+                    // In the best case, validators should produce blocks that become the
+                    // head.
+                    //
+                    // Potential reasons this may not be the case:
+                    //
+                    // - A quick re-org between block produce and publish.
+                    // - Excessive time between block produce and publish.
+                    // - A validator is using another beacon node to produce blocks and
+                    // submitting them here.
+                    if ctx.beacon_chain.head()?.beacon_block_root != block_root {
                         warn!(
-                            ctx.log,
-                            "Invalid block from local validator";
-                            "outcome" => format!("{:?}", other)
+                            log,
+                            "Block from validator is not head";
+                            "desc" => "potential re-org",
                         );
-
-                        Err(ApiError::ProcessingError(format!(
-                            "The SignedBeaconBlock could not be processed and has not been published: {:?}",
-                            other
-                        )))
                     }
                 }
-        })
+
+                Ok(())
+            }
+            Err(BlockError::BeaconChainError(e)) => {
+                error!(
+                    log,
+                    "Error whilst processing block";
+                    "error" => format!("{:?}", e)
+                );
+
+                Err(ApiError::ServerError(format!(
+                    "Error while processing block: {:?}",
+                    e
+                )))
+            }
+            Err(other) => {
+                let outcome = if let BlockError::BlockIsAlreadyKnown = other {
+                    SubmissionOutcome::AlreadyKnown
+                } else {
+                    SubmissionOutcome::Invalid
+                };
+                metrics::inc_submitted_object(SubmittedObject::Block, outcome);
+
+                warn!(
+                    log,
+                    "Invalid block from local validator";
+                    "outcome" => format!("{:?}", other)
+                );
+
+                Err(ApiError::ProcessingError(format!(
+                    "The SignedBeaconBlock could not be processed and has not been published: {:?}",
+                    other
+                )))
+            }
+        }
+    })
 }
 
 /// HTTP Handler to produce a new Attestation from the current state, ready to be signed by a validator.
@@ -423,6 +690,7 @@ pub fn publish_attestations<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
 ) -> Result<(), ApiError> {
+    let log = request_log(&req, &ctx).clone();
     let bytes = req.into_body();
 
     serde_json::from_slice(&bytes)
@@ -432,23 +700,41 @@ pub fn publish_attestations<T: BeaconChainTypes>(
                 e
             ))
         })
-        // Process all of the aggregates _without_ exiting early if one fails.
-        .map(
+        // Verify the signatures of all the attestations as a batch, then process each of them
+        // _without_ exiting early if one fails.
+        .and_then(
             move |attestations: Vec<(Attestation<T::EthSpec>, SubnetId)>| {
-                attestations
+                let items = attestations
+                    .iter()
+                    .map(|(attestation, subnet_id)| (attestation.data.clone(), *subnet_id))
+                    .collect::<Vec<_>>();
+
+                let verification_results = ctx
+                    .beacon_chain
+                    .batch_verify_unaggregated_attestations_for_gossip(attestations)
+                    .map_err(|e| {
+                        ApiError::ServerError(format!(
+                            "Unable to verify unaggregated attestations: {:?}",
+                            e
+                        ))
+                    })?;
+
+                Ok(verification_results
                     .into_iter()
+                    .zip(items)
                     .enumerate()
-                    .map(|(i, (attestation, subnet_id))| {
+                    .map(|(i, (verification_result, (data, subnet_id)))| {
                         process_unaggregated_attestation(
                             &ctx.beacon_chain,
                             ctx.network_chan.clone(),
-                            attestation,
+                            verification_result,
+                            &data,
                             subnet_id,
                             i,
-                            &ctx.log,
+                            &log,
                         )
                     })
-                    .collect::<Vec<Result<_, _>>>()
+                    .collect::<Vec<Result<_, _>>>())
             },
         )
         // Iterate through all the results and return on the first `Err`.
@@ -459,35 +745,32 @@ pub fn publish_attestations<T: BeaconChainTypes>(
 }
 
 /// Processes an unaggregrated attestation that was included in a list of attestations with the
-/// index `i`.
-#[allow(clippy::redundant_clone)] // false positives in this function.
+/// index `i`, given the result of verifying it for gossip.
 fn process_unaggregated_attestation<T: BeaconChainTypes>(
     beacon_chain: &BeaconChain<T>,
     network_chan: NetworkChannel<T::EthSpec>,
-    attestation: Attestation<T::EthSpec>,
+    verification_result: Result<VerifiedUnaggregatedAttestation<T>, AttnError>,
+    data: &AttestationData,
     subnet_id: SubnetId,
     i: usize,
     log: &Logger,
 ) -> Result<(), ApiError> {
-    let data = &attestation.data.clone();
-
-    // Verify that the attestation is valid to included on the gossip network.
-    let verified_attestation = beacon_chain
-        .verify_unaggregated_attestation_for_gossip(attestation.clone(), subnet_id)
-        .map_err(|e| {
-            handle_attestation_error(
-                e,
-                &format!("unaggregated attestation {} failed gossip verification", i),
-                data,
-                log,
-            )
-        })?;
+    // Ensure that the attestation is valid to included on the gossip network.
+    let verified_attestation = verification_result.map_err(|e| {
+        inc_rejected_attestation(SubmittedObject::Attestation, &e);
+        handle_attestation_error(
+            e,
+            &format!("unaggregated attestation {} failed gossip verification", i),
+            data,
+            log,
+        )
+    })?;
 
     // Publish the attestation to the network
     if let Err(e) = network_chan.send(NetworkMessage::Publish {
         messages: vec![PubsubMessage::Attestation(Box::new((
             subnet_id,
-            attestation,
+            verified_attestation.attestation().clone(),
         )))],
     }) {
         return Err(ApiError::ServerError(format!(
@@ -496,9 +779,10 @@ fn process_unaggregated_attestation<T: BeaconChainTypes>(
         )));
     }
 
-    beacon_chain
+    // The attestation has been published, so from here it is either imported or only broadcast.
+    let fork_choice_result = beacon_chain
         .apply_attestation_to_fork_choice(&verified_attestation)
-        .map_err(|e| {
+        .or_else(|e| {
             handle_fork_choice_error(
                 e,
                 &format!(
@@ -508,21 +792,36 @@ fn process_unaggregated_attestation<T: BeaconChainTypes>(
                 data,
                 log,
             )
-        })?;
+        });
+    if fork_choice_result.is_err() {
+        metrics::inc_submitted_object(
+            SubmittedObject::Attestation,
+            SubmissionOutcome::BroadcastWithoutImport,
+        );
+    }
+    fork_choice_result?;
 
-    beacon_chain
+    let pool_result = beacon_chain
         .add_to_naive_aggregation_pool(verified_attestation)
-        .map_err(|e| {
-            handle_attestation_error(
-                e,
-                &format!(
-                    "unaggregated attestation {} was unable to be added to aggregation pool",
-                    i
-                ),
-                data,
-                log,
-            )
-        })?;
+        .map(|_| ());
+    let outcome = if pool_result.is_ok() {
+        SubmissionOutcome::Imported
+    } else {
+        SubmissionOutcome::BroadcastWithoutImport
+    };
+    metrics::inc_submitted_object(SubmittedObject::Attestation, outcome);
+
+    pool_result.or_else(|e| {
+        handle_naive_aggregation_error(
+            e,
+            &format!(
+                "unaggregated attestation {} was unable to be added to aggregation pool",
+                i
+            ),
+            data,
+            log,
+        )
+    })?;
 
     Ok(())
 }
@@ -531,7 +830,8 @@ fn process_unaggregated_attestation<T: BeaconChainTypes>(
 pub fn publish_aggregate_and_proofs<T: BeaconChainTypes>(
     req: Request<Vec<u8>>,
     ctx: Arc<Context<T>>,
-) -> Result<(), ApiError> {
+) -> Result<Vec<AggregateAndProofStatus>, ApiError> {
+    let log = request_log(&req, &ctx).clone();
     let body = req.into_body();
 
     serde_json::from_slice(&body)
@@ -553,16 +853,13 @@ pub fn publish_aggregate_and_proofs<T: BeaconChainTypes>(
                             ctx.network_chan.clone(),
                             signed_aggregate,
                             i,
-                            &ctx.log,
+                            &log,
                         )
+                        .unwrap_or_else(|e| AggregateAndProofStatus::Error(e.status_code().1))
                     })
-                    .collect::<Vec<Result<_, _>>>()
+                    .collect()
             },
         )
-        // Iterate through all the results and return on the first `Err`.
-        //
-        // Note: this will only provide info about the _first_ failure, not all failures.
-        .and_then(|processing_results| processing_results.into_iter().try_for_each(|result| result))
 }
 
 /// Processes an aggregrated attestation that was included in a list of attestations with the index
@@ -574,7 +871,7 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
     signed_aggregate: SignedAggregateAndProof<T::EthSpec>,
     i: usize,
     log: &Logger,
-) -> Result<(), ApiError> {
+) -> Result<AggregateAndProofStatus, ApiError> {
     let data = &signed_aggregate.message.aggregate.data.clone();
 
     // Verify that the attestation is valid to be included on the gossip network.
@@ -588,6 +885,10 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
         match beacon_chain.verify_aggregated_attestation_for_gossip(signed_aggregate.clone()) {
             Ok(verified_attestation) => verified_attestation,
             Err(AttnError::AttestationAlreadyKnown(attestation_root)) => {
+                metrics::inc_submitted_object(
+                    SubmittedObject::AggregateAndProof,
+                    SubmissionOutcome::AlreadyKnown,
+                );
                 trace!(
                     log,
                     "Ignored known attn from local validator";
@@ -596,7 +897,7 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
 
                 // Exit early with success for a known attestation, there's no need to re-process
                 // an aggregate we already know.
-                return Ok(());
+                return Ok(AggregateAndProofStatus::AlreadyKnown);
             }
             /*
              * It's worth noting that we don't check for `Error::AggregatorAlreadyKnown` since (at
@@ -607,12 +908,13 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
              * but I think it's still the sort of condition we should error on, at least for now.
              */
             Err(e) => {
+                inc_rejected_attestation(SubmittedObject::AggregateAndProof, &e);
                 return Err(handle_attestation_error(
                     e,
                     &format!("aggregated attestation {} failed gossip verification", i),
                     data,
                     log,
-                ))
+                ));
             }
         };
 
@@ -628,9 +930,10 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
         )));
     }
 
-    beacon_chain
+    // The aggregate has been published, so from here it is either imported or only broadcast.
+    let fork_choice_result = beacon_chain
         .apply_attestation_to_fork_choice(&verified_attestation)
-        .map_err(|e| {
+        .or_else(|e| {
             handle_fork_choice_error(
                 e,
                 &format!(
@@ -640,23 +943,50 @@ fn process_aggregated_attestation<T: BeaconChainTypes>(
                 data,
                 log,
             )
-        })?;
+        });
+    if fork_choice_result.is_err() {
+        metrics::inc_submitted_object(
+            SubmittedObject::AggregateAndProof,
+            SubmissionOutcome::BroadcastWithoutImport,
+        );
+    }
+    fork_choice_result?;
 
-    beacon_chain
-        .add_to_block_inclusion_pool(verified_attestation)
-        .map_err(|e| {
-            handle_attestation_error(
-                e,
-                &format!(
-                    "aggregated attestation {} was unable to be added to op pool",
-                    i
-                ),
-                data,
-                log,
-            )
-        })?;
+    let pool_result = beacon_chain.add_to_block_inclusion_pool(verified_attestation);
+    let outcome = if pool_result.is_ok() {
+        SubmissionOutcome::Imported
+    } else {
+        SubmissionOutcome::BroadcastWithoutImport
+    };
+    metrics::inc_submitted_object(SubmittedObject::AggregateAndProof, outcome);
+
+    pool_result.map_err(|e| {
+        handle_attestation_error(
+            e,
+            &format!(
+                "aggregated attestation {} was unable to be added to op pool",
+                i
+            ),
+            data,
+            log,
+        )
+    })?;
 
-    Ok(())
+    Ok(AggregateAndProofStatus::Imported)
+}
+
+/// Counts an attestation of type `object` which failed gossip verification with `e`.
+///
+/// Internal errors are not counted.
+fn inc_rejected_attestation(object: SubmittedObject, e: &AttnError) {
+    let outcome = match e {
+        AttnError::BeaconChainError(_) => return,
+        AttnError::AttestationAlreadyKnown(_)
+        | AttnError::AggregatorAlreadyKnown(_)
+        | AttnError::PriorAttestationKnown { .. } => SubmissionOutcome::AlreadyKnown,
+        _ => SubmissionOutcome::Invalid,
+    };
+    metrics::inc_submitted_object(object, outcome);
 }
 
 /// Common handler for `AttnError` during attestation verification.
@@ -704,14 +1034,85 @@ fn handle_attestation_error(
     }
 }
 
+/// Returns `true` if `e` is an expected result of adding a gossip-verified attestation to the
+/// naive aggregation pool, rather than a sign that the attestation or the node is faulty.
+///
+/// E.g., an attestation that is late but still valid for gossip may be too old for the pool.
+fn is_benign_naive_aggregation_error(e: &AttnError) -> bool {
+    matches!(
+        e,
+        AttnError::BeaconChainError(BeaconChainError::NaiveAggregationError(
+            NaiveAggregationError::SlotTooLow { .. }
+        )) | AttnError::BeaconChainError(BeaconChainError::NaiveAggregationError(
+            NaiveAggregationError::ReachedMaxAttestationsPerSlot(_)
+        ))
+    )
+}
+
+/// Common handler for `AttnError` when adding an attestation to the naive aggregation pool.
+///
+/// Benign errors are logged and ignored, since the attestation has already been published.
+fn handle_naive_aggregation_error(
+    e: AttnError,
+    detail: &str,
+    data: &AttestationData,
+    log: &Logger,
+) -> Result<(), ApiError> {
+    if is_benign_naive_aggregation_error(&e) {
+        debug!(
+            log,
+            "Local attestation not added to aggregation pool";
+            "detail" => detail,
+            "reason" => format!("{:?}", e),
+            "index" => data.index,
+            "slot" => data.slot,
+        );
+
+        Ok(())
+    } else {
+        Err(handle_attestation_error(e, detail, data, log))
+    }
+}
+
+/// Returns `true` if `e` is an expected result of applying a gossip-verified attestation to fork
+/// choice, rather than a sign that the attestation or the node is faulty.
+///
+/// E.g., gossip accepts attestations from slightly more than an epoch ago, whilst fork choice only
+/// accepts attestations from the current or previous epoch.
+fn is_benign_fork_choice_error(e: &BeaconChainError) -> bool {
+    matches!(
+        e,
+        BeaconChainError::ForkChoiceError(ForkChoiceError::InvalidAttestation(
+            InvalidForkChoiceAttestation::PastEpoch { .. }
+        ))
+    )
+}
+
 /// Common handler for `ForkChoiceError` during attestation verification.
+///
+/// Benign errors are logged and ignored, since the attestation has already been published.
 fn handle_fork_choice_error(
     e: BeaconChainError,
     detail: &str,
     data: &AttestationData,
     log: &Logger,
-) -> ApiError {
-    match e {
+) -> Result<(), ApiError> {
+    if is_benign_fork_choice_error(&e) {
+        debug!(
+            log,
+            "Local attestation not applied to fork choice";
+            "detail" => detail,
+            "reason" => format!("{:?}", e),
+            "target" => data.target.epoch,
+            "source" => data.source.epoch,
+            "index" => data.index,
+            "slot" => data.slot,
+        );
+
+        return Ok(());
+    }
+
+    Err(match e {
         BeaconChainError::ForkChoiceError(ForkChoiceError::InvalidAttestation(e)) => {
             error!(
                 log,
@@ -746,5 +1147,223 @@ fn handle_fork_choice_error(
                 e, detail
             ))
         }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use beacon_chain::test_utils::{
+        AttestationStrategy, BeaconChainHarness, BlockStrategy,
+        BlockingMigratorEphemeralHarnessType,
+    };
+    use state_processing::per_slot_processing;
+    use types::{
+        test_utils::generate_deterministic_keypairs, AttestationDuty, ChainSpec, MinimalEthSpec,
+        Slot,
+    };
+
+    type E = MinimalEthSpec;
+
+    const VALIDATOR_COUNT: usize = 24;
+
+    /// The attestation duties of every validator during an epoch, and the proposer of each of its
+    /// slots (if known).
+    type Duties = (Vec<Option<AttestationDuty>>, Option<Vec<usize>>);
+
+    fn duties_from_state(mut state: BeaconState<E>, epoch: Epoch, spec: &ChainSpec) -> Duties {
+        let relative_epoch = RelativeEpoch::from_epoch(state.current_epoch(), epoch)
+            .expect("state should be in a suitable epoch");
+        state
+            .build_committee_cache(relative_epoch, spec)
+            .expect("should build committee cache");
+
+        let attestation_duties = (0..VALIDATOR_COUNT)
+            .map(|i| {
+                state
+                    .get_attestation_duties(i, relative_epoch)
+                    .expect("should get duties")
+            })
+            .collect();
+        let proposers = if state.current_epoch() == epoch {
+            let proposers = epoch
+                .slot_iter(E::slots_per_epoch())
+                .map(|slot| {
+                    state
+                        .get_beacon_proposer_index(slot, spec)
+                        .expect("should get proposer")
+                })
+                .collect();
+            Some(proposers)
+        } else {
+            None
+        };
+
+        (attestation_duties, proposers)
+    }
+
+    /// Returns the duties during `epoch` according to `state_processing`, by processing the
+    /// canonical chain up to the start of `epoch` (with skipped slots beyond the head).
+    fn expected_duties(
+        harness: &BeaconChainHarness<BlockingMigratorEphemeralHarnessType<E>>,
+        epoch: Epoch,
+    ) -> Duties {
+        let chain = &harness.chain;
+        let start_slot = epoch.start_slot(E::slots_per_epoch());
+        let head_state = chain.head().expect("should get head").beacon_state;
+
+        let mut state = if start_slot < head_state.slot {
+            chain
+                .state_at_slot(start_slot, StateSkipConfig::WithStateRoots)
+                .expect("should load state")
+        } else {
+            head_state
+        };
+        while state.slot < start_slot {
+            per_slot_processing(&mut state, None, &chain.spec).expect("should process slot");
+        }
+
+        duties_from_state(state, epoch, &chain.spec)
+    }
+
+    /// Checks the duties during each epoch from the previous to the next (by the wall clock)
+    /// against `state_processing`.
+    fn check_duties_around_current_epoch(
+        harness: &BeaconChainHarness<BlockingMigratorEphemeralHarnessType<E>>,
+    ) {
+        let chain = &harness.chain;
+        let current_epoch = chain.epoch().expect("should read clock");
+        let head_slot = chain.head_info().expect("should get head").slot;
+
+        for epoch in
+            (current_epoch.as_u64().saturating_sub(1)..=current_epoch.as_u64() + 1).map(Epoch::new)
+        {
+            let state = get_state_for_epoch(chain, epoch, StateSkipConfig::WithoutStateRoots)
+                .expect("should get state for epoch");
+            let (attestation_duties, proposers) = duties_from_state(state, epoch, &chain.spec);
+            let (expected_attestation_duties, expected_proposers) = expected_duties(harness, epoch);
+            let context = format!(
+                "epoch {}, current epoch {}, head slot {}",
+                epoch, current_epoch, head_slot
+            );
+
+            assert_eq!(
+                attestation_duties, expected_attestation_duties,
+                "attestation duties should match for {}",
+                context
+            );
+            if epoch == current_epoch {
+                assert!(
+                    proposers.is_some(),
+                    "proposers should be known for {}",
+                    context
+                );
+            }
+            if let Some(proposers) = proposers {
+                assert_eq!(
+                    Some(proposers),
+                    expected_proposers,
+                    "proposers should match for {}",
+                    context
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn duties_around_epoch_boundaries() {
+        let slots_per_epoch = E::slots_per_epoch();
+        let mut harness = BeaconChainHarness::new(
+            MinimalEthSpec,
+            generate_deterministic_keypairs(VALIDATOR_COUNT),
+        );
+
+        // The head is at the last slot of an epoch.
+        harness.advance_slot();
+        harness.extend_chain(
+            2 * slots_per_epoch as usize - 1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        assert_eq!(harness.chain.slot().unwrap(), 2 * slots_per_epoch - 1);
+        check_duties_around_current_epoch(&harness);
+
+        // The head is in the next epoch, slightly ahead of the wall clock.
+        harness.advance_slot();
+        harness.extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        harness.chain.slot_clock.set_slot(2 * slots_per_epoch - 1);
+        check_duties_around_current_epoch(&harness);
+
+        // The wall clock has moved into a later epoch, with every slot since the head skipped.
+        harness.chain.slot_clock.set_slot(3 * slots_per_epoch + 2);
+        check_duties_around_current_epoch(&harness);
+
+        // A block is produced after the skipped slots, mid-epoch.
+        harness.extend_chain(
+            1,
+            BlockStrategy::OnCanonicalHead,
+            AttestationStrategy::AllValidators,
+        );
+        check_duties_around_current_epoch(&harness);
+
+        // The wall clock is at the first slot of an epoch, which is skipped.
+        harness.chain.slot_clock.set_slot(4 * slots_per_epoch);
+        check_duties_around_current_epoch(&harness);
+    }
+
+    #[test]
+    fn naive_aggregation_error_classification() {
+        let naive_error = |e: NaiveAggregationError| {
+            AttnError::BeaconChainError(BeaconChainError::NaiveAggregationError(e))
+        };
+
+        assert!(is_benign_naive_aggregation_error(&naive_error(
+            NaiveAggregationError::SlotTooLow {
+                slot: Slot::new(1),
+                lowest_permissible_slot: Slot::new(2),
+            }
+        )));
+        assert!(is_benign_naive_aggregation_error(&naive_error(
+            NaiveAggregationError::ReachedMaxAttestationsPerSlot(1)
+        )));
+        assert!(!is_benign_naive_aggregation_error(&naive_error(
+            NaiveAggregationError::NoAggregationBitsSet
+        )));
+        assert!(!is_benign_naive_aggregation_error(&naive_error(
+            NaiveAggregationError::InconsistentBitfieldLengths
+        )));
+        assert!(!is_benign_naive_aggregation_error(
+            &AttnError::BeaconChainError(BeaconChainError::CanonicalHeadLockTimeout)
+        ));
+    }
+
+    #[test]
+    fn fork_choice_error_classification() {
+        let fork_choice_error = |e: InvalidForkChoiceAttestation| {
+            BeaconChainError::ForkChoiceError(ForkChoiceError::InvalidAttestation(e))
+        };
+
+        assert!(is_benign_fork_choice_error(&fork_choice_error(
+            InvalidForkChoiceAttestation::PastEpoch {
+                attestation_epoch: Epoch::new(1),
+                current_epoch: Epoch::new(3),
+            }
+        )));
+        assert!(!is_benign_fork_choice_error(&fork_choice_error(
+            InvalidForkChoiceAttestation::FutureEpoch {
+                attestation_epoch: Epoch::new(3),
+                current_epoch: Epoch::new(1),
+            }
+        )));
+        assert!(!is_benign_fork_choice_error(&fork_choice_error(
+            InvalidForkChoiceAttestation::EmptyAggregationBitfield
+        )));
+        assert!(!is_benign_fork_choice_error(
+            &BeaconChainError::CanonicalHeadLockTimeout
+        ));
     }
 }
@@ -9,9 +9,14 @@ use node_test_rig::{
     testing_client_config, ClientConfig, ClientGenesis, LocalBeaconNode,
 };
 use remote_beacon_node::{
-    Committee, HeadBeaconBlock, PersistedOperationPool, PublishStatus, ValidatorResponse,
+    AggregateAndProofStatus, BeaconCommitteeSubscription, Committee, PersistedOperationPool,
+    PublishStatus, ValidatorResponse,
+};
+use rest_types::{
+    BlockHeaderData, BlockId, GenericResponse, IdentityData, PaginatedResponse, PeerCountData,
+    PeerState, PeersResponse, RootData, ValidatorBalanceData, ValidatorData, ValidatorDutyBytes,
+    ValidatorStatus,
 };
-use rest_types::ValidatorDutyBytes;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -19,10 +24,11 @@ use types::{
     test_utils::{
         build_double_vote_attester_slashing, build_proposer_slashing,
         generate_deterministic_keypair, AttesterSlashingTestTask, ProposerSlashingTestTask,
+        TestingVoluntaryExitBuilder,
     },
-    BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, MinimalEthSpec, PublicKey,
-    RelativeEpoch, Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedRoot, Slot,
-    SubnetId, Validator,
+    Attestation, BeaconBlock, BeaconState, ChainSpec, Domain, Epoch, EthSpec, Hash256,
+    MinimalEthSpec, PublicKey, RelativeEpoch, Signature, SignedAggregateAndProof,
+    SignedBeaconBlock, SignedRoot, Slot, SubnetId, Validator, YamlConfig,
 };
 
 type E = MinimalEthSpec;
@@ -80,6 +86,48 @@ fn sign_block<T: BeaconChainTypes>(
     block.sign(&keypair.sk, &fork, genesis_validators_root, spec)
 }
 
+/// Reads the `beacon_http_api_submitted_objects_total` counter for the given labels from the
+/// node's `/metrics` endpoint.
+fn submitted_objects_total(
+    env: &mut Environment<E>,
+    node: &LocalBeaconNode<E>,
+    object: &str,
+    outcome: &str,
+) -> u64 {
+    use hyper::{body::to_bytes, Client, Uri};
+
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let uri: Uri = format!("http://{}/metrics", socket_addr)
+        .parse()
+        .expect("should parse uri");
+    let body = env
+        .runtime()
+        .block_on(async {
+            let response = Client::new().get(uri).await?;
+            to_bytes(response.into_body()).await
+        })
+        .expect("should fetch metrics");
+    let metrics = String::from_utf8(body.to_vec()).expect("metrics should be utf8");
+
+    let series = format!(
+        "beacon_http_api_submitted_objects_total{{object=\"{}\",outcome=\"{}\"}}",
+        object, outcome
+    );
+    metrics
+        .lines()
+        .find(|line| line.starts_with(&series))
+        .map(|line| {
+            line[series.len()..]
+                .trim()
+                .parse()
+                .expect("should parse counter")
+        })
+        .unwrap_or(0)
+}
+
 #[test]
 fn validator_produce_attestation() {
     let mut env = build_env();
@@ -111,6 +159,9 @@ fn validator_produce_attestation() {
         }
     };
 
+    let invalid_before = submitted_objects_total(&mut env, &node, "attestation", "invalid");
+    let imported_before = submitted_objects_total(&mut env, &node, "attestation", "imported");
+
     let mut attestation = env
         .runtime()
         .block_on(
@@ -233,6 +284,16 @@ fn validator_produce_attestation() {
         "the signed published attestation should be valid"
     );
 
+    // Other tests may submit attestations concurrently, so only check a lower bound.
+    assert!(
+        submitted_objects_total(&mut env, &node, "attestation", "invalid") >= invalid_before + 2,
+        "should count the two invalid attestations"
+    );
+    assert!(
+        submitted_objects_total(&mut env, &node, "attestation", "imported") > imported_before,
+        "should count the valid attestation"
+    );
+
     // Try obtaining an aggregated attestation with a matching attestation data to the previous
     // one.
     let aggregated_attestation = env
@@ -255,7 +316,29 @@ fn validator_produce_attestation() {
         spec,
     );
 
-    // Publish the signed aggregate.
+    // Publish the signed aggregate twice in the same batch.
+    let statuses = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .publish_aggregate_and_proofs_with_status(vec![
+                    signed_aggregate_and_proof.clone(),
+                    signed_aggregate_and_proof.clone(),
+                ]),
+        )
+        .expect("should publish aggregate and proofs");
+    assert_eq!(
+        statuses,
+        vec![
+            AggregateAndProofStatus::Imported,
+            AggregateAndProofStatus::AlreadyKnown
+        ],
+        "the second copy of the aggregate should be already known"
+    );
+
+    // Re-publishing a known aggregate is still considered valid.
     let publish_status = env
         .runtime()
         .block_on(
@@ -310,19 +393,193 @@ fn validator_duties() {
         spec,
     );
 
-    epoch += 4;
+    epoch += 1;
     let duties = env
         .runtime()
         .block_on(remote_node.http.validator().get_duties(epoch, &validators))
         .expect("should fetch duties from http api");
 
-    // 2. Check with a long skip forward.
-    check_duties(duties, epoch, validators, beacon_chain, spec);
+    // 2. Check the next epoch.
+    check_duties(duties, epoch, validators.clone(), beacon_chain, spec);
 
     // TODO: test an epoch in the past. Blocked because the `LocalBeaconNode` cannot produce a
     // chain, yet.
 }
 
+#[test]
+fn validator_duties_far_future_epoch() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let validators = beacon_chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("pubkey should be valid"))
+        .collect::<Vec<_>>();
+
+    let current_epoch = beacon_chain.epoch().expect("should get epoch");
+    assert_eq!(current_epoch, Epoch::new(0), "node should be at genesis");
+
+    for epoch in &[current_epoch + 2, current_epoch + 10] {
+        let result = env
+            .runtime()
+            .block_on(remote_node.http.validator().get_duties(*epoch, &validators));
+
+        match result {
+            Err(DidNotSucceed { status, .. }) => assert_eq!(status, StatusCode::BAD_REQUEST),
+            other => panic!(
+                "expected 400 for epoch {}, got {:?}",
+                epoch,
+                other.map(|_| ())
+            ),
+        }
+    }
+}
+
+#[test]
+fn validator_attester_duties_post() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let validators = beacon_chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("pubkey should be valid"))
+        .collect::<Vec<_>>();
+    let validator_count = validators.len() as u64;
+
+    for epoch in &[Epoch::new(0), Epoch::new(1)] {
+        let expected = env
+            .runtime()
+            .block_on(remote_node.http.validator().get_duties(*epoch, &validators))
+            .expect("should fetch duties from http api");
+
+        // Include an index beyond the validator registry, which should be omitted.
+        let indices = (0..validator_count + 1).collect::<Vec<_>>();
+        let duties = env
+            .runtime()
+            .block_on(
+                remote_node
+                    .http
+                    .validator()
+                    .get_attester_duties(*epoch, &indices),
+            )
+            .expect("should fetch attester duties from http api");
+
+        assert_eq!(
+            duties.len(),
+            expected.len(),
+            "should return a duty for each known validator"
+        );
+
+        for (duty, expected) in duties.iter().zip(expected.iter()) {
+            assert_eq!(duty.pubkey, expected.validator_pubkey);
+            assert_eq!(Some(duty.validator_index), expected.validator_index);
+            assert_eq!(Some(Slot::new(duty.slot)), expected.attestation_slot);
+            assert_eq!(
+                Some(duty.committee_index),
+                expected.attestation_committee_index
+            );
+            assert_eq!(
+                Some(duty.validator_committee_index as usize),
+                expected.attestation_committee_position
+            );
+            assert_eq!(
+                Some(duty.committees_at_slot),
+                expected.committee_count_at_slot
+            );
+        }
+    }
+}
+
+#[test]
+fn validator_beacon_committee_subscriptions() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let future_slot = beacon_chain.slot().expect("should get slot").as_u64() + 8;
+
+    let subscription = |slot, committee_index| BeaconCommitteeSubscription {
+        validator_index: 0,
+        committee_index,
+        committees_at_slot: 1,
+        slot,
+        is_aggregator: false,
+    };
+
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .subscribe_beacon_committees(vec![subscription(future_slot, 0)]),
+        )
+        .expect("should accept a subscription to a future slot");
+
+    let error = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .subscribe_beacon_committees(vec![
+                    subscription(0, 0),
+                    subscription(future_slot, 0),
+                    subscription(future_slot, 1),
+                ]),
+        )
+        .expect_err("should reject invalid subscriptions");
+
+    assert_eq!(
+        error.failed_indices(),
+        vec![0, 2],
+        "should report the past slot and the out-of-range committee index"
+    );
+}
+
 fn check_duties<T: BeaconChainTypes>(
     duties: Vec<ValidatorDutyBytes>,
     epoch: Epoch,
@@ -515,22 +772,48 @@ fn validator_block_post() {
 
     assert_eq!(heads.len(), 1, "there should be only one head");
     assert_eq!(
-        heads,
-        vec![HeadBeaconBlock {
-            beacon_block_root: head.block_root,
-            beacon_block_slot: head.slot,
-        }],
-        "there should be only one head"
+        heads[0].beacon_block_root, head.block_root,
+        "the head should be the published block"
+    );
+    assert_eq!(heads[0].beacon_block_slot, head.slot, "head slot");
+    assert!(
+        heads[0].weight.is_some(),
+        "head should be known to fork choice"
+    );
+    assert_eq!(heads[0].viable, Some(true), "head should be viable");
+    assert_eq!(
+        heads[0].justified_epoch,
+        Some(head.justified_slot.epoch(E::slots_per_epoch())),
+        "head justified epoch"
+    );
+    assert_eq!(
+        heads[0].finalized_epoch,
+        Some(head.finalized_slot.epoch(E::slots_per_epoch())),
+        "head finalized epoch"
     );
 }
 
 #[test]
-fn validator_block_get() {
+fn validator_block_post_ssz() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+
     let mut env = build_env();
 
     let spec = &E::default_spec();
 
-    let node = build_node(&mut env, testing_client_config());
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
 
     let beacon_chain = node
@@ -538,8 +821,23 @@ fn validator_block_get() {
         .beacon_chain()
         .expect("client should have beacon chain");
 
+    // Malformed SSZ is rejected with a 400.
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let request = Request::post(format!("http://{}/validator/block", socket_addr))
+        .header(header::CONTENT_TYPE, "application/octet-stream")
+        .body(Body::from(vec![1, 2, 3]))
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
     let slot = Slot::new(1);
-    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
 
     let block = env
         .runtime()
@@ -547,65 +845,364 @@ fn validator_block_get() {
             remote_node
                 .http
                 .validator()
-                .produce_block(slot, randao_reveal.clone(), None),
+                .produce_block(slot, randao_reveal, None),
         )
         .expect("should fetch block from http api");
 
-    let (expected_block, _state) = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .produce_block(randao_reveal, slot, None)
-        .expect("should produce block");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
+
+    let publish_status = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .publish_block_ssz(&signed_block),
+        )
+        .expect("should publish block");
+
+    if cfg!(not(feature = "fake_crypto")) {
+        assert_eq!(
+            publish_status,
+            PublishStatus::Valid,
+            "the signed published block should be valid"
+        );
+    }
+
+    let head = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_head())
+        .expect("should get head");
 
     assert_eq!(
-        block, expected_block,
-        "the block returned from the API should be as expected"
+        head.block_root, block_root,
+        "the published block should become the head block"
     );
 }
 
 #[test]
-fn validator_block_get_with_graffiti() {
+fn get_events() {
+    use hyper::body::HttpBody;
+    use hyper::{header, Body, Client, Request, StatusCode};
+
     let mut env = build_env();
 
     let spec = &E::default_spec();
 
-    let node = build_node(&mut env, testing_client_config());
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
     let beacon_chain = node
         .client
         .beacon_chain()
         .expect("client should have beacon chain");
 
-    let slot = Slot::new(1);
-    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+    let mut subscribe = |topics: &str| {
+        let request = Request::get(format!("http://{}/eth/v1/events{}", socket_addr, topics))
+            .body(Body::empty())
+            .expect("should build request");
+        env.runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response")
+    };
+
+    assert_eq!(
+        subscribe("").status(),
+        StatusCode::BAD_REQUEST,
+        "should require a topic"
+    );
+    assert_eq!(
+        subscribe("?topics=head,attestation").status(),
+        StatusCode::BAD_REQUEST,
+        "should reject an unknown topic"
+    );
+
+    let response = subscribe("?topics=block");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE).unwrap(),
+        "text/event-stream"
+    );
+    let mut body = response.into_body();
 
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
     let block = env
         .runtime()
-        .block_on(remote_node.http.validator().produce_block(
-            slot,
-            randao_reveal.clone(),
-            Some(*b"test-graffiti-test-graffiti-test"),
-        ))
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
         .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
 
-    let (expected_block, _state) = node
-        .client
-        .beacon_chain()
-        .expect("client should have beacon chain")
-        .produce_block(
-            randao_reveal,
-            slot,
-            Some(*b"test-graffiti-test-graffiti-test"),
-        )
-        .expect("should produce block");
+    env.runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
 
-    assert_eq!(
-        block, expected_block,
-        "the block returned from the API should be as expected"
-    );
-}
+    let chunk = env
+        .runtime()
+        .block_on(body.data())
+        .expect("stream should not end")
+        .expect("should read event");
+    let chunk = std::str::from_utf8(&chunk).expect("event should be utf8");
+
+    assert!(
+        chunk.starts_with("event: block\ndata: "),
+        "should be a block event: {}",
+        chunk
+    );
+    assert!(
+        chunk.contains(&format!("{:?}", block_root)),
+        "event should contain the block root: {}",
+        chunk
+    );
+}
+
+#[test]
+fn beacon_head_matches_head_state() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let head = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_head())
+        .expect("should get head");
+
+    let chain_head = beacon_chain.head().expect("should get chain head");
+    let state = &chain_head.beacon_state;
+    let epoch_start_slot = |epoch: Epoch| epoch.start_slot(E::slots_per_epoch());
+
+    assert_eq!(head.slot, state.slot);
+    assert_eq!(head.block_root, chain_head.beacon_block_root);
+    assert_eq!(head.state_root, chain_head.beacon_state_root);
+    assert_eq!(
+        head.finalized_slot,
+        epoch_start_slot(state.finalized_checkpoint.epoch)
+    );
+    assert_eq!(head.finalized_block_root, state.finalized_checkpoint.root);
+    assert_eq!(
+        head.justified_slot,
+        epoch_start_slot(state.current_justified_checkpoint.epoch)
+    );
+    assert_eq!(
+        head.justified_block_root,
+        state.current_justified_checkpoint.root
+    );
+    assert_eq!(
+        head.previous_justified_slot,
+        epoch_start_slot(state.previous_justified_checkpoint.epoch)
+    );
+    assert_eq!(
+        head.previous_justified_block_root,
+        state.previous_justified_checkpoint.root
+    );
+}
+
+#[test]
+fn beacon_validator_balances() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let state = beacon_chain.head().expect("should get head").beacon_state;
+
+    let balances = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validator_balances(state.slot))
+        .expect("should get validator balances");
+
+    assert_eq!(balances, state.balances.to_vec());
+}
+
+#[test]
+fn validator_block_get() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal.clone(), None),
+        )
+        .expect("should fetch block from http api");
+
+    let (expected_block, _state) = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .produce_block(randao_reveal, slot, None)
+        .expect("should produce block");
+
+    assert_eq!(
+        block, expected_block,
+        "the block returned from the API should be as expected"
+    );
+}
+
+#[test]
+fn validator_block_get_with_graffiti() {
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain, slot, spec);
+
+    let block = env
+        .runtime()
+        .block_on(remote_node.http.validator().produce_block(
+            slot,
+            randao_reveal.clone(),
+            Some(*b"test-graffiti-test-graffiti-test"),
+        ))
+        .expect("should fetch block from http api");
+
+    let (expected_block, _state) = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .produce_block(
+            randao_reveal,
+            slot,
+            Some(*b"test-graffiti-test-graffiti-test"),
+        )
+        .expect("should produce block");
+
+    assert_eq!(
+        block, expected_block,
+        "the block returned from the API should be as expected"
+    );
+}
+
+#[test]
+fn beacon_state_to_writer() {
+    use futures::io::AllowStdIo;
+    use remote_beacon_node::StateResponse;
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let (state, root) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_by_slot(Slot::new(0)))
+        .expect("should fetch state from http api");
+
+    let dir = tempdir::TempDir::new("state_download").expect("should create temp dir");
+    let path = dir.path().join("state.json");
+    let mut file = AllowStdIo::new(std::fs::File::create(&path).expect("should create file"));
+
+    let bytes_written = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_state_to_writer_by_root(root, &mut file),
+        )
+        .expect("should stream state from http api");
+
+    let bytes = std::fs::read(&path).expect("should read state file");
+    assert_eq!(bytes_written, bytes.len() as u64);
+
+    let response: StateResponse<E> =
+        serde_json::from_slice(&bytes).expect("should decode streamed state");
+    assert_eq!(response.root, root);
+    assert_eq!(response.beacon_state, state);
+}
+
+#[test]
+fn beacon_state_ssz() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let (json_state, root) = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_by_slot(Slot::new(0)))
+        .expect("should fetch state from http api");
+
+    let state_by_slot = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_state_ssz_by_slot(Slot::new(0)),
+        )
+        .expect("should fetch SSZ state by slot from http api");
+
+    let state_by_root = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_state_ssz_by_root(root))
+        .expect("should fetch SSZ state by root from http api");
+
+    assert_eq!(
+        state_by_slot, json_state,
+        "SSZ state by slot should match the JSON state"
+    );
+    assert_eq!(
+        state_by_root, json_state,
+        "SSZ state by root should match the JSON state"
+    );
+}
 
 #[test]
 fn beacon_state() {
@@ -696,66 +1293,317 @@ fn beacon_block() {
 }
 
 #[test]
-fn genesis_time() {
+fn beacon_block_ssz() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+    use ssz::Decode;
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let client = Client::new();
+
+    let request = |accept: Option<&str>| {
+        let mut builder = Request::get(format!("http://{}/beacon/block?slot=0", socket_addr));
+        if let Some(accept) = accept {
+            builder = builder.header(header::ACCEPT, accept);
+        }
+        builder.body(Body::empty()).expect("should build request")
+    };
 
-    let genesis_time = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_genesis_time())
-        .expect("should fetch genesis time from http api");
+    let db_block = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .block_at_slot(Slot::new(0))
+        .expect("should find block")
+        .expect("block should not be none");
 
+    let response = env
+        .runtime()
+        .block_on(client.request(request(Some("application/octet-stream"))))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::OK);
     assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .head()
-            .expect("should get head")
-            .beacon_state
-            .genesis_time,
-        genesis_time,
-        "should match genesis time from head state"
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static(
+            "application/octet-stream"
+        ))
     );
+    let content_length = response
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .expect("should set content length")
+        .to_str()
+        .expect("content length should be a string")
+        .parse::<usize>()
+        .expect("content length should be a number");
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    assert_eq!(bytes.len(), content_length);
+    assert_eq!(
+        SignedBeaconBlock::<E>::from_ssz_bytes(&bytes).expect("should decode block"),
+        db_block,
+        "SSZ block from api should match that from the DB"
+    );
+
+    for accept in &[None, Some("application/json"), Some("text/html, */*;q=0.8")] {
+        let response = env
+            .runtime()
+            .block_on(client.request(request(*accept)))
+            .expect("should get response");
+        assert_eq!(response.status(), StatusCode::OK, "accept: {:?}", accept);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&header::HeaderValue::from_static("application/json")),
+            "accept: {:?}",
+            accept
+        );
+    }
+
+    let response = env
+        .runtime()
+        .block_on(client.request(request(Some("text/html"))))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
 }
 
 #[test]
-fn genesis_validators_root() {
+fn beacon_block_by_id_json_error() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+    use rest_types::ErrorMessage;
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let client = Client::new();
+
+    // The node is at genesis, so there is no block at slot 100.
+    let request = |accept: Option<&str>| {
+        let mut builder = Request::get(format!("http://{}/eth/v1/beacon/blocks/100", socket_addr));
+        if let Some(accept) = accept {
+            builder = builder.header(header::ACCEPT, accept);
+        }
+        builder.body(Body::empty()).expect("should build request")
+    };
 
-    let genesis_validators_root = env
+    let response = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_genesis_validators_root())
-        .expect("should fetch genesis time from http api");
+        .block_on(client.request(request(Some("application/json"))))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static("application/json"))
+    );
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let msg: ErrorMessage = serde_json::from_slice(&bytes).expect("should parse error message");
+    assert_eq!(msg.code, 404);
+    assert!(
+        msg.message.contains("Unable to find SignedBeaconBlock"),
+        "message: {}",
+        msg.message
+    );
+    assert_eq!(msg.stacktraces, vec!["block_from_id".to_string()]);
+    for frame in &msg.stacktraces {
+        assert!(
+            !frame.contains('/') && !frame.contains('\\') && !frame.contains(".rs"),
+            "frame should not contain a file path: {}",
+            frame
+        );
+    }
 
+    // Clients which don't ask for JSON still receive plain text.
+    let response = env
+        .runtime()
+        .block_on(client.request(request(None)))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
     assert_eq!(
-        node.client
-            .beacon_chain()
-            .expect("should have beacon chain")
-            .head()
-            .expect("should get head")
-            .beacon_state
-            .genesis_validators_root,
-        genesis_validators_root,
-        "should match genesis time from head state"
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static(
+            "text/plain; charset=utf-8"
+        ))
     );
 }
 
 #[test]
-fn fork() {
+fn beacon_block_by_id_ssz() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+    use ssz::Decode;
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-
-    let fork = env
-        .runtime()
-        .block_on(remote_node.http.beacon().get_fork())
-        .expect("should fetch from http api");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let client = Client::new();
+
+    let request = |accept: Option<&str>| {
+        let mut builder = Request::get(format!(
+            "http://{}/eth/v1/beacon/blocks/genesis",
+            socket_addr
+        ));
+        if let Some(accept) = accept {
+            builder = builder.header(header::ACCEPT, accept);
+        }
+        builder.body(Body::empty()).expect("should build request")
+    };
+
+    let db_block = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .block_at_slot(Slot::new(0))
+        .expect("should find block")
+        .expect("block should not be none");
+
+    let response = env
+        .runtime()
+        .block_on(client.request(request(Some("application/octet-stream"))))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static(
+            "application/octet-stream"
+        ))
+    );
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    assert_eq!(
+        SignedBeaconBlock::<E>::from_ssz_bytes(&bytes).expect("should decode block"),
+        db_block,
+        "SSZ block from api should match that from the DB"
+    );
+
+    for accept in &[None, Some("application/json"), Some("text/html, */*;q=0.8")] {
+        let response = env
+            .runtime()
+            .block_on(client.request(request(*accept)))
+            .expect("should get response");
+        assert_eq!(response.status(), StatusCode::OK, "accept: {:?}", accept);
+        assert_eq!(
+            response.headers().get(header::CONTENT_TYPE),
+            Some(&header::HeaderValue::from_static("application/json")),
+            "accept: {:?}",
+            accept
+        );
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        let block: GenericResponse<SignedBeaconBlock<E>> =
+            serde_json::from_slice(&bytes).expect("should decode block");
+        assert_eq!(block.data, db_block, "accept: {:?}", accept);
+    }
+
+    let response = env
+        .runtime()
+        .block_on(client.request(request(Some("text/html"))))
+        .expect("should get response");
+    assert_eq!(response.status(), StatusCode::NOT_ACCEPTABLE);
+}
+
+#[test]
+fn beacon_block_finalized_flag() {
+    let mut env = build_env();
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
+
+    env.runtime()
+        .block_on(remote_node.http.validator().publish_block(signed_block))
+        .expect("should publish block");
+
+    let beacon = remote_node.http.beacon();
+
+    // The genesis block is always finalized.
+    let genesis = env
+        .runtime()
+        .block_on(beacon.get_block_response_by_slot(Slot::new(0)))
+        .expect("should fetch genesis block from http api");
+    assert_eq!(genesis.finalized, Some(true));
+
+    let by_root = env
+        .runtime()
+        .block_on(beacon.get_block_response_by_root(genesis.root))
+        .expect("should fetch genesis block by root from http api");
+    assert_eq!(by_root.finalized, Some(true));
+
+    // The new block is beyond the finalized checkpoint.
+    let head = env
+        .runtime()
+        .block_on(beacon.get_block_response_by_root(block_root))
+        .expect("should fetch new block from http api");
+    assert_eq!(head.finalized, Some(false));
+
+    let by_slot = env
+        .runtime()
+        .block_on(beacon.get_block_response_by_slot(slot))
+        .expect("should fetch new block by slot from http api");
+    assert_eq!(by_slot.root, block_root);
+    assert_eq!(by_slot.finalized, Some(false));
+}
+
+#[test]
+fn genesis_time() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let genesis_time = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_genesis_time())
+        .expect("should fetch genesis time from http api");
 
     assert_eq!(
         node.client
@@ -764,113 +1612,111 @@ fn fork() {
             .head()
             .expect("should get head")
             .beacon_state
-            .fork,
-        fork,
-        "should match head state"
+            .genesis_time,
+        genesis_time,
+        "should match genesis time from head state"
     );
 }
 
 #[test]
-fn eth2_config() {
+fn genesis_validators_root() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let eth2_config = env
+    let genesis_validators_root = env
         .runtime()
-        .block_on(remote_node.http.spec().get_eth2_config())
-        .expect("should fetch eth2 config from http api");
-
-    // TODO: check the entire eth2_config, not just the spec.
+        .block_on(remote_node.http.beacon().get_genesis_validators_root())
+        .expect("should fetch genesis time from http api");
 
     assert_eq!(
         node.client
             .beacon_chain()
             .expect("should have beacon chain")
-            .spec,
-        eth2_config.spec,
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .genesis_validators_root,
+        genesis_validators_root,
         "should match genesis time from head state"
     );
 }
 
 #[test]
-fn get_version() {
+fn genesis_block_root() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let version = env
+    let genesis_block_root = env
         .runtime()
-        .block_on(remote_node.http.node().get_version())
-        .expect("should fetch version from http api");
+        .block_on(remote_node.http.beacon().get_genesis_block_root())
+        .expect("should fetch genesis block root from http api");
+
+    let block_root_at_genesis = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_root(Slot::new(0)))
+        .expect("should fetch block root from http api");
 
     assert_eq!(
-        lighthouse_version::version_with_platform(),
-        version,
-        "result should be as expected"
+        genesis_block_root, block_root_at_genesis,
+        "should match the block root at the genesis slot"
     );
 }
 
 #[test]
-fn get_genesis_state_root() {
+fn fork() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let slot = Slot::new(0);
-
-    let result = env
+    let fork = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_state_root(slot))
+        .block_on(remote_node.http.beacon().get_fork())
         .expect("should fetch from http api");
 
-    let expected = node
-        .client
-        .beacon_chain()
-        .expect("should have beacon chain")
-        .rev_iter_state_roots()
-        .expect("should get iter")
-        .map(Result::unwrap)
-        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
-        .map(|(cur_root, _)| cur_root)
-        .expect("chain should have state root at slot");
-
-    assert_eq!(result, expected, "result should be as expected");
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .head()
+            .expect("should get head")
+            .beacon_state
+            .fork,
+        fork,
+        "should match head state"
+    );
 }
 
 #[test]
-fn get_genesis_block_root() {
+fn eth2_config() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
 
-    let slot = Slot::new(0);
-
-    let result = env
+    let eth2_config = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_block_root(slot))
-        .expect("should fetch from http api");
+        .block_on(remote_node.http.spec().get_eth2_config())
+        .expect("should fetch eth2 config from http api");
 
-    let expected = node
-        .client
-        .beacon_chain()
-        .expect("should have beacon chain")
-        .rev_iter_block_roots()
-        .expect("should get iter")
-        .map(Result::unwrap)
-        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
-        .map(|(cur_root, _)| cur_root)
-        .expect("chain should have state root at slot");
+    // TODO: check the entire eth2_config, not just the spec.
 
-    assert_eq!(result, expected, "result should be as expected");
+    assert_eq!(
+        node.client
+            .beacon_chain()
+            .expect("should have beacon chain")
+            .spec,
+        eth2_config.spec,
+        "should match genesis time from head state"
+    );
 }
 
 #[test]
-fn get_validators() {
+fn get_yaml_config() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
@@ -879,467 +1725,2998 @@ fn get_validators() {
         .client
         .beacon_chain()
         .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
-
-    let validators = state.validators.iter().take(2).collect::<Vec<_>>();
-    let pubkeys = validators
-        .iter()
-        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
-        .collect();
 
-    let result = env
+    let yaml = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_validators(pubkeys, None))
-        .expect("should fetch from http api");
+        .block_on(remote_node.http.spec().get_yaml_config())
+        .expect("should fetch yaml config from http api");
 
-    result
-        .iter()
-        .zip(validators.iter())
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    assert!(
+        yaml.contains("SLOTS_PER_EPOCH:"),
+        "keys should be upper-snake-case"
+    );
+
+    let yaml_config: YamlConfig = serde_yaml::from_str(&yaml).expect("should parse yaml config");
+
+    // No testnet is in use, so the config is named after the spec constants.
+    assert_eq!(yaml_config.config_name, "minimal");
+    let mut expected = YamlConfig::from_spec::<E>(&chain.spec);
+    expected.config_name = "minimal".to_string();
+    assert_eq!(yaml_config, expected);
+
+    let config_name = env
+        .runtime()
+        .block_on(remote_node.http.spec().get_config_name())
+        .expect("should fetch config name from http api");
+    assert_eq!(config_name, "minimal");
+    assert_eq!(
+        yaml_config.apply_to_chain_spec::<E>(&E::default_spec()),
+        Some(chain.spec.clone()),
+        "yaml config should reproduce the chain spec"
+    );
 }
 
 #[test]
-fn get_all_validators() {
+fn get_version() {
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
     let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
-        .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
 
-    let result = env
+    let version = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_all_validators(None))
-        .expect("should fetch from http api");
+        .block_on(remote_node.http.node().get_version())
+        .expect("should fetch version from http api");
 
-    result
-        .iter()
-        .zip(state.validators.iter())
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    assert_eq!(
+        lighthouse_version::version_with_platform(),
+        version,
+        "result should be as expected"
+    );
 }
 
 #[test]
-fn get_active_validators() {
+fn get_standard_api_version() {
+    use hyper::{Body, Client, Request};
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let socket_addr = node
         .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
-    let state = &chain.head().expect("should get head").beacon_state;
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-    let result = env
+    let request = Request::get(format!("http://{}/eth/v1/node/version", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_active_validators(None))
-        .expect("should fetch from http api");
-
-    /*
-     * This test isn't comprehensive because all of the validators in the state are active (i.e.,
-     * there is no one to exclude.
-     *
-     * This should be fixed once we can generate more interesting scenarios with the
-     * `NodeTestRig`.
-     */
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
 
-    let validators = state
-        .validators
-        .iter()
-        .filter(|validator| validator.is_active_at(state.current_epoch()));
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let json: serde_json::Value = serde_json::from_slice(&bytes).expect("should decode JSON");
 
-    result
-        .iter()
-        .zip(validators)
-        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "data": {
+                "version": lighthouse_version::version_with_platform()
+            }
+        }),
+        "response should match the standard API"
+    );
 }
 
 #[test]
-fn get_committees() {
+fn get_fork_schedule() {
+    use hyper::{Body, Client, Request};
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let socket_addr = node
         .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
-
-    let epoch = Epoch::new(0);
-
-    let result = env
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let request = Request::get(format!(
+        "http://{}/eth/v1/config/fork_schedule",
+        socket_addr
+    ))
+    .body(Body::empty())
+    .expect("should build request");
+    let response = env
         .runtime()
-        .block_on(remote_node.http.beacon().get_committees(epoch))
-        .expect("should fetch from http api");
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
 
-    let expected = chain
-        .head()
-        .expect("should get head")
-        .beacon_state
-        .get_beacon_committees_at_epoch(RelativeEpoch::Current)
-        .expect("should get committees")
-        .iter()
-        .map(|c| Committee {
-            slot: c.slot,
-            index: c.index,
-            committee: c.committee.to_vec(),
-        })
-        .collect::<Vec<_>>();
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let schedule: GenericResponse<Vec<types::Fork>> =
+        serde_json::from_slice(&bytes).expect("should decode fork schedule");
 
-    assert_eq!(result, expected, "result should be as expected");
+    let spec = E::default_spec();
+    assert_eq!(
+        schedule.data,
+        vec![types::Fork {
+            previous_version: spec.genesis_fork_version,
+            current_version: spec.genesis_fork_version,
+            epoch: E::genesis_epoch(),
+        }],
+        "only the genesis fork is scheduled"
+    );
 }
 
 #[test]
-fn get_fork_choice() {
+fn get_standard_config_spec() {
+    use hyper::{Body, Client, Request};
+    use std::collections::BTreeMap;
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-    let fork_choice = env
+    let request = Request::get(format!("http://{}/eth/v1/config/spec", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
         .runtime()
-        .block_on(remote_node.http.advanced().get_fork_choice())
-        .expect("should not error when getting fork choice");
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
 
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    // Decoding into a map of strings ensures that every value is a string.
+    let config: GenericResponse<BTreeMap<String, String>> =
+        serde_json::from_slice(&bytes).expect("should decode flat string map");
+    let config = config.data;
+
+    let spec = E::default_spec();
+    let get = |key: &str| config.get(key).map(String::as_str);
+
+    assert_eq!(get("CONFIG_NAME"), Some(env.eth2_config.config_name()));
     assert_eq!(
-        fork_choice,
-        *node
-            .client
-            .beacon_chain()
-            .expect("node should have beacon chain")
-            .fork_choice
-            .read()
-            .proto_array()
-            .core_proto_array(),
-        "result should be as expected"
+        get("SECONDS_PER_SLOT"),
+        Some((spec.milliseconds_per_slot / 1_000).to_string().as_str())
+    );
+    assert_eq!(
+        get("SLOTS_PER_EPOCH"),
+        Some(E::slots_per_epoch().to_string().as_str())
+    );
+    let fork_version = spec
+        .genesis_fork_version
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect::<String>();
+    assert_eq!(
+        get("GENESIS_FORK_VERSION"),
+        Some(format!("0x{}", fork_version).as_str())
+    );
+    assert_eq!(get("DOMAIN_BEACON_PROPOSER"), Some("0x00000000"));
+    assert_eq!(get("DOMAIN_BEACON_ATTESTER"), Some("0x01000000"));
+    // Fields specific to Lighthouse are preserved.
+    assert_eq!(
+        get("RANDOM_SUBNETS_PER_VALIDATOR"),
+        Some(spec.random_subnets_per_validator.to_string().as_str())
     );
 }
 
 #[test]
-fn get_operation_pool() {
+fn get_deposit_contract() {
+    use hyper::{Body, Client, Request};
+
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
+    let config = testing_client_config();
+    let deposit_contract_address = config.eth1.deposit_contract_address.clone();
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let request = Request::get(format!(
+        "http://{}/eth/v1/config/deposit_contract",
+        socket_addr
+    ))
+    .body(Body::empty())
+    .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
 
-    let result = env
+    let bytes = env
         .runtime()
-        .block_on(remote_node.http.advanced().get_operation_pool())
-        .expect("should not error when getting fork choice");
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let json: serde_json::Value = serde_json::from_slice(&bytes).expect("should decode JSON");
 
-    let expected = PersistedOperationPool::from_operation_pool(
-        &node
-            .client
-            .beacon_chain()
-            .expect("node should have chain")
-            .op_pool,
+    // Eth1 is disabled, so the chain id is inferred from the (non-mainnet) network name.
+    assert_eq!(
+        json,
+        serde_json::json!({
+            "data": {
+                "chain_id": "5",
+                "address": deposit_contract_address,
+            }
+        }),
+        "response should match the standard API"
     );
-
-    assert_eq!(result, expected, "result should be as expected");
 }
 
-fn compare_validator_response<T: EthSpec>(
-    state: &BeaconState<T>,
-    response: &ValidatorResponse,
-    validator: &Validator,
-) {
-    let response_validator = response.validator.clone().expect("should have validator");
-    let i = response
-        .validator_index
-        .expect("should have validator index");
-    let balance = response.balance.expect("should have balance");
+#[test]
+fn get_identity() {
+    use hyper::{Body, Client, Request};
 
-    assert_eq!(response.pubkey, validator.pubkey, "pubkey");
-    assert_eq!(response_validator, *validator, "validator");
-    assert_eq!(state.balances[i], balance, "balances");
-    assert_eq!(state.validators[i], *validator, "validator index");
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let enr = node.client.enr().expect("node should have an ENR");
+
+    let request = Request::get(format!("http://{}/eth/v1/node/identity", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
+
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let json: serde_json::Value = serde_json::from_slice(&bytes).expect("should decode JSON");
+    assert!(
+        json["data"]["metadata"]["seq_number"].is_string(),
+        "seq_number should be quoted"
+    );
+
+    let identity: GenericResponse<IdentityData<E>> =
+        serde_json::from_value(json).expect("should decode identity");
+    let identity = identity.data;
+
+    assert_eq!(identity.enr, enr.to_base64());
+    for address in identity
+        .p2p_addresses
+        .iter()
+        .chain(&identity.discovery_addresses)
+    {
+        assert!(
+            address.ends_with(&format!("/p2p/{}", identity.peer_id)),
+            "{} should contain the peer id",
+            address
+        );
+    }
+    for address in &identity.discovery_addresses {
+        assert!(
+            address.contains("/udp/"),
+            "{} should be a UDP address",
+            address
+        );
+    }
 }
 
 #[test]
-fn proposer_slashing() {
+fn get_peers() {
+    use hyper::{Body, Client, Request, StatusCode};
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let socket_addr = node
         .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let mut get = |query: &str| {
+        let request = Request::get(format!("http://{}/eth/v1/node/peers{}", socket_addr, query))
+            .body(Body::empty())
+            .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        let status = response.status();
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        (status, bytes)
+    };
 
-    let state = chain
-        .head()
-        .expect("should have retrieved state")
-        .beacon_state;
+    for query in &[
+        "",
+        "?direction=inbound,outbound",
+        "?state=connected&direction=outbound",
+    ] {
+        let (status, bytes) = get(query);
+        assert_eq!(status, StatusCode::OK, "{} should succeed", query);
 
-    let spec = &chain.spec;
+        let peers: PeersResponse = serde_json::from_slice(&bytes).expect("should decode peers");
+        assert_eq!(
+            peers.meta.count,
+            peers.data.len(),
+            "count should match data"
+        );
+    }
 
-    // Check that there are no proposer slashings before insertion
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 0);
+    let (status, bytes) = get("?state=connected,disconnecting");
+    assert_eq!(status, StatusCode::OK, "state filter should succeed");
+    let peers: PeersResponse = serde_json::from_slice(&bytes).expect("should decode peers");
+    assert!(
+        peers
+            .data
+            .iter()
+            .all(|peer| peer.state == PeerState::Connected),
+        "only connected peers should be returned"
+    );
 
-    let slot = state.slot;
-    let proposer_index = chain
-        .block_proposer(slot)
-        .expect("should get proposer index");
-    let keypair = generate_deterministic_keypair(proposer_index);
-    let key = &keypair.sk;
-    let fork = &state.fork;
-    let proposer_slashing = build_proposer_slashing::<E>(
-        ProposerSlashingTestTask::Valid,
-        proposer_index as u64,
-        &key,
-        fork,
-        state.genesis_validators_root,
-        spec,
+    let (status, _) = get("?state=sleeping");
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "should reject unknown state"
     );
 
-    let result = env
-        .runtime()
-        .block_on(
-            remote_node
-                .http
-                .beacon()
-                .proposer_slashing(proposer_slashing.clone()),
-        )
-        .expect("should fetch from http api");
-    assert!(result, true);
+    let (status, _) = get("?direction=sideways");
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "should reject unknown direction"
+    );
+}
 
-    // Length should be just one as we've inserted only one proposer slashing
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 1);
-    assert_eq!(proposer_slashing.clone(), proposer_slashings[0]);
+#[test]
+fn get_peer() {
+    use hyper::{Body, Client, Request, StatusCode};
 
-    let mut invalid_proposer_slashing = build_proposer_slashing::<E>(
-        ProposerSlashingTestTask::Valid,
-        proposer_index as u64,
-        &key,
-        fork,
-        state.genesis_validators_root,
-        spec,
-    );
-    invalid_proposer_slashing.signed_header_2 = invalid_proposer_slashing.signed_header_1.clone();
+    let mut env = build_env();
 
-    let result = env.runtime().block_on(
-        remote_node
-            .http
-            .beacon()
-            .proposer_slashing(invalid_proposer_slashing),
-    );
-    assert!(result.is_err());
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-    // Length should still be one as we've inserted nothing since last time.
-    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(proposer_slashings.len(), 1);
-    assert_eq!(proposer_slashing, proposer_slashings[0]);
+    let mut get_status = |peer_id: &str| {
+        let request = Request::get(format!(
+            "http://{}/eth/v1/node/peers/{}",
+            socket_addr, peer_id
+        ))
+        .body(Body::empty())
+        .expect("should build request");
+        env.runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response")
+            .status()
+    };
+
+    assert_eq!(
+        get_status("not_a_peer_id"),
+        StatusCode::BAD_REQUEST,
+        "should reject a malformed peer id"
+    );
+    assert_eq!(
+        get_status(&eth2_libp2p::PeerId::random().to_base58()),
+        StatusCode::NOT_FOUND,
+        "should not find an unknown peer"
+    );
 }
 
 #[test]
-fn attester_slashing() {
+fn get_peer_count() {
+    use hyper::{Body, Client, Request};
+
     let mut env = build_env();
 
     let node = build_node(&mut env, testing_client_config());
-    let remote_node = node.remote_node().expect("should produce remote node");
-    let chain = node
+    let socket_addr = node
         .client
-        .beacon_chain()
-        .expect("node should have beacon chain");
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-    let state = chain
-        .head()
-        .expect("should have retrieved state")
-        .beacon_state;
-    let slot = state.slot;
-    let spec = &chain.spec;
+    let request = Request::get(format!("http://{}/eth/v1/node/peer_count", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
 
-    let proposer_index = chain
-        .block_proposer(slot)
-        .expect("should get proposer index");
-    let keypair = generate_deterministic_keypair(proposer_index);
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    let json: serde_json::Value = serde_json::from_slice(&bytes).expect("should decode JSON");
+    for state in &["disconnected", "connecting", "connected", "disconnecting"] {
+        assert!(
+            json["data"][state].is_string(),
+            "{} count should be quoted",
+            state
+        );
+    }
 
-    let secret_keys = vec![&keypair.sk];
-    let validator_indices = vec![proposer_index as u64];
-    let fork = &state.fork;
+    let _: GenericResponse<PeerCountData> =
+        serde_json::from_value(json).expect("should decode peer count");
+}
 
-    // Checking there are no attester slashings before insertion
-    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(attester_slashings.len(), 0);
+#[test]
+fn get_standard_api_health() {
+    use hyper::{Body, Client, Request, StatusCode};
 
-    let attester_slashing = build_double_vote_attester_slashing(
-        AttesterSlashingTestTask::Valid,
-        &validator_indices[..],
-        &secret_keys[..],
-        fork,
-        state.genesis_validators_root,
-        spec,
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let request = Request::get(format!("http://{}/eth/v1/node/health", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+
+    // An isolated node has no peers to sync from, so it may report either state.
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::PARTIAL_CONTENT,
+        "node should be initialized, got {}",
+        response.status()
     );
 
+    let bytes = env
+        .runtime()
+        .block_on(hyper::body::to_bytes(response.into_body()))
+        .expect("should read body");
+    assert!(bytes.is_empty(), "response should have no body");
+}
+
+#[test]
+fn get_genesis_state_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let slot = Slot::new(0);
+
     let result = env
         .runtime()
-        .block_on(
-            remote_node
-                .http
-                .beacon()
-                .attester_slashing(attester_slashing.clone()),
-        )
+        .block_on(remote_node.http.beacon().get_state_root(slot))
         .expect("should fetch from http api");
-    assert!(result, true);
 
-    // Length should be just one as we've inserted only one attester slashing
+    let expected = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .rev_iter_state_roots()
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
+        .map(|(cur_root, _)| cur_root)
+        .expect("chain should have state root at slot");
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_genesis_block_root() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let slot = Slot::new(0);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_block_root(slot))
+        .expect("should fetch from http api");
+
+    let expected = node
+        .client
+        .beacon_chain()
+        .expect("should have beacon chain")
+        .rev_iter_block_roots()
+        .expect("should get iter")
+        .map(Result::unwrap)
+        .find(|(_cur_root, cur_slot)| slot == *cur_slot)
+        .map(|(cur_root, _)| cur_root)
+        .expect("chain should have state root at slot");
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let validators = state.validators.iter().take(2).collect::<Vec<_>>();
+    let pubkeys = validators
+        .iter()
+        .map(|v| (&v.pubkey).try_into().expect("should decode pubkey bytes"))
+        .collect();
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_validators(pubkeys, None))
+        .expect("should fetch from http api");
+
+    result
+        .iter()
+        .zip(validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_all_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_all_validators(None))
+        .expect("should fetch from http api");
+
+    result
+        .iter()
+        .zip(state.validators.iter())
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_active_validators() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_active_validators(None))
+        .expect("should fetch from http api");
+
+    /*
+     * This test isn't comprehensive because all of the validators in the state are active (i.e.,
+     * there is no one to exclude.
+     *
+     * This should be fixed once we can generate more interesting scenarios with the
+     * `NodeTestRig`.
+     */
+
+    let validators = state
+        .validators
+        .iter()
+        .filter(|validator| validator.is_active_at(state.current_epoch()));
+
+    result
+        .iter()
+        .zip(validators)
+        .for_each(|(response, validator)| compare_validator_response(state, response, validator));
+}
+
+#[test]
+fn get_all_validators_with_fields() {
+    use http::StatusCode;
+    use remote_beacon_node::{Error::DidNotSucceed, ValidatorField};
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+    let state = &chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_all_validators_with_fields::<serde_json::Value>(
+                    None,
+                    &[ValidatorField::Balance, ValidatorField::ValidatorIndex],
+                ),
+        )
+        .expect("should fetch from http api");
+
+    assert_eq!(result.len(), state.validators.len());
+    for (i, response) in result.iter().enumerate() {
+        assert_eq!(
+            response,
+            &serde_json::json!({
+                "validator_index": i,
+                "balance": state.balances[i],
+            })
+        );
+    }
+
+    let url = remote_node
+        .http
+        .server_url()
+        .join("beacon/validators/all")
+        .expect("should build url");
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .json_get::<serde_json::Value>(url, vec![("fields".into(), "status".into())]),
+    );
+    assert_matches!(
+        result.expect_err("should reject an unknown field"),
+        DidNotSucceed { status, body } => {
+            assert_eq!(status, StatusCode::BAD_REQUEST);
+            assert!(body.contains("pubkey,validator_index,balance,validator"));
+        }
+    );
+}
+
+#[test]
+fn unfiltered_results_cap() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let get_all = |max_unfiltered_results: Option<usize>| {
+        let mut env = build_env();
+
+        let mut config = testing_client_config();
+        config.rest_api.max_unfiltered_results = max_unfiltered_results;
+
+        let node = build_node(&mut env, config);
+        let remote_node = node.remote_node().expect("should produce remote node");
+        let slot = node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain")
+            .head_info()
+            .expect("should get head")
+            .slot;
+
+        let validators = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_all_validators(None));
+        let active = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_active_validators(None));
+        let balances = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_validator_balances(slot));
+        let page = env
+            .runtime()
+            .block_on(remote_node.http.beacon().get_validators_page(None, 2, 3));
+
+        (validators, active, balances, page)
+    };
+
+    fn assert_rejected<T: std::fmt::Debug>(result: Result<T, remote_beacon_node::Error>) {
+        assert_matches!(
+            result.expect_err("should exceed the cap"),
+            DidNotSucceed { status, .. } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+            }
+        );
+    }
+
+    // The registry has 8 validators, all active.
+    let (validators, active, balances, page) = get_all(Some(8));
+    assert_eq!(validators.expect("should be at the cap").len(), 8);
+    assert_eq!(active.expect("should be at the cap").len(), 8);
+    assert_eq!(balances.expect("should be at the cap").len(), 8);
+    assert_eq!(page.expect("should paginate").len(), 3);
+
+    let (validators, active, balances, page) = get_all(Some(7));
+    assert_rejected(validators);
+    assert_rejected(active);
+    assert_rejected(balances);
+    let page = page.expect("pagination should be exempt from the cap");
+    assert_eq!(page.len(), 3);
+    assert_eq!(
+        page[0].validator_index,
+        Some(2),
+        "should start at the offset"
+    );
+
+    let (validators, active, balances, _page) = get_all(None);
+    assert_eq!(validators.expect("should be unlimited").len(), 8);
+    assert_eq!(active.expect("should be unlimited").len(), 8);
+    assert_eq!(balances.expect("should be unlimited").len(), 8);
+}
+
+#[test]
+fn get_committees() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let epoch = Epoch::new(0);
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_committees(epoch))
+        .expect("should fetch from http api");
+
+    let expected = chain
+        .head()
+        .expect("should get head")
+        .beacon_state
+        .get_beacon_committees_at_epoch(RelativeEpoch::Current)
+        .expect("should get committees")
+        .iter()
+        .map(|c| Committee {
+            slot: c.slot,
+            index: c.index,
+            committee: c.committee.to_vec(),
+            pubkeys: None,
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_committees_with_pubkeys() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let epoch = Epoch::new(0);
+    let state = chain.head().expect("should get head").beacon_state;
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .get_committees_with_options(epoch, None, None, true),
+        )
+        .expect("should fetch from http api");
+
+    let expected = state
+        .get_beacon_committees_at_epoch(RelativeEpoch::Current)
+        .expect("should get committees")
+        .iter()
+        .map(|c| Committee {
+            slot: c.slot,
+            index: c.index,
+            committee: c.committee.to_vec(),
+            pubkeys: Some(
+                c.committee
+                    .iter()
+                    .map(|&i| state.validators[i].pubkey.clone())
+                    .collect(),
+            ),
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(result, expected, "result should include aligned pubkeys");
+
+    let target = expected.last().expect("should have a committee").clone();
+
+    let filtered = env
+        .runtime()
+        .block_on(remote_node.http.beacon().get_committees_with_options(
+            epoch,
+            Some(target.slot),
+            Some(target.index),
+            true,
+        ))
+        .expect("should fetch filtered committees from http api");
+
+    assert_eq!(
+        filtered,
+        vec![target],
+        "filters should compose with pubkeys"
+    );
+}
+
+#[test]
+fn get_fork_choice() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let fork_choice = env
+        .runtime()
+        .block_on(remote_node.http.advanced().get_fork_choice())
+        .expect("should not error when getting fork choice");
+
+    assert_eq!(
+        fork_choice,
+        *node
+            .client
+            .beacon_chain()
+            .expect("node should have beacon chain")
+            .fork_choice
+            .read()
+            .proto_array()
+            .core_proto_array(),
+        "result should be as expected"
+    );
+}
+
+#[test]
+fn get_operation_pool() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.advanced().get_operation_pool())
+        .expect("should not error when getting fork choice");
+
+    let expected = PersistedOperationPool::from_operation_pool(
+        &node
+            .client
+            .beacon_chain()
+            .expect("node should have chain")
+            .op_pool,
+    );
+
+    assert_eq!(result, expected, "result should be as expected");
+}
+
+#[test]
+fn get_op_pool_stats() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let get_stats = |env: &mut Environment<E>| {
+        env.runtime()
+            .block_on(remote_node.http.lighthouse().get_op_pool_stats())
+            .expect("should get op pool stats")
+    };
+
+    let stats = get_stats(&mut env);
+    assert_eq!(stats.attestations, chain.op_pool.num_attestations());
+    assert_eq!(stats.attester_slashings, 0);
+    assert_eq!(stats.proposer_slashings, 0);
+    assert_eq!(stats.voluntary_exits, 0);
+    assert_eq!(
+        stats
+            .attestations_per_slot
+            .iter()
+            .map(|count| count.count)
+            .sum::<usize>(),
+        stats.attestations
+    );
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let proposer_index = chain
+        .block_proposer(state.slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &keypair.sk,
+        &state.fork,
+        state.genesis_validators_root,
+        &chain.spec,
+    );
+
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing),
+        )
+        .expect("should publish proposer slashing");
+
+    let stats = get_stats(&mut env);
+    assert_eq!(stats.proposer_slashings, 1);
+    assert_eq!(stats.attester_slashings, 0);
+    assert_eq!(stats.voluntary_exits, 0);
+}
+
+#[test]
+fn prune_op_pool() {
+    use remote_beacon_node::Error::Unauthorized;
+
+    let admin_token = "admin_token";
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.admin_token = Some(admin_token.to_string());
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().prune_op_pool("wrong_token"));
+    assert_matches!(
+        result.expect_err("should not prune with the wrong token"),
+        Unauthorized(_)
+    );
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let proposer_index = chain
+        .block_proposer(state.slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &keypair.sk,
+        &state.fork,
+        state.genesis_validators_root,
+        &chain.spec,
+    );
+
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing),
+        )
+        .expect("should publish proposer slashing");
+
+    let pruned = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().prune_op_pool(admin_token))
+        .expect("should prune op pool");
+
+    // The slashing is still valid, so it should not have been pruned.
+    assert_eq!(pruned.proposer_slashings, 0);
+    assert_eq!(chain.op_pool.num_proposer_slashings(), 1);
+}
+
+#[test]
+fn prune_op_pool_disabled_without_admin_token() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let result = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().prune_op_pool("admin_token"));
+    assert_matches!(
+        result.expect_err("should not prune without an admin token configured"),
+        DidNotSucceed { status, .. } => assert_eq!(status, StatusCode::NOT_FOUND)
+    );
+}
+
+fn compare_validator_response<T: EthSpec>(
+    state: &BeaconState<T>,
+    response: &ValidatorResponse,
+    validator: &Validator,
+) {
+    let response_validator = response.validator.clone().expect("should have validator");
+    let i = response
+        .validator_index
+        .expect("should have validator index");
+    let balance = response.balance.expect("should have balance");
+
+    assert_eq!(response.pubkey, validator.pubkey, "pubkey");
+    assert_eq!(response_validator, *validator, "validator");
+    assert_eq!(state.balances[i], balance, "balances");
+    assert_eq!(state.validators[i], *validator, "validator index");
+}
+
+#[test]
+fn proposer_slashing() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+
+    let spec = &chain.spec;
+
+    // Check that there are no proposer slashings before insertion
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 0);
+
+    let slot = state.slot;
+    let proposer_index = chain
+        .block_proposer(slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+    let key = &keypair.sk;
+    let fork = &state.fork;
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &key,
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(result.broadcast);
+    assert!(!result.already_known);
+
+    // Re-submitting the same slashing should succeed, but report that it was already known.
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .proposer_slashing(proposer_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(!result.broadcast);
+    assert!(result.already_known);
+
+    // Length should be just one as we've inserted only one proposer slashing
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(proposer_slashing.clone(), proposer_slashings[0]);
+
+    let mut invalid_proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &key,
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    invalid_proposer_slashing.signed_header_2 = invalid_proposer_slashing.signed_header_1.clone();
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .proposer_slashing(invalid_proposer_slashing),
+    );
+    assert!(result.is_err());
+
+    // Length should still be one as we've inserted nothing since last time.
+    let (proposer_slashings, _attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(proposer_slashings.len(), 1);
+    assert_eq!(proposer_slashing, proposer_slashings[0]);
+}
+
+#[test]
+fn attester_slashing() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let slot = state.slot;
+    let spec = &chain.spec;
+
+    let proposer_index = chain
+        .block_proposer(slot)
+        .expect("should get proposer index");
+    let keypair = generate_deterministic_keypair(proposer_index);
+
+    let secret_keys = vec![&keypair.sk];
+    let validator_indices = vec![proposer_index as u64];
+    let fork = &state.fork;
+
+    // Checking there are no attester slashings before insertion
+    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(attester_slashings.len(), 0);
+
+    let attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &validator_indices[..],
+        &secret_keys[..],
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .attester_slashing(attester_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(result.broadcast);
+    assert!(!result.already_known);
+
+    // Re-submitting the same slashing should succeed, but report that it was already known.
+    let result = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .beacon()
+                .attester_slashing(attester_slashing.clone()),
+        )
+        .expect("should fetch from http api");
+    assert!(!result.broadcast);
+    assert!(result.already_known);
+
+    // Length should be just one as we've inserted only one attester slashing
+    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
+    assert_eq!(attester_slashings.len(), 1);
+    assert_eq!(attester_slashing, attester_slashings[0]);
+
+    // Building an invalid attester slashing
+    let mut invalid_attester_slashing = build_double_vote_attester_slashing(
+        AttesterSlashingTestTask::Valid,
+        &validator_indices[..],
+        &secret_keys[..],
+        fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    invalid_attester_slashing.attestation_2 = invalid_attester_slashing.attestation_1.clone();
+
+    let result = env.runtime().block_on(
+        remote_node
+            .http
+            .beacon()
+            .attester_slashing(invalid_attester_slashing),
+    );
+    result.unwrap_err();
+
+    // Length should still be one as we've failed to insert the attester slashing.
     let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
     assert_eq!(attester_slashings.len(), 1);
     assert_eq!(attester_slashing, attester_slashings[0]);
+}
+
+#[test]
+fn voluntary_exit() {
+    let mut env = build_env();
+    // Allow validators to exit immediately after genesis.
+    env.eth2_config.spec.shard_committee_period = 0;
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+
+    let validator_index = 0;
+    let exit = TestingVoluntaryExitBuilder::new(state.current_epoch(), validator_index).build(
+        &generate_deterministic_keypair(validator_index as usize).sk,
+        &state.fork,
+        state.genesis_validators_root,
+        &chain.spec,
+    );
+
+    let mut submit_exit = || {
+        env.runtime()
+            .block_on(remote_node.http.beacon().voluntary_exit(exit.clone()))
+            .expect("should publish voluntary exit")
+    };
+
+    let result = submit_exit();
+    assert!(result.broadcast);
+    assert!(!result.already_known);
+    assert_eq!(chain.op_pool.num_voluntary_exits(), 1);
+
+    // The flags should flip when the same exit is submitted again.
+    let result = submit_exit();
+    assert!(!result.broadcast);
+    assert!(result.already_known);
+    assert_eq!(chain.op_pool.num_voluntary_exits(), 1);
+}
+
+#[test]
+fn get_block_body_summary() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+    // Allow validators to exit immediately after genesis.
+    env.eth2_config.spec.shard_committee_period = 0;
+    let spec = &env.eth2_config.spec.clone();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    let state = beacon_chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let exit = TestingVoluntaryExitBuilder::new(state.current_epoch(), 0).build(
+        &generate_deterministic_keypair(0).sk,
+        &state.fork,
+        state.genesis_validators_root,
+        spec,
+    );
+    env.runtime()
+        .block_on(remote_node.http.beacon().voluntary_exit(exit.clone()))
+        .expect("should publish voluntary exit");
+
+    let slot = Slot::new(1);
+    let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+    let block = env
+        .runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .produce_block(slot, randao_reveal, None),
+        )
+        .expect("should fetch block from http api");
+    let signed_block = sign_block(beacon_chain.clone(), block, spec);
+    let block_root = signed_block.canonical_root();
+
+    env.runtime()
+        .block_on(
+            remote_node
+                .http
+                .validator()
+                .publish_block(signed_block.clone()),
+        )
+        .expect("should publish block");
+
+    let lighthouse = remote_node.http.lighthouse();
+    let summary = env
+        .runtime()
+        .block_on(lighthouse.get_block_body_summary(BlockId::Root(block_root)))
+        .expect("should get block body summary by root");
+    for block_id in &[BlockId::Slot(slot), BlockId::Head] {
+        assert_eq!(
+            env.runtime()
+                .block_on(lighthouse.get_block_body_summary(*block_id))
+                .expect("should get block body summary"),
+            summary,
+            "block id {:?}",
+            block_id
+        );
+    }
+
+    // Block IDs are resolved as for `/eth/v1/beacon/blocks/{block_id}`.
+    match env
+        .runtime()
+        .block_on(lighthouse.get_block_body_summary(BlockId::Slot(slot + 1)))
+    {
+        Err(DidNotSucceed { status, .. }) => assert_eq!(status, StatusCode::NOT_FOUND),
+        other => panic!("expected 404 for a future slot, got {:?}", other),
+    }
+
+    let body = &signed_block.message.body;
+    assert_eq!(summary.root, block_root);
+    assert_eq!(summary.slot, slot);
+    assert_eq!(summary.graffiti, body.graffiti);
+    assert_eq!(summary.eth1_data, body.eth1_data);
+    assert_eq!(summary.num_attestations, body.attestations.len());
+    assert_eq!(summary.num_deposits, body.deposits.len());
+    assert_eq!(summary.num_voluntary_exits, 1);
+    assert_eq!(summary.voluntary_exits, vec![exit]);
+    assert_eq!(summary.num_proposer_slashings, 0);
+    assert_eq!(summary.num_attester_slashings, 0);
+}
+
+#[test]
+fn get_pool_slashings_by_validator_index() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let spec = &chain.spec;
+
+    // Two attester slashings which both slash validator 1.
+    let keypairs = (0..3)
+        .map(generate_deterministic_keypair)
+        .collect::<Vec<_>>();
+    let build_attester_slashing = |validator_indices: &[u64]| {
+        let secret_keys = validator_indices
+            .iter()
+            .map(|&i| &keypairs[i as usize].sk)
+            .collect::<Vec<_>>();
+        build_double_vote_attester_slashing(
+            AttesterSlashingTestTask::Valid,
+            validator_indices,
+            &secret_keys[..],
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        )
+    };
+    let attester_slashing_1 = build_attester_slashing(&[0, 1]);
+    let attester_slashing_2 = build_attester_slashing(&[1, 2]);
+
+    let proposer_index = chain
+        .block_proposer(state.slot)
+        .expect("should get proposer index");
+    let proposer_slashing = build_proposer_slashing::<E>(
+        ProposerSlashingTestTask::Valid,
+        proposer_index as u64,
+        &generate_deterministic_keypair(proposer_index).sk,
+        &state.fork,
+        state.genesis_validators_root,
+        spec,
+    );
+
+    let beacon = remote_node.http.beacon();
+    for attester_slashing in &[&attester_slashing_1, &attester_slashing_2] {
+        env.runtime()
+            .block_on(beacon.attester_slashing((*attester_slashing).clone()))
+            .expect("should publish attester slashing");
+    }
+    env.runtime()
+        .block_on(beacon.proposer_slashing(proposer_slashing.clone()))
+        .expect("should publish proposer slashing");
+
+    let mut get_attester_slashings = |validator_index: Option<u64>| {
+        env.runtime()
+            .block_on(beacon.get_pool_attester_slashings(validator_index))
+            .expect("should get attester slashings")
+    };
+
+    assert_eq!(get_attester_slashings(None).len(), 2);
+    assert_eq!(
+        get_attester_slashings(Some(0)),
+        vec![attester_slashing_1.clone()]
+    );
+    assert_eq!(
+        get_attester_slashings(Some(2)),
+        vec![attester_slashing_2.clone()]
+    );
+    assert!(get_attester_slashings(Some(3)).is_empty());
+
+    let overlapping = get_attester_slashings(Some(1));
+    assert_eq!(overlapping.len(), 2);
+    assert!(overlapping.contains(&attester_slashing_1));
+    assert!(overlapping.contains(&attester_slashing_2));
+
+    let mut get_proposer_slashings = |validator_index: Option<u64>| {
+        env.runtime()
+            .block_on(beacon.get_pool_proposer_slashings(validator_index))
+            .expect("should get proposer slashings")
+    };
+
+    assert_eq!(
+        get_proposer_slashings(None),
+        vec![proposer_slashing.clone()]
+    );
+    assert_eq!(
+        get_proposer_slashings(Some(proposer_index as u64)),
+        vec![proposer_slashing]
+    );
+    assert!(get_proposer_slashings(Some(proposer_index as u64 + 1)).is_empty());
+}
+
+#[test]
+fn get_pool_attester_slashings_sorted_and_paged() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+    let chain = node
+        .client
+        .beacon_chain()
+        .expect("node should have beacon chain");
+
+    let state = chain
+        .head()
+        .expect("should have retrieved state")
+        .beacon_state;
+    let spec = &chain.spec;
+
+    let keypairs = (0..5)
+        .map(generate_deterministic_keypair)
+        .collect::<Vec<_>>();
+    let beacon = remote_node.http.beacon();
+    // Publish in reverse order, so the sorted order differs from the order of insertion.
+    for i in (0..4).rev() {
+        let validator_indices = [i, i + 1];
+        let secret_keys = validator_indices
+            .iter()
+            .map(|&i| &keypairs[i as usize].sk)
+            .collect::<Vec<_>>();
+        let slashing = build_double_vote_attester_slashing(
+            AttesterSlashingTestTask::Valid,
+            &validator_indices,
+            &secret_keys[..],
+            &state.fork,
+            state.genesis_validators_root,
+            spec,
+        );
+        env.runtime()
+            .block_on(beacon.attester_slashing(slashing))
+            .expect("should publish attester slashing");
+    }
+
+    let all = env
+        .runtime()
+        .block_on(beacon.get_pool_attester_slashings(None))
+        .expect("should get attester slashings");
+    assert_eq!(all.len(), 4);
+    let first_indices = all
+        .iter()
+        .map(|slashing| slashing.attestation_1.attesting_indices[0])
+        .collect::<Vec<_>>();
+    assert_eq!(first_indices, vec![0, 1, 2, 3]);
+
+    // Polling again gives the same order.
+    assert_eq!(
+        env.runtime()
+            .block_on(beacon.get_pool_attester_slashings(None))
+            .expect("should get attester slashings"),
+        all
+    );
+
+    let mut paged = vec![];
+    for offset in (0..6).step_by(3) {
+        let page = env
+            .runtime()
+            .block_on(beacon.get_pool_attester_slashings_page(offset, 3))
+            .expect("should get page of attester slashings");
+        assert!(page.len() <= 3);
+        paged.extend(page);
+    }
+    assert_eq!(paged, all);
+}
+
+mod validator_attestation {
+    use super::*;
+    use http::StatusCode;
+    use node_test_rig::environment::Environment;
+    use remote_beacon_node::{Error::DidNotSucceed, HttpClient};
+    use types::{Attestation, AttestationDuty, MinimalEthSpec};
+    use url::Url;
+
+    fn setup() -> (
+        Environment<MinimalEthSpec>,
+        LocalBeaconNode<MinimalEthSpec>,
+        HttpClient<MinimalEthSpec>,
+        Url,
+        AttestationDuty,
+    ) {
+        let mut env = build_env();
+        let node = build_node(&mut env, testing_client_config());
+        let remote_node = node.remote_node().expect("should produce remote node");
+        let client = remote_node.http.clone();
+        let socket_addr = node
+            .client
+            .http_listen_addr()
+            .expect("A remote beacon node must have a http server");
+        let url = Url::parse(&format!(
+            "http://{}:{}/validator/attestation",
+            socket_addr.ip(),
+            socket_addr.port()
+        ))
+        .expect("should be valid endpoint");
+
+        // Find a validator that has duties in the current slot of the chain.
+        let mut validator_index = 0;
+        let beacon_chain = node
+            .client
+            .beacon_chain()
+            .expect("client should have beacon chain");
+        let state = beacon_chain.head().expect("should get head").beacon_state;
+        let duties = loop {
+            let duties = state
+                .get_attestation_duties(validator_index, RelativeEpoch::Current)
+                .expect("should have attestation duties cache")
+                .expect("should have attestation duties");
+
+            if duties.slot == node.client.beacon_chain().unwrap().slot().unwrap() {
+                break duties;
+            } else {
+                validator_index += 1
+            }
+        };
+
+        (env, node, client, url, duties)
+    }
+
+    #[test]
+    fn requires_query_parameters() {
+        let (mut env, _node, client, url, _duties) = setup();
+
+        let attestation = env.runtime().block_on(
+            // query parameters are missing
+            client.json_get::<Attestation<MinimalEthSpec>>(url.clone(), vec![]),
+        );
+
+        assert_matches!(
+            attestation.expect_err("should not succeed"),
+            DidNotSucceed { status, body } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"slot\"]".to_owned());
+            }
+        );
+    }
+
+    #[test]
+    fn requires_slot() {
+        let (mut env, _node, client, url, duties) = setup();
+
+        let attestation = env.runtime().block_on(
+            // `slot` is missing
+            client.json_get::<Attestation<MinimalEthSpec>>(
+                url.clone(),
+                vec![("committee_index".into(), format!("{}", duties.index))],
+            ),
+        );
+
+        assert_matches!(
+            attestation.expect_err("should not succeed"),
+            DidNotSucceed { status, body } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"slot\"]".to_owned());
+            }
+        );
+    }
+
+    #[test]
+    fn requires_committee_index() {
+        let (mut env, _node, client, url, duties) = setup();
+
+        let attestation = env.runtime().block_on(
+            // `committee_index` is missing.
+            client.json_get::<Attestation<MinimalEthSpec>>(
+                url.clone(),
+                vec![("slot".into(), format!("{}", duties.slot))],
+            ),
+        );
+
+        assert_matches!(
+            attestation.expect_err("should not succeed"),
+            DidNotSucceed { status, body } => {
+                assert_eq!(status, StatusCode::BAD_REQUEST);
+                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"committee_index\"]".to_owned());
+            }
+        );
+    }
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn get_health() {
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    env.runtime()
+        .block_on(remote_node.http.node().get_health())
+        .unwrap();
+}
+
+#[test]
+fn request_id_is_echoed() {
+    use hyper::{Body, Client, Request};
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let client = Client::new();
+
+    let request = |path: &str, request_id: Option<&str>| {
+        let mut builder = Request::get(format!("http://{}{}", socket_addr, path));
+        if let Some(id) = request_id {
+            builder = builder.header("X-Request-Id", id);
+        }
+        builder.body(Body::empty()).expect("should build request")
+    };
+
+    // The supplied ID is echoed on successful responses.
+    let response = env
+        .runtime()
+        .block_on(client.request(request("/node/version", Some("vc-request-1"))))
+        .expect("should get response");
+    assert!(response.status().is_success());
+    assert_eq!(
+        response.headers().get("x-request-id").map(|v| v.as_bytes()),
+        Some(&b"vc-request-1"[..]),
+        "should echo request id on success"
+    );
+
+    // The supplied ID is echoed on error responses.
+    let response = env
+        .runtime()
+        .block_on(client.request(request("/not/a/route", Some("vc-request-2"))))
+        .expect("should get response");
+    assert!(!response.status().is_success());
+    assert_eq!(
+        response.headers().get("x-request-id").map(|v| v.as_bytes()),
+        Some(&b"vc-request-2"[..]),
+        "should echo request id on error"
+    );
+
+    // An ID is generated when none is supplied.
+    let response = env
+        .runtime()
+        .block_on(client.request(request("/node/version", None)))
+        .expect("should get response");
+    let generated = response
+        .headers()
+        .get("x-request-id")
+        .expect("should generate request id");
+    assert_eq!(generated.len(), 32, "should be a 16 byte hex string");
+}
+
+#[test]
+fn handler_logs_include_request_id() {
+    use hyper::{Body, Client, Request};
+    use std::time::{Duration, Instant};
+
+    // Log to a JSON file, so that the logs of the handler can be read back.
+    let dir = tempdir::TempDir::new("request_id_log").expect("should create temp dir");
+    let path = dir.path().join("beacon.log");
+    let mut env = EnvironmentBuilder::minimal()
+        .log_to_file(path.clone(), "debug", Some("JSON"))
+        .expect("should build env logger")
+        .single_thread_tokio_runtime()
+        .expect("should start tokio runtime")
+        .build()
+        .expect("environment should build");
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    // Publishing the genesis block again is rejected, which the handler logs.
+    let genesis_block = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain")
+        .block_at_slot(Slot::new(0))
+        .expect("should find block")
+        .expect("block should not be none");
+    let request = Request::post(format!("http://{}/validator/block", socket_addr))
+        .header("X-Request-Id", "publish-block-1")
+        .body(Body::from(
+            serde_json::to_vec(&genesis_block).expect("should encode block"),
+        ))
+        .expect("should build request");
+    env.runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+
+    // Logs are written by a separate thread, so wait for the handler's log to appear.
+    let is_handler_log = |line: &str| {
+        serde_json::from_str::<serde_json::Value>(line).map_or(false, |entry| {
+            entry["msg"] == "Invalid block from local validator"
+                && entry["request_id"] == "publish-block-1"
+        })
+    };
+    let deadline = Instant::now() + Duration::from_secs(10);
+    loop {
+        let contents = std::fs::read_to_string(&path).unwrap_or_default();
+        if contents.lines().any(is_handler_log) {
+            break;
+        }
+        assert!(
+            Instant::now() < deadline,
+            "handler log should include the request id"
+        );
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+fn liveness_and_readiness() {
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.readiness_min_peers = 0;
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    assert!(
+        env.runtime()
+            .block_on(remote_node.http.lighthouse().get_liveness())
+            .expect("should fetch liveness"),
+        "node should be live"
+    );
+
+    let readiness = env
+        .runtime()
+        .block_on(remote_node.http.lighthouse().get_readiness())
+        .expect("node at genesis without a peer requirement should be ready");
+    assert_eq!(readiness.connected_peers, 0, "should have no peers");
+}
+
+#[test]
+fn not_ready_without_peers() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.readiness_min_peers = 1;
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.lighthouse().get_readiness())
+            .expect_err("node without peers should not be ready"),
+        DidNotSucceed { status, .. } => {
+            assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        }
+    );
+}
+
+#[test]
+fn not_ready_when_head_is_behind() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+
+    let spec = &E::default_spec();
+
+    let ten_slots_secs = (spec.milliseconds_per_slot / 1_000) * 10;
+
+    // With genesis ten slots ago and no blocks produced, the head lags the wall clock.
+    let mut config = testing_client_config();
+    config.rest_api.readiness_min_peers = 0;
+    config.rest_api.readiness_max_head_distance = 2;
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - ten_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.lighthouse().get_readiness())
+            .expect_err("node with a stale head should not be ready"),
+        DidNotSucceed { status, .. } => {
+            assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        }
+    );
+}
+
+#[test]
+fn eth1_deposit_cache_without_eth1_service() {
+    use http::StatusCode;
+    use remote_beacon_node::Error::DidNotSucceed;
+
+    let mut env = build_env();
+
+    // The test node uses a dummy eth1 backend, so there is no deposit cache to inspect.
+    let node = build_node(&mut env, testing_client_config());
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    assert_matches!(
+        env.runtime()
+            .block_on(remote_node.http.lighthouse().get_eth1_deposit_cache())
+            .expect_err("node without an eth1 service should not have a deposit cache"),
+        DidNotSucceed { status, .. } => {
+            assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        }
+    );
+}
+
+#[test]
+fn cors_allow_origins() {
+    use hyper::{Body, Client, Request};
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.allow_origins = vec![
+        "http://localhost:3000".to_string(),
+        "https://explorer.example.org".to_string(),
+    ];
+
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let client = Client::new();
+
+    let mut allow_origin = |origin: &str| {
+        let request = Request::get(format!("http://{}/node/version", socket_addr))
+            .header("Origin", origin)
+            .body(Body::empty())
+            .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(client.request(request))
+            .expect("should get response");
+        assert!(response.status().is_success());
+        assert_eq!(
+            response.headers().get("vary").map(|v| v.as_bytes()),
+            Some(&b"Origin"[..])
+        );
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .map(|v| v.to_str().expect("should be ascii").to_string())
+    };
+
+    assert_eq!(
+        allow_origin("http://localhost:3000"),
+        Some("http://localhost:3000".to_string()),
+        "should allow the first origin"
+    );
+    assert_eq!(
+        allow_origin("https://explorer.example.org"),
+        Some("https://explorer.example.org".to_string()),
+        "should allow the second origin"
+    );
+    assert_eq!(
+        allow_origin("https://evil.example.org"),
+        None,
+        "should not allow an unlisted origin"
+    );
+
+    // A preflight request is answered without reaching the handler, which only accepts POST.
+    let request = Request::options(format!(
+        "http://{}/eth/v1/validator/beacon_committee_subscriptions",
+        socket_addr
+    ))
+    .header("Origin", "http://localhost:3000")
+    .header("Access-Control-Request-Method", "POST")
+    .header("Access-Control-Request-Headers", "content-type")
+    .body(Body::empty())
+    .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(client.request(request))
+        .expect("should get response");
+    assert_eq!(response.status(), hyper::StatusCode::NO_CONTENT);
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .map(|v| v.to_str().expect("should be ascii").to_string())
+    };
+    assert_eq!(
+        header("access-control-allow-origin"),
+        Some("http://localhost:3000".to_string())
+    );
+    assert_eq!(
+        header("access-control-allow-methods"),
+        Some("GET, POST".to_string())
+    );
+    assert!(header("access-control-allow-headers")
+        .expect("should allow headers")
+        .contains("Content-Type"));
+
+    // Error responses are readable by allowed origins.
+    let request = Request::get(format!("http://{}/unknown", socket_addr))
+        .header("Origin", "http://localhost:3000")
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(client.request(request))
+        .expect("should get response");
+    assert_eq!(response.status(), hyper::StatusCode::NOT_FOUND);
+    assert_eq!(
+        response
+            .headers()
+            .get("access-control-allow-origin")
+            .map(|v| v.as_bytes()),
+        Some(&b"http://localhost:3000"[..])
+    );
+}
+
+#[test]
+fn tls() {
+    use std::path::PathBuf;
+
+    let mut env = build_env();
+
+    let tls_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/tls");
+    let mut config = testing_client_config();
+    config.rest_api.tls_cert_path = Some(tls_dir.join("cert.pem"));
+    config.rest_api.tls_key_path = Some(tls_dir.join("key.pem"));
+
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    assert_ne!(socket_addr.port(), 0, "should report the bound port");
+
+    // The test certificate is self-signed.
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .build()
+        .expect("should build client");
+
+    let response = env
+        .runtime()
+        .block_on(
+            client
+                .get(&format!("https://{}/node/version", socket_addr))
+                .send(),
+        )
+        .expect("should get response over https");
+    assert!(response.status().is_success());
+    let version = env
+        .runtime()
+        .block_on(response.json::<String>())
+        .expect("should decode version");
+    assert_eq!(version, lighthouse_version::version_with_platform());
+
+    env.runtime()
+        .block_on(
+            client
+                .get(&format!("http://{}/node/version", socket_addr))
+                .send(),
+        )
+        .expect_err("should not serve plain http");
+
+    // A failed handshake does not stop the server.
+    let response = env
+        .runtime()
+        .block_on(
+            client
+                .get(&format!("https://{}/node/version", socket_addr))
+                .send(),
+        )
+        .expect("should still get response over https");
+    assert!(response.status().is_success());
+}
+
+#[test]
+fn listen_on_ipv4_and_ipv6() {
+    use hyper::{Body, Client, Request};
+    use std::net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener};
+
+    // Not every test environment has IPv6.
+    if TcpListener::bind((Ipv6Addr::LOCALHOST, 0)).is_err() {
+        return;
+    }
+
+    let mut env = build_env();
+
+    // Find a free port, to bind both the unspecified IPv4 and IPv6 addresses to.
+    let port = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))
+        .and_then(|listener| listener.local_addr())
+        .expect("should find a free port")
+        .port();
+
+    let mut config = testing_client_config();
+    config.rest_api.listen_addresses =
+        vec![Ipv4Addr::UNSPECIFIED.into(), Ipv6Addr::UNSPECIFIED.into()];
+    config.rest_api.port = port;
+
+    let node = build_node(&mut env, config);
+    assert_eq!(
+        node.client.http_listen_addrs(),
+        &[
+            SocketAddr::from((Ipv4Addr::UNSPECIFIED, port)),
+            SocketAddr::from((Ipv6Addr::UNSPECIFIED, port)),
+        ][..]
+    );
+
+    for ip in &[
+        std::net::IpAddr::from(Ipv4Addr::LOCALHOST),
+        Ipv6Addr::LOCALHOST.into(),
+    ] {
+        let request = Request::get(format!(
+            "http://{}/node/version",
+            SocketAddr::new(*ip, port)
+        ))
+        .body(Body::empty())
+        .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        assert!(response.status().is_success(), "should serve {}", ip);
+    }
+}
+
+#[test]
+fn bind_failure_fails_startup() {
+    use std::net::{Ipv4Addr, TcpListener};
+
+    let mut env = build_env();
+
+    let occupied = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).expect("should bind");
+    let occupied_addr = occupied.local_addr().expect("should get address");
+
+    let mut config = testing_client_config();
+    config.rest_api.listen_addresses = vec![occupied_addr.ip()];
+    config.rest_api.port = occupied_addr.port();
+
+    let context = env.core_context();
+    let error = env
+        .runtime()
+        .block_on(LocalBeaconNode::production(context, config))
+        .err()
+        .expect("should fail to start");
+    assert!(
+        error.contains(&occupied_addr.to_string()),
+        "error should name the address: {}",
+        error
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn unix_socket() {
+    use hyper::{Body, Request};
+    use std::os::unix::fs::PermissionsExt;
+    use tempdir::TempDir;
+
+    let mut env = build_env();
+
+    let dir = TempDir::new("http_unix_socket").expect("should create temp dir");
+    let path = dir.path().join("http.sock");
+
+    let mut config = testing_client_config();
+    // Only listen on the socket.
+    config.rest_api.listen_addresses = vec![];
+    config.rest_api.listen_unix_socket = Some(path.clone());
+
+    let node = build_node(&mut env, config);
+    assert!(node.client.http_listen_addrs().is_empty());
+
+    let mode = std::fs::metadata(&path)
+        .expect("socket should exist")
+        .permissions()
+        .mode();
+    assert_eq!(mode & 0o777, 0o600, "only the owner should have access");
+
+    let body = env.runtime().block_on(async {
+        let stream = tokio::net::UnixStream::connect(&path)
+            .await
+            .expect("should connect to socket");
+        let (mut sender, connection) = hyper::client::conn::handshake(stream)
+            .await
+            .expect("should complete http handshake");
+        tokio::spawn(connection);
+
+        let request = Request::get("/node/version")
+            .header("Host", "localhost")
+            .body(Body::empty())
+            .expect("should build request");
+        let response = sender
+            .send_request(request)
+            .await
+            .expect("should get response");
+        assert!(response.status().is_success());
+
+        hyper::body::to_bytes(response.into_body())
+            .await
+            .expect("should read body")
+    });
+    let version: String = serde_json::from_slice(&body).expect("should decode version");
+    assert_eq!(version, lighthouse_version::version_with_platform());
+}
+
+#[test]
+fn request_body_size_limit() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.max_request_body_size = 1024;
+
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let mut post = |body: Vec<u8>| {
+        let request = Request::post(format!("http://{}/validator/block", socket_addr))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .expect("should build request");
+        env.runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response")
+    };
+
+    // An over-limit body is rejected before it is parsed.
+    let response = post(vec![b' '; 1025]);
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A body within the limit reaches the handler, which rejects it as invalid.
+    let response = post(vec![b' '; 1024]);
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[test]
+fn gzip_compression() {
+    use flate2::read::GzDecoder;
+    use hyper::{header, Body, Client, Request};
+    use std::io::Read;
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let mut get = |accept: &str, accept_encoding: Option<&str>| {
+        let mut request = Request::get(format!("http://{}/beacon/state", socket_addr))
+            .header(header::ACCEPT, accept);
+        if let Some(accept_encoding) = accept_encoding {
+            request = request.header(header::ACCEPT_ENCODING, accept_encoding);
+        }
+        let request = request.body(Body::empty()).expect("should build request");
+
+        env.runtime().block_on(async {
+            let response = Client::new()
+                .request(request)
+                .await
+                .expect("should get response");
+            assert!(response.status().is_success());
+            let content_encoding = response
+                .headers()
+                .get(header::CONTENT_ENCODING)
+                .map(|value| value.to_str().unwrap().to_string());
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .expect("should read body");
+            (content_encoding, body.to_vec())
+        })
+    };
+
+    let (content_encoding, plain) = get("application/json", None);
+    assert_eq!(content_encoding, None);
+
+    let (content_encoding, compressed) = get("application/json", Some("gzip, deflate"));
+    assert_eq!(content_encoding.as_deref(), Some("gzip"));
+    assert!(
+        compressed.len() < plain.len(),
+        "response should be compressed"
+    );
+
+    let mut decompressed = vec![];
+    GzDecoder::new(&compressed[..])
+        .read_to_end(&mut decompressed)
+        .expect("should decompress response");
+    assert_eq!(
+        serde_json::from_slice::<serde_json::Value>(&decompressed).expect("should decode JSON"),
+        serde_json::from_slice::<serde_json::Value>(&plain).expect("should decode JSON"),
+    );
+
+    // Binary responses are not compressed.
+    let (content_encoding, _) = get("application/octet-stream", Some("gzip"));
+    assert_eq!(content_encoding, None);
+}
+
+#[test]
+fn rate_limit() {
+    use hyper::{header, Body, Client, Request, StatusCode};
+    use rest_api::RateLimit;
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.rate_limit = Some(RateLimit {
+        requests_per_second: 1,
+        burst: 2,
+    });
+    config.rest_api.trusted_proxy_header = Some("X-Forwarded-For".to_string());
+
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let mut get = |path: &str, forwarded_for: Option<&str>| {
+        let mut request = Request::get(format!("http://{}{}", socket_addr, path));
+        if let Some(forwarded_for) = forwarded_for {
+            request = request.header("X-Forwarded-For", forwarded_for);
+        }
+        let request = request.body(Body::empty()).expect("should build request");
+        env.runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response")
+    };
+
+    for _ in 0..2 {
+        assert_eq!(get("/node/version", None).status(), StatusCode::OK);
+    }
+    let response = get("/node/version", None);
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert_eq!(
+        response.headers().get(header::RETRY_AFTER),
+        Some(&header::HeaderValue::from_static("1"))
+    );
+
+    // Validator endpoints are limited separately, and are unlimited by default.
+    for _ in 0..3 {
+        assert_ne!(
+            get("/validator/duties/all?epoch=0", None).status(),
+            StatusCode::TOO_MANY_REQUESTS
+        );
+    }
+
+    // Clients behind the trusted proxy are limited by their own address.
+    assert_eq!(
+        get("/node/version", Some("10.0.0.1, 10.0.0.2")).status(),
+        StatusCode::OK
+    );
+}
+
+#[test]
+fn cors_disabled_by_default() {
+    use hyper::{Body, Client, Request, StatusCode};
+
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let request = Request::options(format!("http://{}/node/version", socket_addr))
+        .header("Origin", "http://localhost:3000")
+        .header("Access-Control-Request-Method", "GET")
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response
+        .headers()
+        .get("access-control-allow-origin")
+        .is_none());
+    assert!(response
+        .headers()
+        .get("access-control-allow-methods")
+        .is_none());
+}
+
+#[test]
+fn json_access_log() {
+    use hyper::{Body, Client, Request};
+    use rest_api::access_log::AccessLogEntry;
+
+    let mut env = build_env();
+
+    let dir = tempdir::TempDir::new("access_log").expect("should create temp dir");
+    let path = dir.path().join("access.log");
+
+    let mut config = testing_client_config();
+    config.rest_api.json_access_log = true;
+    config.rest_api.json_access_log_path = Some(path.clone());
+
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let request = Request::get(format!("http://{}/node/version", socket_addr))
+        .header("X-Request-Id", "access-log-test")
+        .header("User-Agent", "access-log-agent")
+        .body(Body::empty())
+        .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
+
+    let contents = std::fs::read_to_string(&path).expect("should read access log");
+    let entries = contents
+        .lines()
+        .map(|line| serde_json::from_str::<AccessLogEntry>(line).expect("should be JSON"))
+        .collect::<Vec<_>>();
+    let entry = entries
+        .iter()
+        .find(|entry| entry.request_id == "access-log-test")
+        .expect("should log the request");
+
+    assert_eq!(entry.method, "GET");
+    assert_eq!(entry.path, "/node/version");
+    assert_eq!(entry.status, 200);
+    assert_eq!(entry.user_agent.as_deref(), Some("access-log-agent"));
+    assert!(entry.remote.is_some(), "should log the remote address");
+
+    // A request to a route with path parameters is logged with the path of the route.
+    let request = Request::get(format!(
+        "http://{}/eth/v1/beacon/blocks/genesis/root",
+        socket_addr
+    ))
+    .header("X-Request-Id", "access-log-template")
+    .body(Body::empty())
+    .expect("should build request");
+    let response = env
+        .runtime()
+        .block_on(Client::new().request(request))
+        .expect("should get response");
+    assert!(response.status().is_success());
+
+    let contents = std::fs::read_to_string(&path).expect("should read access log");
+    let entry = contents
+        .lines()
+        .map(|line| serde_json::from_str::<AccessLogEntry>(line).expect("should be JSON"))
+        .find(|entry| entry.request_id == "access-log-template")
+        .expect("should log the request");
+    assert_eq!(entry.path, "/eth/v1/beacon/blocks/{block_id}/root");
+}
 
-    // Building an invalid attester slashing
-    let mut invalid_attester_slashing = build_double_vote_attester_slashing(
-        AttesterSlashingTestTask::Valid,
-        &validator_indices[..],
-        &secret_keys[..],
-        fork,
-        state.genesis_validators_root,
-        spec,
+#[test]
+fn api_index() {
+    use hyper::{body::to_bytes, Body, Client, Request};
+    use remote_beacon_node::ApiIndex;
+
+    let admin_token = "admin_token";
+
+    let mut env = build_env();
+
+    let mut config = testing_client_config();
+    config.rest_api.admin_token = Some(admin_token.to_string());
+    let node = build_node(&mut env, config);
+    let remote_node = node.remote_node().expect("should produce remote node");
+
+    let has_route = |index: &ApiIndex, method: &str, path: &str| {
+        index
+            .routes
+            .iter()
+            .any(|route| route.method == method && route.path == path)
+    };
+
+    let index = env
+        .runtime()
+        .block_on(remote_node.http.node().get_api_index())
+        .expect("should get api index");
+    let version = env
+        .runtime()
+        .block_on(remote_node.http.node().get_version())
+        .expect("should get version");
+
+    assert_eq!(index.name, "Lighthouse");
+    assert_eq!(index.version, version);
+    assert!(has_route(&index, "GET", "/"), "should list itself");
+    assert!(has_route(&index, "GET", "/node/version"));
+    assert!(has_route(&index, "POST", "/validator/block"));
+    assert_eq!(
+        index
+            .routes
+            .iter()
+            .filter(|route| route.method == "GET" && route.path == "/spec")
+            .count(),
+        1,
+        "routes should not be duplicated"
+    );
+    assert!(
+        !has_route(&index, "POST", "/lighthouse/op_pool/prune"),
+        "admin routes should be hidden without the admin token"
     );
-    invalid_attester_slashing.attestation_2 = invalid_attester_slashing.attestation_1.clone();
 
-    let result = env.runtime().block_on(
-        remote_node
-            .http
-            .beacon()
-            .attester_slashing(invalid_attester_slashing),
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+    let request = Request::get(format!("http://{}/", socket_addr))
+        .header("Authorization", format!("Bearer {}", admin_token))
+        .body(Body::empty())
+        .expect("should build request");
+    let index: ApiIndex = env
+        .runtime()
+        .block_on(async {
+            let response = Client::new().request(request).await?;
+            to_bytes(response.into_body()).await
+        })
+        .map(|bytes| serde_json::from_slice(&bytes).expect("should decode api index"))
+        .expect("should get api index");
+
+    assert!(
+        has_route(&index, "POST", "/lighthouse/op_pool/prune"),
+        "admin routes should be listed with the admin token"
     );
-    result.unwrap_err();
 
-    // Length should still be one as we've failed to insert the attester slashing.
-    let (_proposer_slashings, attester_slashings) = chain.op_pool.get_slashings(&state);
-    assert_eq!(attester_slashings.len(), 1);
-    assert_eq!(attester_slashing, attester_slashings[0]);
+    let request = Request::get(format!("http://{}/eth/v1", socket_addr))
+        .body(Body::empty())
+        .expect("should build request");
+    let index: ApiIndex = env
+        .runtime()
+        .block_on(async {
+            let response = Client::new().request(request).await?;
+            to_bytes(response.into_body()).await
+        })
+        .map(|bytes| serde_json::from_slice(&bytes).expect("should decode api index"))
+        .expect("should get api index");
+
+    assert_eq!(index.name, "Lighthouse");
+    assert!(has_route(&index, "GET", "/eth/v1"), "should list itself");
+    assert!(has_route(&index, "GET", "/eth/v1/node/version"));
+    assert!(has_route(&index, "GET", "/eth/v1/beacon/blocks/{block_id}"));
+    assert!(
+        index
+            .routes
+            .iter()
+            .all(|route| route.path.starts_with("/eth/v1")),
+        "only /eth/v1 routes should be listed"
+    );
 }
 
-mod validator_attestation {
-    use super::*;
-    use http::StatusCode;
-    use node_test_rig::environment::Environment;
-    use remote_beacon_node::{Error::DidNotSucceed, HttpClient};
-    use types::{Attestation, AttestationDuty, MinimalEthSpec};
-    use url::Url;
+#[test]
+fn get_state_validators() {
+    use hyper::{Body, Client, Request, StatusCode};
 
-    fn setup() -> (
-        Environment<MinimalEthSpec>,
-        LocalBeaconNode<MinimalEthSpec>,
-        HttpClient<MinimalEthSpec>,
-        Url,
-        AttestationDuty,
-    ) {
-        let mut env = build_env();
-        let node = build_node(&mut env, testing_client_config());
-        let remote_node = node.remote_node().expect("should produce remote node");
-        let client = remote_node.http.clone();
-        let socket_addr = node
-            .client
-            .http_listen_addr()
-            .expect("A remote beacon node must have a http server");
-        let url = Url::parse(&format!(
-            "http://{}:{}/validator/attestation",
-            socket_addr.ip(),
-            socket_addr.port()
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let state = beacon_chain.head().expect("should get head").beacon_state;
+    let pubkey = serde_json::to_value(&state.validators[3].pubkey).expect("should encode pubkey");
+
+    let get = |env: &mut Environment<E>, endpoint: &str, query: &str| {
+        let request = Request::get(format!(
+            "http://{}/eth/v1/beacon/states/head/{}{}",
+            socket_addr, endpoint, query
         ))
-        .expect("should be valid endpoint");
+        .body(Body::empty())
+        .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        let status = response.status();
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        (status, bytes)
+    };
 
-        // Find a validator that has duties in the current slot of the chain.
-        let mut validator_index = 0;
-        let beacon_chain = node
-            .client
-            .beacon_chain()
-            .expect("client should have beacon chain");
-        let state = beacon_chain.head().expect("should get head").beacon_state;
-        let duties = loop {
-            let duties = state
-                .get_attestation_duties(validator_index, RelativeEpoch::Current)
-                .expect("should have attestation duties cache")
-                .expect("should have attestation duties");
+    let (status, bytes) = get(&mut env, "validators", "");
+    assert_eq!(status, StatusCode::OK);
+    let all: PaginatedResponse<ValidatorData> =
+        serde_json::from_slice(&bytes).expect("should decode validators");
+    assert_eq!(all.data.len(), state.validators.len());
+    assert_eq!(all.meta.total, state.validators.len());
+
+    // Unknown ids are omitted and duplicates are only returned once.
+    let query = format!(
+        "?id=1,{},999999&id=1",
+        pubkey.as_str().expect("pubkey should be a string")
+    );
+    let (status, bytes) = get(&mut env, "validators", &query);
+    assert_eq!(status, StatusCode::OK);
+    let filtered: PaginatedResponse<ValidatorData> =
+        serde_json::from_slice(&bytes).expect("should decode validators");
+    assert_eq!(
+        filtered.data,
+        vec![all.data[1].clone(), all.data[3].clone()],
+        "should return the validators with the given ids"
+    );
+    assert_eq!(filtered.data[1].balance, state.balances[3]);
 
-            if duties.slot == node.client.beacon_chain().unwrap().slot().unwrap() {
-                break duties;
-            } else {
-                validator_index += 1
-            }
-        };
+    let (status, _) = get(&mut env, "validators", "?id=not_an_id");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
 
-        (env, node, client, url, duties)
-    }
+    // All validators are active from genesis.
+    assert!(all
+        .data
+        .iter()
+        .all(|validator| validator.status == ValidatorStatus::ActiveOngoing));
+
+    let get_data = |env: &mut Environment<E>, query: &str| {
+        let (status, bytes) = get(env, "validators", query);
+        assert_eq!(status, StatusCode::OK, "query {} should succeed", query);
+        serde_json::from_slice::<PaginatedResponse<ValidatorData>>(&bytes)
+            .expect("should decode validators")
+            .data
+    };
 
-    #[test]
-    fn requires_query_parameters() {
-        let (mut env, _node, client, url, _duties) = setup();
+    assert_eq!(get_data(&mut env, "?status=active"), all.data);
+    assert_eq!(get_data(&mut env, "?status=pending_queued,exited"), vec![]);
+    assert_eq!(
+        get_data(&mut env, "?id=1,2&status=pending,active_ongoing"),
+        vec![all.data[1].clone(), all.data[2].clone()],
+        "the status filter should compose with the id filter"
+    );
+    assert_eq!(get_data(&mut env, "?id=1&status=exited"), vec![]);
+
+    let (status, bytes) = get(&mut env, "validators", "?status=active,running");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8_lossy(&bytes).contains("active_ongoing"));
+
+    // Pages are taken from the filtered validators, in index order.
+    let (status, bytes) = get(&mut env, "validators", "?status=active&offset=2&limit=3");
+    assert_eq!(status, StatusCode::OK);
+    let page: PaginatedResponse<ValidatorData> =
+        serde_json::from_slice(&bytes).expect("should decode validators");
+    assert_eq!(page.data, all.data[2..5].to_vec());
+    assert_eq!(page.meta.total, state.validators.len());
+
+    assert_eq!(get_data(&mut env, "?offset=999999"), vec![]);
+    assert_eq!(get_data(&mut env, "?offset=6"), all.data[6..].to_vec());
+
+    let (status, bytes) = get(&mut env, "validator_balances", "?id=4,1&limit=1");
+    assert_eq!(status, StatusCode::OK);
+    let balances: PaginatedResponse<ValidatorBalanceData> =
+        serde_json::from_slice(&bytes).expect("should decode balances");
+    assert_eq!(
+        balances.data,
+        vec![ValidatorBalanceData {
+            index: 1,
+            balance: state.balances[1],
+        }]
+    );
+    assert_eq!(balances.meta.total, 2);
+}
 
-        let attestation = env.runtime().block_on(
-            // query parameters are missing
-            client.json_get::<Attestation<MinimalEthSpec>>(url.clone(), vec![]),
-        );
+#[test]
+fn filtered_state_validators_are_exempt_from_cap() {
+    use hyper::{Body, Client, Request, StatusCode};
 
-        assert_matches!(
-            attestation.expect_err("should not succeed"),
-            DidNotSucceed { status, body } => {
-                assert_eq!(status, StatusCode::BAD_REQUEST);
-                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"slot\"]".to_owned());
-            }
-        );
-    }
+    let mut env = build_env();
 
-    #[test]
-    fn requires_slot() {
-        let (mut env, _node, client, url, duties) = setup();
+    let mut config = testing_client_config();
+    config.rest_api.max_unfiltered_results = Some(2);
 
-        let attestation = env.runtime().block_on(
-            // `slot` is missing
-            client.json_get::<Attestation<MinimalEthSpec>>(
-                url.clone(),
-                vec![("committee_index".into(), format!("{}", duties.index))],
-            ),
-        );
+    let node = build_node(&mut env, config);
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-        assert_matches!(
-            attestation.expect_err("should not succeed"),
-            DidNotSucceed { status, body } => {
-                assert_eq!(status, StatusCode::BAD_REQUEST);
-                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"slot\"]".to_owned());
-            }
+    let mut get = |endpoint: &str, query: &str| {
+        let request = Request::get(format!(
+            "http://{}/eth/v1/beacon/states/head/{}{}",
+            socket_addr, endpoint, query
+        ))
+        .body(Body::empty())
+        .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        let status = response.status();
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        (status, bytes)
+    };
+
+    // The registry has 8 validators, more than the cap.
+    let (status, _) = get("validators", "");
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "unfiltered should be capped"
+    );
+    let (status, _) = get("validator_balances", "");
+    assert_eq!(
+        status,
+        StatusCode::BAD_REQUEST,
+        "unfiltered should be capped"
+    );
+
+    for (endpoint, query) in &[
+        ("validators", "?status=active_ongoing"),
+        ("validators", "?id=0,1,2,3"),
+        ("validator_balances", "?id=0,1,2,3,4"),
+    ] {
+        let (status, bytes) = get(endpoint, query);
+        assert_eq!(
+            status,
+            StatusCode::OK,
+            "{}{} should be exempt",
+            endpoint,
+            query
+        );
+        let response: PaginatedResponse<serde_json::Value> =
+            serde_json::from_slice(&bytes).expect("should decode response");
+        assert!(
+            response.data.len() > 2,
+            "{}{} should not be capped",
+            endpoint,
+            query
         );
     }
+}
 
-    #[test]
-    fn requires_committee_index() {
-        let (mut env, _node, client, url, duties) = setup();
+#[test]
+fn get_blocks_by_id() {
+    use hyper::{Body, Client, Request, StatusCode};
 
-        let attestation = env.runtime().block_on(
-            // `committee_index` is missing.
-            client.json_get::<Attestation<MinimalEthSpec>>(
-                url.clone(),
-                vec![("slot".into(), format!("{}", duties.slot))],
-            ),
+    let mut env = build_env();
+
+    let node = build_node(&mut env, testing_client_config());
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
+
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+    let head_info = beacon_chain.head_info().expect("should get head info");
+    let genesis_block_root = beacon_chain.genesis_block_root;
+
+    let get = |env: &mut Environment<E>, path: &str| {
+        let request = Request::get(format!("http://{}/eth/v1/beacon/{}", socket_addr, path))
+            .body(Body::empty())
+            .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        let status = response.status();
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        (status, bytes)
+    };
+
+    // Checkpoint roots are zero prior to the first justification and finalization.
+    let checkpoint_block_root = |root: Hash256| {
+        if root == Hash256::zero() {
+            genesis_block_root
+        } else {
+            root
+        }
+    };
+    let block_ids = vec![
+        ("head".to_string(), head_info.block_root),
+        ("genesis".to_string(), genesis_block_root),
+        (
+            "finalized".to_string(),
+            checkpoint_block_root(head_info.finalized_checkpoint.root),
+        ),
+        (
+            "justified".to_string(),
+            checkpoint_block_root(head_info.current_justified_checkpoint.root),
+        ),
+        ("0".to_string(), genesis_block_root),
+        (format!("{:?}", genesis_block_root), genesis_block_root),
+    ];
+
+    for (block_id, expected_root) in block_ids {
+        let (status, bytes) = get(&mut env, &format!("blocks/{}/root", block_id));
+        assert_eq!(status, StatusCode::OK, "{} root should be found", block_id);
+        let root: GenericResponse<RootData> =
+            serde_json::from_slice(&bytes).expect("should decode root");
+        assert_eq!(root.data.root, expected_root, "{} root", block_id);
+
+        let (status, bytes) = get(&mut env, &format!("blocks/{}", block_id));
+        assert_eq!(status, StatusCode::OK, "{} block should be found", block_id);
+        let block: GenericResponse<SignedBeaconBlock<E>> =
+            serde_json::from_slice(&bytes).expect("should decode block");
+        assert_eq!(
+            block.data.canonical_root(),
+            expected_root,
+            "{} block",
+            block_id
         );
 
-        assert_matches!(
-            attestation.expect_err("should not succeed"),
-            DidNotSucceed { status, body } => {
-                assert_eq!(status, StatusCode::BAD_REQUEST);
-                assert_eq!(body, "URL query must be valid and contain at least one of the following keys: [\"committee_index\"]".to_owned());
-            }
+        let (status, bytes) = get(&mut env, &format!("headers/{}", block_id));
+        assert_eq!(
+            status,
+            StatusCode::OK,
+            "{} header should be found",
+            block_id
+        );
+        let header: GenericResponse<BlockHeaderData> =
+            serde_json::from_slice(&bytes).expect("should decode header");
+        assert_eq!(header.data.root, expected_root);
+        assert_eq!(header.data.header.message.canonical_root(), expected_root);
+        assert!(header.data.canonical);
+
+        let (status, bytes) = get(&mut env, &format!("blocks/{}/attestations", block_id));
+        assert_eq!(status, StatusCode::OK);
+        let attestations: GenericResponse<Vec<Attestation<E>>> =
+            serde_json::from_slice(&bytes).expect("should decode attestations");
+        assert_eq!(
+            attestations.data,
+            block.data.message.body.attestations.to_vec()
         );
     }
+
+    let (status, _) = get(&mut env, "blocks/latest");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get(&mut env, &format!("blocks/{:?}", Hash256::repeat_byte(42)));
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get(&mut env, "blocks/1000000/root");
+    assert_eq!(status, StatusCode::NOT_FOUND);
 }
 
-#[cfg(target_os = "linux")]
 #[test]
-fn get_health() {
+fn get_block_headers_at_forked_slot() {
+    use hyper::{Body, Client, Request, StatusCode};
+
     let mut env = build_env();
 
-    let node = build_node(&mut env, testing_client_config());
+    let spec = &E::default_spec();
+
+    let two_slots_secs = (spec.milliseconds_per_slot / 1_000) * 2;
+
+    let mut config = testing_client_config();
+    config.genesis = ClientGenesis::Interop {
+        validator_count: 8,
+        genesis_time: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+            - two_slots_secs,
+    };
+
+    let node = build_node(&mut env, config);
     let remote_node = node.remote_node().expect("should produce remote node");
+    let socket_addr = node
+        .client
+        .http_listen_addr()
+        .expect("A remote beacon node must have a http server");
 
-    env.runtime()
-        .block_on(remote_node.http.node().get_health())
-        .unwrap();
+    let beacon_chain = node
+        .client
+        .beacon_chain()
+        .expect("client should have beacon chain");
+
+    // Publish two different blocks at slot 1, creating a fork.
+    let slot = Slot::new(1);
+    let fork_roots = [[1; 32], [2; 32]]
+        .iter()
+        .map(|graffiti| {
+            let randao_reveal = get_randao_reveal(beacon_chain.clone(), slot, spec);
+            let block = env
+                .runtime()
+                .block_on(remote_node.http.validator().produce_block(
+                    slot,
+                    randao_reveal,
+                    Some(*graffiti),
+                ))
+                .expect("should fetch block from http api");
+            let signed_block = sign_block(beacon_chain.clone(), block, spec);
+            let block_root = signed_block.canonical_root();
+
+            env.runtime()
+                .block_on(remote_node.http.validator().publish_block(signed_block))
+                .expect("should publish block");
+
+            block_root
+        })
+        .collect::<Vec<_>>();
+    assert_ne!(fork_roots[0], fork_roots[1]);
+
+    let head_root = beacon_chain
+        .head_info()
+        .expect("should get head")
+        .block_root;
+    assert!(fork_roots.contains(&head_root));
+
+    let get_headers = |env: &mut Environment<E>, query: &str| {
+        let request = Request::get(format!(
+            "http://{}/eth/v1/beacon/headers{}",
+            socket_addr, query
+        ))
+        .body(Body::empty())
+        .expect("should build request");
+        let response = env
+            .runtime()
+            .block_on(Client::new().request(request))
+            .expect("should get response");
+        assert_eq!(response.status(), StatusCode::OK);
+        let bytes = env
+            .runtime()
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        serde_json::from_slice::<GenericResponse<Vec<BlockHeaderData>>>(&bytes)
+            .expect("should decode headers")
+            .data
+    };
+
+    let headers = get_headers(&mut env, "?slot=1");
+    assert_eq!(headers.len(), 2, "both forks should be returned");
+    assert_eq!(headers[0].root, head_root, "the canonical block is first");
+    assert!(headers[0].canonical);
+    assert!(fork_roots.contains(&headers[1].root));
+    assert_ne!(headers[1].root, head_root);
+    assert!(!headers[1].canonical);
+    for header in &headers {
+        assert_eq!(header.header.message.slot, slot);
+        assert_eq!(header.header.message.canonical_root(), header.root);
+        assert!(!header.finalized, "blocks after genesis are not finalized");
+    }
+
+    let headers = get_headers(&mut env, "");
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].root, head_root);
+    assert!(headers[0].canonical);
+
+    let headers = get_headers(&mut env, "?slot=0");
+    assert_eq!(headers.len(), 1);
+    assert_eq!(headers[0].root, beacon_chain.genesis_block_root);
+    assert!(headers[0].finalized, "the genesis block is finalized");
+
+    // Headers from a server which predates the `finalized` field still decode.
+    let mut legacy_header = serde_json::to_value(&headers[0]).expect("should encode header");
+    legacy_header
+        .as_object_mut()
+        .expect("header should be an object")
+        .remove("finalized");
+    let legacy_header: BlockHeaderData =
+        serde_json::from_value(legacy_header).expect("should decode header without finalized");
+    assert!(!legacy_header.finalized);
+
+    // Slot 2 has no blocks.
+    assert_eq!(get_headers(&mut env, "?slot=2"), vec![]);
+
+    // Both forks are children of the genesis block.
+    let genesis_query = format!("?parent_root={:?}", beacon_chain.genesis_block_root);
+    let children = get_headers(&mut env, &genesis_query);
+    assert_eq!(children.len(), 2, "both children should be returned");
+    assert_eq!(children[0].root, head_root, "the canonical child is first");
+    assert!(children[0].canonical);
+    assert!(fork_roots.contains(&children[1].root));
+    assert!(!children[1].canonical);
+
+    assert_eq!(
+        get_headers(&mut env, &format!("{}&slot=1", genesis_query)),
+        children
+    );
+    assert_eq!(
+        get_headers(&mut env, &format!("{}&slot=2", genesis_query)),
+        vec![]
+    );
+    assert_eq!(
+        get_headers(&mut env, &format!("?parent_root={:?}", head_root)),
+        vec![],
+        "the head has no children"
+    );
 }
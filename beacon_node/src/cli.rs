@@ -143,7 +143,7 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
             Arg::with_name("http-address")
                 .long("http-address")
                 .value_name("ADDRESS")
-                .help("Set the listen address for the RESTful HTTP API server.")
+                .help("Set the listen address for the RESTful HTTP API server. May be an IPv4 or IPv6 address, or a comma-separated list of addresses to listen on all of them (e.g., 0.0.0.0,::).")
                 .default_value("127.0.0.1")
                 .takes_value(true),
         )
@@ -158,11 +158,169 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
         .arg(
             Arg::with_name("http-allow-origin")
                 .long("http-allow-origin")
-                .value_name("ORIGIN")
-                .help("Set the value of the Access-Control-Allow-Origin response HTTP header.  Use * to allow any origin (not recommended in production)")
+                .value_name("ORIGINS")
+                .help("A comma-separated list of origins (e.g., http://localhost:3000) allowed to make cross-origin requests. Matching origins are echoed in the Access-Control-Allow-Origin response HTTP header. Use * alone to allow any origin (not recommended in production)")
                 .default_value("")
                 .takes_value(true),
         )
+        .arg(
+            Arg::with_name("http-admin-token")
+                .long("http-admin-token")
+                .value_name("TOKEN")
+                .help("Enable the admin HTTP endpoints (e.g., /lighthouse/op_pool/prune) and require this token in an 'Authorization: Bearer TOKEN' header. Disabled by default.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-request-timeout")
+                .long("http-request-timeout")
+                .value_name("SECONDS")
+                .help("The maximum time to spend on a HTTP request before responding with 504, unless a more specific timeout applies. Does not limit streamed responses (e.g., server-sent events). A value of 0 disables the limit.")
+                .default_value("30")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-debug-timeout")
+                .long("http-debug-timeout")
+                .value_name("SECONDS")
+                .help("The maximum time to spend on a request to a debug HTTP endpoint (e.g., /advanced/fork_choice) before responding with 504. A value of 0 disables the limit.")
+                .default_value("30")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-state-timeout")
+                .long("http-state-timeout")
+                .value_name("SECONDS")
+                .help("The maximum time to spend on a HTTP request that reads a state (e.g., /beacon/state) before responding with 504. A value of 0 disables the limit.")
+                .default_value("10")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-disable-compression")
+                .long("http-disable-compression")
+                .help("Disables gzip compression of HTTP responses, which is otherwise used for large JSON, YAML and plain text responses when the client accepts it.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-state-cache-size")
+                .long("http-state-cache-size")
+                .value_name("STATES")
+                .help("The number of recently requested states (other than the head) the HTTP server keeps in memory, up to 256 MiB in total. A value of 0 disables the cache.")
+                .default_value("4")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-max-committee-replay-slots")
+                .long("http-max-committee-replay-slots")
+                .value_name("SLOTS")
+                .help("The maximum number of slots the HTTP server will replay on top of a freezer restore point to compute the committees of a finalized epoch. Requests which would exceed it are rejected with 400 Bad Request.")
+                .default_value("1024")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-max-blocking-tasks")
+                .long("http-max-blocking-tasks")
+                .value_name("COUNT")
+                .help("The maximum number of HTTP requests which may be processed at once on the blocking thread pool. Further requests wait for a free slot, until their timeout expires.")
+                .default_value("32")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-max-request-body-size")
+                .long("http-max-request-body-size")
+                .value_name("BYTES")
+                .help("The maximum size of a HTTP request body. Larger requests are rejected with 413 Payload Too Large.")
+                .default_value("8388608")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-rate-limit")
+                .long("http-rate-limit")
+                .value_name("RATE[,BURST]")
+                .help("Limits each IP address to RATE HTTP requests per second, with bursts of up to BURST requests (default RATE). Requests to /validator endpoints are not counted. Over-limit requests receive 429 Too Many Requests.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-validator-rate-limit")
+                .long("http-validator-rate-limit")
+                .value_name("RATE[,BURST]")
+                .help("Limits each IP address to RATE HTTP requests to /validator endpoints per second, with bursts of up to BURST requests (default RATE). Unlimited by default.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-trusted-proxy-header")
+                .long("http-trusted-proxy-header")
+                .value_name("HEADER")
+                .help("Rate limit HTTP clients by the last IP address in this header (e.g., X-Forwarded-For), as set by a trusted reverse proxy. Only use this if all requests pass through such a proxy.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-slow-request-threshold")
+                .long("http-slow-request-threshold")
+                .value_name("MILLIS")
+                .help("HTTP requests which take longer than this to respond to are logged as a warning. A value of 0 disables these warnings.")
+                .default_value("1000")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-readiness-max-head-distance")
+                .long("http-readiness-max-head-distance")
+                .value_name("SLOTS")
+                .help("The maximum number of slots the head may be behind the current slot for /lighthouse/readiness to report the node as ready.")
+                .default_value("2")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-readiness-min-peers")
+                .long("http-readiness-min-peers")
+                .value_name("COUNT")
+                .help("The minimum number of connected peers for /lighthouse/readiness to report the node as ready.")
+                .default_value("1")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-json-access-log")
+                .long("http-json-access-log")
+                .help("Write an access log entry for every HTTP API request as a line of JSON. Written to stdout unless --http-json-access-log-path is supplied.")
+                .takes_value(false),
+        )
+        .arg(
+            Arg::with_name("http-json-access-log-path")
+                .long("http-json-access-log-path")
+                .value_name("PATH")
+                .help("The file to append the JSON HTTP access log to. Implies --http-json-access-log.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-unix-socket")
+                .long("http-unix-socket")
+                .value_name("PATH")
+                .help("Also serve the HTTP API on a Unix domain socket at this path, which only the current user may connect to. Supply an empty --http-address to only serve on the socket.")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-tls-cert")
+                .long("http-tls-cert")
+                .value_name("PATH")
+                .help("A PEM file containing the TLS certificate chain for the HTTP API. Requires --http-tls-key; the API is then served over HTTPS only.")
+                .requires("http-tls-key")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-tls-key")
+                .long("http-tls-key")
+                .value_name("PATH")
+                .help("A PEM file containing the private key (PKCS#8 or RSA) of --http-tls-cert.")
+                .requires("http-tls-cert")
+                .takes_value(true),
+        )
+        .arg(
+            Arg::with_name("http-max-unfiltered-results")
+                .long("http-max-unfiltered-results")
+                .value_name("COUNT")
+                .help("The maximum number of entries returned by a registry-wide HTTP endpoint (e.g., /beacon/validators/all) without the offset or limit query parameters. Larger responses are rejected with 400. A value of 0 disables the limit.")
+                .default_value("16384")
+                .takes_value(true),
+        )
         /* Websocket related arguments */
         .arg(
             Arg::with_name("ws")
@@ -262,4 +420,16 @@ pub fn cli_app<'a, 'b>() -> App<'a, 'b> {
                 .takes_value(true)
                 .default_value("700")
         )
+        .arg(
+            Arg::with_name("committee-cache-warm-slots")
+                .long("committee-cache-warm-slots")
+                .help(
+                    "Compute the attester shuffling for the next epoch this many slots before \
+                    each epoch boundary, so that committee and duties requests at the start of \
+                    the epoch do not have to wait for it. Set to 0 to disable."
+                )
+                .value_name("NUM_SLOTS")
+                .takes_value(true)
+                .default_value("2")
+        )
 }
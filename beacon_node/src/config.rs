@@ -11,6 +11,7 @@ use std::fs;
 use std::net::{IpAddr, Ipv4Addr, ToSocketAddrs};
 use std::net::{TcpListener, UdpSocket};
 use std::path::PathBuf;
+use std::time::Duration;
 use types::{ChainSpec, EthSpec, GRAFFITI_BYTES_LEN};
 
 pub const BEACON_NODE_DIR: &str = "beacon";
@@ -91,10 +92,17 @@ pub fn get_config<E: EthSpec>(
         client_config.rest_api.enabled = true;
     }
 
-    if let Some(address) = cli_args.value_of("http-address") {
-        client_config.rest_api.listen_address = address
-            .parse::<Ipv4Addr>()
-            .map_err(|_| "http-address is not a valid IPv4 address.")?;
+    if let Some(addresses) = cli_args.value_of("http-address") {
+        client_config.rest_api.listen_addresses = addresses
+            .split(',')
+            .filter(|address| !address.trim().is_empty())
+            .map(|address| {
+                address
+                    .trim()
+                    .parse::<IpAddr>()
+                    .map_err(|_| format!("http-address {:?} is not a valid IP address.", address))
+            })
+            .collect::<Result<_, _>>()?;
     }
 
     if let Some(port) = cli_args.value_of("http-port") {
@@ -104,12 +112,133 @@ pub fn get_config<E: EthSpec>(
     }
 
     if let Some(allow_origin) = cli_args.value_of("http-allow-origin") {
-        // Pre-validate the config value to give feedback to the user on node startup, instead of
-        // as late as when the first API response is produced.
-        hyper::header::HeaderValue::from_str(allow_origin)
-            .map_err(|_| "Invalid allow-origin value")?;
+        let allow_origins = allow_origin
+            .split(',')
+            .map(str::trim)
+            .filter(|origin| !origin.is_empty())
+            .map(String::from)
+            .collect::<Vec<_>>();
+
+        // Pre-validate the config value to give feedback to the user before the rest of the
+        // node starts.
+        rest_api::AllowOrigin::from_config(&allow_origins)?;
+
+        client_config.rest_api.allow_origins = allow_origins;
+    }
+
+    if let Some(admin_token) = cli_args.value_of("http-admin-token") {
+        if admin_token.is_empty() {
+            return Err("http-admin-token must not be empty".into());
+        }
+
+        client_config.rest_api.admin_token = Some(admin_token.to_string());
+    }
+
+    if let Some(timeout) = cli_args.value_of("http-request-timeout") {
+        client_config.rest_api.default_request_timeout = parse_request_timeout(timeout)
+            .map_err(|_| "http-request-timeout is not a valid u64.")?;
+    }
+
+    if let Some(timeout) = cli_args.value_of("http-debug-timeout") {
+        client_config.rest_api.debug_request_timeout =
+            parse_request_timeout(timeout).map_err(|_| "http-debug-timeout is not a valid u64.")?;
+    }
+
+    if let Some(timeout) = cli_args.value_of("http-state-timeout") {
+        client_config.rest_api.state_request_timeout =
+            parse_request_timeout(timeout).map_err(|_| "http-state-timeout is not a valid u64.")?;
+    }
+
+    if cli_args.is_present("http-disable-compression") {
+        client_config.rest_api.compression = false;
+    }
+
+    if let Some(size) = cli_args.value_of("http-state-cache-size") {
+        client_config.rest_api.state_cache_size = size
+            .parse::<usize>()
+            .map_err(|_| "http-state-cache-size is not a valid usize.")?;
+    }
+
+    if let Some(slots) = cli_args.value_of("http-max-committee-replay-slots") {
+        client_config.rest_api.max_committee_replay_slots = slots
+            .parse::<u64>()
+            .map_err(|_| "http-max-committee-replay-slots is not a valid u64.")?;
+    }
 
-        client_config.rest_api.allow_origin = allow_origin.to_string();
+    if let Some(tasks) = cli_args.value_of("http-max-blocking-tasks") {
+        client_config.rest_api.max_blocking_tasks = tasks
+            .parse::<usize>()
+            .map_err(|_| "http-max-blocking-tasks is not a valid usize.")?;
+    }
+
+    if let Some(size) = cli_args.value_of("http-max-request-body-size") {
+        client_config.rest_api.max_request_body_size = size
+            .parse::<u64>()
+            .map_err(|_| "http-max-request-body-size is not a valid u64.")?;
+    }
+
+    if let Some(limit) = cli_args.value_of("http-rate-limit") {
+        client_config.rest_api.rate_limit = Some(parse_rate_limit(limit, "http-rate-limit")?);
+    }
+
+    if let Some(limit) = cli_args.value_of("http-validator-rate-limit") {
+        client_config.rest_api.validator_rate_limit =
+            Some(parse_rate_limit(limit, "http-validator-rate-limit")?);
+    }
+
+    if let Some(header) = cli_args.value_of("http-trusted-proxy-header") {
+        client_config.rest_api.trusted_proxy_header = Some(header.to_string());
+    }
+
+    if let Some(threshold) = cli_args.value_of("http-slow-request-threshold") {
+        client_config.rest_api.slow_request_threshold = Some(
+            threshold
+                .parse::<u64>()
+                .map_err(|_| "http-slow-request-threshold is not a valid u64.")?,
+        )
+        .filter(|millis| *millis > 0)
+        .map(Duration::from_millis);
+    }
+
+    if let Some(distance) = cli_args.value_of("http-readiness-max-head-distance") {
+        client_config.rest_api.readiness_max_head_distance = distance
+            .parse::<u64>()
+            .map_err(|_| "http-readiness-max-head-distance is not a valid u64.")?;
+    }
+
+    if let Some(peers) = cli_args.value_of("http-readiness-min-peers") {
+        client_config.rest_api.readiness_min_peers = peers
+            .parse::<usize>()
+            .map_err(|_| "http-readiness-min-peers is not a valid usize.")?;
+    }
+
+    if let Some(max) = cli_args.value_of("http-max-unfiltered-results") {
+        client_config.rest_api.max_unfiltered_results = Some(
+            max.parse::<usize>()
+                .map_err(|_| "http-max-unfiltered-results is not a valid usize.")?,
+        )
+        .filter(|max| *max > 0);
+    }
+
+    if cli_args.is_present("http-json-access-log") {
+        client_config.rest_api.json_access_log = true;
+    }
+
+    if let Some(path) = cli_args.value_of("http-json-access-log-path") {
+        client_config.rest_api.json_access_log = true;
+        client_config.rest_api.json_access_log_path = Some(PathBuf::from(path));
+    }
+
+    if let Some(path) = cli_args.value_of("http-unix-socket") {
+        client_config.rest_api.listen_unix_socket = Some(PathBuf::from(path));
+    }
+
+    if let Some(path) = cli_args.value_of("http-tls-cert") {
+        client_config.rest_api.tls_cert_path = Some(PathBuf::from(path));
+    }
+
+    if let Some(path) = cli_args.value_of("http-tls-key") {
+        client_config.rest_api.tls_key_path = Some(PathBuf::from(path));
     }
 
     /*
@@ -261,6 +390,12 @@ pub fn get_config<E: EthSpec>(
         };
     }
 
+    if let Some(warm_slots) = cli_args.value_of("committee-cache-warm-slots") {
+        client_config.chain.committee_cache_warm_slots = warm_slots
+            .parse()
+            .map_err(|_| "Invalid committee-cache-warm-slots".to_string())?;
+    }
+
     Ok(client_config)
 }
 
@@ -454,6 +589,33 @@ pub fn get_eth2_testnet_config<E: EthSpec>(
 /// Does not guarantee that the given port is unused after the function exists, just that it was
 /// unused before the function started (i.e., it does not reserve a port).
 ///
+/// Parses a HTTP request timeout in seconds, where `0` means "no timeout".
+/// Parses a rate limit of the form `RATE` or `RATE,BURST`, where the burst defaults to the rate.
+fn parse_rate_limit(limit: &str, flag: &str) -> Result<rest_api::RateLimit, String> {
+    let invalid = || format!("{} must be RATE or RATE,BURST, where both are u32s.", flag);
+
+    let mut parts = limit.split(',').map(|part| part.trim().parse::<u32>());
+    let requests_per_second = parts.next().ok_or_else(invalid)?.map_err(|_| invalid())?;
+    let burst = parts
+        .next()
+        .unwrap_or(Ok(requests_per_second))
+        .map_err(|_| invalid())?;
+    if parts.next().is_some() {
+        return Err(invalid());
+    }
+
+    Ok(rest_api::RateLimit {
+        requests_per_second,
+        burst,
+    })
+}
+
+fn parse_request_timeout(seconds: &str) -> Result<Option<Duration>, std::num::ParseIntError> {
+    seconds
+        .parse::<u64>()
+        .map(|seconds| Some(seconds).filter(|s| *s > 0).map(Duration::from_secs))
+}
+
 /// Used for passing unused ports to libp2 so that lighthouse won't have to update
 /// its own ENR.
 ///
@@ -119,7 +119,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             builder.no_eth1_backend()?
         };
 
-        let (builder, events) = builder
+        let (builder, events, standard_events) = builder
             .system_time_slot_clock()?
             .tee_event_handler(client_config.websocket_server.clone())?;
 
@@ -133,7 +133,7 @@ impl<E: EthSpec> ProductionBeaconNode<E> {
             .notifier()?;
 
         let builder = if client_config.rest_api.enabled {
-            builder.http_server(&client_config, &http_eth2_config, events)?
+            builder.http_server(&client_config, &http_eth2_config, events, standard_events)?
         } else {
             builder
         };
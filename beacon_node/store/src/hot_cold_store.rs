@@ -1,5 +1,6 @@
 use crate::chunked_vector::{
-    store_updated_vector, BlockRoots, HistoricalRoots, RandaoMixes, StateRoots,
+    chunk_key, store_updated_vector, BlockRoots, Chunk, Field, HistoricalRoots, RandaoMixes,
+    StateRoots,
 };
 use crate::config::StoreConfig;
 use crate::forwards_iter::HybridForwardsBlockRootsIterator;
@@ -95,6 +96,17 @@ pub enum HotColdDBError {
         slots_per_epoch: u64,
     },
     RestorePointBlockHashError(BeaconStateError),
+    /// The requested slot is not in the freezer database.
+    SlotNotFrozen {
+        slot: Slot,
+        split_slot: Slot,
+    },
+    /// Reconstructing the requested slot requires replaying more slots than permitted.
+    ReplayLimitExceeded {
+        slot: Slot,
+        replay_slots: u64,
+        max_replay_slots: u64,
+    },
 }
 
 impl<E: EthSpec> HotColdDB<E, MemoryStore<E>, MemoryStore<E>> {
@@ -560,6 +572,89 @@ impl<E: EthSpec, Hot: ItemStore<E>, Cold: ItemStore<E>> HotColdDB<E, Hot, Cold>
         }
     }
 
+    /// Load the root of the canonical block at `slot` from the freezer's `block_roots` table.
+    ///
+    /// As in `state.block_roots`, skipped slots have the root of the most recent prior block.
+    ///
+    /// Return `None` if `slot` is not prior to the latest restore point, as the table only
+    /// covers slots up to the latest restore point.
+    pub fn load_cold_block_root(&self, slot: Slot) -> Result<Option<Hash256>, Error> {
+        self.load_cold_root::<BlockRoots>(slot)
+    }
+
+    /// Load the root of the canonical state at `slot` from the freezer's `state_roots` table.
+    ///
+    /// Return `None` if `slot` is not prior to the latest restore point, as the table only
+    /// covers slots up to the latest restore point.
+    pub fn load_cold_state_root(&self, slot: Slot) -> Result<Option<Hash256>, Error> {
+        self.load_cold_root::<StateRoots>(slot)
+    }
+
+    /// Load the value for `slot` from the freezer table for the per-slot roots field `F`.
+    fn load_cold_root<F: Field<E, Value = Hash256>>(
+        &self,
+        slot: Slot,
+    ) -> Result<Option<Hash256>, Error> {
+        let (_, end_vindex) =
+            F::start_and_end_vindex(self.get_latest_restore_point_slot(), &self.spec);
+        let vindex = slot.as_usize();
+        if vindex >= end_vindex {
+            return Ok(None);
+        }
+
+        let chunk_size = F::chunk_size();
+        let chunk = Chunk::<Hash256>::load(
+            &self.cold_db,
+            F::column(),
+            &chunk_key((vindex / chunk_size) as u64),
+        )?;
+
+        Ok(chunk.and_then(|chunk| chunk.values.get(vindex % chunk_size).copied()))
+    }
+
+    /// Load the balances of the pre-finalization state at `slot` from the freezer database.
+    ///
+    /// The state is reconstructed from the nearest restore point below `slot`, and only its
+    /// balances are returned (without cloning them or the rest of the state). To bound the cost
+    /// of reconstruction, an error is returned if more than `max_replay_slots` slots would need to
+    /// be replayed on top of the restore point.
+    pub fn load_cold_balances_by_slot(
+        &self,
+        slot: Slot,
+        max_replay_slots: u64,
+    ) -> Result<Vec<u64>, Error> {
+        let state = self.load_cold_state_by_slot_with_limit(slot, max_replay_slots)?;
+
+        Ok(state.balances.into())
+    }
+
+    /// Load the pre-finalization state at `slot` from the freezer database.
+    ///
+    /// As `load_cold_state_by_slot`, except that an error is returned if more than
+    /// `max_replay_slots` slots would need to be replayed on top of the nearest restore point.
+    pub fn load_cold_state_by_slot_with_limit(
+        &self,
+        slot: Slot,
+        max_replay_slots: u64,
+    ) -> Result<BeaconState<E>, Error> {
+        let split_slot = self.get_split_slot();
+        if slot >= split_slot {
+            return Err(HotColdDBError::SlotNotFrozen { slot, split_slot }.into());
+        }
+
+        let replay_slots = slot.as_u64() % self.config.slots_per_restore_point;
+        if replay_slots > max_replay_slots {
+            return Err(HotColdDBError::ReplayLimitExceeded {
+                slot,
+                replay_slots,
+                max_replay_slots,
+            }
+            .into());
+        }
+
+        self.load_cold_state_by_slot(slot)
+    }
+
     /// Load a restore point state by its `state_root`.
     fn load_restore_point(&self, state_root: &Hash256) -> Result<BeaconState<E>, Error> {
         let mut partial_state: PartialBeaconState<E> = self
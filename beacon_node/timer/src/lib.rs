@@ -4,7 +4,7 @@
 
 use beacon_chain::{BeaconChain, BeaconChainTypes};
 use futures::stream::StreamExt;
-use slog::info;
+use slog::{info, warn};
 use slot_clock::SlotClock;
 use std::sync::Arc;
 use std::time::Duration;
@@ -25,9 +25,27 @@ pub fn spawn_timer<T: BeaconChainTypes>(
 
     // Warning: `interval_at` panics if `milliseconds_per_slot` = 0.
     let mut interval = interval_at(start_instant, Duration::from_millis(milliseconds_per_slot));
+    let inner_executor = executor.clone();
     let timer_future = async move {
         while interval.next().await.is_some() {
             beacon_chain.per_slot_task();
+
+            // Computing shufflings is expensive, so it is done on a blocking thread rather than
+            // delaying the timer.
+            let chain = beacon_chain.clone();
+            let log = inner_executor.log().clone();
+            inner_executor.spawn_blocking(
+                move || {
+                    if let Err(e) = chain.warm_committee_cache() {
+                        warn!(
+                            log,
+                            "Failed to warm committee cache";
+                            "error" => format!("{:?}", e)
+                        );
+                    }
+                },
+                "committee_cache_warming",
+            );
         }
     };
 
@@ -4,16 +4,15 @@
 use eth2_keystore::Keystore;
 use eth2_wallet::Wallet;
 use rand::{distributions::Alphanumeric, Rng};
-use serde_derive::{Deserialize, Serialize};
 use std::fs::{self, File};
 use std::io;
 use std::io::prelude::*;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
-use zeroize::Zeroize;
 
 pub mod validator_definitions;
 
+pub use bls::ZeroizeString;
 pub use eth2_keystore;
 pub use eth2_wallet::PlainText;
 
@@ -124,26 +123,6 @@ pub fn read_mnemonic_from_user(use_stdin: bool) -> Result<String, String> {
     Ok(input)
 }
 
-/// Provides a new-type wrapper around `String` that is zeroized on `Drop`.
-///
-/// Useful for ensuring that password memory is zeroed-out on drop.
-#[derive(Clone, PartialEq, Serialize, Deserialize, Zeroize)]
-#[zeroize(drop)]
-#[serde(transparent)]
-pub struct ZeroizeString(String);
-
-impl From<String> for ZeroizeString {
-    fn from(s: String) -> Self {
-        Self(s)
-    }
-}
-
-impl AsRef<[u8]> for ZeroizeString {
-    fn as_ref(&self) -> &[u8] {
-        self.0.as_bytes()
-    }
-}
-
 #[cfg(test)]
 mod test {
     use super::strip_off_newlines;
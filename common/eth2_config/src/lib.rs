@@ -9,6 +9,8 @@ use types::ChainSpec;
 pub struct Eth2Config {
     pub spec_constants: String,
     pub spec: ChainSpec,
+    /// The name of the testnet, if one is in use.
+    pub config_name: Option<String>,
 }
 
 impl Default for Eth2Config {
@@ -16,15 +18,23 @@ impl Default for Eth2Config {
         Self {
             spec_constants: "minimal".to_string(),
             spec: ChainSpec::minimal(),
+            config_name: None,
         }
     }
 }
 
 impl Eth2Config {
+    /// Returns the name of the testnet or, if no testnet is in use, the spec constants (e.g.,
+    /// `mainnet`).
+    pub fn config_name(&self) -> &str {
+        self.config_name.as_deref().unwrap_or(&self.spec_constants)
+    }
+
     pub fn mainnet() -> Self {
         Self {
             spec_constants: "mainnet".to_string(),
             spec: ChainSpec::mainnet(),
+            config_name: None,
         }
     }
 
@@ -32,6 +42,7 @@ impl Eth2Config {
         Self {
             spec_constants: "minimal".to_string(),
             spec: ChainSpec::minimal(),
+            config_name: None,
         }
     }
 
@@ -39,6 +50,7 @@ impl Eth2Config {
         Self {
             spec_constants: "interop".to_string(),
             spec: ChainSpec::interop(),
+            config_name: None,
         }
     }
 }
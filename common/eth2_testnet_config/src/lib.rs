@@ -14,7 +14,7 @@ use ssz::{Decode, Encode};
 use std::fs::{create_dir_all, File};
 use std::io::{Read, Write};
 use std::path::PathBuf;
-use types::{Address, BeaconState, EthSpec, YamlConfig};
+use types::{Address, BeaconState, EthSpec, YamlConfig, CUSTOM_CONFIG_NAME};
 
 pub const ADDRESS_FILE: &str = "deposit_contract.txt";
 pub const DEPLOY_BLOCK_FILE: &str = "deploy_block.txt";
@@ -86,6 +86,13 @@ impl<E: EthSpec> Eth2TestnetConfig<E> {
 
     /// Instantiates `Self` from a `HardcodedNet`.
     fn from_hardcoded_net(net: &HardcodedNet) -> Result<Self, String> {
+        let mut yaml_config: YamlConfig = serde_yaml::from_reader(net.yaml_config)
+            .map_err(|e| format!("Unable to parse yaml config: {:?}", e))?;
+        // The config files of some hardcoded nets predate `CONFIG_NAME`.
+        if yaml_config.config_name == CUSTOM_CONFIG_NAME {
+            yaml_config.config_name = net.name.to_string();
+        }
+
         let genesis_state = if net.genesis_state.is_empty() {
             None
         } else {
@@ -105,10 +112,7 @@ impl<E: EthSpec> Eth2TestnetConfig<E> {
                     .map_err(|e| format!("Unable to parse boot enr: {:?}", e))?,
             ),
             genesis_state,
-            yaml_config: Some(
-                serde_yaml::from_reader(net.yaml_config)
-                    .map_err(|e| format!("Unable to parse yaml config: {:?}", e))?,
-            ),
+            yaml_config: Some(yaml_config),
         })
     }
 
@@ -0,0 +1,269 @@
+//! Provides a `FallbackBeaconNode`, which sends requests to the first of several beacon nodes
+//! that is able to serve them.
+
+use crate::{Error, RemoteBeaconNode, Timeouts};
+use futures::future::join_all;
+use parking_lot::Mutex;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use types::EthSpec;
+use url::Url;
+
+/// The default period for which a node that failed a request is tried after all others.
+pub const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Returned when no beacon node was able to serve a request.
+#[derive(Debug)]
+pub struct FallbackError {
+    /// The error from each node that was tried, in the order they were tried.
+    pub errors: Vec<(Url, Error)>,
+}
+
+/// Sends requests to an ordered list of beacon nodes, falling back to the next node if a request
+/// fails.
+///
+/// A node which fails a request is moved behind all other nodes until `cooldown` has passed
+/// since the failure.
+#[derive(Clone)]
+pub struct FallbackBeaconNode<E: EthSpec> {
+    nodes: Vec<RemoteBeaconNode<E>>,
+    last_failures: Arc<Mutex<Vec<Option<Instant>>>>,
+    cooldown: Duration,
+}
+
+impl<E: EthSpec> FallbackBeaconNode<E> {
+    /// Creates a client for `nodes`, which are tried in the given order.
+    pub fn new(nodes: Vec<RemoteBeaconNode<E>>) -> Self {
+        Self {
+            last_failures: Arc::new(Mutex::new(vec![None; nodes.len()])),
+            nodes,
+            cooldown: DEFAULT_COOLDOWN,
+        }
+    }
+
+    /// Creates a client for the nodes at `urls`, which are tried in the given order.
+    pub fn from_urls(urls: Vec<String>, timeouts: Timeouts) -> Result<Self, String> {
+        let nodes = urls
+            .into_iter()
+            .map(|url| RemoteBeaconNode::new_with_timeouts(url, timeouts))
+            .collect::<Result<_, _>>()?;
+        Ok(Self::new(nodes))
+    }
+
+    /// Sets the period for which a node that failed a request is tried after all others.
+    pub fn with_cooldown(mut self, cooldown: Duration) -> Self {
+        self.cooldown = cooldown;
+        self
+    }
+
+    /// Returns the indices of `self.nodes`, with the nodes that have failed within the cooldown
+    /// period moved to the back.
+    fn ordered_indices(&self) -> (Vec<usize>, Vec<usize>) {
+        let last_failures = self.last_failures.lock();
+        (0..self.nodes.len()).partition(|&i| {
+            last_failures[i].map_or(true, |failure| failure.elapsed() >= self.cooldown)
+        })
+    }
+
+    fn record_result<T>(&self, i: usize, result: &Result<T, Error>) {
+        self.last_failures.lock()[i] = match result {
+            Ok(_) => None,
+            Err(_) => Some(Instant::now()),
+        };
+    }
+
+    /// Calls `func` on each node in turn until it succeeds, returning the result along with the
+    /// URL of the node that served it.
+    pub async fn first_success<F, R, T>(&self, func: F) -> Result<(Url, T), FallbackError>
+    where
+        F: Fn(RemoteBeaconNode<E>) -> R,
+        R: Future<Output = Result<T, Error>>,
+    {
+        let (healthy, cooling) = self.ordered_indices();
+        let mut errors = vec![];
+
+        for i in healthy.into_iter().chain(cooling) {
+            let node = &self.nodes[i];
+            let result = func(node.clone()).await;
+            self.record_result(i, &result);
+
+            match result {
+                Ok(value) => return Ok((node.http.server_url().clone(), value)),
+                Err(e) => errors.push((node.http.server_url().clone(), e)),
+            }
+        }
+
+        Err(FallbackError { errors })
+    }
+
+    /// Calls `func` on all healthy nodes concurrently (or all nodes, if none are healthy), e.g.,
+    /// to publish an object as widely as possible.
+    ///
+    /// Returns the successful results along with the URLs of the nodes that served them, or an
+    /// error if no node succeeded.
+    pub async fn broadcast<F, R, T>(&self, func: F) -> Result<Vec<(Url, T)>, FallbackError>
+    where
+        F: Fn(RemoteBeaconNode<E>) -> R,
+        R: Future<Output = Result<T, Error>>,
+    {
+        let (healthy, cooling) = self.ordered_indices();
+        let indices = if healthy.is_empty() { cooling } else { healthy };
+
+        let results = join_all(indices.iter().map(|&i| func(self.nodes[i].clone()))).await;
+
+        let mut successes = vec![];
+        let mut errors = vec![];
+        for (i, result) in indices.into_iter().zip(results) {
+            self.record_result(i, &result);
+
+            let url = self.nodes[i].http.server_url().clone();
+            match result {
+                Ok(value) => successes.push((url, value)),
+                Err(e) => errors.push((url, e)),
+            }
+        }
+
+        if successes.is_empty() {
+            Err(FallbackError { errors })
+        } else {
+            Ok(successes)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Server};
+    use std::convert::Infallible;
+    use std::net::SocketAddr;
+    use tokio::sync::oneshot;
+    use tokio::task::JoinHandle;
+    use types::MinimalEthSpec;
+
+    /// A server which responds to every request with `version`, until it is killed.
+    struct StubServer {
+        addr: SocketAddr,
+        shutdown: oneshot::Sender<()>,
+        handle: JoinHandle<Result<(), hyper::Error>>,
+    }
+
+    impl StubServer {
+        fn spawn(version: &'static str) -> Self {
+            let make_service = make_service_fn(move |_| async move {
+                Ok::<_, Infallible>(service_fn(move |_: Request<Body>| async move {
+                    let body = serde_json::to_string(version).unwrap();
+                    Ok::<_, Infallible>(hyper::Response::new(Body::from(body)))
+                }))
+            });
+            let (shutdown, shutdown_rx) = oneshot::channel();
+            let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+            let addr = server.local_addr();
+            let handle = tokio::spawn(server.with_graceful_shutdown(async {
+                shutdown_rx.await.ok();
+            }));
+
+            Self {
+                addr,
+                shutdown,
+                handle,
+            }
+        }
+
+        fn url(&self) -> String {
+            format!("http://{}/", self.addr)
+        }
+
+        async fn kill(self) {
+            self.shutdown.send(()).unwrap();
+            self.handle.await.unwrap().unwrap();
+        }
+    }
+
+    fn fallback(servers: &[&StubServer]) -> FallbackBeaconNode<MinimalEthSpec> {
+        FallbackBeaconNode::from_urls(
+            servers.iter().map(|server| server.url()).collect(),
+            Timeouts::set_all(Duration::from_secs(5)),
+        )
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn falls_back_when_primary_is_killed() {
+        let primary = StubServer::spawn("primary");
+        let secondary = StubServer::spawn("secondary");
+        let (primary_url, secondary_url) = (primary.url(), secondary.url());
+        let client = fallback(&[&primary, &secondary]);
+
+        let (url, version) = client
+            .first_success(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap();
+        assert_eq!(url.as_str(), primary_url);
+        assert_eq!(version, "primary");
+
+        primary.kill().await;
+
+        let (url, version) = client
+            .first_success(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap();
+        assert_eq!(url.as_str(), secondary_url);
+        assert_eq!(version, "secondary");
+
+        // The primary is cooling down, so the secondary is tried first.
+        assert_eq!(client.ordered_indices(), (vec![1], vec![0]));
+
+        secondary.kill().await;
+
+        let error = client
+            .first_success(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap_err();
+        let urls = error
+            .errors
+            .iter()
+            .map(|(url, _)| url.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(urls, vec![secondary_url, primary_url]);
+    }
+
+    #[tokio::test]
+    async fn broadcast_to_healthy_nodes() {
+        let first = StubServer::spawn("first");
+        let second = StubServer::spawn("second");
+        let client = fallback(&[&first, &second]);
+
+        let mut versions = client
+            .broadcast(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|(_, version)| version)
+            .collect::<Vec<_>>();
+        versions.sort();
+        assert_eq!(versions, vec!["first", "second"]);
+
+        first.kill().await;
+
+        // The broadcast succeeds as long as one node succeeds.
+        let results = client
+            .broadcast(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+
+        // The failed node is now cooling down, so it is excluded from the next broadcast.
+        assert_eq!(client.ordered_indices(), (vec![1], vec![0]));
+        let results = client
+            .broadcast(|node| async move { node.http.node().get_version().await })
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].1, "second");
+
+        second.kill().await;
+    }
+}
@@ -4,24 +4,37 @@
 //! Presently, this is only used for testing but it _could_ become a user-facing library.
 
 use eth2_config::Eth2Config;
-use reqwest::{Client, ClientBuilder, Response, StatusCode};
+use futures::io::{AsyncWrite, AsyncWriteExt};
+use reqwest::{
+    header::{ACCEPT, CONTENT_TYPE},
+    Client, ClientBuilder, RequestBuilder, Response, StatusCode,
+};
+use rest_types::OCTET_STREAM_CONTENT_TYPE;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use ssz::Encode;
+use ssz::{Decode, Encode};
+use std::fmt;
 use std::marker::PhantomData;
 use std::time::Duration;
 use types::{
     Attestation, AttestationData, AttesterSlashing, BeaconBlock, BeaconState, CommitteeIndex,
     Epoch, EthSpec, Fork, Graffiti, Hash256, ProposerSlashing, PublicKey, PublicKeyBytes,
-    Signature, SignedAggregateAndProof, SignedBeaconBlock, Slot, SubnetId,
+    Signature, SignedAggregateAndProof, SignedBeaconBlock, SignedVoluntaryExit, Slot, SubnetId,
 };
 use url::Url;
 
+mod fallback;
+
+pub use fallback::{FallbackBeaconNode, FallbackError, DEFAULT_COOLDOWN};
 pub use operation_pool::PersistedOperationPool;
 pub use proto_array::core::ProtoArray;
 pub use rest_types::{
-    CanonicalHeadResponse, Committee, HeadBeaconBlock, Health, IndividualVotesRequest,
-    IndividualVotesResponse, SyncingResponse, ValidatorDutiesRequest, ValidatorDutyBytes,
-    ValidatorRequest, ValidatorResponse, ValidatorSubscription,
+    AggregateAndProofStatus, ApiIndex, ApiRoute, AttesterData, BeaconCommitteeSubscription,
+    BlockBodySummary, BlockId, CanonicalHeadResponse, Committee, ErrorMessage,
+    Eth1DepositCacheSnapshot, Eth1DepositLog, Failure, GenericResponse, HeadBeaconBlock, Health,
+    IndexedErrorMessage, IndividualVotesRequest, IndividualVotesResponse, OpPoolPruneResponse,
+    OpPoolStats, PublishOperationResponse, Readiness, SlotAttestationCount, SyncingResponse,
+    ValidatorDutiesRequest, ValidatorDutyBytes, ValidatorField, ValidatorIndices, ValidatorRequest,
+    ValidatorResponse, ValidatorSubscription,
 };
 
 // Setting a long timeout for debug ensures that crypto-heavy operations can still succeed.
@@ -44,23 +57,123 @@ impl<E: EthSpec> RemoteBeaconNode<E> {
     }
 
     pub fn new_with_timeout(http_endpoint: String, timeout: Duration) -> Result<Self, String> {
+        Self::new_with_timeouts(http_endpoint, Timeouts::set_all(timeout))
+    }
+
+    /// Uses a separate timeout for each `RequestCategory`.
+    pub fn new_with_timeouts(http_endpoint: String, timeouts: Timeouts) -> Result<Self, String> {
         Ok(Self {
-            http: HttpClient::new(http_endpoint, timeout)
+            http: HttpClient::new_with_timeouts(http_endpoint, timeouts)
                 .map_err(|e| format!("Unable to create http client: {:?}", e))?,
         })
     }
 }
 
+/// The latency budget of a request, which determines the timeout applied to it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RequestCategory {
+    /// Requests which must complete in time for an attestation or aggregate to be published.
+    Attestation,
+    /// Requests which must complete in time for a block to be published.
+    Proposal,
+    /// Requests which may return large responses, e.g., states or the full validator set.
+    Bulk,
+    /// All other requests.
+    Default,
+}
+
+/// The timeout applied to each `RequestCategory`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Timeouts {
+    pub attestation: Duration,
+    pub proposal: Duration,
+    pub bulk: Duration,
+    pub default: Duration,
+}
+
+impl Timeouts {
+    /// Use the same `timeout` for all categories.
+    pub fn set_all(timeout: Duration) -> Self {
+        Self {
+            attestation: timeout,
+            proposal: timeout,
+            bulk: timeout,
+            default: timeout,
+        }
+    }
+
+    /// Returns the timeout for requests in `category`.
+    pub fn get(&self, category: RequestCategory) -> Duration {
+        match category {
+            RequestCategory::Attestation => self.attestation,
+            RequestCategory::Proposal => self.proposal,
+            RequestCategory::Bulk => self.bulk,
+            RequestCategory::Default => self.default,
+        }
+    }
+}
+
+/// Credentials sent in the `Authorization` header of requests to the node.
+///
+/// The `Debug` implementation redacts all secrets.
+#[derive(Clone, PartialEq)]
+pub enum Auth {
+    /// A bearer token, e.g., the admin token of a Lighthouse node.
+    Bearer(String),
+    /// HTTP basic authentication.
+    Basic {
+        username: String,
+        password: Option<String>,
+    },
+}
+
+impl Auth {
+    /// Adds the `Authorization` header to `request`.
+    fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            Auth::Bearer(token) => request.bearer_auth(token),
+            Auth::Basic { username, password } => request.basic_auth(username, password.as_ref()),
+        }
+    }
+}
+
+impl fmt::Debug for Auth {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Auth::Bearer(_) => write!(f, "Bearer(<redacted>)"),
+            Auth::Basic { username, .. } => write!(f, "Basic({}:<redacted>)", username),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     /// Unable to parse a URL. Check the server URL.
     UrlParseError(url::ParseError),
     /// The `reqwest` library returned an error.
     ReqwestError(reqwest::Error),
+    /// The request did not complete within the timeout for its category.
+    Timeout(RequestCategory),
+    /// The server responded with an unexpected `Content-Type`.
+    UnexpectedContentType(String),
+    /// A response was being streamed to a writer but the stream failed part-way. Any bytes
+    /// already written are left in place.
+    StreamInterrupted { bytes_written: u64, reason: String },
     /// There was an error when encoding/decoding an object using serde.
     SerdeJsonError(serde_json::Error),
+    /// There was an error when decoding an SSZ response.
+    SszDecodeError(ssz::DecodeError),
+    /// The server responded with `401 Unauthorized`, i.e., the request carried missing or invalid
+    /// credentials. Contains the body of the response.
+    Unauthorized(String),
     /// The server responded to the request, however it did not return a 200-type success code.
     DidNotSucceed { status: StatusCode, body: String },
+    /// The server did not return a 200-type success code and described the error with an
+    /// `ErrorMessage`.
+    ServerMessage(ErrorMessage),
+    /// The server did not return a 200-type success code and described which objects in the
+    /// request failed with an `IndexedErrorMessage`.
+    IndexedServerMessage(IndexedErrorMessage),
     /// The request input was invalid.
     InvalidInput,
 }
@@ -69,24 +182,81 @@ pub enum Error {
 pub struct HttpClient<E> {
     client: Client,
     url: Url,
-    timeout: Duration,
+    timeouts: Timeouts,
+    get_retries: usize,
+    auth: Option<Auth>,
     _phantom: PhantomData<E>,
 }
 
+impl<E> fmt::Debug for HttpClient<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("HttpClient")
+            .field("url", &self.url.as_str())
+            .field("timeouts", &self.timeouts)
+            .field("get_retries", &self.get_retries)
+            .field("auth", &self.auth)
+            .finish()
+    }
+}
+
 impl<E: EthSpec> HttpClient<E> {
-    /// Creates a new instance (without connecting to the node).
+    /// Creates a new instance (without connecting to the node), applying `timeout` to all
+    /// requests.
     pub fn new(server_url: String, timeout: Duration) -> Result<Self, Error> {
+        Self::new_with_timeouts(server_url, Timeouts::set_all(timeout))
+    }
+
+    /// Creates a new instance (without connecting to the node), applying a timeout to each
+    /// request based upon its `RequestCategory`.
+    pub fn new_with_timeouts(server_url: String, timeouts: Timeouts) -> Result<Self, Error> {
         Ok(Self {
             client: ClientBuilder::new()
-                .timeout(timeout)
                 .build()
                 .expect("should build from static configuration"),
             url: Url::parse(&server_url)?,
-            timeout: Duration::from_secs(15),
+            timeouts,
+            get_retries: 0,
+            auth: None,
             _phantom: PhantomData,
         })
     }
 
+    /// Sends `auth` with every request.
+    pub fn with_auth(mut self, auth: Auth) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sends `token` as a bearer token with every request.
+    pub fn with_bearer_token(self, token: String) -> Self {
+        self.with_auth(Auth::Bearer(token))
+    }
+
+    /// Sends `username` and `password` using HTTP basic authentication with every request.
+    pub fn with_basic_auth(self, username: String, password: Option<String>) -> Self {
+        self.with_auth(Auth::Basic { username, password })
+    }
+
+    /// Returns a copy of this client which sends `auth` instead of the configured credentials,
+    /// or no credentials if `auth` is `None`.
+    ///
+    /// Useful for mixing authenticated and anonymous requests, e.g.,
+    /// `client.with_request_auth(None).node().get_version()`.
+    pub fn with_request_auth(&self, auth: Option<Auth>) -> Self {
+        Self {
+            auth,
+            ..self.clone()
+        }
+    }
+
+    /// Retry `GET` requests up to `retries` times if they fail to connect to the server.
+    ///
+    /// Other requests are never retried, since they may not be idempotent.
+    pub fn with_get_retries(mut self, retries: usize) -> Self {
+        self.get_retries = retries;
+        self
+    }
+
     pub fn beacon(&self) -> Beacon<E> {
         Beacon(self.clone())
     }
@@ -111,21 +281,65 @@ impl<E: EthSpec> HttpClient<E> {
         Consensus(self.clone())
     }
 
+    pub fn lighthouse(&self) -> Lighthouse<E> {
+        Lighthouse(self.clone())
+    }
+
+    /// Returns the base URL of the server.
+    pub fn server_url(&self) -> &Url {
+        &self.url
+    }
+
     fn url(&self, path: &str) -> Result<Url, Error> {
         self.url.join(path).map_err(|e| e.into())
     }
 
     pub async fn json_post<T: Serialize>(&self, url: Url, body: T) -> Result<Response, Error> {
-        self.client
-            .post(&url.to_string())
-            .json(&body)
-            .send()
+        self.json_post_with_category(RequestCategory::Default, url, body)
             .await
-            .map_err(Error::from)
+    }
+
+    /// Posts `body` to `url`, applying the timeout for `category`.
+    pub async fn json_post_with_category<T: Serialize>(
+        &self,
+        category: RequestCategory,
+        url: Url,
+        body: T,
+    ) -> Result<Response, Error> {
+        self.send(category, self.client.post(&url.to_string()).json(&body))
+            .await
+    }
+
+    /// Posts the SSZ encoding of `body` to `url` as `application/octet-stream`, applying the
+    /// timeout for `category`.
+    pub async fn ssz_post_with_category<T: Encode>(
+        &self,
+        category: RequestCategory,
+        url: Url,
+        body: &T,
+    ) -> Result<Response, Error> {
+        let request = self
+            .client
+            .post(&url.to_string())
+            .header(CONTENT_TYPE, OCTET_STREAM_CONTENT_TYPE)
+            .body(body.as_ssz_bytes());
+        self.send(category, request).await
     }
 
     pub async fn json_get<T: DeserializeOwned>(
         &self,
+        url: Url,
+        query_pairs: Vec<(String, String)>,
+    ) -> Result<T, Error> {
+        self.json_get_with_category(RequestCategory::Default, url, query_pairs)
+            .await
+    }
+
+    /// Gets `url` with the given query parameters, applying the timeout for `category` and
+    /// retrying connection failures as configured by `with_get_retries`.
+    pub async fn json_get_with_category<T: DeserializeOwned>(
+        &self,
+        category: RequestCategory,
         mut url: Url,
         query_pairs: Vec<(String, String)>,
     ) -> Result<T, Error> {
@@ -133,15 +347,119 @@ impl<E: EthSpec> HttpClient<E> {
             url.query_pairs_mut().append_pair(&key, &param);
         });
 
-        let response = self
+        let mut retries = self.get_retries;
+        let response = loop {
+            match self.send(category, self.client.get(&url.to_string())).await {
+                Err(Error::ReqwestError(e)) if e.is_connect() && retries > 0 => retries -= 1,
+                result => break result?,
+            }
+        };
+
+        let success = error_for_status(response).await?;
+        success
+            .json::<T>()
+            .await
+            .map_err(|e| Error::from_reqwest(e, category))
+    }
+
+    /// Gets `url` with the given query parameters as `application/octet-stream`, decoding the SSZ
+    /// bytes of the response.
+    pub async fn ssz_get_with_category<T: Decode>(
+        &self,
+        category: RequestCategory,
+        mut url: Url,
+        query_pairs: Vec<(String, String)>,
+    ) -> Result<T, Error> {
+        query_pairs.into_iter().for_each(|(key, param)| {
+            url.query_pairs_mut().append_pair(&key, &param);
+        });
+
+        let request = self
             .client
             .get(&url.to_string())
+            .header(ACCEPT, OCTET_STREAM_CONTENT_TYPE);
+        let response = error_for_status(self.send(category, request).await?).await?;
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if content_type != OCTET_STREAM_CONTENT_TYPE {
+            return Err(Error::UnexpectedContentType(content_type.to_string()));
+        }
+
+        let bytes = response
+            .bytes()
+            .await
+            .map_err(|e| Error::from_reqwest(e, category))?;
+        Ok(T::from_ssz_bytes(&bytes)?)
+    }
+
+    /// Sends `request` with the configured credentials and the timeout for `category`.
+    async fn send(
+        &self,
+        category: RequestCategory,
+        request: RequestBuilder,
+    ) -> Result<Response, Error> {
+        let request = match &self.auth {
+            Some(auth) => auth.apply(request),
+            None => request,
+        };
+
+        request
+            .timeout(self.timeouts.get(category))
             .send()
             .await
-            .map_err(Error::from)?;
+            .map_err(|e| Error::from_reqwest(e, category))
+    }
+}
 
-        let success = error_for_status(response).await.map_err(Error::from)?;
-        success.json::<T>().await.map_err(Error::from)
+impl Error {
+    /// Converts a `reqwest` error from a request in `category`, distinguishing timeouts.
+    fn from_reqwest(e: reqwest::Error, category: RequestCategory) -> Self {
+        if e.is_timeout() {
+            Error::Timeout(category)
+        } else {
+            Error::ReqwestError(e)
+        }
+    }
+
+    /// Returns the error message from the server, if the server responded.
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Error::Unauthorized(body) => Some(body),
+            Error::DidNotSucceed { body, .. } => Some(body),
+            Error::ServerMessage(msg) => Some(&msg.message),
+            Error::IndexedServerMessage(msg) => Some(&msg.message),
+            _ => None,
+        }
+    }
+
+    /// Returns the indices of the objects in the request which the server failed to process.
+    ///
+    /// Empty unless the server responded with an `IndexedErrorMessage`.
+    pub fn failed_indices(&self) -> Vec<u64> {
+        match self {
+            Error::IndexedServerMessage(msg) => {
+                msg.failures.iter().map(|failure| failure.index).collect()
+            }
+            _ => vec![],
+        }
+    }
+}
+
+/// Interprets the `response` to publishing a block.
+async fn block_publish_status(response: Response) -> Result<PublishStatus, Error> {
+    match response.status() {
+        StatusCode::OK => Ok(PublishStatus::Valid),
+        StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
+            response.text().await.map_err(Error::from)?,
+        )),
+        _ => response
+            .error_for_status()
+            .map_err(Error::from)
+            .map(|_| PublishStatus::Unknown),
     }
 }
 
@@ -149,18 +467,47 @@ impl<E: EthSpec> HttpClient<E> {
 ///
 /// Distinct from `Response::error_for_status` because it includes the body of the response as
 /// text. This ensures the error message from the server is not discarded.
+///
+/// A JSON body is parsed as an `IndexedErrorMessage` or `ErrorMessage`, if it matches either.
 async fn error_for_status(response: Response) -> Result<Response, Error> {
     let status = response.status();
 
     if status.is_success() {
         Ok(response)
     } else {
+        let is_json = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .map_or(false, |content_type| {
+                content_type.starts_with("application/json")
+            });
         let text_result = response.text().await;
         match text_result {
             Err(e) => Err(Error::ReqwestError(e)),
-            Ok(body) => Err(Error::DidNotSucceed { status, body }),
+            Ok(body) => Err(parse_error_body(status, body, is_json)),
+        }
+    }
+}
+
+/// Converts the body of an unsuccessful response into an `Error`.
+///
+/// A `401 Unauthorized` response is always returned as `Error::Unauthorized`. Otherwise, an
+/// `IndexedErrorMessage` is tried first, since any such message is also a valid `ErrorMessage`.
+fn parse_error_body(status: StatusCode, body: String, is_json: bool) -> Error {
+    if status == StatusCode::UNAUTHORIZED {
+        return Error::Unauthorized(body);
+    }
+
+    if is_json {
+        if let Ok(msg) = serde_json::from_str::<IndexedErrorMessage>(&body) {
+            return Error::IndexedServerMessage(msg);
+        }
+        if let Ok(msg) = serde_json::from_str::<ErrorMessage>(&body) {
+            return Error::ServerMessage(msg);
         }
     }
+    Error::DidNotSucceed { status, body }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -206,7 +553,9 @@ impl<E: EthSpec> Validator<E> {
 
         let client = self.0.clone();
         let url = self.url("attestation")?;
-        client.json_get(url, query_params).await
+        client
+            .json_get_with_category(RequestCategory::Attestation, url, query_params)
+            .await
     }
 
     /// Produces an aggregate attestation.
@@ -221,7 +570,9 @@ impl<E: EthSpec> Validator<E> {
 
         let client = self.0.clone();
         let url = self.url("aggregate_attestation")?;
-        client.json_get(url, query_params).await
+        client
+            .json_get_with_category(RequestCategory::Attestation, url, query_params)
+            .await
     }
 
     /// Posts a list of attestations to the beacon node, expecting it to verify it and publish it to the network.
@@ -231,7 +582,9 @@ impl<E: EthSpec> Validator<E> {
     ) -> Result<PublishStatus, Error> {
         let client = self.0.clone();
         let url = self.url("attestations")?;
-        let response = client.json_post::<_>(url, attestation).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Attestation, url, attestation)
+            .await?;
 
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
@@ -253,11 +606,33 @@ impl<E: EthSpec> Validator<E> {
         let client = self.0.clone();
         let url = self.url("aggregate_and_proofs")?;
         let response = client
-            .json_post::<_>(url, signed_aggregate_and_proofs)
+            .json_post_with_category(
+                RequestCategory::Attestation,
+                url,
+                signed_aggregate_and_proofs,
+            )
             .await?;
 
         match response.status() {
-            StatusCode::OK => Ok(PublishStatus::Valid),
+            StatusCode::OK => {
+                let errors = response
+                    .json::<Vec<AggregateAndProofStatus>>()
+                    .await
+                    .map_err(Error::from)?
+                    .into_iter()
+                    .enumerate()
+                    .filter_map(|(i, status)| match status {
+                        AggregateAndProofStatus::Error(e) => Some(format!("{}: {}", i, e)),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+
+                if errors.is_empty() {
+                    Ok(PublishStatus::Valid)
+                } else {
+                    Ok(PublishStatus::Invalid(errors.join(", ")))
+                }
+            }
             StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
                 response.text().await.map_err(Error::from)?,
             )),
@@ -268,6 +643,24 @@ impl<E: EthSpec> Validator<E> {
         }
     }
 
+    /// Publishes a batch of aggregates and returns the outcome for each, in the same order.
+    pub async fn publish_aggregate_and_proofs_with_status(
+        &self,
+        signed_aggregate_and_proofs: Vec<SignedAggregateAndProof<E>>,
+    ) -> Result<Vec<AggregateAndProofStatus>, Error> {
+        let client = self.0.clone();
+        let url = self.url("aggregate_and_proofs")?;
+        let response = client
+            .json_post_with_category(
+                RequestCategory::Attestation,
+                url,
+                signed_aggregate_and_proofs,
+            )
+            .await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        success.json().await.map_err(Error::from)
+    }
+
     /// Returns the duties required of the given validator pubkeys in the given epoch.
     pub async fn get_duties(
         &self,
@@ -285,27 +678,74 @@ impl<E: EthSpec> Validator<E> {
         };
 
         let url = self.url("duties")?;
-        let response = client.json_post::<_>(url, bulk_request).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Attestation, url, bulk_request)
+            .await?;
         let success = error_for_status(response).await.map_err(Error::from)?;
         success.json().await.map_err(Error::from)
     }
 
+    /// Returns the attester duties during `epoch` of the validators with `validator_indices`.
+    ///
+    /// The indices are sent in the request body, so any number of validators may be queried.
+    pub async fn get_attester_duties(
+        &self,
+        epoch: Epoch,
+        validator_indices: &[u64],
+    ) -> Result<Vec<AttesterData>, Error> {
+        let client = self.0.clone();
+        let url = client.url(&format!("eth/v1/validator/duties/attester/{}", epoch))?;
+        let response = client
+            .json_post_with_category(
+                RequestCategory::Attestation,
+                url,
+                ValidatorIndices(validator_indices.to_vec()),
+            )
+            .await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        let response: GenericResponse<Vec<AttesterData>> =
+            success.json().await.map_err(Error::from)?;
+        Ok(response.data)
+    }
+
+    /// Subscribes validators to the attestation subnets of their beacon committees, using the
+    /// standard Eth2 API.
+    pub async fn subscribe_beacon_committees(
+        &self,
+        subscriptions: Vec<BeaconCommitteeSubscription>,
+    ) -> Result<(), Error> {
+        let client = self.0.clone();
+        let url = client.url("eth/v1/validator/beacon_committee_subscriptions")?;
+        let response = client
+            .json_post_with_category(RequestCategory::Attestation, url, subscriptions)
+            .await?;
+        error_for_status(response).await.map_err(Error::from)?;
+        Ok(())
+    }
+
     /// Posts a block to the beacon node, expecting it to verify it and publish it to the network.
     pub async fn publish_block(&self, block: SignedBeaconBlock<E>) -> Result<PublishStatus, Error> {
         let client = self.0.clone();
         let url = self.url("block")?;
-        let response = client.json_post::<_>(url, block).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Proposal, url, block)
+            .await?;
 
-        match response.status() {
-            StatusCode::OK => Ok(PublishStatus::Valid),
-            StatusCode::ACCEPTED => Ok(PublishStatus::Invalid(
-                response.text().await.map_err(Error::from)?,
-            )),
-            _ => response
-                .error_for_status()
-                .map_err(Error::from)
-                .map(|_| PublishStatus::Unknown),
-        }
+        block_publish_status(response).await
+    }
+
+    /// Posts a signed block to the beacon node as SSZ, avoiding the cost of encoding it as JSON.
+    pub async fn publish_block_ssz(
+        &self,
+        block: &SignedBeaconBlock<E>,
+    ) -> Result<PublishStatus, Error> {
+        let client = self.0.clone();
+        let url = self.url("block")?;
+        let response = client
+            .ssz_post_with_category(RequestCategory::Proposal, url, block)
+            .await?;
+
+        block_publish_status(response).await
     }
 
     /// Requests a new (unsigned) block from the beacon node.
@@ -327,7 +767,36 @@ impl<E: EthSpec> Validator<E> {
             query_pairs.push(("graffiti".into(), as_ssz_hex_string(&graffiti_bytes)));
         }
 
-        client.json_get::<BeaconBlock<E>>(url, query_pairs).await
+        client
+            .json_get_with_category::<BeaconBlock<E>>(RequestCategory::Proposal, url, query_pairs)
+            .await
+    }
+
+    /// Produces an unsigned block upon the block with the given `parent_root`, instead of upon
+    /// the head of the beacon node.
+    pub async fn produce_block_with_parent(
+        &self,
+        slot: Slot,
+        randao_reveal: Signature,
+        graffiti: Option<Graffiti>,
+        parent_root: Hash256,
+    ) -> Result<BeaconBlock<E>, Error> {
+        let client = self.0.clone();
+        let url = self.url("block")?;
+
+        let mut query_pairs = vec![
+            ("slot".into(), format!("{}", slot.as_u64())),
+            ("randao_reveal".into(), as_ssz_hex_string(&randao_reveal)),
+            ("parent_root".into(), root_as_string(parent_root)),
+        ];
+
+        if let Some(graffiti_bytes) = graffiti {
+            query_pairs.push(("graffiti".into(), as_ssz_hex_string(&graffiti_bytes)));
+        }
+
+        client
+            .json_get_with_category::<BeaconBlock<E>>(RequestCategory::Proposal, url, query_pairs)
+            .await
     }
 
     /// Subscribes a list of validators to particular slots for attestation production/publication.
@@ -337,7 +806,9 @@ impl<E: EthSpec> Validator<E> {
     ) -> Result<PublishStatus, Error> {
         let client = self.0.clone();
         let url = self.url("subscribe")?;
-        let response = client.json_post::<_>(url, subscriptions).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Attestation, url, subscriptions)
+            .await?;
 
         match response.status() {
             StatusCode::OK => Ok(PublishStatus::Valid),
@@ -371,6 +842,13 @@ impl<E: EthSpec> Beacon<E> {
         client.json_get(url, vec![]).await
     }
 
+    /// Returns the root of the genesis block.
+    pub async fn get_genesis_block_root(&self) -> Result<Hash256, Error> {
+        let client = self.0.clone();
+        let url = self.url("genesis_block_root")?;
+        client.json_get(url, vec![]).await
+    }
+
     /// Returns the genesis validators root.
     pub async fn get_genesis_validators_root(&self) -> Result<Hash256, Error> {
         let client = self.0.clone();
@@ -423,12 +901,36 @@ impl<E: EthSpec> Beacon<E> {
         query_key: String,
         query_param: String,
     ) -> Result<(SignedBeaconBlock<E>, Hash256), Error> {
+        self.get_block_response(query_key, query_param)
+            .await
+            .map(|response| (response.beacon_block, response.root))
+    }
+
+    /// Returns the full block response (including finalization status) at the given slot.
+    pub async fn get_block_response_by_slot(&self, slot: Slot) -> Result<BlockResponse<E>, Error> {
+        self.get_block_response("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the full block response (including finalization status) at the given root.
+    pub async fn get_block_response_by_root(
+        &self,
+        root: Hash256,
+    ) -> Result<BlockResponse<E>, Error> {
+        self.get_block_response("root".to_string(), root_as_string(root))
+            .await
+    }
+
+    async fn get_block_response(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<BlockResponse<E>, Error> {
         let client = self.0.clone();
         let url = self.url("block")?;
         client
             .json_get::<BlockResponse<E>>(url, vec![(query_key, query_param)])
             .await
-            .map(|response| (response.beacon_block, response.root))
     }
 
     /// Returns the state and state root at the given slot.
@@ -446,6 +948,40 @@ impl<E: EthSpec> Beacon<E> {
             .await
     }
 
+    /// Returns the state at the given slot, downloaded as SSZ rather than JSON.
+    pub async fn get_state_ssz_by_slot(&self, slot: Slot) -> Result<BeaconState<E>, Error> {
+        self.get_state_ssz("slot".to_string(), format!("{}", slot.as_u64()))
+            .await
+    }
+
+    /// Returns the state at the given root, downloaded as SSZ rather than JSON.
+    pub async fn get_state_ssz_by_root(&self, root: Hash256) -> Result<BeaconState<E>, Error> {
+        self.get_state_ssz("root".to_string(), root_as_string(root))
+            .await
+    }
+
+    /// Streams the JSON-encoded `StateResponse` at the given slot into `writer`, without
+    /// buffering it in memory. Returns the number of bytes written.
+    pub async fn get_state_to_writer_by_slot<W: AsyncWrite + Unpin>(
+        &self,
+        slot: Slot,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        self.get_state_to_writer("slot".to_string(), format!("{}", slot.as_u64()), writer)
+            .await
+    }
+
+    /// Streams the JSON-encoded `StateResponse` at the given root into `writer`, without
+    /// buffering it in memory. Returns the number of bytes written.
+    pub async fn get_state_to_writer_by_root<W: AsyncWrite + Unpin>(
+        &self,
+        root: Hash256,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        self.get_state_to_writer("root".to_string(), root_as_string(root), writer)
+            .await
+    }
+
     /// Returns the root of the state at the given slot.
     pub async fn get_state_root(&self, slot: Slot) -> Result<Hash256, Error> {
         let client = self.0.clone();
@@ -473,11 +1009,79 @@ impl<E: EthSpec> Beacon<E> {
         let client = self.0.clone();
         let url = self.url("state")?;
         client
-            .json_get::<StateResponse<E>>(url, vec![(query_key, query_param)])
+            .json_get_with_category::<StateResponse<E>>(
+                RequestCategory::Bulk,
+                url,
+                vec![(query_key, query_param)],
+            )
             .await
             .map(|response| (response.beacon_state, response.root))
     }
 
+    /// Returns the SSZ-encoded state for the given query.
+    async fn get_state_ssz(
+        &self,
+        query_key: String,
+        query_param: String,
+    ) -> Result<BeaconState<E>, Error> {
+        let client = self.0.clone();
+        let url = self.url("state")?;
+        client
+            .ssz_get_with_category(RequestCategory::Bulk, url, vec![(query_key, query_param)])
+            .await
+    }
+
+    /// Streams the state response into `writer` chunk-by-chunk.
+    ///
+    /// Errors before the first byte is written (e.g., connection or HTTP errors) are reported as
+    /// usual, whilst failures part-way through the body are reported as
+    /// `Error::StreamInterrupted`.
+    async fn get_state_to_writer<W: AsyncWrite + Unpin>(
+        &self,
+        query_key: String,
+        query_param: String,
+        writer: &mut W,
+    ) -> Result<u64, Error> {
+        let mut url = self.url("state")?;
+        url.query_pairs_mut().append_pair(&query_key, &query_param);
+
+        let request = self.0.client.get(&url.to_string());
+        let response = self.0.send(RequestCategory::Bulk, request).await?;
+        let mut response = error_for_status(response).await?;
+
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if !content_type.starts_with("application/json") {
+            return Err(Error::UnexpectedContentType(content_type.to_string()));
+        }
+
+        let mut bytes_written = 0;
+        let interrupted = |bytes_written, reason| Error::StreamInterrupted {
+            bytes_written,
+            reason,
+        };
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|e| interrupted(bytes_written, format!("{:?}", e)))?
+        {
+            writer
+                .write_all(&chunk)
+                .await
+                .map_err(|e| interrupted(bytes_written, format!("{:?}", e)))?;
+            bytes_written += chunk.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| interrupted(bytes_written, format!("{:?}", e)))?;
+
+        Ok(bytes_written)
+    }
+
     /// Returns the block and block root at the given slot.
     ///
     /// If `state_root` is `Some`, the query will use the given state instead of the default
@@ -498,7 +1102,9 @@ impl<E: EthSpec> Beacon<E> {
         };
 
         let url = self.url("validators")?;
-        let response = client.json_post::<_>(url, bulk_request).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Bulk, url, bulk_request)
+            .await?;
         let success = error_for_status(response).await.map_err(Error::from)?;
         success.json().await.map_err(Error::from)
     }
@@ -520,7 +1126,62 @@ impl<E: EthSpec> Beacon<E> {
         };
 
         let url = self.url("validators/all")?;
-        client.json_get(url, query_params).await
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, query_params)
+            .await
+    }
+
+    /// Returns only the given `fields` of all validators, deserialized as `T`.
+    ///
+    /// If `state_root` is `Some`, the query will use the given state instead of the default
+    /// canonical head state.
+    pub async fn get_all_validators_with_fields<T: DeserializeOwned>(
+        &self,
+        state_root: Option<Hash256>,
+        fields: &[ValidatorField],
+    ) -> Result<Vec<T>, Error> {
+        let client = self.0.clone();
+
+        let fields = fields
+            .iter()
+            .map(|field| field.name())
+            .collect::<Vec<_>>()
+            .join(",");
+        let mut query_params = vec![("fields".into(), fields)];
+        if let Some(state_root) = state_root {
+            query_params.push(("state_root".into(), root_as_string(state_root)));
+        }
+
+        let url = self.url("validators/all")?;
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, query_params)
+            .await
+    }
+
+    /// Returns up to `limit` validators, starting at validator index `offset`.
+    ///
+    /// If `state_root` is `Some`, the query will use the given state instead of the default
+    /// canonical head state.
+    pub async fn get_validators_page(
+        &self,
+        state_root: Option<Hash256>,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ValidatorResponse>, Error> {
+        let client = self.0.clone();
+
+        let mut query_params = vec![
+            ("offset".into(), format!("{}", offset)),
+            ("limit".into(), format!("{}", limit)),
+        ];
+        if let Some(state_root) = state_root {
+            query_params.push(("state_root".into(), root_as_string(state_root)));
+        }
+
+        let url = self.url("validators/all")?;
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, query_params)
+            .await
     }
 
     /// Returns the active validators.
@@ -540,7 +1201,9 @@ impl<E: EthSpec> Beacon<E> {
         };
 
         let url = self.url("validators/active")?;
-        client.json_get(url, query_params).await
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, query_params)
+            .await
     }
 
     /// Returns committees at the given epoch.
@@ -549,14 +1212,49 @@ impl<E: EthSpec> Beacon<E> {
 
         let url = self.url("committees")?;
         client
-            .json_get(url, vec![("epoch".into(), format!("{}", epoch.as_u64()))])
+            .json_get_with_category(
+                RequestCategory::Bulk,
+                url,
+                vec![("epoch".into(), format!("{}", epoch.as_u64()))],
+            )
+            .await
+    }
+
+    /// Returns committees at the given epoch, optionally restricted to a `slot` and/or committee
+    /// `index`.
+    ///
+    /// If `include_pubkeys` is `true`, each committee will also contain the public keys of its
+    /// members.
+    pub async fn get_committees_with_options(
+        &self,
+        epoch: Epoch,
+        slot: Option<Slot>,
+        index: Option<CommitteeIndex>,
+        include_pubkeys: bool,
+    ) -> Result<Vec<Committee>, Error> {
+        let client = self.0.clone();
+
+        let mut query_params = vec![("epoch".into(), format!("{}", epoch.as_u64()))];
+        if let Some(slot) = slot {
+            query_params.push(("slot".into(), format!("{}", slot.as_u64())));
+        }
+        if let Some(index) = index {
+            query_params.push(("index".into(), format!("{}", index)));
+        }
+        if include_pubkeys {
+            query_params.push(("include_pubkeys".into(), "true".into()));
+        }
+
+        let url = self.url("committees")?;
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, query_params)
             .await
     }
 
     pub async fn proposer_slashing(
         &self,
         proposer_slashing: ProposerSlashing,
-    ) -> Result<bool, Error> {
+    ) -> Result<PublishOperationResponse, Error> {
         let client = self.0.clone();
 
         let url = self.url("proposer_slashing")?;
@@ -568,7 +1266,7 @@ impl<E: EthSpec> Beacon<E> {
     pub async fn attester_slashing(
         &self,
         attester_slashing: AttesterSlashing<E>,
-    ) -> Result<bool, Error> {
+    ) -> Result<PublishOperationResponse, Error> {
         let client = self.0.clone();
 
         let url = self.url("attester_slashing")?;
@@ -576,6 +1274,81 @@ impl<E: EthSpec> Beacon<E> {
         let success = error_for_status(response).await.map_err(Error::from)?;
         success.json().await.map_err(Error::from)
     }
+
+    pub async fn voluntary_exit(
+        &self,
+        exit: SignedVoluntaryExit,
+    ) -> Result<PublishOperationResponse, Error> {
+        let client = self.0.clone();
+
+        let url = self.url("voluntary_exit")?;
+        let response = client.json_post::<_>(url, exit).await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        success.json().await.map_err(Error::from)
+    }
+
+    /// Returns the balances of all validators at the given slot, indexed by validator index.
+    pub async fn get_validator_balances(&self, slot: Slot) -> Result<Vec<u64>, Error> {
+        let client = self.0.clone();
+        let url = self.url("validator_balances")?;
+        client
+            .json_get_with_category(
+                RequestCategory::Bulk,
+                url,
+                vec![("slot".into(), format!("{}", slot.as_u64()))],
+            )
+            .await
+    }
+
+    /// Returns the attester slashings in the operation pool, optionally only those involving
+    /// `validator_index`.
+    pub async fn get_pool_attester_slashings(
+        &self,
+        validator_index: Option<u64>,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let client = self.0.clone();
+        let url = self.url("pool/attester_slashings")?;
+        client
+            .json_get(url, validator_index_query_params(validator_index))
+            .await
+    }
+
+    /// Returns the proposer slashings in the operation pool, optionally only the one for
+    /// `validator_index`.
+    pub async fn get_pool_proposer_slashings(
+        &self,
+        validator_index: Option<u64>,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        let client = self.0.clone();
+        let url = self.url("pool/proposer_slashings")?;
+        client
+            .json_get(url, validator_index_query_params(validator_index))
+            .await
+    }
+
+    /// Returns up to `limit` attester slashings in the operation pool, starting at position
+    /// `offset` in the order returned by the server.
+    pub async fn get_pool_attester_slashings_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<AttesterSlashing<E>>, Error> {
+        let client = self.0.clone();
+        let url = self.url("pool/attester_slashings")?;
+        client.json_get(url, page_query_params(offset, limit)).await
+    }
+
+    /// Returns up to `limit` proposer slashings in the operation pool, starting at position
+    /// `offset` in the order returned by the server.
+    pub async fn get_pool_proposer_slashings_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Vec<ProposerSlashing>, Error> {
+        let client = self.0.clone();
+        let url = self.url("pool/proposer_slashings")?;
+        client.json_get(url, page_query_params(offset, limit)).await
+    }
 }
 
 /// Provides the functions on the `/spec` endpoint of the node.
@@ -595,6 +1368,30 @@ impl<E: EthSpec> Spec<E> {
         let url = self.url("eth2_config")?;
         client.json_get(url, vec![]).await
     }
+
+    /// Returns the name of the testnet (e.g., `medalla`) or spec constants (e.g., `mainnet`) the
+    /// node is running, or `custom` for an unnamed testnet.
+    pub async fn get_config_name(&self) -> Result<String, Error> {
+        let client = self.0.clone();
+        let url = self.url("config_name")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns the spec in the YAML format of a testnet `config.yaml` file.
+    pub async fn get_yaml_config(&self) -> Result<String, Error> {
+        let mut url = self.0.url("spec")?;
+        url.query_pairs_mut().append_pair("format", "yaml");
+
+        let response = self
+            .0
+            .send(
+                RequestCategory::Default,
+                self.0.client.get(&url.to_string()),
+            )
+            .await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        success.text().await.map_err(Error::from)
+    }
 }
 
 /// Provides the functions on the `/node` endpoint of the node.
@@ -626,6 +1423,13 @@ impl<E: EthSpec> Node<E> {
         let url = self.url("syncing")?;
         client.json_get(url, vec![]).await
     }
+
+    /// Gets the name and version of the server and the routes it serves, from the API root.
+    pub async fn get_api_index(&self) -> Result<ApiIndex, Error> {
+        let client = self.0.clone();
+        let url = self.0.url("")?;
+        client.json_get(url, vec![]).await
+    }
 }
 
 /// Provides the functions on the `/advanced` endpoint of the node.
@@ -644,14 +1448,18 @@ impl<E: EthSpec> Advanced<E> {
     pub async fn get_fork_choice(&self) -> Result<ProtoArray, Error> {
         let client = self.0.clone();
         let url = self.url("fork_choice")?;
-        client.json_get(url, vec![]).await
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, vec![])
+            .await
     }
 
     /// Gets the core `PersistedOperationPool` struct from the node.
     pub async fn get_operation_pool(&self) -> Result<PersistedOperationPool<E>, Error> {
         let client = self.0.clone();
         let url = self.url("operation_pool")?;
-        client.json_get(url, vec![]).await
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, vec![])
+            .await
     }
 }
 
@@ -677,7 +1485,9 @@ impl<E: EthSpec> Consensus<E> {
         let req_body = IndividualVotesRequest { epoch, pubkeys };
 
         let url = self.url("individual_votes")?;
-        let response = client.json_post::<_>(url, req_body).await?;
+        let response = client
+            .json_post_with_category(RequestCategory::Bulk, url, req_body)
+            .await?;
         let success = error_for_status(response).await.map_err(Error::from)?;
         success.json().await.map_err(Error::from)
     }
@@ -691,11 +1501,101 @@ impl<E: EthSpec> Consensus<E> {
     }
 }
 
+/// Provides the functions on the `/lighthouse` endpoint of the node.
+#[derive(Clone)]
+pub struct Lighthouse<E>(HttpClient<E>);
+
+impl<E: EthSpec> Lighthouse<E> {
+    fn url(&self, path: &str) -> Result<Url, Error> {
+        self.0
+            .url("lighthouse/")
+            .and_then(move |url| url.join(path).map_err(Error::from))
+            .map_err(Into::into)
+    }
+
+    /// Returns `Ok` if the beacon node is responsive.
+    pub async fn get_liveness(&self) -> Result<bool, Error> {
+        let client = self.0.clone();
+        let url = self.url("liveness")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns `Ok` if the beacon node is ready to serve validators.
+    ///
+    /// A node which is not ready responds with `503 Service Unavailable`.
+    pub async fn get_readiness(&self) -> Result<Readiness, Error> {
+        let client = self.0.clone();
+        let url = self.url("readiness")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Gets the number of each type of operation in the operation pool.
+    pub async fn get_op_pool_stats(&self) -> Result<OpPoolStats, Error> {
+        let client = self.0.clone();
+        let url = self.url("op_pool")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns a summary of the body of the block identified by `block_id`.
+    pub async fn get_block_body_summary(
+        &self,
+        block_id: BlockId,
+    ) -> Result<BlockBodySummary<E>, Error> {
+        let client = self.0.clone();
+        let url = self.url(&format!(
+            "beacon/blocks/{}/body_summary",
+            block_id_as_string(block_id)
+        ))?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns a summary of the eth1 deposit cache, without the deposit logs.
+    pub async fn get_eth1_deposit_cache(&self) -> Result<Eth1DepositCacheSnapshot, Error> {
+        let client = self.0.clone();
+        let url = self.url("eth1/deposit_cache")?;
+        client.json_get(url, vec![]).await
+    }
+
+    /// Returns a summary of the eth1 deposit cache, including at most `limit` deposit logs
+    /// starting from the `offset`-th log.
+    pub async fn get_eth1_deposit_cache_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> Result<Eth1DepositCacheSnapshot, Error> {
+        let client = self.0.clone();
+        let url = self.url("eth1/deposit_cache")?;
+        let mut params = page_query_params(offset, limit);
+        params.push(("include_deposits".into(), "true".into()));
+        client
+            .json_get_with_category(RequestCategory::Bulk, url, params)
+            .await
+    }
+
+    /// Prunes operations which are no longer valid from the operation pool, returning the number
+    /// of each type of operation that was removed.
+    ///
+    /// Requires the admin token configured on the beacon node.
+    pub async fn prune_op_pool(&self, admin_token: &str) -> Result<OpPoolPruneResponse, Error> {
+        let client = self
+            .0
+            .with_request_auth(Some(Auth::Bearer(admin_token.to_string())));
+        let url = self.url("op_pool/prune")?;
+        let request = client.client.post(&url.to_string());
+        let response = client.send(RequestCategory::Default, request).await?;
+        let success = error_for_status(response).await.map_err(Error::from)?;
+        success.json().await.map_err(Error::from)
+    }
+}
+
 #[derive(Deserialize)]
 #[serde(bound = "T: EthSpec")]
 pub struct BlockResponse<T: EthSpec> {
     pub beacon_block: SignedBeaconBlock<T>,
     pub root: Hash256,
+    /// Whether the block is finalized. `None` if the server did not report it.
+    #[serde(default)]
+    pub finalized: Option<bool>,
 }
 
 #[derive(Deserialize)]
@@ -705,10 +1605,34 @@ pub struct StateResponse<T: EthSpec> {
     pub root: Hash256,
 }
 
+fn validator_index_query_params(validator_index: Option<u64>) -> Vec<(String, String)> {
+    validator_index
+        .map(|index| vec![("validator_index".into(), format!("{}", index))])
+        .unwrap_or_default()
+}
+
+fn page_query_params(offset: usize, limit: usize) -> Vec<(String, String)> {
+    vec![
+        ("offset".into(), format!("{}", offset)),
+        ("limit".into(), format!("{}", limit)),
+    ]
+}
+
 fn root_as_string(root: Hash256) -> String {
     format!("0x{:?}", root)
 }
 
+fn block_id_as_string(block_id: BlockId) -> String {
+    match block_id {
+        BlockId::Head => "head".into(),
+        BlockId::Genesis => "genesis".into(),
+        BlockId::Finalized => "finalized".into(),
+        BlockId::Justified => "justified".into(),
+        BlockId::Slot(slot) => format!("{}", slot.as_u64()),
+        BlockId::Root(root) => root_as_string(root),
+    }
+}
+
 fn as_ssz_hex_string<T: Encode>(item: &T) -> String {
     format!("0x{}", hex::encode(item.as_ssz_bytes()))
 }
@@ -730,3 +1654,337 @@ impl From<serde_json::Error> for Error {
         Error::SerdeJsonError(e)
     }
 }
+
+impl From<ssz::DecodeError> for Error {
+    fn from(e: ssz::DecodeError) -> Error {
+        Error::SszDecodeError(e)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use hyper::service::{make_service_fn, service_fn};
+    use hyper::{Body, Request, Server};
+    use std::convert::Infallible;
+    use std::net::{SocketAddr, TcpListener};
+    use types::MinimalEthSpec;
+
+    /// Starts a server which responds to every request with an empty JSON list after `delay`.
+    fn spawn_slow_server(delay: Duration) -> SocketAddr {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |_: Request<Body>| async move {
+                tokio::time::delay_for(delay).await;
+                Ok::<_, Infallible>(hyper::Response::new(Body::from("[]")))
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    /// Starts a server which responds to every request with `status`, `content_type` and `body`.
+    fn spawn_error_server(
+        status: StatusCode,
+        content_type: &'static str,
+        body: String,
+    ) -> SocketAddr {
+        let make_service = make_service_fn(move |_| {
+            let body = body.clone();
+            async move {
+                Ok::<_, Infallible>(service_fn(move |_: Request<Body>| {
+                    let body = body.clone();
+                    async move {
+                        let response = hyper::Response::builder()
+                            .status(status.as_u16())
+                            .header("content-type", content_type)
+                            .body(Body::from(body))
+                            .unwrap();
+                        Ok::<_, Infallible>(response)
+                    }
+                }))
+            }
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    /// Starts a server which responds to requests carrying one of the `accepted` `Authorization`
+    /// headers with a version string, and to all others with `401 Unauthorized`.
+    fn spawn_auth_server(accepted: &'static [&'static str]) -> SocketAddr {
+        let make_service = make_service_fn(move |_| async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| async move {
+                let authorized = req
+                    .headers()
+                    .get("authorization")
+                    .and_then(|value| value.to_str().ok())
+                    .map_or(false, |value| accepted.contains(&value));
+                let response = if authorized {
+                    hyper::Response::new(Body::from("\"version\""))
+                } else {
+                    hyper::Response::builder()
+                        .status(StatusCode::UNAUTHORIZED.as_u16())
+                        .body(Body::from("Missing admin token"))
+                        .unwrap()
+                };
+                Ok::<_, Infallible>(response)
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let addr = server.local_addr();
+        tokio::spawn(server);
+        addr
+    }
+
+    fn client(addr: SocketAddr, timeouts: Timeouts) -> HttpClient<MinimalEthSpec> {
+        HttpClient::new_with_timeouts(format!("http://{}", addr), timeouts)
+            .expect("should create client")
+    }
+
+    #[tokio::test]
+    async fn timeouts_by_category() {
+        let short = Duration::from_millis(50);
+        let addr = spawn_slow_server(Duration::from_millis(500));
+        let client = client(
+            addr,
+            Timeouts {
+                attestation: short,
+                proposal: short,
+                bulk: Duration::from_secs(10),
+                default: short,
+            },
+        );
+
+        let result = client
+            .validator()
+            .produce_attestation(Slot::new(0), 0)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(RequestCategory::Attestation))
+        ));
+
+        let result = client
+            .validator()
+            .produce_block(Slot::new(0), Signature::empty(), None)
+            .await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(RequestCategory::Proposal))
+        ));
+
+        let result = client.node().get_version().await;
+        assert!(matches!(
+            result,
+            Err(Error::Timeout(RequestCategory::Default))
+        ));
+
+        // Bulk requests have a longer timeout, so they succeed despite the slow server.
+        let validators = client
+            .beacon()
+            .get_all_validators(None)
+            .await
+            .expect("bulk request should not time out");
+        assert!(validators.is_empty());
+    }
+
+    #[tokio::test]
+    async fn connection_errors_are_not_timeouts() {
+        // Bind to a free port, then close it so that connections are refused.
+        let addr = TcpListener::bind("127.0.0.1:0")
+            .and_then(|listener| listener.local_addr())
+            .expect("should find a free port");
+        let client = client(addr, Timeouts::set_all(Duration::from_secs(10))).with_get_retries(2);
+
+        match client.node().get_version().await {
+            Err(Error::ReqwestError(e)) => assert!(e.is_connect()),
+            other => panic!("expected a connection error, got {:?}", other),
+        }
+    }
+
+    async fn version_error(status: StatusCode, content_type: &'static str, body: String) -> Error {
+        let addr = spawn_error_server(status, content_type, body);
+        client(addr, Timeouts::set_all(Duration::from_secs(10)))
+            .node()
+            .get_version()
+            .await
+            .expect_err("request should fail")
+    }
+
+    #[tokio::test]
+    async fn server_error_message() {
+        let msg = ErrorMessage {
+            code: 400,
+            message: "BAD_REQUEST: invalid slot".to_string(),
+            stacktraces: vec![],
+        };
+        let error = version_error(
+            StatusCode::BAD_REQUEST,
+            "application/json",
+            serde_json::to_string(&msg).unwrap(),
+        )
+        .await;
+
+        assert_eq!(error.message(), Some("BAD_REQUEST: invalid slot"));
+        assert!(error.failed_indices().is_empty());
+        match error {
+            Error::ServerMessage(received) => assert_eq!(received, msg),
+            other => panic!("expected a server message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn indexed_server_error_message() {
+        let msg = IndexedErrorMessage {
+            code: 400,
+            message: "BAD_REQUEST: some attestations were invalid".to_string(),
+            failures: vec![
+                Failure {
+                    index: 1,
+                    message: "invalid signature".to_string(),
+                },
+                Failure {
+                    index: 3,
+                    message: "unknown head block".to_string(),
+                },
+            ],
+        };
+        let error = version_error(
+            StatusCode::BAD_REQUEST,
+            "application/json; charset=utf-8",
+            serde_json::to_string(&msg).unwrap(),
+        )
+        .await;
+
+        assert_eq!(error.message(), Some(msg.message.as_str()));
+        assert_eq!(error.failed_indices(), vec![1, 3]);
+        match error {
+            Error::IndexedServerMessage(received) => assert_eq!(received, msg),
+            other => panic!("expected an indexed server message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn unstructured_server_errors() {
+        // A plain-text body is not parsed, even if it happens to be valid JSON.
+        let body = r#"{"code":500,"message":"oops","stacktraces":[]}"#.to_string();
+        let error = version_error(
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "text/plain; charset=utf-8",
+            body.clone(),
+        )
+        .await;
+        assert_eq!(error.message(), Some(body.as_str()));
+        assert!(matches!(
+            error,
+            Error::DidNotSucceed { status, .. } if status == StatusCode::INTERNAL_SERVER_ERROR
+        ));
+
+        // A JSON body which matches neither error shape is returned as-is.
+        let error = version_error(
+            StatusCode::NOT_FOUND,
+            "application/json",
+            r#"{"error":"not found"}"#.to_string(),
+        )
+        .await;
+        assert_eq!(error.message(), Some(r#"{"error":"not found"}"#));
+        assert!(matches!(
+            error,
+            Error::DidNotSucceed { status, .. } if status == StatusCode::NOT_FOUND
+        ));
+    }
+
+    #[tokio::test]
+    async fn bearer_token_auth() {
+        let token = "secret_token";
+        let addr = spawn_auth_server(&["Bearer secret_token"]);
+        let anonymous = client(addr, Timeouts::set_all(Duration::from_secs(10)));
+        let authenticated = anonymous.clone().with_bearer_token(token.to_string());
+
+        let version = authenticated
+            .node()
+            .get_version()
+            .await
+            .expect("authenticated request should succeed");
+        assert_eq!(version, "version");
+
+        let error = anonymous
+            .node()
+            .get_version()
+            .await
+            .expect_err("anonymous request should fail");
+        assert!(matches!(error, Error::Unauthorized(_)));
+        assert_eq!(error.message(), Some("Missing admin token"));
+
+        // The token must never be revealed by the client or its errors.
+        assert!(!format!("{:?}", authenticated).contains(token));
+        assert!(!format!("{:?}", error).contains(token));
+    }
+
+    #[tokio::test]
+    async fn basic_auth() {
+        // The base64 encoding of `user:pass`.
+        let addr = spawn_auth_server(&["Basic dXNlcjpwYXNz"]);
+        let client = client(addr, Timeouts::set_all(Duration::from_secs(10)))
+            .with_basic_auth("user".to_string(), Some("pass".to_string()));
+
+        client
+            .node()
+            .get_version()
+            .await
+            .expect("authenticated request should succeed");
+        assert!(!format!("{:?}", client).contains("pass"));
+
+        let error = client
+            .with_request_auth(Some(Auth::Basic {
+                username: "user".to_string(),
+                password: Some("wrong".to_string()),
+            }))
+            .node()
+            .get_version()
+            .await
+            .expect_err("request with the wrong password should fail");
+        assert!(matches!(error, Error::Unauthorized(_)));
+    }
+
+    #[tokio::test]
+    async fn request_auth_override() {
+        let addr = spawn_auth_server(&["Bearer secret_token"]);
+        let anonymous = client(addr, Timeouts::set_all(Duration::from_secs(10)));
+        let authenticated = anonymous
+            .clone()
+            .with_bearer_token("secret_token".to_string());
+
+        // Credentials may be removed for a single request...
+        assert!(matches!(
+            authenticated
+                .with_request_auth(None)
+                .node()
+                .get_version()
+                .await,
+            Err(Error::Unauthorized(_))
+        ));
+
+        // ...or provided for a single request.
+        anonymous
+            .with_request_auth(Some(Auth::Bearer("secret_token".to_string())))
+            .node()
+            .get_version()
+            .await
+            .expect("request with overridden credentials should succeed");
+
+        // Neither override affects the original clients.
+        authenticated
+            .node()
+            .get_version()
+            .await
+            .expect("authenticated client should still be authenticated");
+        assert!(matches!(
+            anonymous.node().get_version().await,
+            Err(Error::Unauthorized(_))
+        ));
+    }
+}
@@ -1,6 +1,32 @@
 use hyper::{Body, Response, StatusCode};
+use serde::{Deserialize, Serialize};
 use std::error::Error as StdError;
 
+/// A structured error returned in a JSON response body.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorMessage {
+    pub code: u16,
+    pub message: String,
+    #[serde(default)]
+    pub stacktraces: Vec<String>,
+}
+
+/// A structured error for a request containing a list of objects, identifying which of those
+/// objects could not be processed.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct IndexedErrorMessage {
+    pub code: u16,
+    pub message: String,
+    pub failures: Vec<Failure>,
+}
+
+/// The reason that the object at `index` of a request could not be processed.
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub struct Failure {
+    pub index: u64,
+    pub message: String,
+}
+
 #[derive(PartialEq, Debug, Clone)]
 pub enum ApiError {
     MethodNotAllowed(String),
@@ -8,15 +34,78 @@ pub enum ApiError {
     NotImplemented(String),
     BadRequest(String),
     NotFound(String),
+    Unauthorized(String),
+    Conflict(String),
+    ServiceUnavailable(String),
+    GatewayTimeout(String),
     UnsupportedType(String),
+    NotAcceptable(String),
+    PayloadTooLarge(String),
+    /// A 400 error for a request containing a list of objects, some of which were invalid. It is
+    /// returned as a JSON `IndexedErrorMessage`.
+    IndexedBadRequest(IndexedErrorMessage),
     ImATeapot(String),       // Just in case.
     ProcessingError(String), // A 202 error, for when a block/attestation cannot be processed, but still transmitted.
     InvalidHeaderValue(String),
+    /// An error annotated with a static context frame, see `ApiError::context`.
+    Context(&'static str, Box<ApiError>),
 }
 
 pub type ApiResult = Result<Response<Body>, ApiError>;
 
 impl ApiError {
+    /// Annotates the error with `frame`, e.g., the name of the function it is propagating
+    /// through. Frames are returned in the `stacktraces` of a JSON `ErrorMessage`.
+    ///
+    /// Frames are static strings, so they never contain request data or file paths.
+    pub fn context(self, frame: &'static str) -> Self {
+        ApiError::Context(frame, Box::new(self))
+    }
+
+    /// Splits the error into its context frames, innermost first, and the annotated error.
+    fn into_frames(self) -> (Vec<&'static str>, ApiError) {
+        let mut frames = vec![];
+        let mut error = self;
+        while let ApiError::Context(frame, inner) = error {
+            frames.push(frame);
+            error = *inner;
+        }
+        frames.reverse();
+        (frames, error)
+    }
+
+    /// Converts the error into a response with a JSON `ErrorMessage` body if `json` is true, or a
+    /// plain-text body otherwise. An `IndexedBadRequest` always has an `IndexedErrorMessage` body.
+    ///
+    /// The `code` of an `ErrorMessage` is the HTTP status code.
+    pub fn into_response(self, json: bool) -> Response<Body> {
+        let (frames, error) = self.into_frames();
+        let indexed = matches!(error, ApiError::IndexedBadRequest(_));
+        let (status_code, message) = error.status_code();
+
+        let (content_type, body) = if indexed {
+            ("application/json", message)
+        } else if json {
+            let msg = ErrorMessage {
+                code: status_code.as_u16(),
+                message,
+                stacktraces: frames.into_iter().map(String::from).collect(),
+            };
+            (
+                "application/json",
+                serde_json::to_string(&msg).unwrap_or(msg.message),
+            )
+        } else {
+            ("text/plain; charset=utf-8", message)
+        };
+
+        Response::builder()
+            .status(status_code)
+            .header("content-type", content_type)
+            .body(Body::from(body))
+            .expect("Response should always be created.")
+    }
+
     pub fn status_code(self) -> (StatusCode, String) {
         match self {
             ApiError::MethodNotAllowed(desc) => (StatusCode::METHOD_NOT_ALLOWED, desc),
@@ -24,22 +113,28 @@ impl ApiError {
             ApiError::NotImplemented(desc) => (StatusCode::NOT_IMPLEMENTED, desc),
             ApiError::BadRequest(desc) => (StatusCode::BAD_REQUEST, desc),
             ApiError::NotFound(desc) => (StatusCode::NOT_FOUND, desc),
+            ApiError::Unauthorized(desc) => (StatusCode::UNAUTHORIZED, desc),
+            ApiError::Conflict(desc) => (StatusCode::CONFLICT, desc),
+            ApiError::ServiceUnavailable(desc) => (StatusCode::SERVICE_UNAVAILABLE, desc),
+            ApiError::GatewayTimeout(desc) => (StatusCode::GATEWAY_TIMEOUT, desc),
             ApiError::UnsupportedType(desc) => (StatusCode::UNSUPPORTED_MEDIA_TYPE, desc),
+            ApiError::NotAcceptable(desc) => (StatusCode::NOT_ACCEPTABLE, desc),
+            ApiError::PayloadTooLarge(desc) => (StatusCode::PAYLOAD_TOO_LARGE, desc),
+            ApiError::IndexedBadRequest(msg) => (
+                StatusCode::BAD_REQUEST,
+                serde_json::to_string(&msg).unwrap_or(msg.message),
+            ),
             ApiError::ImATeapot(desc) => (StatusCode::IM_A_TEAPOT, desc),
             ApiError::ProcessingError(desc) => (StatusCode::ACCEPTED, desc),
             ApiError::InvalidHeaderValue(desc) => (StatusCode::INTERNAL_SERVER_ERROR, desc),
+            ApiError::Context(_, error) => error.status_code(),
         }
     }
 }
 
 impl Into<Response<Body>> for ApiError {
     fn into(self) -> Response<Body> {
-        let (status_code, desc) = self.status_code();
-        Response::builder()
-            .status(status_code)
-            .header("content-type", "text/plain; charset=utf-8")
-            .body(Body::from(desc))
-            .expect("Response should always be created.")
+        self.into_response(false)
     }
 }
 
@@ -56,8 +151,35 @@ impl From<types::BeaconStateError> for ApiError {
 }
 
 impl From<beacon_chain::BeaconChainError> for ApiError {
+    /// Maps errors caused by missing data or invalid requests to 4xx codes and transient errors to
+    /// 503, so they may be distinguished from genuine server errors.
     fn from(e: beacon_chain::BeaconChainError) -> ApiError {
-        ApiError::ServerError(format!("BeaconChainError error: {:?}", e))
+        use beacon_chain::BeaconChainError::*;
+
+        let desc = format!("BeaconChainError: {:?}", e);
+
+        match e {
+            NoStateForSlot(_)
+            | UnableToFindTargetRoot(_)
+            | MissingBeaconBlock(_)
+            | MissingBeaconState(_)
+            | NoStateForAttestation { .. }
+            | MissingFinalizedStateRoot(_) => ApiError::NotFound(desc),
+            CannotAttestToFutureState
+            | StateSkipTooLarge { .. }
+            | IncorrectStateForAttestation(_)
+            | InvalidValidatorPubkeyBytes(_)
+            | AttestationValidationError(_)
+            | ExitValidationError(_)
+            | ProposerSlashingValidationError(_)
+            | AttesterSlashingValidationError(_) => ApiError::BadRequest(desc),
+            UnableToReadSlot
+            | SlotClockDidNotStart
+            | CanonicalHeadLockTimeout
+            | AttestationCacheLockTimeout
+            | ValidatorPubkeyCacheLockTimeout => ApiError::ServiceUnavailable(desc),
+            _ => ApiError::ServerError(desc),
+        }
     }
 }
 
@@ -97,3 +219,80 @@ impl std::fmt::Display for ApiError {
         write!(f, "{:?}: {:?}", status.0, status.1)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use beacon_chain::BeaconChainError;
+    use types::{Hash256, Slot};
+
+    fn status(e: BeaconChainError) -> StatusCode {
+        ApiError::from(e).status_code().0
+    }
+
+    #[test]
+    fn beacon_chain_error_status_codes() {
+        assert_eq!(
+            status(BeaconChainError::MissingBeaconBlock(Hash256::zero())),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(BeaconChainError::NoStateForSlot(Slot::new(1))),
+            StatusCode::NOT_FOUND
+        );
+        assert_eq!(
+            status(BeaconChainError::CannotAttestToFutureState),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(BeaconChainError::CanonicalHeadLockTimeout),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert_eq!(
+            status(BeaconChainError::InsufficientValidators),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
+    fn body_string(response: Response<Body>) -> String {
+        let bytes = futures::executor::block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        String::from_utf8(bytes.to_vec()).expect("body should be utf8")
+    }
+
+    fn content_type(response: &Response<Body>) -> &str {
+        response.headers()["content-type"].to_str().unwrap()
+    }
+
+    #[test]
+    fn json_error_message() {
+        let error = ApiError::NotFound("Unable to find block".into())
+            .context("block_from_id")
+            .context("get_block_by_id");
+
+        let response = error.clone().into_response(true);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(content_type(&response), "application/json");
+        let body: serde_json::Value = serde_json::from_str(&body_string(response)).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({
+                "code": 404,
+                "message": "Unable to find block",
+                "stacktraces": ["block_from_id", "get_block_by_id"],
+            })
+        );
+
+        // Other clients still receive the message as plain text.
+        let response = error.into_response(false);
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(content_type(&response), "text/plain; charset=utf-8");
+        assert_eq!(body_string(response), "Unable to find block");
+    }
+
+    #[test]
+    fn beacon_chain_error_includes_variant() {
+        let (_, desc) = ApiError::from(BeaconChainError::CanonicalHeadLockTimeout).status_code();
+        assert!(desc.contains("CanonicalHeadLockTimeout"));
+    }
+}
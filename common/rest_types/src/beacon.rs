@@ -1,10 +1,15 @@
 //! A collection of REST API types for interaction with the beacon node.
 
 use bls::PublicKeyBytes;
-use serde::{Deserialize, Serialize};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Serialize, Serializer};
 use ssz_derive::{Decode, Encode};
+use std::str::FromStr;
 use types::beacon_state::EthSpec;
-use types::{BeaconState, CommitteeIndex, Hash256, SignedBeaconBlock, Slot, Validator};
+use types::{
+    BeaconState, CommitteeIndex, Epoch, Hash256, SignedBeaconBlock, SignedBeaconBlockHeader, Slot,
+    Validator,
+};
 
 /// Information about a block that is at the head of a chain. May or may not represent the
 /// canonical head.
@@ -12,6 +17,27 @@ use types::{BeaconState, CommitteeIndex, Hash256, SignedBeaconBlock, Slot, Valid
 pub struct HeadBeaconBlock {
     pub beacon_block_root: Hash256,
     pub beacon_block_slot: Slot,
+    /// The fork choice weight of the block. `None` if the block is unknown to fork choice.
+    #[serde(default)]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub weight: Option<u64>,
+    /// Whether the block's justified and finalized checkpoints allow it to become the head. `None`
+    /// if the block is unknown to fork choice.
+    #[serde(default)]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub viable: Option<bool>,
+    /// The justified epoch of the block's post-state. `None` if the block is unknown to fork choice.
+    #[serde(default)]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub justified_epoch: Option<Epoch>,
+    /// The finalized epoch of the block's post-state. `None` if the block is unknown to fork choice.
+    #[serde(default)]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub finalized_epoch: Option<Epoch>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
@@ -19,6 +45,72 @@ pub struct HeadBeaconBlock {
 pub struct BlockResponse<T: EthSpec> {
     pub root: Hash256,
     pub beacon_block: SignedBeaconBlock<T>,
+    /// `true` if the block is in the canonical chain and at or prior to the finalized checkpoint.
+    ///
+    /// Not included in the SSZ encoding, so that encoding remains unchanged.
+    #[serde(default)]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub finalized: bool,
+}
+
+/// Identifies a block in the paths of the standard Eth2 API.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BlockId {
+    /// The block at the head of the canonical chain.
+    Head,
+    Genesis,
+    /// The block of the finalized checkpoint.
+    Finalized,
+    /// The block of the current justified checkpoint.
+    Justified,
+    /// The block at the slot in the canonical chain.
+    Slot(Slot),
+    Root(Hash256),
+}
+
+impl FromStr for BlockId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "head" => Ok(BlockId::Head),
+            "genesis" => Ok(BlockId::Genesis),
+            "finalized" => Ok(BlockId::Finalized),
+            "justified" => Ok(BlockId::Justified),
+            other if other.starts_with("0x") => Hash256::from_str(&other[2..])
+                .map(BlockId::Root)
+                .map_err(|e| format!("Unable to parse block root {:?}: {:?}", other, e)),
+            other => other
+                .parse::<u64>()
+                .map(|slot| BlockId::Slot(Slot::new(slot)))
+                .map_err(|_| {
+                    format!(
+                        "Invalid block ID {:?}, expected head, genesis, finalized, justified, \
+                         a slot or a 0x-prefixed root",
+                        other
+                    )
+                }),
+        }
+    }
+}
+
+/// The root of an object, in the format of the standard Eth2 API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RootData {
+    pub root: Hash256,
+}
+
+/// The header of a block, in the format of the standard Eth2 API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BlockHeaderData {
+    pub root: Hash256,
+    /// `true` if the block is in the canonical chain.
+    pub canonical: bool,
+    /// `true` if the block is in the canonical chain and at or prior to the finalized checkpoint.
+    #[serde(default)]
+    pub finalized: bool,
+    pub header: SignedBeaconBlockHeader,
 }
 
 /// Information about the block and state that are at head of the beacon chain.
@@ -43,6 +135,304 @@ pub struct ValidatorResponse {
     pub validator: Option<Validator>,
 }
 
+/// A validator in the registry of a state, in the format of the standard Eth2 API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorData {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+    pub status: ValidatorStatus,
+    pub validator: Validator,
+}
+
+/// The balance of a validator in the registry of a state, in the format of the standard Eth2 API.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidatorBalanceData {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub index: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub balance: u64,
+}
+
+/// A page of a list response, as selected by the `offset` and `limit` query parameters.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaginatedResponse<T> {
+    pub data: Vec<T>,
+    pub meta: PaginationMetaData,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PaginationMetaData {
+    /// The number of entries in the full list, of which `data` is a page.
+    pub total: usize,
+}
+
+/// The status of a validator, as defined by the standard Eth2 API.
+///
+/// The last four variants are the broader categories of the others, see `Self::superstatus`. A
+/// validator always has one of the first nine statuses, whilst any status may be used as a
+/// filter.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValidatorStatus {
+    PendingInitialized,
+    PendingQueued,
+    ActiveOngoing,
+    ActiveExiting,
+    ActiveSlashed,
+    ExitedUnslashed,
+    ExitedSlashed,
+    WithdrawalPossible,
+    WithdrawalDone,
+    Pending,
+    Active,
+    Exited,
+    Withdrawal,
+}
+
+impl ValidatorStatus {
+    /// All statuses, including the categories.
+    pub const ALL: [ValidatorStatus; 13] = [
+        ValidatorStatus::PendingInitialized,
+        ValidatorStatus::PendingQueued,
+        ValidatorStatus::ActiveOngoing,
+        ValidatorStatus::ActiveExiting,
+        ValidatorStatus::ActiveSlashed,
+        ValidatorStatus::ExitedUnslashed,
+        ValidatorStatus::ExitedSlashed,
+        ValidatorStatus::WithdrawalPossible,
+        ValidatorStatus::WithdrawalDone,
+        ValidatorStatus::Pending,
+        ValidatorStatus::Active,
+        ValidatorStatus::Exited,
+        ValidatorStatus::Withdrawal,
+    ];
+
+    /// Returns the status of `validator` at `epoch`.
+    pub fn from_validator(validator: &Validator, epoch: Epoch, far_future_epoch: Epoch) -> Self {
+        if validator.is_withdrawable_at(epoch) {
+            if validator.effective_balance == 0 {
+                ValidatorStatus::WithdrawalDone
+            } else {
+                ValidatorStatus::WithdrawalPossible
+            }
+        } else if validator.is_exited_at(epoch) {
+            if validator.slashed {
+                ValidatorStatus::ExitedSlashed
+            } else {
+                ValidatorStatus::ExitedUnslashed
+            }
+        } else if validator.is_active_at(epoch) {
+            if validator.exit_epoch == far_future_epoch {
+                ValidatorStatus::ActiveOngoing
+            } else if validator.slashed {
+                ValidatorStatus::ActiveSlashed
+            } else {
+                ValidatorStatus::ActiveExiting
+            }
+        } else if validator.activation_eligibility_epoch == far_future_epoch {
+            ValidatorStatus::PendingInitialized
+        } else {
+            ValidatorStatus::PendingQueued
+        }
+    }
+
+    /// Returns the category of the status, e.g., `Active` for `ActiveOngoing`. A category is its
+    /// own superstatus.
+    pub fn superstatus(self) -> Self {
+        match self {
+            ValidatorStatus::PendingInitialized
+            | ValidatorStatus::PendingQueued
+            | ValidatorStatus::Pending => ValidatorStatus::Pending,
+            ValidatorStatus::ActiveOngoing
+            | ValidatorStatus::ActiveExiting
+            | ValidatorStatus::ActiveSlashed
+            | ValidatorStatus::Active => ValidatorStatus::Active,
+            ValidatorStatus::ExitedUnslashed
+            | ValidatorStatus::ExitedSlashed
+            | ValidatorStatus::Exited => ValidatorStatus::Exited,
+            ValidatorStatus::WithdrawalPossible
+            | ValidatorStatus::WithdrawalDone
+            | ValidatorStatus::Withdrawal => ValidatorStatus::Withdrawal,
+        }
+    }
+
+    /// Returns `true` if a validator with this status is selected by the `filter` status, i.e.,
+    /// if they are equal or `filter` is the category of this status.
+    pub fn matches(self, filter: Self) -> bool {
+        self == filter || self.superstatus() == filter
+    }
+
+    /// The name of the status, as used in the query parameter and the JSON encoding.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValidatorStatus::PendingInitialized => "pending_initialized",
+            ValidatorStatus::PendingQueued => "pending_queued",
+            ValidatorStatus::ActiveOngoing => "active_ongoing",
+            ValidatorStatus::ActiveExiting => "active_exiting",
+            ValidatorStatus::ActiveSlashed => "active_slashed",
+            ValidatorStatus::ExitedUnslashed => "exited_unslashed",
+            ValidatorStatus::ExitedSlashed => "exited_slashed",
+            ValidatorStatus::WithdrawalPossible => "withdrawal_possible",
+            ValidatorStatus::WithdrawalDone => "withdrawal_done",
+            ValidatorStatus::Pending => "pending",
+            ValidatorStatus::Active => "active",
+            ValidatorStatus::Exited => "exited",
+            ValidatorStatus::Withdrawal => "withdrawal",
+        }
+    }
+}
+
+impl FromStr for ValidatorStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|status| status.name() == s)
+            .ok_or_else(|| {
+                let valid = Self::ALL
+                    .iter()
+                    .map(|status| status.name())
+                    .collect::<Vec<_>>();
+                format!(
+                    "Unknown validator status {:?}, valid statuses are: {}",
+                    s,
+                    valid.join(",")
+                )
+            })
+    }
+}
+
+/// A field of a `ValidatorResponse`, which may be selected with the `fields` query parameter of
+/// the validator listing endpoints.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValidatorField {
+    Pubkey,
+    ValidatorIndex,
+    Balance,
+    Validator,
+}
+
+impl ValidatorField {
+    /// All fields, in the order in which they appear in a `ValidatorResponse`.
+    pub const ALL: [ValidatorField; 4] = [
+        ValidatorField::Pubkey,
+        ValidatorField::ValidatorIndex,
+        ValidatorField::Balance,
+        ValidatorField::Validator,
+    ];
+
+    /// The name of the field, as used in the query parameter and the JSON encoding.
+    pub fn name(self) -> &'static str {
+        match self {
+            ValidatorField::Pubkey => "pubkey",
+            ValidatorField::ValidatorIndex => "validator_index",
+            ValidatorField::Balance => "balance",
+            ValidatorField::Validator => "validator",
+        }
+    }
+
+    /// Parses a comma-separated list of field names, e.g., `validator_index,balance`.
+    ///
+    /// The fields are returned in the order of `Self::ALL`, without duplicates.
+    pub fn parse_list(list: &str) -> Result<Vec<Self>, String> {
+        let requested = list
+            .split(',')
+            .map(|name| {
+                Self::ALL
+                    .iter()
+                    .copied()
+                    .find(|field| field.name() == name.trim())
+                    .ok_or_else(|| {
+                        let valid = Self::ALL
+                            .iter()
+                            .map(|field| field.name())
+                            .collect::<Vec<_>>();
+                        format!(
+                            "Unknown validator field {:?}, valid fields are: {}",
+                            name,
+                            valid.join(",")
+                        )
+                    })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self::ALL
+            .iter()
+            .copied()
+            .filter(|field| requested.contains(field))
+            .collect())
+    }
+}
+
+/// A list of validators, of which only `fields` are included in the JSON and YAML encodings if
+/// `fields` is `Some`.
+///
+/// If `fields` is `None`, the encoding is identical to that of `Vec<ValidatorResponse>`. The SSZ
+/// encoding always contains the full `ValidatorResponse`s.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidatorListing {
+    pub validators: Vec<ValidatorResponse>,
+    pub fields: Option<Vec<ValidatorField>>,
+}
+
+impl Serialize for ValidatorListing {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match &self.fields {
+            None => self.validators.serialize(serializer),
+            Some(fields) => serializer.collect_seq(
+                self.validators
+                    .iter()
+                    .map(|response| SelectedFields { response, fields }),
+            ),
+        }
+    }
+}
+
+impl ssz::Encode for ValidatorListing {
+    fn is_ssz_fixed_len() -> bool {
+        <Vec<ValidatorResponse> as ssz::Encode>::is_ssz_fixed_len()
+    }
+
+    fn ssz_append(&self, buf: &mut Vec<u8>) {
+        ssz::Encode::ssz_append(&self.validators, buf)
+    }
+
+    fn ssz_bytes_len(&self) -> usize {
+        ssz::Encode::ssz_bytes_len(&self.validators)
+    }
+}
+
+/// Serializes only the selected `fields` of a `ValidatorResponse`, without copying it.
+struct SelectedFields<'a> {
+    response: &'a ValidatorResponse,
+    fields: &'a [ValidatorField],
+}
+
+impl<'a> Serialize for SelectedFields<'a> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.fields.len()))?;
+        for field in self.fields {
+            match field {
+                ValidatorField::Pubkey => map.serialize_entry(field.name(), &self.response.pubkey),
+                ValidatorField::ValidatorIndex => {
+                    map.serialize_entry(field.name(), &self.response.validator_index)
+                }
+                ValidatorField::Balance => {
+                    map.serialize_entry(field.name(), &self.response.balance)
+                }
+                ValidatorField::Validator => {
+                    map.serialize_entry(field.name(), &self.response.validator)
+                }
+            }?;
+        }
+        map.end()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
 pub struct ValidatorRequest {
     /// If set to `None`, uses the canonical head state.
@@ -55,6 +445,13 @@ pub struct Committee {
     pub slot: Slot,
     pub index: CommitteeIndex,
     pub committee: Vec<usize>,
+    /// The public key of each member of `committee`, in the same order.
+    ///
+    /// Only present when explicitly requested via the `include_pubkeys` query parameter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ssz(skip_serializing)]
+    #[ssz(skip_deserializing)]
+    pub pubkeys: Option<Vec<PublicKeyBytes>>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Encode, Decode)]
@@ -63,3 +460,161 @@ pub struct StateResponse<T: EthSpec> {
     pub root: Hash256,
     pub beacon_state: BeaconState<T>,
 }
+
+/// The response to publishing an operation (e.g., a slashing or voluntary exit) to the node.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PublishOperationResponse {
+    /// The operation was new to the node and has been published to the network.
+    pub broadcast: bool,
+    /// The node had already seen an equivalent operation, so nothing was imported or published.
+    pub already_known: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bls::SecretKey;
+    use ssz::Encode;
+
+    fn validators() -> Vec<ValidatorResponse> {
+        let known = PublicKeyBytes::from(SecretKey::deserialize(&[1; 32]).unwrap().public_key());
+        let unknown = PublicKeyBytes::from(SecretKey::deserialize(&[2; 32]).unwrap().public_key());
+        vec![
+            ValidatorResponse {
+                pubkey: known,
+                validator_index: Some(7),
+                balance: Some(32_000_000_000),
+                validator: Some(Validator::default()),
+            },
+            ValidatorResponse {
+                pubkey: unknown,
+                validator_index: None,
+                balance: None,
+                validator: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn validator_listing_without_fields_is_unchanged() {
+        let listing = ValidatorListing {
+            validators: validators(),
+            fields: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&listing).unwrap(),
+            serde_json::to_string(&validators()).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_string_pretty(&listing).unwrap(),
+            serde_json::to_string_pretty(&validators()).unwrap()
+        );
+        assert_eq!(listing.as_ssz_bytes(), validators().as_ssz_bytes());
+    }
+
+    #[test]
+    fn validator_listing_with_fields() {
+        let listing = ValidatorListing {
+            validators: validators(),
+            fields: Some(ValidatorField::parse_list("balance,validator_index").unwrap()),
+        };
+
+        assert_eq!(
+            serde_json::to_string(&listing).unwrap(),
+            r#"[{"validator_index":7,"balance":32000000000},{"validator_index":null,"balance":null}]"#
+        );
+        // The SSZ encoding ignores the selected fields.
+        assert_eq!(listing.as_ssz_bytes(), validators().as_ssz_bytes());
+    }
+
+    #[test]
+    fn parse_validator_fields() {
+        assert_eq!(
+            ValidatorField::parse_list("validator,pubkey, balance,pubkey"),
+            Ok(vec![
+                ValidatorField::Pubkey,
+                ValidatorField::Balance,
+                ValidatorField::Validator
+            ])
+        );
+
+        let error = ValidatorField::parse_list("validator_index,status").unwrap_err();
+        assert!(error.contains("\"status\""));
+        assert!(error.contains("pubkey,validator_index,balance,validator"));
+        assert!(ValidatorField::parse_list("").is_err());
+    }
+
+    #[test]
+    fn validator_status() {
+        let far_future_epoch = Epoch::new(u64::max_value());
+        let status = |validator: &Validator, epoch| {
+            ValidatorStatus::from_validator(validator, Epoch::new(epoch), far_future_epoch)
+        };
+
+        let mut validator = Validator {
+            effective_balance: 32,
+            activation_eligibility_epoch: far_future_epoch,
+            activation_epoch: far_future_epoch,
+            exit_epoch: far_future_epoch,
+            withdrawable_epoch: far_future_epoch,
+            ..Validator::default()
+        };
+        assert_eq!(status(&validator, 0), ValidatorStatus::PendingInitialized);
+
+        validator.activation_eligibility_epoch = Epoch::new(1);
+        validator.activation_epoch = Epoch::new(3);
+        assert_eq!(status(&validator, 2), ValidatorStatus::PendingQueued);
+        assert_eq!(status(&validator, 3), ValidatorStatus::ActiveOngoing);
+
+        validator.exit_epoch = Epoch::new(5);
+        validator.withdrawable_epoch = Epoch::new(7);
+        assert_eq!(status(&validator, 4), ValidatorStatus::ActiveExiting);
+        assert_eq!(status(&validator, 5), ValidatorStatus::ExitedUnslashed);
+        assert_eq!(status(&validator, 7), ValidatorStatus::WithdrawalPossible);
+
+        validator.slashed = true;
+        assert_eq!(status(&validator, 4), ValidatorStatus::ActiveSlashed);
+        assert_eq!(status(&validator, 5), ValidatorStatus::ExitedSlashed);
+
+        validator.effective_balance = 0;
+        assert_eq!(status(&validator, 7), ValidatorStatus::WithdrawalDone);
+    }
+
+    #[test]
+    fn validator_status_filters() {
+        assert!(ValidatorStatus::ActiveOngoing.matches(ValidatorStatus::ActiveOngoing));
+        assert!(ValidatorStatus::ActiveOngoing.matches(ValidatorStatus::Active));
+        assert!(!ValidatorStatus::ActiveOngoing.matches(ValidatorStatus::ActiveExiting));
+        assert!(!ValidatorStatus::PendingQueued.matches(ValidatorStatus::Active));
+
+        for status in ValidatorStatus::ALL.iter() {
+            assert_eq!(status.name().parse(), Ok(*status));
+            assert_eq!(
+                serde_json::to_string(status).unwrap(),
+                format!("\"{}\"", status.name())
+            );
+        }
+
+        let error = "active_pending".parse::<ValidatorStatus>().unwrap_err();
+        assert!(error.contains("\"active_pending\""));
+        assert!(error.contains("pending_initialized,pending_queued,active_ongoing"));
+    }
+
+    #[test]
+    fn parse_block_id() {
+        assert_eq!("head".parse(), Ok(BlockId::Head));
+        assert_eq!("genesis".parse(), Ok(BlockId::Genesis));
+        assert_eq!("finalized".parse(), Ok(BlockId::Finalized));
+        assert_eq!("justified".parse(), Ok(BlockId::Justified));
+        assert_eq!("12".parse(), Ok(BlockId::Slot(Slot::new(12))));
+        assert_eq!(
+            format!("0x{}", "01".repeat(32)).parse(),
+            Ok(BlockId::Root(Hash256::repeat_byte(1)))
+        );
+
+        assert!("0x01".parse::<BlockId>().is_err());
+        assert!("-1".parse::<BlockId>().is_err());
+        assert!("Finalized".parse::<BlockId>().is_err());
+    }
+}
@@ -1,10 +1,39 @@
-use crate::{ApiError, ApiResult};
+use crate::{metrics, ApiError, ApiResult};
 use environment::TaskExecutor;
+use futures::channel::{mpsc, oneshot};
+use hyper::body::{Bytes, HttpBody};
 use hyper::header;
 use hyper::{Body, Request, Response, StatusCode};
 use serde::Deserialize;
 use serde::Serialize;
+use slog::{crit, Logger};
 use ssz::Encode;
+use std::any::Any;
+use std::future::Future;
+use std::io::{self, Write};
+use std::mem;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// An `Accept` header value requesting indented, human-readable JSON.
+pub const PRETTY_JSON_CONTENT_TYPE: &str = "application/json+pretty";
+
+/// An `Accept` header value requesting the raw SSZ bytes of a response.
+pub const OCTET_STREAM_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// The size in bytes of each chunk of a streamed JSON response.
+const JSON_STREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The number of chunks of a streamed JSON response that may be serialized ahead of the client.
+const JSON_STREAM_BUFFERED_CHUNKS: usize = 4;
+
+/// How long a streamed JSON response waits for the client to read a chunk before it is aborted.
+const JSON_STREAM_SEND_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How often a streamed JSON response which is waiting for the client checks for space.
+const JSON_STREAM_POLL_INTERVAL: Duration = Duration::from_millis(1);
 
 /// Defines the encoding for the API.
 #[derive(Clone, Serialize, Deserialize, Copy)]
@@ -40,8 +69,14 @@ pub struct Handler<T> {
     req: Request<()>,
     body: Body,
     ctx: T,
+    accept_header: String,
     encoding: ApiEncodingFormat,
     allow_body: bool,
+    timeout: Option<Duration>,
+    max_body_size: Option<u64>,
+    blocking_permits: Option<Arc<Semaphore>>,
+    pretty: bool,
+    log: Option<Logger>,
 }
 
 impl<T: Clone + Send + Sync + 'static> Handler<T> {
@@ -68,10 +103,53 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
             body,
             ctx,
             allow_body: false,
+            timeout: None,
+            max_body_size: None,
+            blocking_permits: None,
+            pretty: accept_header == PRETTY_JSON_CONTENT_TYPE,
             encoding: ApiEncodingFormat::from(accept_header.as_str()),
+            accept_header,
+            log: None,
         })
     }
 
+    /// Returns the media types listed in the `Accept` header, without their parameters.
+    fn accepted_media_types(&self) -> impl Iterator<Item = &str> {
+        self.accept_header
+            .split(',')
+            .filter_map(|media_type| media_type.split(';').next())
+            .map(str::trim)
+            .filter(|media_type| !media_type.is_empty())
+    }
+
+    /// Returns `true` if `content_type` is the first media type listed in the `Accept` header.
+    pub fn prefers(&self, content_type: &str) -> bool {
+        self.accepted_media_types().next() == Some(content_type)
+    }
+
+    /// Returns a `406 Not Acceptable` error unless the `Accept` header is absent, or lists a
+    /// wildcard or one of `content_types`.
+    pub fn require_accept(self, content_types: &[&str]) -> Result<Self, ApiError> {
+        let mut media_types = self.accepted_media_types().peekable();
+
+        let acceptable = media_types.peek().is_none()
+            || media_types.any(|media_type| {
+                media_type == "*/*"
+                    || media_type == "application/*"
+                    || content_types.contains(&media_type)
+            });
+
+        if acceptable {
+            Ok(self)
+        } else {
+            Err(ApiError::NotAcceptable(format!(
+                "Unable to respond with {}, supported types are: {}",
+                self.accept_header,
+                content_types.join(", ")
+            )))
+        }
+    }
+
     /// The default behaviour is to return an error if any body is supplied in the request. Calling
     /// this function disables that error.
     pub fn allow_body(mut self) -> Self {
@@ -79,16 +157,70 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         self
     }
 
+    /// Returns the encoding that will be used for the response.
+    pub fn encoding(&self) -> ApiEncodingFormat {
+        self.encoding
+    }
+
+    /// Overrides the response encoding that was determined from the `Accept` header.
+    pub fn with_encoding(mut self, encoding: ApiEncodingFormat) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
+    /// If `pretty` is `true`, JSON responses will be indented for human readability. Has no
+    /// effect on other encodings.
+    pub fn pretty_json(mut self, pretty: bool) -> Self {
+        self.pretty |= pretty;
+        self
+    }
+
+    /// Causes `in_blocking_task` to return a `504 Gateway Timeout` error if the task does not
+    /// complete within `timeout`. A `None` timeout (the default) waits indefinitely.
+    ///
+    /// The timeout only applies to producing the response, not to streaming its body.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Causes a `413 Payload Too Large` error if the request body is longer than `max_size`
+    /// bytes. The body is not read beyond `max_size`. A `None` limit (the default) allows a body
+    /// of any size.
+    pub fn with_max_body_size(mut self, max_size: Option<u64>) -> Self {
+        self.max_body_size = max_size;
+        self
+    }
+
+    /// Causes `in_blocking_task` to wait for a permit from `permits` before starting its task, so
+    /// the number of tasks running at once is bounded. The time spent waiting counts towards the
+    /// timeout set with `with_timeout`. With no permits (the default), tasks start immediately.
+    ///
+    /// The permit is held until the response has been serialized, including on the blocking
+    /// executor for a streamed response.
+    pub fn with_blocking_permits(mut self, permits: Arc<Semaphore>) -> Self {
+        self.blocking_permits = Some(permits);
+        self
+    }
+
+    /// Sets the logger used to report panics in `in_blocking_task`.
+    pub fn with_log(mut self, log: Logger) -> Self {
+        self.log = Some(log);
+        self
+    }
+
     /// Return a simple static value.
     ///
     /// Does not use the blocking executor.
     pub async fn static_value<V>(self, value: V) -> Result<HandledRequest<V>, ApiError> {
         // Always check and disallow a body for a static value.
-        let _ = Self::get_body(self.body, false).await?;
+        let _ = Self::get_body(self.body, false, self.max_body_size).await?;
 
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            pretty: self.pretty,
+            permit: None,
         })
     }
 
@@ -100,7 +232,7 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         V: Send + Sync + 'static,
         F: Fn(Request<Vec<u8>>, T) -> Result<V, ApiError> + Send + Sync + 'static,
     {
-        let body = Self::get_body(self.body, self.allow_body).await?;
+        let body = Self::get_body(self.body, self.allow_body, self.max_body_size).await?;
         let (req_parts, _) = self.req.into_parts();
         let req = Request::from_parts(req_parts, body);
 
@@ -109,38 +241,84 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            pretty: self.pretty,
+            permit: None,
         })
     }
 
     /// Spawns `func` on the blocking executor.
     ///
-    /// This method is suitable for handling long-running or intensive tasks.
+    /// This method is suitable for handling long-running or intensive tasks, since it does not
+    /// occupy a core executor thread. If permits have been set with `with_blocking_permits`, the
+    /// task is queued until one is available. If a timeout has been set with `with_timeout`, the
+    /// request fails once that timeout expires.
+    ///
+    /// If `func` panics, the panic is logged and the request fails with a generic server error.
     pub async fn in_blocking_task<F, V>(self, func: F) -> Result<HandledRequest<V>, ApiError>
     where
         V: Send + Sync + 'static,
         F: Fn(Request<Vec<u8>>, T) -> Result<V, ApiError> + Send + Sync + 'static,
     {
         let ctx = self.ctx;
-        let body = Self::get_body(self.body, self.allow_body).await?;
+        let log = self.log;
+        let body = Self::get_body(self.body, self.allow_body, self.max_body_size).await?;
         let (req_parts, _) = self.req.into_parts();
         let req = Request::from_parts(req_parts, body);
 
-        let value = self
-            .executor
-            .clone()
-            .handle
-            .spawn_blocking(move || func(req, ctx))
-            .await
-            .map_err(|e| {
-                ApiError::ServerError(format!(
-                    "Failed to get blocking join handle: {}",
-                    e.to_string()
+        let run_task = move || {
+            // The request and context are moved into `func` and dropped if it panics, so no
+            // broken invariants can be observed after unwinding.
+            panic::catch_unwind(AssertUnwindSafe(|| func(req, ctx))).unwrap_or_else(|payload| {
+                metrics::inc_counter(&metrics::BEACON_HTTP_API_HANDLER_PANICS_TOTAL);
+                if let Some(log) = log {
+                    crit!(
+                        log,
+                        "HTTP API handler panicked";
+                        "message" => panic_message(payload.as_ref())
+                    );
+                }
+                Err(ApiError::ServerError(
+                    "Internal server error: the request handler failed".to_string(),
                 ))
-            })??;
+            })
+        };
+
+        // The permit is held by a separate task until the blocking task completes, even if this
+        // request times out first, so a timed out task still counts towards the bound. It is then
+        // passed on with the result, to be held until the response is serialized.
+        let (result_tx, result_rx) = oneshot::channel();
+        let handle = self.executor.handle.clone();
+        let permits = self.blocking_permits;
+        self.executor.handle.spawn(async move {
+            let permit = match permits {
+                Some(permits) => Some(BlockingPermit::acquire(permits).await),
+                None => None,
+            };
+
+            // Don't start a task for a request which timed out whilst waiting for a permit.
+            if result_tx.is_canceled() {
+                return;
+            }
+
+            let result = handle.spawn_blocking(run_task).await;
+            let _ = result_tx.send((result, permit));
+        });
+
+        let (result, permit) = with_deadline(result_rx, self.timeout)
+            .await?
+            .map_err(|_| ApiError::ServerError("Blocking task was cancelled".to_string()))?;
+        let value = result.map_err(|e| {
+            ApiError::ServerError(format!(
+                "Failed to get blocking join handle: {}",
+                e.to_string()
+            ))
+        })??;
 
         Ok(HandledRequest {
             value,
             encoding: self.encoding,
+            pretty: self.pretty,
+            permit,
         })
     }
 
@@ -161,27 +339,109 @@ impl<T: Clone + Send + Sync + 'static> Handler<T> {
             .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
     }
 
-    /// Downloads the bytes for `body`.
-    async fn get_body(body: Body, allow_body: bool) -> Result<Vec<u8>, ApiError> {
-        let bytes = hyper::body::to_bytes(body)
-            .await
-            .map_err(|e| ApiError::ServerError(format!("Unable to get request body: {:?}", e)))?;
+    /// Downloads the bytes for `body`, failing as soon as more than `max_size` bytes are
+    /// declared or received.
+    async fn get_body(
+        mut body: Body,
+        allow_body: bool,
+        max_size: Option<u64>,
+    ) -> Result<Vec<u8>, ApiError> {
+        let too_large = |max_size| {
+            ApiError::PayloadTooLarge(format!(
+                "The request body must not be longer than {} bytes",
+                max_size
+            ))
+        };
+
+        // Reject a body with a `Content-Length` over the limit without reading any of it.
+        if let Some(max_size) = max_size {
+            if body.size_hint().lower() > max_size {
+                return Err(too_large(max_size));
+            }
+        }
+
+        let mut bytes = vec![];
+        while let Some(chunk) = body.data().await {
+            let chunk = chunk.map_err(|e| {
+                ApiError::ServerError(format!("Unable to get request body: {:?}", e))
+            })?;
+            bytes.extend_from_slice(&chunk);
 
-        if !allow_body && !bytes[..].is_empty() {
+            if let Some(max_size) = max_size {
+                if bytes.len() as u64 > max_size {
+                    return Err(too_large(max_size));
+                }
+            }
+        }
+
+        if !allow_body && !bytes.is_empty() {
             Err(ApiError::BadRequest(
                 "The request body must be empty".to_string(),
             ))
         } else {
-            Ok(bytes.into_iter().collect())
+            Ok(bytes)
         }
     }
 }
 
+/// Awaits `future`, returning an `ApiError::GatewayTimeout` if it does not resolve within
+/// `timeout`.
+///
+/// Blocking tasks cannot be cancelled once started, so if `future` is a join handle the task runs
+/// to completion in the background and its result is discarded. Any locks it holds are released
+/// as normal when it completes, rather than being left poisoned.
+async fn with_deadline<F: Future>(
+    future: F,
+    timeout: Option<Duration>,
+) -> Result<F::Output, ApiError> {
+    match timeout {
+        Some(timeout) => tokio::time::timeout(timeout, future).await.map_err(|_| {
+            ApiError::GatewayTimeout(format!(
+                "Request did not complete within {} ms, try narrowing the query",
+                timeout.as_millis()
+            ))
+        }),
+        None => Ok(future.await),
+    }
+}
+
+/// A permit from the semaphore set with `with_blocking_permits`, which is returned to it when
+/// dropped.
+///
+/// Unlike a `SemaphorePermit`, it does not borrow the semaphore, so it may be moved into the task
+/// which serializes a streamed response.
+struct BlockingPermit(Arc<Semaphore>);
+
+impl BlockingPermit {
+    async fn acquire(permits: Arc<Semaphore>) -> Self {
+        permits.acquire().await.forget();
+        BlockingPermit(permits)
+    }
+}
+
+impl Drop for BlockingPermit {
+    fn drop(&mut self) {
+        self.0.add_permits(1);
+    }
+}
+
+/// Returns the message of a panic, if it was raised with a string.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic payload")
+}
+
 /// A request that has been "handled" and now a result (`value`) needs to be serialize and
 /// returned.
 pub struct HandledRequest<V> {
     encoding: ApiEncodingFormat,
     value: V,
+    pretty: bool,
+    /// The blocking permit of the task which produced `value`, if any.
+    permit: Option<BlockingPermit>,
 }
 
 impl HandledRequest<String> {
@@ -195,6 +455,16 @@ impl HandledRequest<String> {
     }
 }
 
+impl HandledRequest<StatusCode> {
+    /// Returns an empty response with the status code of the value.
+    pub fn status_encoding(self) -> ApiResult {
+        Response::builder()
+            .status(self.value)
+            .body(Body::empty())
+            .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+    }
+}
+
 impl<V: Serialize + Encode> HandledRequest<V> {
     /// Suitable for all items which implement `serde` and `ssz`.
     pub fn all_encodings(self) -> ApiResult {
@@ -209,19 +479,52 @@ impl<V: Serialize + Encode> HandledRequest<V> {
     }
 }
 
+impl<V: Serialize + Encode + Send + 'static> HandledRequest<V> {
+    /// As `all_encodings`, except that a JSON response is streamed, see
+    /// `streaming_serde_encodings`.
+    pub fn streaming_all_encodings(self) -> ApiResult {
+        match self.encoding {
+            ApiEncodingFormat::SSZ => self.all_encodings(),
+            _ => self.streaming_serde_encodings(),
+        }
+    }
+}
+
+impl<V: Encode> HandledRequest<V> {
+    /// Returns the raw SSZ bytes of the value, without going through `serde`.
+    pub fn octet_stream_encoding(self) -> ApiResult {
+        let bytes = self.value.as_ssz_bytes();
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .header(header::CONTENT_TYPE, OCTET_STREAM_CONTENT_TYPE)
+            .header(header::CONTENT_LENGTH, bytes.len())
+            .body(Body::from(bytes))
+            .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+    }
+}
+
 impl<V: Serialize> HandledRequest<V> {
     /// Suitable for items which only implement `serde`.
     pub fn serde_encodings(self) -> ApiResult {
         let (body, content_type) = match self.encoding {
-            ApiEncodingFormat::JSON => (
-                Body::from(serde_json::to_string(&self.value).map_err(|e| {
-                    ApiError::ServerError(format!(
-                        "Unable to serialize response body as JSON: {:?}",
-                        e
-                    ))
-                })?),
-                "application/json",
-            ),
+            ApiEncodingFormat::JSON => {
+                let json = if self.pretty {
+                    serde_json::to_string_pretty(&self.value)
+                } else {
+                    serde_json::to_string(&self.value)
+                };
+
+                (
+                    Body::from(json.map_err(|e| {
+                        ApiError::ServerError(format!(
+                            "Unable to serialize response body as JSON: {:?}",
+                            e
+                        ))
+                    })?),
+                    "application/json",
+                )
+            }
             ApiEncodingFormat::SSZ => {
                 return Err(ApiError::UnsupportedType(
                     "Response cannot be encoded as SSZ.".into(),
@@ -245,3 +548,563 @@ impl<V: Serialize> HandledRequest<V> {
             .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
     }
 }
+
+impl<V: Serialize + Send + 'static> HandledRequest<V> {
+    /// As `serde_encodings`, except that a JSON response is serialized on the blocking executor
+    /// and streamed to the client in chunks of `JSON_STREAM_CHUNK_SIZE` bytes, rather than being
+    /// built in memory. This should be used for values with large JSON encodings.
+    ///
+    /// Serialization waits for the client once `JSON_STREAM_BUFFERED_CHUNKS` chunks are pending,
+    /// and stops if the client disconnects or does not read a chunk within
+    /// `JSON_STREAM_SEND_TIMEOUT`. An error during serialization aborts the response.
+    pub fn streaming_serde_encodings(self) -> ApiResult {
+        match self.encoding {
+            ApiEncodingFormat::JSON => {}
+            _ => return self.serde_encodings(),
+        }
+
+        let (sender, receiver) = mpsc::channel(JSON_STREAM_BUFFERED_CHUNKS);
+        let pretty = self.pretty;
+        let value = self.value;
+        let permit = self.permit;
+
+        tokio::task::spawn_blocking(move || {
+            // Serialization counts towards the bound on blocking tasks, like producing the value.
+            let _permit = permit;
+            let mut writer = ChunkWriter {
+                buffer: Vec::with_capacity(JSON_STREAM_CHUNK_SIZE),
+                sender,
+                send_timeout: JSON_STREAM_SEND_TIMEOUT,
+            };
+
+            let result = if pretty {
+                serde_json::to_writer_pretty(&mut writer, &value)
+            } else {
+                serde_json::to_writer(&mut writer, &value)
+            };
+
+            if let Err(e) = result
+                .map_err(io::Error::from)
+                .and_then(|()| writer.flush())
+            {
+                // If the client has disconnected or stalled this fails too, which is fine.
+                let _ = writer.send(Err(e));
+            }
+        });
+
+        Response::builder()
+            .status(StatusCode::OK)
+            .header("content-type", "application/json")
+            .body(Body::wrap_stream(receiver))
+            .map_err(|e| ApiError::ServerError(format!("Failed to build response: {:?}", e)))
+    }
+}
+
+/// Buffers bytes written to it and sends them to `sender` in chunks of `JSON_STREAM_CHUNK_SIZE`
+/// bytes, blocking whilst the channel is full.
+///
+/// Writes fail once the receiver has been dropped, e.g., because the client disconnected, or if
+/// the channel stays full for longer than `send_timeout`, e.g., because the client stopped reading.
+struct ChunkWriter {
+    buffer: Vec<u8>,
+    sender: mpsc::Sender<Result<Bytes, io::Error>>,
+    send_timeout: Duration,
+}
+
+impl ChunkWriter {
+    /// Sends `item` to the receiver, waiting at most `send_timeout` for space in the channel.
+    fn send(&mut self, mut item: Result<Bytes, io::Error>) -> io::Result<()> {
+        let deadline = Instant::now() + self.send_timeout;
+
+        loop {
+            match self.sender.try_send(item) {
+                Ok(()) => return Ok(()),
+                Err(e) if e.is_full() => {
+                    if Instant::now() >= deadline {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            "Response body was not read in time",
+                        ));
+                    }
+                    item = e.into_inner();
+                    std::thread::sleep(JSON_STREAM_POLL_INTERVAL);
+                }
+                Err(_) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::BrokenPipe,
+                        "Response body was dropped",
+                    ))
+                }
+            }
+        }
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.buffer.extend_from_slice(buf);
+        if self.buffer.len() >= JSON_STREAM_CHUNK_SIZE {
+            self.flush()?;
+        }
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+
+        let chunk = mem::replace(&mut self.buffer, Vec::with_capacity(JSON_STREAM_CHUNK_SIZE));
+        self.send(Ok(Bytes::from(chunk)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::runtime::Builder;
+
+    fn slow_task(duration: Duration) -> impl Future<Output = Result<u64, tokio::task::JoinError>> {
+        tokio::task::spawn_blocking(move || {
+            std::thread::sleep(duration);
+            42
+        })
+    }
+
+    fn get_body_with_limit(body: Body, max_size: Option<u64>) -> Result<Vec<u8>, ApiError> {
+        let mut runtime = Builder::new().basic_scheduler().build().unwrap();
+        runtime.block_on(Handler::<()>::get_body(body, true, max_size))
+    }
+
+    #[test]
+    fn body_within_limit() {
+        assert_eq!(
+            get_body_with_limit(Body::from(vec![1; 10]), Some(10)),
+            Ok(vec![1; 10])
+        );
+        assert_eq!(
+            get_body_with_limit(Body::from(vec![1; 10]), None),
+            Ok(vec![1; 10])
+        );
+    }
+
+    #[test]
+    fn body_over_limit() {
+        match get_body_with_limit(Body::from(vec![1; 11]), Some(10)) {
+            Err(ApiError::PayloadTooLarge(_)) => {}
+            other => panic!("expected PayloadTooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn streamed_body_over_limit() {
+        // A body without a `Content-Length`, which can only be detected as too large once read.
+        let chunks = (0..4).map(|_| Ok::<_, io::Error>(vec![1; 4]));
+        let body = Body::wrap_stream(futures::stream::iter(chunks));
+
+        match get_body_with_limit(body, Some(10)) {
+            Err(ApiError::PayloadTooLarge(_)) => {}
+            other => panic!("expected PayloadTooLarge, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn deadline_expires() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let result = runtime.block_on(with_deadline(
+            slow_task(Duration::from_millis(500)),
+            Some(Duration::from_millis(10)),
+        ));
+
+        match result {
+            Err(ApiError::GatewayTimeout(_)) => {}
+            other => panic!("expected GatewayTimeout, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn deadline_not_reached() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let result = runtime.block_on(with_deadline(
+            slow_task(Duration::from_millis(10)),
+            Some(Duration::from_secs(10)),
+        ));
+
+        assert_eq!(result.unwrap().unwrap(), 42);
+    }
+
+    #[test]
+    fn no_deadline() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let result = runtime.block_on(with_deadline(slow_task(Duration::from_millis(10)), None));
+
+        assert_eq!(result.unwrap().unwrap(), 42);
+    }
+
+    fn json_body(pretty: bool) -> String {
+        let mut runtime = Builder::new().basic_scheduler().build().unwrap();
+
+        let response = HandledRequest {
+            encoding: ApiEncodingFormat::JSON,
+            value: vec![(1u64, "a".to_string()), (2, "b".to_string())],
+            pretty,
+            permit: None,
+        }
+        .serde_encodings()
+        .expect("should encode as JSON");
+
+        let bytes = runtime
+            .block_on(hyper::body::to_bytes(response.into_body()))
+            .expect("should read body");
+        String::from_utf8(bytes.to_vec()).expect("body should be utf-8")
+    }
+
+    #[test]
+    fn blocking_task_panic() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+
+        let (_signal, exit) = exit_future::signal();
+        let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+        let log = Logger::root(slog::Discard, slog::o!());
+        let executor = TaskExecutor::new(runtime.handle().clone(), exit, log.clone(), shutdown_tx);
+
+        let panics_before = metrics::BEACON_HTTP_API_HANDLER_PANICS_TOTAL
+            .as_ref()
+            .unwrap()
+            .get();
+
+        let request = Request::get("/panic").body(Body::empty()).unwrap();
+        let result = runtime.block_on(async {
+            Handler::new(request, (), executor)?
+                .with_log(log)
+                .in_blocking_task(|_, _| -> Result<u64, ApiError> {
+                    let values: Vec<u64> = Vec::new();
+                    Ok(values[1])
+                })
+                .await?
+                .serde_encodings()
+        });
+
+        match result {
+            Err(ApiError::ServerError(message)) => assert!(
+                !message.contains("index out of bounds"),
+                "the panic message should not be returned to the client"
+            ),
+            other => panic!("expected ServerError, got {:?}", other.map(|_| ())),
+        }
+        assert_eq!(
+            metrics::BEACON_HTTP_API_HANDLER_PANICS_TOTAL
+                .as_ref()
+                .unwrap()
+                .get(),
+            panics_before + 1
+        );
+    }
+
+    fn test_executor(runtime: &tokio::runtime::Runtime) -> (TaskExecutor, exit_future::Signal) {
+        let (signal, exit) = exit_future::signal();
+        let (shutdown_tx, _) = futures::channel::mpsc::channel(1);
+        let log = Logger::root(slog::Discard, slog::o!());
+        let executor = TaskExecutor::new(runtime.handle().clone(), exit, log, shutdown_tx);
+        (executor, signal)
+    }
+
+    /// Records the most tasks running at once, each of which sleeps for a while.
+    #[derive(Clone, Default)]
+    struct Concurrency {
+        running: Arc<AtomicUsize>,
+        max_running: Arc<std::sync::Mutex<usize>>,
+    }
+
+    impl Concurrency {
+        fn run_slow_task(&self) {
+            let now_running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            {
+                let mut max_running = self.max_running.lock().unwrap();
+                *max_running = (*max_running).max(now_running);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// A value which is slow to serialize.
+    struct SlowToSerialize(Concurrency);
+
+    impl Serialize for SlowToSerialize {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.run_slow_task();
+            serializer.serialize_u64(0)
+        }
+    }
+
+    /// A burst of slow requests must neither exceed the permitted number of blocking tasks nor
+    /// delay other tasks on the core executor (e.g., gossip processing).
+    #[test]
+    fn blocking_tasks_are_bounded() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (executor, _signal) = test_executor(&runtime);
+
+        let permits = Arc::new(Semaphore::new(2));
+        let concurrency = Concurrency::default();
+
+        let requests = (0..8).map(|_| {
+            let concurrency = concurrency.clone();
+            let request = Request::get("/slow").body(Body::empty()).unwrap();
+
+            Handler::new(request, (), executor.clone())
+                .unwrap()
+                .with_blocking_permits(permits.clone())
+                .in_blocking_task(move |_, _| {
+                    concurrency.run_slow_task();
+                    Ok(())
+                })
+        });
+
+        // Streamed responses are serialized on the blocking executor too, after the handler has
+        // returned.
+        let streamed_requests = (0..8).map(|_| {
+            let concurrency = concurrency.clone();
+            let request = Request::get("/streamed").body(Body::empty()).unwrap();
+            let handler = Handler::new(request, (), executor.clone())
+                .unwrap()
+                .with_blocking_permits(permits.clone());
+
+            async move {
+                let response = handler
+                    .in_blocking_task(move |_, _| Ok(SlowToSerialize(concurrency.clone())))
+                    .await?
+                    .streaming_serde_encodings()?;
+                hyper::body::to_bytes(response.into_body())
+                    .await
+                    .map_err(|e| ApiError::ServerError(e.to_string()))
+            }
+        });
+
+        // Measures how late a periodic task on the core executor runs during the burst.
+        let core_task_latency = async {
+            let mut max_latency = Duration::from_millis(0);
+            for _ in 0..20 {
+                let start = std::time::Instant::now();
+                tokio::time::delay_for(Duration::from_millis(10)).await;
+                let latency = start
+                    .elapsed()
+                    .checked_sub(Duration::from_millis(10))
+                    .unwrap_or_default();
+                max_latency = max_latency.max(latency);
+            }
+            max_latency
+        };
+
+        let ((results, streamed_results), max_latency) = runtime.block_on(futures::future::join(
+            futures::future::join(
+                futures::future::join_all(requests),
+                futures::future::join_all(streamed_requests),
+            ),
+            core_task_latency,
+        ));
+
+        assert!(results.iter().all(Result::is_ok));
+        for result in streamed_results {
+            assert_eq!(&result.expect("should stream response")[..], b"0");
+        }
+        assert_eq!(*concurrency.max_running.lock().unwrap(), 2);
+        assert!(
+            max_latency < Duration::from_millis(40),
+            "core executor was delayed by {:?}",
+            max_latency
+        );
+    }
+
+    #[test]
+    fn queued_blocking_task_times_out() {
+        let mut runtime = Builder::new()
+            .basic_scheduler()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (executor, _signal) = test_executor(&runtime);
+
+        // No permits are available, so the task can never start.
+        let permits = Arc::new(Semaphore::new(0));
+        let started = Arc::new(AtomicUsize::new(0));
+        let task_started = started.clone();
+
+        let request = Request::get("/queued").body(Body::empty()).unwrap();
+        let result = runtime.block_on(
+            Handler::new(request, (), executor)
+                .unwrap()
+                .with_blocking_permits(permits.clone())
+                .with_timeout(Some(Duration::from_millis(10)))
+                .in_blocking_task(move |_, _| {
+                    task_started.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                }),
+        );
+
+        match result {
+            Err(ApiError::GatewayTimeout(_)) => {}
+            other => panic!("expected GatewayTimeout, got {:?}", other.map(|_| ())),
+        }
+
+        // A permit released after the timeout must not start the abandoned task.
+        permits.add_permits(1);
+        runtime.block_on(tokio::time::delay_for(Duration::from_millis(50)));
+        assert_eq!(started.load(Ordering::SeqCst), 0);
+    }
+
+    #[test]
+    fn panic_messages() {
+        let message = panic::catch_unwind(|| panic!("static")).unwrap_err();
+        assert_eq!(panic_message(message.as_ref()), "static");
+
+        let message = panic::catch_unwind(|| panic!("formatted {}", 1)).unwrap_err();
+        assert_eq!(panic_message(message.as_ref()), "formatted 1");
+    }
+
+    fn streamed_json_body<V: Serialize + Send + 'static>(value: V, pretty: bool) -> String {
+        let mut runtime = Builder::new().basic_scheduler().build().unwrap();
+
+        let bytes = runtime
+            .block_on(async {
+                let response = HandledRequest {
+                    encoding: ApiEncodingFormat::JSON,
+                    value,
+                    pretty,
+                    permit: None,
+                }
+                .streaming_serde_encodings()
+                .expect("should encode as JSON");
+
+                hyper::body::to_bytes(response.into_body()).await
+            })
+            .expect("should read body");
+        String::from_utf8(bytes.to_vec()).expect("body should be utf-8")
+    }
+
+    #[test]
+    fn streamed_json() {
+        // Large enough to span several chunks.
+        let value = (0..100_000u64)
+            .map(|i| (i, i.to_string()))
+            .collect::<Vec<_>>();
+        assert!(serde_json::to_string(&value).unwrap().len() > JSON_STREAM_CHUNK_SIZE * 2);
+
+        assert_eq!(
+            streamed_json_body(value.clone(), false),
+            serde_json::to_string(&value).unwrap()
+        );
+        assert_eq!(
+            streamed_json_body(value.clone(), true),
+            serde_json::to_string_pretty(&value).unwrap()
+        );
+        assert_eq!(streamed_json_body(Vec::<u64>::new(), false), "[]");
+    }
+
+    /// Counts the number of times it is serialized.
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Serialize for Counted {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            serializer.serialize_u64(0)
+        }
+    }
+
+    #[test]
+    fn streamed_json_stops_when_body_dropped() {
+        let mut runtime = Builder::new().basic_scheduler().build().unwrap();
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let num_items = 1_000_000;
+        let value = (0..num_items)
+            .map(|_| Counted(count.clone()))
+            .collect::<Vec<_>>();
+
+        runtime.block_on(async {
+            let response = HandledRequest {
+                encoding: ApiEncodingFormat::JSON,
+                value,
+                pretty: false,
+                permit: None,
+            }
+            .streaming_serde_encodings()
+            .expect("should encode as JSON");
+
+            let mut body = response.into_body();
+            body.data()
+                .await
+                .expect("should have a chunk")
+                .expect("chunk should be ok");
+        });
+
+        // The value is dropped once serialization stops.
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while Arc::strong_count(&count) > 1 {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "serialization should stop"
+            );
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(count.load(Ordering::SeqCst) < num_items);
+    }
+
+    #[test]
+    fn streamed_json_stops_when_client_stalls() {
+        let count = Arc::new(AtomicUsize::new(0));
+        let num_items = 1_000_000;
+        let value = (0..num_items)
+            .map(|_| Counted(count.clone()))
+            .collect::<Vec<_>>();
+
+        // The receiver stays open but is never read.
+        let (sender, _receiver) = mpsc::channel(JSON_STREAM_BUFFERED_CHUNKS);
+        let mut writer = ChunkWriter {
+            buffer: Vec::with_capacity(JSON_STREAM_CHUNK_SIZE),
+            sender,
+            send_timeout: Duration::from_millis(50),
+        };
+
+        let error = serde_json::to_writer(&mut writer, &value).expect_err("should time out");
+        assert_eq!(io::Error::from(error).kind(), io::ErrorKind::TimedOut);
+        assert!(count.load(Ordering::SeqCst) < num_items);
+    }
+
+    #[test]
+    fn pretty_json() {
+        let compact = json_body(false);
+        let pretty = json_body(true);
+
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains("\n  "), "pretty JSON should be indented");
+
+        let compact: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        let pretty: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(compact, pretty);
+    }
+}
@@ -2,21 +2,39 @@
 //!
 //! This is primarily used by the validator client and the beacon node rest API.
 
+#[macro_use]
+extern crate lazy_static;
+
 mod api_error;
 mod beacon;
 mod consensus;
 mod handler;
+mod lighthouse;
+mod metrics;
 mod node;
 mod validator;
 
-pub use api_error::{ApiError, ApiResult};
+pub use api_error::{ApiError, ApiResult, ErrorMessage, Failure, IndexedErrorMessage};
 pub use beacon::{
-    BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock, StateResponse,
-    ValidatorRequest, ValidatorResponse,
+    BlockHeaderData, BlockId, BlockResponse, CanonicalHeadResponse, Committee, HeadBeaconBlock,
+    PaginatedResponse, PaginationMetaData, PublishOperationResponse, RootData, StateResponse,
+    ValidatorBalanceData, ValidatorData, ValidatorField, ValidatorListing, ValidatorRequest,
+    ValidatorResponse, ValidatorStatus,
 };
 pub use consensus::{IndividualVote, IndividualVotesRequest, IndividualVotesResponse};
-pub use handler::{ApiEncodingFormat, Handler};
-pub use node::{Health, SyncingResponse, SyncingStatus};
+pub use handler::{
+    ApiEncodingFormat, Handler, OCTET_STREAM_CONTENT_TYPE, PRETTY_JSON_CONTENT_TYPE,
+};
+pub use lighthouse::{
+    BlockBodySummary, Eth1DepositCacheSnapshot, Eth1DepositLog, OpPoolPruneResponse, OpPoolStats,
+    Readiness, SlotAttestationCount,
+};
+pub use node::{
+    ApiIndex, ApiRoute, DepositContractData, GenericResponse, Health, IdentityData,
+    IdentityMetaData, PeerCountData, PeerData, PeerDirection, PeerMetaData, PeerResponse,
+    PeerState, PeersMetaData, PeersResponse, SyncingResponse, SyncingStatus, VersionData,
+};
 pub use validator::{
-    ValidatorDutiesRequest, ValidatorDuty, ValidatorDutyBytes, ValidatorSubscription,
+    AggregateAndProofStatus, AttesterData, BeaconCommitteeSubscription, ValidatorDutiesRequest,
+    ValidatorDuty, ValidatorDutyBytes, ValidatorIndices, ValidatorSubscription,
 };
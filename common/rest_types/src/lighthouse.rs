@@ -0,0 +1,237 @@
+//! Collection of types for the /lighthouse HTTP
+use serde::{Deserialize, Serialize};
+use types::utils::{graffiti_from_hex_str, graffiti_to_hex_str};
+use types::{
+    AttesterSlashing, Deposit, DepositData, Eth1Data, EthSpec, Graffiti, Hash256, ProposerSlashing,
+    SignedBeaconBlock, SignedVoluntaryExit, Slot,
+};
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The number of attestations in the operation pool for some slot.
+pub struct SlotAttestationCount {
+    pub slot: Slot,
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /lighthouse/op_pool HTTP GET.
+pub struct OpPoolStats {
+    /// The total number of attestations in the operation pool.
+    pub attestations: usize,
+    /// The number of attestations in the operation pool for each of the most recent slots, in
+    /// ascending slot order.
+    pub attestations_per_slot: Vec<SlotAttestationCount>,
+    /// The number of attester slashings in the operation pool.
+    pub attester_slashings: usize,
+    /// The number of proposer slashings in the operation pool.
+    pub proposer_slashings: usize,
+    /// The number of voluntary exits in the operation pool.
+    pub voluntary_exits: usize,
+    /// The number of attestations in the naive aggregation pool.
+    pub naive_aggregation_pool: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /lighthouse/readiness HTTP GET, when the node is ready.
+pub struct Readiness {
+    /// The slot of the canonical head.
+    pub head_slot: Slot,
+    /// The slot read from the wall clock.
+    pub current_slot: Slot,
+    /// The number of libp2p connected peers.
+    pub connected_peers: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /lighthouse/op_pool/prune HTTP POST.
+pub struct OpPoolPruneResponse {
+    /// The number of attestations removed from the operation pool.
+    pub attestations: usize,
+    /// The number of attester slashings removed from the operation pool.
+    pub attester_slashings: usize,
+    /// The number of proposer slashings removed from the operation pool.
+    pub proposer_slashings: usize,
+    /// The number of voluntary exits removed from the operation pool.
+    pub voluntary_exits: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+/// The response for the /lighthouse/beacon/blocks/{block_id}/body_summary HTTP GET.
+///
+/// Contains all of the operations in a block body except the attestations, which are only
+/// counted.
+pub struct BlockBodySummary<T: EthSpec> {
+    pub root: Hash256,
+    pub slot: Slot,
+    #[serde(
+        serialize_with = "graffiti_to_hex_str",
+        deserialize_with = "graffiti_from_hex_str"
+    )]
+    pub graffiti: Graffiti,
+    pub eth1_data: Eth1Data,
+    pub num_attestations: usize,
+    pub num_deposits: usize,
+    pub num_voluntary_exits: usize,
+    pub num_proposer_slashings: usize,
+    pub num_attester_slashings: usize,
+    pub deposits: Vec<Deposit>,
+    pub voluntary_exits: Vec<SignedVoluntaryExit>,
+    pub proposer_slashings: Vec<ProposerSlashing>,
+    pub attester_slashings: Vec<AttesterSlashing<T>>,
+}
+
+impl<T: EthSpec> BlockBodySummary<T> {
+    /// Summarizes the body of `block`, which has the given `root`.
+    pub fn from_block(root: Hash256, block: &SignedBeaconBlock<T>) -> Self {
+        let body = &block.message.body;
+
+        Self {
+            root,
+            slot: block.message.slot,
+            graffiti: body.graffiti,
+            eth1_data: body.eth1_data.clone(),
+            num_attestations: body.attestations.len(),
+            num_deposits: body.deposits.len(),
+            num_voluntary_exits: body.voluntary_exits.len(),
+            num_proposer_slashings: body.proposer_slashings.len(),
+            num_attester_slashings: body.attester_slashings.len(),
+            deposits: body.deposits.to_vec(),
+            voluntary_exits: body.voluntary_exits.to_vec(),
+            proposer_slashings: body.proposer_slashings.to_vec(),
+            attester_slashings: body.attester_slashings.to_vec(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /lighthouse/eth1/deposit_cache HTTP GET.
+pub struct Eth1DepositCacheSnapshot {
+    /// The number of deposits in the cache.
+    pub deposit_count: u64,
+    /// The number of the latest eth1 block from which deposit logs have been processed.
+    pub last_processed_block: Option<u64>,
+    /// The root of the deposit tree containing all deposits in the cache.
+    pub deposit_root: Hash256,
+    /// The deposit logs in the cache, only present if they were requested.
+    pub deposits: Option<Vec<Eth1DepositLog>>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A deposit log in the eth1 deposit cache.
+pub struct Eth1DepositLog {
+    pub deposit_data: DepositData,
+    /// The number of the eth1 block which included the log.
+    pub block_number: u64,
+    /// The index of the deposit in the deposit contract.
+    pub index: u64,
+    /// True if the deposit signature is valid.
+    pub signature_is_valid: bool,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::{
+        test_utils::{SeedableRng, TestRandom, XorShiftRng},
+        Attestation, BeaconBlock, ChainSpec, MinimalEthSpec, Signature, VoluntaryExit,
+    };
+
+    type E = MinimalEthSpec;
+
+    #[test]
+    fn block_body_summary_serde_round_trip() {
+        let spec = ChainSpec::minimal();
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        let mut block = BeaconBlock::<E>::empty(&spec);
+        block.slot = Slot::new(3);
+        block.body.graffiti = [7; 32];
+        block
+            .body
+            .attestations
+            .push(Attestation::random_for_test(&mut rng))
+            .unwrap();
+        block
+            .body
+            .deposits
+            .push(Deposit::random_for_test(&mut rng))
+            .unwrap();
+        block
+            .body
+            .voluntary_exits
+            .push(SignedVoluntaryExit {
+                message: VoluntaryExit {
+                    epoch: 0.into(),
+                    validator_index: 1,
+                },
+                signature: Signature::empty(),
+            })
+            .unwrap();
+        let block = SignedBeaconBlock {
+            message: block,
+            signature: Signature::empty(),
+        };
+
+        let root = Hash256::repeat_byte(1);
+        let summary = BlockBodySummary::from_block(root, &block);
+
+        assert_eq!(summary.root, root);
+        assert_eq!(summary.slot, block.message.slot);
+        assert_eq!(summary.graffiti, block.message.body.graffiti);
+        assert_eq!(summary.num_attestations, 1);
+        assert_eq!(summary.num_deposits, 1);
+        assert_eq!(summary.num_voluntary_exits, 1);
+        assert_eq!(summary.num_proposer_slashings, 0);
+        assert_eq!(summary.num_attester_slashings, 0);
+        assert_eq!(summary.deposits, block.message.body.deposits.to_vec());
+        assert_eq!(
+            summary.voluntary_exits,
+            block.message.body.voluntary_exits.to_vec()
+        );
+
+        let json = serde_json::to_string(&summary).unwrap();
+        assert!(!json.contains("\"attestations\""));
+        assert_eq!(
+            serde_json::from_str::<BlockBodySummary<E>>(&json).unwrap(),
+            summary
+        );
+    }
+
+    #[test]
+    fn eth1_deposit_cache_snapshot_serde_round_trip() {
+        let mut rng = XorShiftRng::from_seed([42; 16]);
+
+        let without_deposits = Eth1DepositCacheSnapshot {
+            deposit_count: 2,
+            last_processed_block: Some(100),
+            deposit_root: Hash256::from_low_u64_be(7),
+            deposits: None,
+        };
+        let with_deposits = Eth1DepositCacheSnapshot {
+            deposits: Some(vec![
+                Eth1DepositLog {
+                    deposit_data: DepositData::random_for_test(&mut rng),
+                    block_number: 99,
+                    index: 0,
+                    signature_is_valid: true,
+                },
+                Eth1DepositLog {
+                    deposit_data: DepositData::random_for_test(&mut rng),
+                    block_number: 100,
+                    index: 1,
+                    signature_is_valid: false,
+                },
+            ]),
+            ..without_deposits.clone()
+        };
+
+        for snapshot in &[without_deposits, with_deposits] {
+            let json = serde_json::to_string(snapshot).unwrap();
+            assert_eq!(
+                &serde_json::from_str::<Eth1DepositCacheSnapshot>(&json).unwrap(),
+                snapshot
+            );
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub use lighthouse_metrics::*;
+
+lazy_static! {
+    pub static ref BEACON_HTTP_API_HANDLER_PANICS_TOTAL: Result<IntCounter> =
+        try_create_int_counter(
+            "beacon_http_api_handler_panics_total",
+            "Count of HTTP API handlers which panicked"
+        );
+}
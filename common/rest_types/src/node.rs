@@ -1,7 +1,8 @@
 //! Collection of types for the /node HTTP
 use serde::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
-use types::Slot;
+use std::str::FromStr;
+use types::{Address, BitVector, EthSpec, Slot};
 
 #[cfg(target_os = "linux")]
 use {procinfo::pid, psutil::process::Process};
@@ -34,6 +35,172 @@ pub struct SyncingResponse {
     pub sync_status: SyncingStatus,
 }
 
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A route served by the HTTP API.
+pub struct ApiRoute {
+    pub method: String,
+    pub path: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the / HTTP GET, describing the server and the routes it serves.
+pub struct ApiIndex {
+    /// The name of the server, e.g., `Lighthouse`.
+    pub name: String,
+    /// The version string, as returned by `/node/version`.
+    pub version: String,
+    pub routes: Vec<ApiRoute>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Wraps a response in the `{"data": ...}` object used by the standard Eth2 API.
+pub struct GenericResponse<T> {
+    pub data: T,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The `data` of the response for the /eth/v1/node/version HTTP GET.
+pub struct VersionData {
+    /// The version string, as returned by `/node/version`.
+    pub version: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The `data` of the response for the /eth/v1/config/deposit_contract HTTP GET.
+pub struct DepositContractData {
+    /// The id of the eth1 chain where the deposit contract is deployed.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub chain_id: u64,
+    pub address: Address,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+/// The `data` of the response for the /eth/v1/node/identity HTTP GET.
+pub struct IdentityData<T: EthSpec> {
+    /// The base58 encoded libp2p peer ID.
+    pub peer_id: String,
+    /// The base64 encoded ENR.
+    pub enr: String,
+    /// The multiaddrs on which libp2p is listening, including the peer ID.
+    pub p2p_addresses: Vec<String>,
+    /// The UDP multiaddrs of the ENR, on which discovery is reachable.
+    pub discovery_addresses: Vec<String>,
+    pub metadata: IdentityMetaData<T>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(bound = "T: EthSpec")]
+/// The metadata of the node, as sent to peers.
+pub struct IdentityMetaData<T: EthSpec> {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub seq_number: u64,
+    /// The bitfield of the attestation subnets to which the node is persistently subscribed.
+    pub attnets: BitVector<T::SubnetBitfieldLength>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /eth/v1/node/peers HTTP GET.
+pub struct PeersResponse {
+    pub data: Vec<PeerData>,
+    pub meta: PeersMetaData,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// The response for the /eth/v1/node/peers/{peer_id} HTTP GET.
+pub struct PeerResponse {
+    pub data: PeerData,
+    pub meta: PeerMetaData,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// Lighthouse-specific information about a peer, which is not part of the standard API.
+pub struct PeerMetaData {
+    /// The score of the peer, as used to decide whether to disconnect or ban it.
+    pub score: f64,
+    /// A description of the client of the peer.
+    pub client: String,
+    /// The agent string sent by the peer, if it has identified itself.
+    pub agent_string: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+/// A peer known to the node.
+pub struct PeerData {
+    /// The base58 encoded libp2p peer ID.
+    pub peer_id: String,
+    /// The base64 encoded ENR of the peer, if known.
+    pub enr: Option<String>,
+    /// The last known listening multiaddr of the peer, if any.
+    pub last_seen_p2p_address: Option<String>,
+    pub state: PeerState,
+    /// The direction of the connection, if the peer is connected or being dialed.
+    pub direction: Option<PeerDirection>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PeersMetaData {
+    /// The number of peers in `data`.
+    pub count: usize,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+/// The `data` of the response for the /eth/v1/node/peer_count HTTP GET.
+pub struct PeerCountData {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub disconnected: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub connecting: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub connected: u64,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub disconnecting: u64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The connection state of a peer.
+pub enum PeerState {
+    Disconnected,
+    Connecting,
+    Connected,
+    Disconnecting,
+}
+
+impl FromStr for PeerState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "disconnected" => Ok(PeerState::Disconnected),
+            "connecting" => Ok(PeerState::Connecting),
+            "connected" => Ok(PeerState::Connected),
+            "disconnecting" => Ok(PeerState::Disconnecting),
+            _ => Err(format!("Unknown peer state: {}", s)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+/// The direction of the connection to a peer.
+pub enum PeerDirection {
+    Inbound,
+    Outbound,
+}
+
+impl FromStr for PeerDirection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "inbound" => Ok(PeerDirection::Inbound),
+            "outbound" => Ok(PeerDirection::Outbound),
+            _ => Err(format!("Unknown peer direction: {}", s)),
+        }
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 /// Reports on the health of the Lighthouse instance.
 pub struct Health {
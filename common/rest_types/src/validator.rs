@@ -55,6 +55,33 @@ pub struct ValidatorDutiesRequest {
     pub pubkeys: Vec<PublicKeyBytes>,
 }
 
+/// A list of validator indices, each of which may be a quoted or unquoted integer.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct ValidatorIndices(#[serde(with = "serde_utils::quoted_u64_vec")] pub Vec<u64>);
+
+/// The attestation duty of a validator during an epoch, in the format of the standard Eth2 API.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct AttesterData {
+    pub pubkey: PublicKeyBytes,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The number of committees at `slot`.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committees_at_slot: u64,
+    /// The index of the committee within `slot` of which the validator is a member.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: CommitteeIndex,
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_length: u64,
+    /// The position of the validator in the committee.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_committee_index: u64,
+    /// The slot at which the validator must attest.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+}
+
 /// A validator subscription, created when a validator subscribes to a slot to perform optional aggregation
 /// duties.
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone, Encode, Decode)]
@@ -73,6 +100,49 @@ pub struct ValidatorSubscription {
     pub is_aggregator: bool,
 }
 
+/// A subscription to the attestation subnet of a beacon committee, in the format of the standard
+/// Eth2 API.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+pub struct BeaconCommitteeSubscription {
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub validator_index: u64,
+    /// The index of the committee within `slot` of which the validator is a member.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committee_index: CommitteeIndex,
+    /// The number of committees at `slot`.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub committees_at_slot: u64,
+    /// The slot in which to subscribe.
+    #[serde(with = "serde_utils::quoted_u64")]
+    pub slot: u64,
+    /// If true, the beacon node should aggregate attestations for this slot.
+    pub is_aggregator: bool,
+}
+
+impl From<BeaconCommitteeSubscription> for ValidatorSubscription {
+    fn from(subscription: BeaconCommitteeSubscription) -> Self {
+        Self {
+            validator_index: subscription.validator_index,
+            attestation_committee_index: subscription.committee_index,
+            slot: Slot::new(subscription.slot),
+            committee_count_at_slot: subscription.committees_at_slot,
+            is_aggregator: subscription.is_aggregator,
+        }
+    }
+}
+
+/// The outcome of processing a single `SignedAggregateAndProof` from a batch.
+#[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregateAndProofStatus {
+    /// The aggregate was verified, published and imported.
+    Imported,
+    /// The aggregate was already known, so it was neither re-published nor re-imported.
+    AlreadyKnown,
+    /// The aggregate was not imported, for the given reason.
+    Error(String),
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -100,4 +170,23 @@ mod test {
         assert!(duty1.eq_ignoring_proposal_slots(&duty2));
         assert!(duty2.eq_ignoring_proposal_slots(&duty1));
     }
+
+    #[test]
+    fn aggregate_and_proof_status_serde() {
+        let statuses = vec![
+            AggregateAndProofStatus::Imported,
+            AggregateAndProofStatus::AlreadyKnown,
+            AggregateAndProofStatus::Error("bad signature".to_string()),
+        ];
+
+        let json = serde_json::to_string(&statuses).unwrap();
+        assert_eq!(
+            json,
+            r#"["imported","already_known",{"error":"bad signature"}]"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Vec<AggregateAndProofStatus>>(&json).unwrap(),
+            statuses
+        );
+    }
 }
@@ -27,6 +27,14 @@ pub struct ProtoNode {
     best_descendant: Option<usize>,
 }
 
+impl ProtoNode {
+    /// The total effective balance of the validators whose latest vote is for this node or one of
+    /// its descendants, as of the last call to `ProtoArray::apply_score_changes`.
+    pub fn weight(&self) -> u64 {
+        self.weight
+    }
+}
+
 #[derive(PartialEq, Debug, Serialize, Deserialize, Clone)]
 pub struct ProtoArray {
     /// Do not attempt to prune the tree unless it has at least this many nodes. Small prunes
@@ -401,7 +409,7 @@ impl ProtoArray {
     ///
     /// Any node that has a different finalized or justified epoch should not be viable for the
     /// head.
-    fn node_is_viable_for_head(&self, node: &ProtoNode) -> bool {
+    pub fn node_is_viable_for_head(&self, node: &ProtoNode) -> bool {
         (node.justified_epoch == self.justified_epoch || self.justified_epoch == Epoch::new(0))
             && (node.finalized_epoch == self.finalized_epoch
                 || self.finalized_epoch == Epoch::new(0))
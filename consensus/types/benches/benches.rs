@@ -2,9 +2,10 @@ use criterion::Criterion;
 use criterion::{black_box, criterion_group, criterion_main, Benchmark};
 use rayon::prelude::*;
 use ssz::{Decode, Encode};
+use std::collections::HashSet;
 use types::{
     test_utils::generate_deterministic_keypair, BeaconState, Epoch, Eth1Data, EthSpec, Hash256,
-    MainnetEthSpec, Validator,
+    MainnetEthSpec, PublicKeyBytes, RelativeEpoch, Validator,
 };
 
 fn get_state<E: EthSpec>(validator_count: usize) -> BeaconState<E> {
@@ -169,5 +170,80 @@ fn all_benches(c: &mut Criterion) {
     );
 }
 
-criterion_group!(benches, all_benches,);
+fn attestation_duties_benches(c: &mut Criterion) {
+    // Enough validators for the maximum number of committees per slot.
+    let validator_count = 262_144;
+    let spec = &MainnetEthSpec::default_spec();
+
+    // Keypairs are not needed to compute duties, so skip the (slow) key generation.
+    let mut state = BeaconState::<MainnetEthSpec>::new(0, Eth1Data::default(), spec);
+    let validator = Validator {
+        pubkey: PublicKeyBytes::empty(),
+        withdrawal_credentials: Hash256::zero(),
+        effective_balance: spec.max_effective_balance,
+        slashed: false,
+        activation_eligibility_epoch: Epoch::new(0),
+        activation_epoch: Epoch::new(0),
+        exit_epoch: Epoch::from(u64::max_value()),
+        withdrawable_epoch: Epoch::from(u64::max_value()),
+    };
+    state.validators = vec![validator; validator_count].into();
+    state.balances = vec![spec.max_effective_balance; validator_count].into();
+    state
+        .build_committee_cache(RelativeEpoch::Current, spec)
+        .expect("should build committee cache");
+
+    for &index_count in &[1_000, 10_000] {
+        let indices = (0..validator_count)
+            .step_by(validator_count / index_count)
+            .collect::<Vec<_>>();
+
+        let inner_state = state.clone();
+        let inner_indices = indices.clone();
+        c.bench(
+            &format!("{}_validators", validator_count),
+            Benchmark::new(
+                format!("attestation_duties_individually/{}_indices", index_count),
+                move |b| {
+                    b.iter(|| {
+                        black_box(
+                            inner_indices
+                                .iter()
+                                .map(|&i| {
+                                    inner_state.get_attestation_duties(i, RelativeEpoch::Current)
+                                })
+                                .collect::<Result<Vec<_>, _>>()
+                                .expect("should get duties"),
+                        )
+                    })
+                },
+            )
+            .sample_size(10),
+        );
+
+        let inner_state = state.clone();
+        let inner_indices = indices.into_iter().collect::<HashSet<_>>();
+        c.bench(
+            &format!("{}_validators", validator_count),
+            Benchmark::new(
+                format!("attestation_duties_in_bulk/{}_indices", index_count),
+                move |b| {
+                    b.iter(|| {
+                        black_box(
+                            inner_state
+                                .get_attestation_duties_for_indices(
+                                    &inner_indices,
+                                    RelativeEpoch::Current,
+                                )
+                                .expect("should get duties"),
+                        )
+                    })
+                },
+            )
+            .sample_size(10),
+        );
+    }
+}
+
+criterion_group!(benches, all_benches, attestation_duties_benches);
 criterion_main!(benches);
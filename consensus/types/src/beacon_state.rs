@@ -13,6 +13,7 @@ use serde_derive::{Deserialize, Serialize};
 use ssz::{ssz_encode, Encode};
 use ssz_derive::{Decode, Encode};
 use ssz_types::{typenum::Unsigned, BitVector, FixedVector};
+use std::collections::{HashMap, HashSet};
 use std::convert::TryInto;
 use std::fmt;
 use swap_or_not_shuffle::compute_shuffled_index;
@@ -853,6 +854,20 @@ impl<T: EthSpec> BeaconState<T> {
         Ok(cache.get_attestation_duties(validator_index))
     }
 
+    /// Returns the attestation duty of each validator in `validator_indices` which has one, in a
+    /// single pass over the committees of `relative_epoch`.
+    ///
+    /// Note: Utilizes the cache and will fail if the appropriate cache is not initialized.
+    pub fn get_attestation_duties_for_indices(
+        &self,
+        validator_indices: &HashSet<usize>,
+        relative_epoch: RelativeEpoch,
+    ) -> Result<HashMap<usize, AttestationDuty>, Error> {
+        let cache = self.committee_cache(relative_epoch)?;
+
+        Ok(cache.get_attestation_duties_for_indices(validator_indices))
+    }
+
     /// Return the combined effective balance of an array of validators.
     ///
     /// Spec v0.12.1
@@ -937,6 +952,23 @@ impl<T: EthSpec> BeaconState<T> {
         Ok(())
     }
 
+    /// Sets the cache for `relative_epoch` to `cache`, which may have been built from another
+    /// state with the same shuffling.
+    ///
+    /// Returns an error if `cache` is not initialized for `relative_epoch`.
+    pub fn set_committee_cache(
+        &mut self,
+        relative_epoch: RelativeEpoch,
+        cache: CommitteeCache,
+    ) -> Result<(), Error> {
+        if cache.is_initialized_at(relative_epoch.into_epoch(self.current_epoch())) {
+            self.committee_caches[Self::committee_cache_index(relative_epoch)] = cache;
+            Ok(())
+        } else {
+            Err(Error::CommitteeCacheUninitialized(Some(relative_epoch)))
+        }
+    }
+
     /// Advances the cache for this state into the next epoch.
     ///
     /// This should be used if the `slot` of this state is advanced beyond an epoch boundary.
@@ -5,6 +5,7 @@ use crate::*;
 use core::num::NonZeroUsize;
 use serde_derive::{Deserialize, Serialize};
 use ssz_derive::{Decode, Encode};
+use std::collections::{HashMap, HashSet};
 use std::ops::Range;
 use swap_or_not_shuffle::shuffle_list;
 
@@ -189,6 +190,63 @@ impl CommitteeCache {
             })
     }
 
+    /// Returns the `AttestationDuty` of each validator in `validator_indices` which has one.
+    ///
+    /// Equivalent to calling `get_attestation_duties` for each validator, but walks the committees
+    /// of the epoch once in total, rather than once per validator.
+    pub fn get_attestation_duties_for_indices(
+        &self,
+        validator_indices: &HashSet<usize>,
+    ) -> HashMap<usize, AttestationDuty> {
+        let mut duties = HashMap::with_capacity(validator_indices.len());
+
+        // Sort the validators by their position in the shuffling, which is also the order of their
+        // committees, so each committee only needs to be visited once.
+        let mut positions = validator_indices
+            .iter()
+            .filter_map(|&validator_index| {
+                Some((self.shuffled_position(validator_index)?, validator_index))
+            })
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        let mut positions = positions.into_iter().peekable();
+
+        for nth_committee in 0..self.epoch_committee_count() {
+            let (range, (slot, index)) = match (
+                self.compute_committee_range(nth_committee),
+                self.convert_to_slot_and_index(nth_committee as u64),
+            ) {
+                (Some(range), Some(slot_and_index)) => (range, slot_and_index),
+                _ => break,
+            };
+
+            // The committees partition the shuffling, so every remaining position is at least
+            // `range.start`.
+            while let Some(&(position, validator_index)) = positions.peek() {
+                if position >= range.end {
+                    break;
+                }
+                positions.next();
+
+                duties.insert(
+                    validator_index,
+                    AttestationDuty {
+                        slot,
+                        index,
+                        committee_position: position - range.start,
+                        committee_len: range.end - range.start,
+                    },
+                );
+            }
+
+            if positions.peek().is_none() {
+                break;
+            }
+        }
+
+        duties
+    }
+
     /// Convert an index addressing the list of all epoch committees into a slot and per-slot index.
     fn convert_to_slot_and_index(
         &self,
@@ -118,3 +118,39 @@ fn shuffles_for_the_right_epoch() {
     assert_eq!(cache.shuffling, shuffling_with_seed(next_seed));
     assert_shuffling_positions_accurate(&cache);
 }
+
+#[test]
+fn bulk_attestation_duties_match_individual() {
+    let num_validators = MinimalEthSpec::minimum_validator_count() * 2;
+    let state = new_state::<MinimalEthSpec>(num_validators, Slot::new(0));
+    let spec = &MinimalEthSpec::default_spec();
+    let cache = CommitteeCache::initialized(&state, state.current_epoch(), spec).unwrap();
+
+    // Include some indices which are not in the registry.
+    let all_indices = (0..num_validators + 4).collect::<HashSet<_>>();
+    let duties = cache.get_attestation_duties_for_indices(&all_indices);
+    assert_eq!(duties.len(), num_validators);
+    for &validator_index in &all_indices {
+        assert_eq!(
+            duties.get(&validator_index).copied(),
+            cache.get_attestation_duties(validator_index),
+            "validator {}",
+            validator_index
+        );
+    }
+
+    let some_indices = vec![1, 5, num_validators - 1].into_iter().collect();
+    assert_eq!(
+        cache
+            .get_attestation_duties_for_indices(&some_indices)
+            .len(),
+        3
+    );
+
+    assert!(cache
+        .get_attestation_duties_for_indices(&HashSet::new())
+        .is_empty());
+    assert!(CommitteeCache::default()
+        .get_attestation_duties_for_indices(&all_indices)
+        .is_empty());
+}
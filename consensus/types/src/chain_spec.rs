@@ -69,6 +69,10 @@ pub struct ChainSpec {
         deserialize_with = "fork_from_hex_str"
     )]
     pub genesis_fork_version: [u8; 4],
+    /// Forks scheduled after genesis, in epoch order.
+    ///
+    /// These are only informational (e.g., for the HTTP API); they are not applied to the state.
+    pub future_forks: Vec<Fork>,
     #[serde(deserialize_with = "u8_from_hex_str", serialize_with = "u8_to_hex_str")]
     pub bls_withdrawal_prefix_byte: u8,
 
@@ -152,6 +156,21 @@ impl ChainSpec {
         None
     }
 
+    /// Returns every fork of the chain, starting with the genesis fork, in epoch order.
+    pub fn fork_schedule<T: EthSpec>(&self) -> Vec<Fork> {
+        let genesis_fork = Fork {
+            previous_version: self.genesis_fork_version,
+            current_version: self.genesis_fork_version,
+            epoch: T::genesis_epoch(),
+        };
+
+        let mut schedule = std::iter::once(genesis_fork)
+            .chain(self.future_forks.iter().copied())
+            .collect::<Vec<_>>();
+        schedule.sort_by_key(|fork| fork.epoch);
+        schedule
+    }
+
     /// Get the domain number, unmodified by the fork.
     ///
     /// Spec v0.12.1
@@ -280,6 +299,7 @@ impl ChainSpec {
              * Initial Values
              */
             genesis_fork_version: [0; 4],
+            future_forks: vec![],
             bls_withdrawal_prefix_byte: 0,
 
             /*
@@ -395,6 +415,28 @@ mod tests {
         let _ = ChainSpec::mainnet();
     }
 
+    #[test]
+    fn test_fork_schedule() {
+        let mut spec = ChainSpec::minimal();
+        let genesis_fork = Fork {
+            previous_version: spec.genesis_fork_version,
+            current_version: spec.genesis_fork_version,
+            epoch: Epoch::new(0),
+        };
+        assert_eq!(spec.fork_schedule::<MinimalEthSpec>(), vec![genesis_fork]);
+
+        let next_fork = Fork {
+            previous_version: spec.genesis_fork_version,
+            current_version: [1, 0, 0, 1],
+            epoch: Epoch::new(1024),
+        };
+        spec.future_forks = vec![next_fork];
+        assert_eq!(
+            spec.fork_schedule::<MinimalEthSpec>(),
+            vec![genesis_fork, next_fork]
+        );
+    }
+
     fn test_domain(domain_type: Domain, raw_domain: u32, spec: &ChainSpec) {
         let previous_version = [0, 0, 0, 1];
         let current_version = [0, 0, 0, 2];
@@ -442,12 +484,17 @@ mod tests {
 ///
 /// Doesn't include fields of the YAML that we don't need yet (e.g. Phase 1 stuff).
 ///
+/// The `CONFIG_NAME` of a `YamlConfig` which does not specify one.
+pub const CUSTOM_CONFIG_NAME: &str = "custom";
+
 /// Spec v0.12.1
 // Yaml Config is declared here in order to access domain fields of ChainSpec which are private.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 #[serde(rename_all = "UPPERCASE")]
 #[serde(default)]
 pub struct YamlConfig {
+    /// The name of the network or preset (e.g., `medalla`), or `custom` if it is not named.
+    pub config_name: String,
     // ChainSpec
     far_future_epoch: u64,
     base_rewards_per_epoch: u64,
@@ -565,6 +612,7 @@ impl YamlConfig {
     #[allow(clippy::integer_arithmetic)]
     pub fn from_spec<T: EthSpec>(spec: &ChainSpec) -> Self {
         Self {
+            config_name: CUSTOM_CONFIG_NAME.to_string(),
             // ChainSpec
             far_future_epoch: spec.far_future_epoch.into(),
             base_rewards_per_epoch: spec.base_rewards_per_epoch,
@@ -703,6 +751,7 @@ impl YamlConfig {
             domain_deposit: self.domain_deposit,
             domain_voluntary_exit: self.domain_voluntary_exit,
             boot_nodes: chain_spec.boot_nodes.clone(),
+            future_forks: chain_spec.future_forks.clone(),
             genesis_fork_version: self.genesis_fork_version,
             eth1_follow_distance: self.eth1_follow_distance,
             ..*chain_spec
@@ -762,6 +811,38 @@ mod yaml_tests {
         assert_eq!(from, yamlconfig);
     }
 
+    #[test]
+    fn named_config_round_trip() {
+        let mut yamlconfig = YamlConfig::from_spec::<MainnetEthSpec>(&ChainSpec::mainnet());
+        yamlconfig.config_name = "medalla".to_string();
+
+        let yaml = serde_yaml::to_string(&yamlconfig).expect("should serialize");
+        assert!(yaml.contains("CONFIG_NAME: medalla"), "should contain name");
+
+        let from: YamlConfig = serde_yaml::from_str(&yaml).expect("should deserialize");
+        assert_eq!(from.config_name, "medalla");
+        assert_eq!(from, yamlconfig);
+    }
+
+    #[test]
+    fn unnamed_config_is_custom() {
+        let yamlconfig = YamlConfig::from_spec::<MainnetEthSpec>(&ChainSpec::mainnet());
+        let yaml = serde_yaml::to_string(&yamlconfig).expect("should serialize");
+        assert!(
+            yaml.contains(&format!("CONFIG_NAME: {}", CUSTOM_CONFIG_NAME)),
+            "should contain the custom name"
+        );
+
+        // A config file without a `CONFIG_NAME` is custom.
+        let unnamed = yaml
+            .lines()
+            .filter(|line| !line.starts_with("CONFIG_NAME"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let from: YamlConfig = serde_yaml::from_str(&unnamed).expect("should deserialize");
+        assert_eq!(from.config_name, CUSTOM_CONFIG_NAME);
+    }
+
     #[test]
     fn apply_to_spec() {
         let mut spec = ChainSpec::minimal();
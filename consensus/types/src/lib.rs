@@ -64,7 +64,7 @@ pub use crate::beacon_block_body::BeaconBlockBody;
 pub use crate::beacon_block_header::BeaconBlockHeader;
 pub use crate::beacon_committee::{BeaconCommittee, OwnedBeaconCommittee};
 pub use crate::beacon_state::{BeaconTreeHashCache, Error as BeaconStateError, *};
-pub use crate::chain_spec::{ChainSpec, Domain, YamlConfig};
+pub use crate::chain_spec::{ChainSpec, Domain, YamlConfig, CUSTOM_CONFIG_NAME};
 pub use crate::checkpoint::Checkpoint;
 pub use crate::deposit::{Deposit, DEPOSIT_TREE_DEPTH};
 pub use crate::deposit_data::DepositData;
@@ -0,0 +1,93 @@
+use bls::{
+    verify_signature_sets, verify_signature_sets_parallel, AggregateSignature, Hash256, PublicKey,
+    SecretKey, SignatureSet,
+};
+use criterion::{black_box, criterion_group, criterion_main, Benchmark, Criterion};
+use std::borrow::Cow;
+
+/// The number of signature sets in each benchmark, roughly the number of attestations that might
+/// be submitted to the HTTP API at once.
+const NUM_SETS: usize = 128;
+
+struct OwnedSignatureSet {
+    signature: AggregateSignature,
+    signing_keys: Vec<PublicKey>,
+    message: Hash256,
+}
+
+impl OwnedSignatureSet {
+    fn new(i: usize, num_signers: usize) -> Self {
+        let message = Hash256::from_low_u64_be(i as u64);
+        let mut signature = AggregateSignature::infinity();
+
+        let signing_keys = (0..num_signers)
+            .map(|j| {
+                let mut secret_bytes = [0; 32];
+                secret_bytes[32 - 8..]
+                    .copy_from_slice(&((i * num_signers + j + 1) as u64).to_be_bytes());
+                let secret = SecretKey::deserialize(&secret_bytes).unwrap();
+                signature.add_assign(&secret.sign(message));
+                secret.public_key()
+            })
+            .collect();
+
+        Self {
+            signature,
+            signing_keys,
+            message,
+        }
+    }
+
+    fn signature_set(&self) -> SignatureSet {
+        let signing_keys = self.signing_keys.iter().map(Cow::Borrowed).collect();
+        SignatureSet::multiple_pubkeys(&self.signature, signing_keys, self.message)
+    }
+}
+
+fn owned_sets() -> Vec<OwnedSignatureSet> {
+    (0..NUM_SETS)
+        .map(|i| OwnedSignatureSet::new(i, 4))
+        .collect()
+}
+
+fn signature_sets(c: &mut Criterion) {
+    let owned = owned_sets();
+    c.bench(
+        &format!("{} signature sets", NUM_SETS),
+        Benchmark::new("individually", move |b| {
+            b.iter(|| black_box(owned.iter().all(|set| set.signature_set().verify())))
+        })
+        .sample_size(10),
+    );
+
+    let owned = owned_sets();
+    c.bench(
+        &format!("{} signature sets", NUM_SETS),
+        Benchmark::new("single batch", move |b| {
+            b.iter(|| {
+                let sets = owned
+                    .iter()
+                    .map(|set| set.signature_set())
+                    .collect::<Vec<_>>();
+                black_box(verify_signature_sets(sets.iter()))
+            })
+        })
+        .sample_size(10),
+    );
+
+    let owned = owned_sets();
+    c.bench(
+        &format!("{} signature sets", NUM_SETS),
+        Benchmark::new("parallel batch", move |b| {
+            b.iter(|| {
+                black_box(verify_signature_sets_parallel(
+                    owned.iter().map(|set| set.signature_set()),
+                ))
+            })
+        })
+        .sample_size(10),
+    );
+}
+
+criterion_group!(benches, signature_sets);
+criterion_main!(benches);
@@ -1,7 +1,13 @@
-use crate::{Error, PUBLIC_KEY_BYTES_LEN};
+use crate::{
+    generic_public_key::{GenericPublicKey, TPublicKey},
+    Error, INFINITY_PUBLIC_KEY, PUBLIC_KEY_BYTES_LEN, PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN,
+};
+use serde_hex::encode as hex_encode;
+use std::fmt;
+use std::marker::PhantomData;
 
 /// Implemented on some struct from a BLS library so it may be used internally in this crate.
-pub trait TAggregatePublicKey: Sized + Clone {
+pub trait TAggregatePublicKey<Pub>: Sized + Clone {
     /// Initialize `Self` to the infinity value which can then have other public keys aggregated
     /// upon it.
     fn infinity() -> Self;
@@ -11,8 +17,112 @@ pub trait TAggregatePublicKey: Sized + Clone {
 
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn deserialize_uncompressed(_bytes: &[u8]) -> Result<Self, Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Aggregates all the `pubkeys` into a new `Self`.
+    ///
+    /// Implementations should return an error if `pubkeys` is empty.
+    fn aggregate(pubkeys: &[&GenericPublicKey<Pub>]) -> Result<Self, Error>;
+
+    /// Converts `self` into a public key point.
+    fn to_public_key(&self) -> Pub;
+}
+
+/// A BLS aggregate public key that is generic across:
+///
+/// - `Pub`: A BLS public key.
+/// - `AggPub`: A BLS aggregate public key.
+///
+/// Provides generic functionality whilst deferring all serious cryptographic operations to the
+/// generics.
+#[derive(Clone, PartialEq)]
+pub struct GenericAggregatePublicKey<Pub, AggPub> {
+    /// The underlying point which performs *actual* cryptographic operations.
+    point: AggPub,
+    _phantom: PhantomData<Pub>,
+}
+
+impl<Pub, AggPub> GenericAggregatePublicKey<Pub, AggPub>
+where
+    Pub: TPublicKey,
+    AggPub: TAggregatePublicKey<Pub>,
+{
+    /// Aggregates all the `pubkeys` into a new `Self`, using the optimised routine of the backend.
+    ///
+    /// Returns an error if `pubkeys` is empty, since an aggregate of no public keys is
+    /// meaningless.
+    pub fn aggregate(pubkeys: &[&GenericPublicKey<Pub>]) -> Result<Self, Error> {
+        if pubkeys.is_empty() {
+            return Err(Error::EmptyAggregate);
+        }
+
+        Ok(Self {
+            point: AggPub::aggregate(pubkeys)?,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns the aggregate as a regular public key, suitable for single signature verification.
+    pub fn to_public_key(&self) -> GenericPublicKey<Pub> {
+        let is_infinity = self.serialize()[..] == INFINITY_PUBLIC_KEY[..];
+        GenericPublicKey::from_point(self.point.to_public_key(), is_infinity)
+    }
+
+    /// Serialize `self` as compressed bytes.
+    pub fn serialize(&self) -> [u8; PUBLIC_KEY_BYTES_LEN] {
+        self.point.serialize()
+    }
+
+    /// Deserialize `self` from compressed bytes.
+    pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            point: AggPub::deserialize(bytes)?,
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        self.point.serialize_uncompressed()
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN {
+            return Err(Error::InvalidByteLength {
+                got: bytes.len(),
+                expected: PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN,
+            });
+        }
+
+        Ok(Self {
+            point: AggPub::deserialize_uncompressed(bytes)?,
+            _phantom: PhantomData,
+        })
+    }
 }
 
-/*
- * Note: there is no immediate need for a `GenericAggregatePublicKey` struct.
- */
+impl<Pub, AggPub> fmt::Debug for GenericAggregatePublicKey<Pub, AggPub>
+where
+    Pub: TPublicKey,
+    AggPub: TAggregatePublicKey<Pub>,
+{
+    impl_debug!();
+}
@@ -1,8 +1,8 @@
 use crate::{
     generic_aggregate_public_key::TAggregatePublicKey,
     generic_public_key::{GenericPublicKey, TPublicKey},
-    generic_signature::{GenericSignature, TSignature},
-    Error, Hash256, INFINITY_SIGNATURE, SIGNATURE_BYTES_LEN,
+    generic_signature::{GenericSignature, TSignature, NONE_SIGNATURE_UNCOMPRESSED},
+    Error, Hash256, INFINITY_SIGNATURE, SIGNATURE_BYTES_LEN, SIGNATURE_UNCOMPRESSED_BYTES_LEN,
 };
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
@@ -34,6 +34,20 @@ pub trait TAggregateSignature<Pub, AggPub, Sig>: Sized + Clone {
     /// Deserialize `self` from compressed bytes.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
 
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn deserialize_uncompressed(_bytes: &[u8]) -> Result<Self, Error> {
+        Err(Error::Unsupported)
+    }
+
     /// Verify that `self` represents an aggregate signature where all `pubkeys` have signed `msg`.
     fn fast_aggregate_verify(&self, msg: Hash256, pubkeys: &[&GenericPublicKey<Pub>]) -> bool;
 
@@ -168,12 +182,51 @@ where
             _phantom_sig: PhantomData,
         })
     }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// The "empty" value is serialized as all-zeros. Returns `Error::Unsupported` if the BLS
+    /// backend does not support uncompressed points.
+    pub fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        if let Some(point) = &self.point {
+            point.serialize_uncompressed()
+        } else {
+            Ok(NONE_SIGNATURE_UNCOMPRESSED)
+        }
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != SIGNATURE_UNCOMPRESSED_BYTES_LEN {
+            return Err(Error::InvalidByteLength {
+                got: bytes.len(),
+                expected: SIGNATURE_UNCOMPRESSED_BYTES_LEN,
+            });
+        }
+
+        if bytes == &NONE_SIGNATURE_UNCOMPRESSED[..] {
+            return Ok(Self::empty());
+        }
+
+        let point = AggSig::deserialize_uncompressed(bytes)?;
+        let is_infinity = point.serialize()[..] == INFINITY_SIGNATURE[..];
+
+        Ok(Self {
+            point: Some(point),
+            is_infinity,
+            _phantom_pub: PhantomData,
+            _phantom_agg_pub: PhantomData,
+            _phantom_sig: PhantomData,
+        })
+    }
 }
 
 impl<Pub, AggPub, Sig, AggSig> GenericAggregateSignature<Pub, AggPub, Sig, AggSig>
 where
     Pub: TPublicKey + Clone,
-    AggPub: TAggregatePublicKey + Clone,
+    AggPub: TAggregatePublicKey<Pub> + Clone,
     Sig: TSignature<Pub>,
     AggSig: TAggregateSignature<Pub, AggPub, Sig>,
 {
@@ -1,4 +1,4 @@
-use crate::Error;
+use crate::{hex_decode::decode_hex_into, Error};
 use serde::de::{Deserialize, Deserializer};
 use serde::ser::{Serialize, Serializer};
 use serde_hex::{encode as hex_encode, PrefixedHexVisitor};
@@ -10,6 +10,9 @@ use tree_hash::TreeHash;
 /// The byte-length of a BLS public key when serialized in compressed form.
 pub const PUBLIC_KEY_BYTES_LEN: usize = 48;
 
+/// The byte-length of a BLS public key when serialized in uncompressed form.
+pub const PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN: usize = 96;
+
 /// Represents the public key at infinity.
 pub const INFINITY_PUBLIC_KEY: [u8; PUBLIC_KEY_BYTES_LEN] = [
     0xc0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -22,8 +25,39 @@ pub trait TPublicKey: Sized + Clone {
     /// Serialize `self` as compressed bytes.
     fn serialize(&self) -> [u8; PUBLIC_KEY_BYTES_LEN];
 
-    /// Deserialize `self` from compressed bytes.
+    /// Deserialize `self` from compressed bytes, checking that the point is in the correct
+    /// subgroup.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Deserialize `self` from compressed bytes without checking that the point is in the correct
+    /// subgroup.
+    ///
+    /// Backends without a native unchecked path may rely on the default, which is fully checked.
+    fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
+        Self::deserialize(bytes)
+    }
+
+    /// Returns `true` if `self` is in the correct subgroup.
+    ///
+    /// Backends which do not implement `deserialize_unchecked` may rely on the default, since all
+    /// their points have already been checked.
+    fn is_in_correct_subgroup(&self) -> bool {
+        true
+    }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn deserialize_uncompressed(_bytes: &[u8]) -> Result<Self, Error> {
+        Err(Error::Unsupported)
+    }
 }
 
 /// A BLS aggregate public key that is generic across some BLS point (`Pub`).
@@ -51,23 +85,81 @@ where
         &self.point
     }
 
-    /// Returns `self.serialize()` as a `0x`-prefixed hex string.
+    /// Returns `self.serialize()` as a lowercase, `0x`-prefixed hex string.
+    ///
+    /// This is the canonical string representation of a public key, see `Self::from_hex_str`.
     pub fn to_hex_string(&self) -> String {
         format!("{:?}", self)
     }
 
+    /// Parses a public key from a hex string, as produced by `Self::to_hex_string`.
+    ///
+    /// The `0x` prefix is optional and either case is accepted, but the length must be exact.
+    pub fn from_hex_str(s: &str) -> Result<Self, Error> {
+        let mut bytes = [0; PUBLIC_KEY_BYTES_LEN];
+        decode_hex_into(s, &mut bytes)?;
+        Self::deserialize(&bytes)
+    }
+
     /// Serialize `self` as compressed bytes.
     pub fn serialize(&self) -> [u8; PUBLIC_KEY_BYTES_LEN] {
         self.point.serialize()
     }
 
     /// Deserialize `self` from compressed bytes.
+    ///
+    /// The point is checked to be in the correct subgroup.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Ok(Self {
             point: Pub::deserialize(bytes)?,
             is_infinity: bytes == &INFINITY_PUBLIC_KEY[..],
         })
     }
+
+    /// Deserialize `self` from compressed bytes, skipping the subgroup check.
+    ///
+    /// ## Warning
+    ///
+    /// Only use this for bytes which are already known to be a valid public key (e.g., they were
+    /// checked when the deposit was processed). Otherwise, check `Self::is_in_correct_subgroup`
+    /// before use.
+    pub fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Self {
+            point: Pub::deserialize_unchecked(bytes)?,
+            is_infinity: bytes == &INFINITY_PUBLIC_KEY[..],
+        })
+    }
+
+    /// Returns `true` if `self` is in the correct subgroup.
+    ///
+    /// Always `true` for a public key returned from `Self::deserialize`.
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        self.point.is_in_correct_subgroup()
+    }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        self.point.serialize_uncompressed()
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN {
+            return Err(Error::InvalidByteLength {
+                got: bytes.len(),
+                expected: PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN,
+            });
+        }
+
+        let point = Pub::deserialize_uncompressed(bytes)?;
+        let is_infinity = point.serialize()[..] == INFINITY_PUBLIC_KEY[..];
+
+        Ok(Self { point, is_infinity })
+    }
 }
 
 impl<Pub: TPublicKey> Eq for GenericPublicKey<Pub> {}
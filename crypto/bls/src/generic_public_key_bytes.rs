@@ -1,5 +1,6 @@
 use crate::{
     generic_public_key::{GenericPublicKey, TPublicKey},
+    hex_decode::decode_hex_into,
     Error, INFINITY_PUBLIC_KEY, PUBLIC_KEY_BYTES_LEN,
 };
 use serde::de::{Deserialize, Deserializer};
@@ -60,6 +61,26 @@ impl<Pub> GenericPublicKeyBytes<Pub> {
         self.bytes
     }
 
+    /// Returns the bytes in `self` as a lowercase, `0x`-prefixed hex string.
+    ///
+    /// Identical to `GenericPublicKey::to_hex_string` for the same key.
+    pub fn to_hex_string(&self) -> String {
+        hex_encode(&self.bytes[..])
+    }
+
+    /// Parses `Self` from a hex string, as produced by `Self::to_hex_string`.
+    ///
+    /// The `0x` prefix is optional and either case is accepted, but the length must be exact. The
+    /// bytes are not verified to be a valid BLS point.
+    pub fn from_hex_str(s: &str) -> Result<Self, Error> {
+        let mut bytes = [0; PUBLIC_KEY_BYTES_LEN];
+        decode_hex_into(s, &mut bytes)?;
+        Ok(Self {
+            bytes,
+            _phantom: PhantomData,
+        })
+    }
+
     /// Instantiates `Self` from bytes.
     ///
     /// The bytes are not fully verified (i.e., they may not represent a valid BLS point). Only the
@@ -1,13 +1,25 @@
 use crate::{
+    generate_secret_key_from_ikm,
     generic_public_key::{GenericPublicKey, TPublicKey},
     generic_signature::{GenericSignature, TSignature},
     Error, Hash256, ZeroizeHash,
 };
+use serde::de::{Deserialize, Deserializer};
+use std::convert::TryFrom;
+use std::fmt;
 use std::marker::PhantomData;
 
 /// The byte-length of a BLS secret key.
 pub const SECRET_KEY_BYTES_LEN: usize = 32;
 
+/// The order of the BLS12-381 curve (`r`), as big-endian bytes.
+///
+/// A valid secret key is a scalar in the range `0 < sk < r`.
+pub const CURVE_ORDER: [u8; SECRET_KEY_BYTES_LEN] = [
+    0x73, 0xed, 0xa7, 0x53, 0x29, 0x9d, 0x7d, 0x48, 0x33, 0x39, 0xd8, 0x08, 0x09, 0xa1, 0xd8, 0x05,
+    0x53, 0xbd, 0xa4, 0x02, 0xff, 0xfe, 0x5b, 0xfe, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x01,
+];
+
 /// Implemented on some struct from a BLS library so it may be used as the `point` in a
 /// `GenericSecretKey`.
 pub trait TSecretKey<SignaturePoint, PublicKeyPoint>: Sized {
@@ -87,4 +99,100 @@ where
             })
         }
     }
+
+    /// Instantiate `Self` from a `ZeroizeHash`, consuming it.
+    ///
+    /// The scalar is checked to be in the range `0 < sk < r` before it is given to the backend, so
+    /// an invalid scalar will return an error instead of relying on the backend to detect it.
+    ///
+    /// ## Note
+    ///
+    /// The backend is only given a reference to the bytes inside `hash`, which is zeroized when it
+    /// is dropped at the end of this function. This avoids the unzeroized copies that arise from
+    /// the `hash.as_bytes().to_vec()` pattern.
+    pub fn from_secret_hash(hash: ZeroizeHash) -> Result<Self, Error> {
+        let bytes = hash.as_bytes();
+
+        if !is_valid_secret_scalar(bytes) {
+            return Err(Error::InvalidSecretKey);
+        }
+
+        Ok(Self {
+            point: Sec::deserialize(bytes)?,
+            _phantom_signature: PhantomData,
+            _phantom_public_key: PhantomData,
+        })
+    }
+
+    /// Returns the secret key as a `ZeroizeHash`, e.g., for encrypting into an EIP-2335 keystore.
+    ///
+    /// ## Note
+    ///
+    /// The returned bytes are the unencrypted secret key. This is deliberately not a `From`
+    /// implementation, so that keys are only ever extracted explicitly.
+    pub fn to_secret_hash(&self) -> ZeroizeHash {
+        self.serialize()
+    }
+
+    /// Generates `Self` from `ikm` (input key material) and `key_info`.
+    ///
+    /// See `generate_secret_key_from_ikm`.
+    pub fn from_ikm(ikm: &[u8], key_info: &[u8]) -> Result<Self, Error> {
+        Self::from_secret_hash(generate_secret_key_from_ikm(ikm, key_info)?)
+    }
+}
+
+/// Consumes the `ZeroizeHash`, which is zeroized once the key has been created.
+///
+/// See `GenericSecretKey::from_secret_hash`.
+impl<Sig, Pub, Sec> TryFrom<ZeroizeHash> for GenericSecretKey<Sig, Pub, Sec>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    type Error = Error;
+
+    fn try_from(hash: ZeroizeHash) -> Result<Self, Error> {
+        Self::from_secret_hash(hash)
+    }
+}
+
+/// Prints only the fingerprint of the serialized key (see `ZeroizeHash::fingerprint`), so the
+/// secret cannot leak into logs.
+impl<Sig, Pub, Sec> fmt::Debug for GenericSecretKey<Sig, Pub, Sec>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretKey(fp={})", self.serialize().fingerprint())
+    }
+}
+
+/// Deserializes from a `0x`-prefixed hex string, zeroizing all intermediate copies of the secret.
+///
+/// `Serialize` is deliberately not implemented to avoid accidentally exporting secret keys.
+impl<'de, Sig, Pub, Sec> Deserialize<'de> for GenericSecretKey<Sig, Pub, Sec>
+where
+    Sig: TSignature<Pub>,
+    Pub: TPublicKey,
+    Sec: TSecretKey<Sig, Pub>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let hash = ZeroizeHash::deserialize(deserializer)?;
+        Self::from_secret_hash(hash)
+            .map_err(|e| serde::de::Error::custom(format!("invalid secret key ({:?})", e)))
+    }
+}
+
+/// Returns `true` if the big-endian `bytes` represent a scalar in the range `0 < sk < r`.
+///
+/// Assumes `bytes.len() == SECRET_KEY_BYTES_LEN`.
+fn is_valid_secret_scalar(bytes: &[u8]) -> bool {
+    bytes.iter().any(|byte| *byte != 0) && bytes < &CURVE_ORDER[..]
 }
@@ -13,6 +13,9 @@ use tree_hash::TreeHash;
 /// The byte-length of a BLS signature when serialized in compressed form.
 pub const SIGNATURE_BYTES_LEN: usize = 96;
 
+/// The byte-length of a BLS signature when serialized in uncompressed form.
+pub const SIGNATURE_UNCOMPRESSED_BYTES_LEN: usize = 192;
+
 /// Represents the signature at infinity.
 pub const INFINITY_SIGNATURE: [u8; SIGNATURE_BYTES_LEN] = [
     0xc0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
@@ -24,15 +27,50 @@ pub const INFINITY_SIGNATURE: [u8; SIGNATURE_BYTES_LEN] = [
 /// The compressed bytes used to represent `GenericSignature::empty()`.
 pub const NONE_SIGNATURE: [u8; SIGNATURE_BYTES_LEN] = [0; SIGNATURE_BYTES_LEN];
 
+/// The uncompressed bytes used to represent `GenericSignature::empty()`.
+pub const NONE_SIGNATURE_UNCOMPRESSED: [u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN] =
+    [0; SIGNATURE_UNCOMPRESSED_BYTES_LEN];
+
 /// Implemented on some struct from a BLS library so it may be used as the `point` in an
 /// `GenericSignature`.
 pub trait TSignature<GenericPublicKey>: Sized + Clone {
     /// Serialize `self` as compressed bytes.
     fn serialize(&self) -> [u8; SIGNATURE_BYTES_LEN];
 
-    /// Deserialize `self` from compressed bytes.
+    /// Deserialize `self` from compressed bytes, checking that the point is in the correct
+    /// subgroup.
     fn deserialize(bytes: &[u8]) -> Result<Self, Error>;
 
+    /// Deserialize `self` from compressed bytes without checking that the point is in the correct
+    /// subgroup.
+    ///
+    /// Backends without a native unchecked path may rely on the default, which is fully checked.
+    fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
+        Self::deserialize(bytes)
+    }
+
+    /// Returns `true` if `self` is in the correct subgroup.
+    ///
+    /// Backends which do not implement `deserialize_unchecked` may rely on the default, since all
+    /// their points have already been checked.
+    fn is_in_correct_subgroup(&self) -> bool {
+        true
+    }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        Err(Error::Unsupported)
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` unless implemented by the backend.
+    fn deserialize_uncompressed(_bytes: &[u8]) -> Result<Self, Error> {
+        Err(Error::Unsupported)
+    }
+
     /// Returns `true` if `self` is a signature across `msg` by `pubkey`.
     fn verify(&self, pubkey: &GenericPublicKey, msg: Hash256) -> bool;
 }
@@ -103,6 +141,8 @@ where
     }
 
     /// Deserialize `self` from compressed bytes.
+    ///
+    /// The point is checked to be in the correct subgroup.
     pub fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         let point = if bytes == &NONE_SIGNATURE[..] {
             None
@@ -116,6 +156,68 @@ where
             _phantom: PhantomData,
         })
     }
+
+    /// Deserialize `self` from compressed bytes, skipping the subgroup check.
+    ///
+    /// ## Warning
+    ///
+    /// Only use this for bytes which are already known to be a valid signature. Otherwise, check
+    /// `Self::is_in_correct_subgroup` before use.
+    pub fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
+        let point = if bytes == &NONE_SIGNATURE[..] {
+            None
+        } else {
+            Some(Sig::deserialize_unchecked(bytes)?)
+        };
+
+        Ok(Self {
+            point,
+            is_infinity: bytes == &INFINITY_SIGNATURE[..],
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Returns `true` if `self` is in the correct subgroup.
+    ///
+    /// Always `false` for the "empty" signature, since it is not a point.
+    pub fn is_in_correct_subgroup(&self) -> bool {
+        self.point
+            .as_ref()
+            .map_or(false, |point| point.is_in_correct_subgroup())
+    }
+
+    /// Serialize `self` as uncompressed bytes.
+    ///
+    /// The "empty" value is serialized as all-zeros. Returns `Error::Unsupported` if the BLS
+    /// backend does not support uncompressed points.
+    pub fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        if let Some(point) = &self.point {
+            point.serialize_uncompressed()
+        } else {
+            Ok(NONE_SIGNATURE_UNCOMPRESSED)
+        }
+    }
+
+    /// Deserialize `self` from uncompressed bytes.
+    ///
+    /// Returns `Error::Unsupported` if the BLS backend does not support uncompressed points.
+    pub fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != SIGNATURE_UNCOMPRESSED_BYTES_LEN {
+            return Err(Error::InvalidByteLength {
+                got: bytes.len(),
+                expected: SIGNATURE_UNCOMPRESSED_BYTES_LEN,
+            });
+        }
+
+        if bytes == &NONE_SIGNATURE_UNCOMPRESSED[..] {
+            return Ok(Self::empty());
+        }
+
+        let point = Sig::deserialize_uncompressed(bytes)?;
+        let is_infinity = point.serialize()[..] == INFINITY_SIGNATURE[..];
+
+        Ok(Self::from_point(point, is_infinity))
+    }
 }
 
 impl<Pub, Sig> GenericSignature<Pub, Sig>
@@ -0,0 +1,41 @@
+//! Strict, allocation-free hex decoding shared by the types in this crate.
+
+use crate::Error;
+
+/// Decodes the hex string `s` (with or without a `0x` prefix, in either case) into `out`.
+///
+/// The length is checked before any characters are decoded, so `s` must contain exactly
+/// `out.len() * 2` hex characters (excluding the prefix).
+pub(crate) fn decode_hex_into(s: &str, out: &mut [u8]) -> Result<(), Error> {
+    let bytes = s.as_bytes();
+    let hex = if bytes.starts_with(b"0x") {
+        &bytes[2..]
+    } else {
+        bytes
+    };
+
+    if hex.len() != out.len() * 2 {
+        return Err(Error::InvalidHexLength {
+            got: hex.len(),
+            expected: out.len() * 2,
+        });
+    }
+
+    for (i, (byte, pair)) in out.iter_mut().zip(hex.chunks(2)).enumerate() {
+        let high = decode_nibble(pair[0]).ok_or(Error::InvalidHexCharacter { index: i * 2 })?;
+        let low = decode_nibble(pair[1]).ok_or(Error::InvalidHexCharacter { index: i * 2 + 1 })?;
+        *byte = (high << 4) | low;
+    }
+
+    Ok(())
+}
+
+/// Decodes a single ASCII hex character.
+fn decode_nibble(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
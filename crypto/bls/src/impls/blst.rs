@@ -1,9 +1,11 @@
 use crate::{
     generic_aggregate_public_key::TAggregatePublicKey,
     generic_aggregate_signature::TAggregateSignature,
-    generic_public_key::{GenericPublicKey, TPublicKey, PUBLIC_KEY_BYTES_LEN},
+    generic_public_key::{
+        GenericPublicKey, TPublicKey, PUBLIC_KEY_BYTES_LEN, PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN,
+    },
     generic_secret_key::TSecretKey,
-    generic_signature::{TSignature, SIGNATURE_BYTES_LEN},
+    generic_signature::{TSignature, SIGNATURE_BYTES_LEN, SIGNATURE_UNCOMPRESSED_BYTES_LEN},
     Error, Hash256, ZeroizeHash, INFINITY_PUBLIC_KEY, INFINITY_SIGNATURE,
 };
 pub use blst::min_pk as blst_core;
@@ -124,8 +126,42 @@ impl TPublicKey for blst_core::PublicKey {
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let pubkey = <Self as TPublicKey>::deserialize_unchecked(bytes)?;
+        if TPublicKey::is_in_correct_subgroup(&pubkey) {
+            Ok(pubkey)
+        } else {
+            Err(BLST_ERROR::BLST_POINT_NOT_IN_GROUP.into())
+        }
+    }
+
+    fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
         Self::uncompress(&bytes).map_err(Into::into)
     }
+
+    fn is_in_correct_subgroup(&self) -> bool {
+        let bytes = blst_core::PublicKey::serialize(self);
+        let mut point = std::mem::MaybeUninit::<blst::blst_p1_affine>::uninit();
+
+        // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+        unsafe {
+            blst::blst_p1_deserialize(point.as_mut_ptr(), bytes.as_ptr())
+                == BLST_ERROR::BLST_SUCCESS
+                && blst::blst_p1_affine_in_g1(point.as_ptr())
+        }
+    }
+
+    fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        Ok(blst_core::PublicKey::serialize(self))
+    }
+
+    fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        let pubkey = blst_core::PublicKey::deserialize(bytes)?;
+        if TPublicKey::is_in_correct_subgroup(&pubkey) {
+            Ok(pubkey)
+        } else {
+            Err(BLST_ERROR::BLST_POINT_NOT_IN_GROUP.into())
+        }
+    }
 }
 
 /// A wrapper that allows for `PartialEq` and `Clone` impls.
@@ -145,7 +181,7 @@ impl PartialEq for BlstAggregatePublicKey {
     }
 }
 
-impl TAggregatePublicKey for BlstAggregatePublicKey {
+impl TAggregatePublicKey<blst_core::PublicKey> for BlstAggregatePublicKey {
     fn infinity() -> Self {
         blst_core::PublicKey::from_bytes(&INFINITY_PUBLIC_KEY)
             .map(|pk| blst_core::AggregatePublicKey::from_public_key(&pk))
@@ -163,6 +199,30 @@ impl TAggregatePublicKey for BlstAggregatePublicKey {
             .map(|pk| blst_core::AggregatePublicKey::from_public_key(&pk))
             .map(Self)
     }
+
+    fn serialize_uncompressed(&self) -> Result<[u8; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN], Error> {
+        Ok(self.0.to_public_key().serialize())
+    }
+
+    fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        blst_core::PublicKey::deserialize(bytes)
+            .map_err(Into::into)
+            .map(|pk| blst_core::AggregatePublicKey::from_public_key(&pk))
+            .map(Self)
+    }
+
+    fn aggregate(pubkeys: &[&GenericPublicKey<blst_core::PublicKey>]) -> Result<Self, Error> {
+        if pubkeys.is_empty() {
+            return Err(Error::EmptyAggregate);
+        }
+
+        let pubkeys = pubkeys.iter().map(|pk| pk.point()).collect::<Vec<_>>();
+        Ok(Self(blst_core::AggregatePublicKey::aggregate(&pubkeys)))
+    }
+
+    fn to_public_key(&self) -> blst_core::PublicKey {
+        self.0.to_public_key()
+    }
 }
 
 impl TSignature<blst_core::PublicKey> for blst_core::Signature {
@@ -171,9 +231,43 @@ impl TSignature<blst_core::PublicKey> for blst_core::Signature {
     }
 
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
+        let signature = <Self as TSignature<blst_core::PublicKey>>::deserialize_unchecked(bytes)?;
+        if <Self as TSignature<blst_core::PublicKey>>::is_in_correct_subgroup(&signature) {
+            Ok(signature)
+        } else {
+            Err(BLST_ERROR::BLST_POINT_NOT_IN_GROUP.into())
+        }
+    }
+
+    fn deserialize_unchecked(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(bytes).map_err(Into::into)
     }
 
+    fn is_in_correct_subgroup(&self) -> bool {
+        let bytes = blst_core::Signature::serialize(self);
+        let mut point = std::mem::MaybeUninit::<blst::blst_p2_affine>::uninit();
+
+        // TODO: remove this `unsafe` code-block once we get a safe option from `blst`.
+        unsafe {
+            blst::blst_p2_deserialize(point.as_mut_ptr(), bytes.as_ptr())
+                == BLST_ERROR::BLST_SUCCESS
+                && blst::blst_p2_affine_in_g2(point.as_ptr())
+        }
+    }
+
+    fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        Ok(blst_core::Signature::serialize(self))
+    }
+
+    fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        let signature = blst_core::Signature::deserialize(bytes)?;
+        if <Self as TSignature<blst_core::PublicKey>>::is_in_correct_subgroup(&signature) {
+            Ok(signature)
+        } else {
+            Err(BLST_ERROR::BLST_POINT_NOT_IN_GROUP.into())
+        }
+    }
+
     fn verify(&self, pubkey: &blst_core::PublicKey, msg: Hash256) -> bool {
         self.verify(msg.as_bytes(), DST, &[], pubkey) == BLST_ERROR::BLST_SUCCESS
     }
@@ -225,6 +319,17 @@ impl TAggregateSignature<blst_core::PublicKey, BlstAggregatePublicKey, blst_core
             .map(Self)
     }
 
+    fn serialize_uncompressed(&self) -> Result<[u8; SIGNATURE_UNCOMPRESSED_BYTES_LEN], Error> {
+        Ok(self.0.to_signature().serialize())
+    }
+
+    fn deserialize_uncompressed(bytes: &[u8]) -> Result<Self, Error> {
+        blst_core::Signature::deserialize(bytes)
+            .map_err(Into::into)
+            .map(|sig| blst_core::AggregateSignature::from_signature(&sig))
+            .map(Self)
+    }
+
     fn fast_aggregate_verify(
         &self,
         msg: Hash256,
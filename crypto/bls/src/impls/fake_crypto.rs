@@ -63,7 +63,7 @@ impl PartialEq for PublicKey {
 #[derive(Clone)]
 pub struct AggregatePublicKey([u8; PUBLIC_KEY_BYTES_LEN]);
 
-impl TAggregatePublicKey for AggregatePublicKey {
+impl TAggregatePublicKey<PublicKey> for AggregatePublicKey {
     fn infinity() -> Self {
         Self([0; PUBLIC_KEY_BYTES_LEN])
     }
@@ -81,6 +81,17 @@ impl TAggregatePublicKey for AggregatePublicKey {
 
         Ok(Self(key))
     }
+
+    fn aggregate(pubkeys: &[&GenericPublicKey<PublicKey>]) -> Result<Self, Error> {
+        pubkeys
+            .first()
+            .map(|pubkey| Self(pubkey.serialize()))
+            .ok_or(Error::EmptyAggregate)
+    }
+
+    fn to_public_key(&self) -> PublicKey {
+        PublicKey(self.0)
+    }
 }
 
 impl Eq for AggregatePublicKey {}
@@ -86,7 +86,7 @@ impl TPublicKey for milagro::PublicKey {
     }
 }
 
-impl TAggregatePublicKey for milagro::AggregatePublicKey {
+impl TAggregatePublicKey<milagro::PublicKey> for milagro::AggregatePublicKey {
     fn infinity() -> Self {
         Self::from_bytes(&INFINITY_PUBLIC_KEY).expect("should decode infinity public key")
     }
@@ -100,6 +100,24 @@ impl TAggregatePublicKey for milagro::AggregatePublicKey {
     fn deserialize(bytes: &[u8]) -> Result<Self, Error> {
         Self::from_bytes(&bytes).map_err(Into::into)
     }
+
+    fn aggregate(pubkeys: &[&GenericPublicKey<milagro::PublicKey>]) -> Result<Self, Error> {
+        let mut aggregate = milagro::AggregatePublicKey::from_public_key(
+            pubkeys.first().ok_or(Error::EmptyAggregate)?.point(),
+        );
+
+        for pubkey in pubkeys.iter().skip(1) {
+            aggregate.add(pubkey.point())
+        }
+
+        Ok(aggregate)
+    }
+
+    fn to_public_key(&self) -> milagro::PublicKey {
+        milagro::PublicKey {
+            point: self.point.clone(),
+        }
+    }
 }
 
 impl TSignature<milagro::PublicKey> for milagro::Signature {
@@ -0,0 +1,85 @@
+//! Implements `KeyGen` from the BLS signature specification:
+//!
+//! https://tools.ietf.org/html/draft-irtf-cfrg-bls-signature-04#section-2.3
+//!
+//! All intermediate buffers which hold secret material are zeroized on drop.
+
+use crate::{Error, ZeroizeHash, CURVE_ORDER, SECRET_KEY_BYTES_LEN};
+use eth2_hashing::hash;
+use num_bigint_dig::BigUint;
+use ring::hkdf::{KeyType, Salt, HKDF_SHA256};
+use zeroize::{Zeroize, Zeroizing};
+
+/// The minimum byte-length of the IKM (input key material).
+pub const MIN_IKM_BYTES_LEN: usize = 32;
+
+/// The initial salt, which is hashed before every attempt.
+const KEYGEN_SALT: &[u8] = b"BLS-SIG-KEYGEN-SALT-";
+
+/// The `L` value used for `HKDF-Expand`, defined as `ceil((3 * ceil(log2(r))) / 16)`.
+const L: usize = 48;
+
+/// Generates a secret key from `ikm` (input key material) and `key_info`.
+///
+/// Equivalent to `KeyGen` in the BLS signature specification. The salt is re-hashed and the
+/// derivation retried until a non-zero secret key is produced.
+///
+/// ## Errors
+///
+/// Returns an error if `ikm` is shorter than `MIN_IKM_BYTES_LEN`.
+pub fn generate_secret_key_from_ikm(ikm: &[u8], key_info: &[u8]) -> Result<ZeroizeHash, Error> {
+    if ikm.len() < MIN_IKM_BYTES_LEN {
+        return Err(Error::IkmTooShort {
+            got: ikm.len(),
+            min: MIN_IKM_BYTES_LEN,
+        });
+    }
+
+    // IKM || I2OSP(0, 1)
+    let mut padded_ikm = Zeroizing::new(Vec::with_capacity(ikm.len() + 1));
+    padded_ikm.extend_from_slice(ikm);
+    padded_ikm.push(0);
+
+    let r = BigUint::from_bytes_be(&CURVE_ORDER);
+    let mut salt = KEYGEN_SALT.to_vec();
+
+    loop {
+        salt = hash(&salt);
+
+        let mut okm = Zeroizing::new(vec![0; L]);
+        Salt::new(HKDF_SHA256, &salt)
+            .extract(&padded_ikm)
+            .expand(&[key_info, &(L as u16).to_be_bytes()], ExpandLen(L))
+            .expect("expand len is constant and cannot be too large")
+            .fill(&mut okm)
+            .expect("fill len is constant and cannot be too large");
+
+        let mut n = BigUint::from_bytes_be(&okm);
+        let mut sk = &n % &r;
+        n.zeroize();
+
+        let sk_bytes = Zeroizing::new(sk.to_bytes_be());
+        sk.zeroize();
+
+        // `to_bytes_be` returns `[0]` for zero.
+        if sk_bytes.iter().all(|byte| *byte == 0) {
+            continue;
+        }
+
+        debug_assert!(sk_bytes.len() <= SECRET_KEY_BYTES_LEN);
+
+        let mut output = ZeroizeHash::zero();
+        output.as_mut_bytes()[SECRET_KEY_BYTES_LEN - sk_bytes.len()..].copy_from_slice(&sk_bytes);
+
+        return Ok(output);
+    }
+}
+
+/// Allows an arbitrary output length to be requested from `HKDF-Expand`.
+struct ExpandLen(usize);
+
+impl KeyType for ExpandLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
@@ -31,15 +31,26 @@ mod generic_signature;
 mod generic_signature_bytes;
 mod generic_signature_set;
 mod get_withdrawal_credentials;
+mod hex_decode;
+mod keygen;
+mod parallel_verification;
 mod zeroize_hash;
+mod zeroize_string;
 
 pub mod impls;
-
-pub use generic_public_key::{INFINITY_PUBLIC_KEY, PUBLIC_KEY_BYTES_LEN};
-pub use generic_secret_key::SECRET_KEY_BYTES_LEN;
-pub use generic_signature::{INFINITY_SIGNATURE, SIGNATURE_BYTES_LEN};
+pub mod serde_zeroize_hash;
+
+pub use generic_public_key::{
+    INFINITY_PUBLIC_KEY, PUBLIC_KEY_BYTES_LEN, PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN,
+};
+pub use generic_secret_key::{CURVE_ORDER, SECRET_KEY_BYTES_LEN};
+pub use generic_signature::{
+    INFINITY_SIGNATURE, SIGNATURE_BYTES_LEN, SIGNATURE_UNCOMPRESSED_BYTES_LEN,
+};
 pub use get_withdrawal_credentials::get_withdrawal_credentials;
-pub use zeroize_hash::ZeroizeHash;
+pub use keygen::{generate_secret_key_from_ikm, MIN_IKM_BYTES_LEN};
+pub use zeroize_hash::{ZeroizeHash, FINGERPRINT_BYTES_LEN};
+pub use zeroize_string::ZeroizeString;
 
 use blst::BLST_ERROR as BlstError;
 use milagro_bls::AmclError;
@@ -56,6 +67,20 @@ pub enum Error {
     InvalidByteLength { got: usize, expected: usize },
     /// The provided secret key bytes were an incorrect length.
     InvalidSecretKeyLength { got: usize, expected: usize },
+    /// The provided secret key is not a scalar in the range `0 < sk < r`.
+    InvalidSecretKey,
+    /// An attempt was made to aggregate an empty list of points.
+    EmptyAggregate,
+    /// The operation is not supported by the selected BLS backend.
+    Unsupported,
+    /// The provided hex string was an incorrect length (measured in characters, excluding any
+    /// `0x` prefix).
+    InvalidHexLength { got: usize, expected: usize },
+    /// The provided hex string contained a non-hex character at `index` (excluding any `0x`
+    /// prefix).
+    InvalidHexCharacter { index: usize },
+    /// The IKM (input key material) provided to `KeyGen` was too short.
+    IkmTooShort { got: usize, min: usize },
 }
 
 impl From<AmclError> for Error {
@@ -72,6 +97,7 @@ impl From<BlstError> for Error {
 
 /// Generic implementations which are only generally useful for docs.
 pub mod generics {
+    pub use crate::generic_aggregate_public_key::GenericAggregatePublicKey;
     pub use crate::generic_aggregate_signature::GenericAggregateSignature;
     pub use crate::generic_keypair::GenericKeypair;
     pub use crate::generic_public_key::GenericPublicKey;
@@ -93,8 +119,39 @@ macro_rules! define_mod {
 
             pub use bls_variant::{verify_signature_sets, SignatureSet};
 
+            /// Verifies `signature_sets` by splitting them across the `rayon` thread pool and
+            /// calling `verify_signature_sets` on each chunk.
+            ///
+            /// Returns `true` only if every set is valid (a single invalid set fails the batch).
+            /// Returns `false` if `signature_sets` is empty.
+            pub fn verify_signature_sets_parallel<'a>(
+                signature_sets: impl Iterator<Item = SignatureSet<'a>>,
+            ) -> bool {
+                crate::parallel_verification::verify_in_parallel(
+                    signature_sets.collect(),
+                    |chunk| verify_signature_sets(chunk.iter()),
+                )
+            }
+
+            /// As per `verify_signature_sets_parallel`, but returns a result for each set in
+            /// `signature_sets` (in the same order).
+            ///
+            /// Sets are verified in batches and only chunks containing an invalid set fall back
+            /// to per-set verification, so this is just as fast when all sets are valid.
+            pub fn verify_signature_sets_parallel_per_set<'a>(
+                signature_sets: impl Iterator<Item = SignatureSet<'a>>,
+            ) -> Vec<bool> {
+                crate::parallel_verification::verify_each_in_parallel(
+                    signature_sets.collect(),
+                    |chunk| verify_signature_sets(chunk.iter()),
+                    |set| set.clone().verify(),
+                )
+            }
+
             pub type PublicKey = GenericPublicKey<bls_variant::PublicKey>;
             pub type PublicKeyBytes = GenericPublicKeyBytes<bls_variant::PublicKey>;
+            pub type AggregatePublicKey =
+                GenericAggregatePublicKey<bls_variant::PublicKey, bls_variant::AggregatePublicKey>;
             pub type Signature = GenericSignature<bls_variant::PublicKey, bls_variant::Signature>;
             pub type AggregateSignature = GenericAggregateSignature<
                 bls_variant::PublicKey,
@@ -0,0 +1,57 @@
+//! Provides backend-agnostic functions for verifying signature sets across multiple cores.
+//!
+//! The functions in this module are generic across some signature set `T` and a `verify` function
+//! which performs batch verification for the backend. They are made concrete for each backend by
+//! the `define_mod!` macro in `lib.rs`.
+
+use rayon::prelude::*;
+
+/// Returns the size of each chunk when spreading `num_sets` across the `rayon` thread pool.
+fn chunk_size(num_sets: usize) -> usize {
+    std::cmp::max(1, num_sets / rayon::current_num_threads())
+}
+
+/// Splits `sets` into chunks across the `rayon` thread pool and calls `verify` on each chunk,
+/// returning `true` if all chunks are valid.
+///
+/// Returns `false` if `sets` is empty, in the same way as `verify_signature_sets`.
+pub fn verify_in_parallel<T, F>(sets: Vec<T>, verify: F) -> bool
+where
+    T: Send + Sync,
+    F: Fn(&[T]) -> bool + Send + Sync,
+{
+    if sets.is_empty() {
+        return false;
+    }
+
+    sets.par_chunks(chunk_size(sets.len()))
+        .all(|chunk| verify(chunk))
+}
+
+/// Splits `sets` into chunks across the `rayon` thread pool and calls `verify` on each chunk. If a
+/// chunk is invalid, each of its sets is verified individually with `verify_single`.
+///
+/// Returns one result per set in `sets`, in the same order.
+pub fn verify_each_in_parallel<T, F, G>(sets: Vec<T>, verify: F, verify_single: G) -> Vec<bool>
+where
+    T: Send + Sync,
+    F: Fn(&[T]) -> bool + Send + Sync,
+    G: Fn(&T) -> bool + Send + Sync,
+{
+    if sets.is_empty() {
+        return vec![];
+    }
+
+    sets.par_chunks(chunk_size(sets.len()))
+        .map(|chunk| {
+            if verify(chunk) {
+                vec![true; chunk.len()]
+            } else {
+                chunk.iter().map(|set| verify_single(set)).collect()
+            }
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .flatten()
+        .collect()
+}
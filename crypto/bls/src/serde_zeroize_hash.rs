@@ -0,0 +1,27 @@
+//! Provides a `serde` deserializer for secret material which avoids leaving unzeroized copies of
+//! the secret on the heap.
+//!
+//! The `0x`-prefixed hex string is read into a `ZeroizeString` and decoded directly into a
+//! `ZeroizeHash` (see `ZeroizeHash::from_hex`), so no intermediate `Vec<u8>` is allocated. Both
+//! are zeroized on drop.
+//!
+//! Use it on your own structs with `#[serde(with = "bls::serde_zeroize_hash")]`. There is
+//! deliberately no `serialize` function, so secret material cannot accidentally be exported.
+
+use crate::{ZeroizeHash, ZeroizeString};
+use serde::de::{Deserialize, Deserializer, Error};
+
+/// Deserialize a `0x`-prefixed hex string into a `ZeroizeHash`.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<ZeroizeHash, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let string = ZeroizeString::deserialize(deserializer)?;
+
+    if !string.as_str().starts_with("0x") {
+        return Err(D::Error::custom("secret must have 0x prefix"));
+    }
+
+    ZeroizeHash::from_hex(string.as_str())
+        .map_err(|e| D::Error::custom(format!("invalid secret ({:?})", e)))
+}
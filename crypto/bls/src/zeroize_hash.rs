@@ -1,6 +1,12 @@
-use super::SECRET_KEY_BYTES_LEN;
+use super::{hex_decode::decode_hex_into, Error, SECRET_KEY_BYTES_LEN};
+use eth2_hashing::hash;
+use serde::de::{Deserialize, Deserializer};
+use std::fmt;
 use zeroize::Zeroize;
 
+/// The number of bytes of the SHA-256 hash of a secret included in its fingerprint.
+pub const FINGERPRINT_BYTES_LEN: usize = 4;
+
 /// Provides a wrapper around a `[u8; SECRET_KEY_BYTES_LEN]` that implements `Zeroize` on `Drop`.
 #[derive(Zeroize)]
 #[zeroize(drop)]
@@ -21,6 +27,31 @@ impl ZeroizeHash {
     pub fn as_mut_bytes(&mut self) -> &mut [u8] {
         &mut self.0
     }
+
+    /// Decodes the hex string `s` (with or without a `0x` prefix) into `Self`.
+    ///
+    /// The length is checked before decoding and each character is decoded directly into the
+    /// internal array, so no intermediate (unzeroized) buffer is allocated.
+    pub fn from_hex(s: &str) -> Result<Self, Error> {
+        let mut hash = Self::zero();
+        decode_hex_into(s, &mut hash.0)?;
+        Ok(hash)
+    }
+
+    /// Returns the first `FINGERPRINT_BYTES_LEN` bytes of the SHA-256 hash of the secret, as hex.
+    ///
+    /// The fingerprint is not sensitive, so it may be logged or shared to confirm that two
+    /// machines hold the same secret.
+    pub fn fingerprint(&self) -> String {
+        hex::encode(&hash(&self.0)[..FINGERPRINT_BYTES_LEN])
+    }
+}
+
+/// Prints only the fingerprint, so the secret cannot leak into logs.
+impl fmt::Debug for ZeroizeHash {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZeroizeHash(fp={})", self.fingerprint())
+    }
 }
 
 impl From<[u8; SECRET_KEY_BYTES_LEN]> for ZeroizeHash {
@@ -34,3 +65,15 @@ impl AsRef<[u8]> for ZeroizeHash {
         &self.0
     }
 }
+
+/// Deserializes from a `0x`-prefixed hex string without leaving unzeroized copies of the secret.
+///
+/// See the `serde_zeroize_hash` module.
+impl<'de> Deserialize<'de> for ZeroizeHash {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        crate::serde_zeroize_hash::deserialize(deserializer)
+    }
+}
@@ -0,0 +1,40 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+use zeroize::Zeroize;
+
+/// Provides a new-type wrapper around `String` that is zeroized on `Drop`.
+///
+/// Useful for ensuring that password memory is zeroed-out on drop.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Zeroize)]
+#[zeroize(drop)]
+#[serde(transparent)]
+pub struct ZeroizeString(String);
+
+impl ZeroizeString {
+    /// Returns a reference to the underlying string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for ZeroizeString {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+/// Prints nothing about the contents.
+///
+/// Unlike `ZeroizeHash`, no fingerprint is printed since passwords may have little enough entropy
+/// to be recovered from one.
+impl fmt::Debug for ZeroizeString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "ZeroizeString(<redacted>)")
+    }
+}
+
+impl AsRef<[u8]> for ZeroizeString {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+}
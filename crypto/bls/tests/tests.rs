@@ -1,8 +1,16 @@
-use bls::{Hash256, INFINITY_PUBLIC_KEY, INFINITY_SIGNATURE};
+use bls::{
+    Hash256, ZeroizeHash, ZeroizeString, CURVE_ORDER, FINGERPRINT_BYTES_LEN, INFINITY_PUBLIC_KEY,
+    INFINITY_SIGNATURE, PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN, SIGNATURE_UNCOMPRESSED_BYTES_LEN,
+};
 use ssz::{Decode, Encode};
 use std::borrow::Cow;
 use std::fmt::Debug;
 
+/// The first interop secret key and its public key.
+const INTEROP_SECRET_KEY: &str =
+    "0x25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a6866";
+const INTEROP_PUBLIC_KEY: &str = "0xa99a76ed7796f7be22d5b7e85deeb7c5677e88e511e0b337618f8c4eb61349b4bf2d153f649f7b53359fe8b94a38e44c";
+
 fn ssz_round_trip<T: Encode + Decode + PartialEq + Debug>(item: T) {
     assert_eq!(item, T::from_ssz_bytes(&item.as_ssz_bytes()).unwrap());
 }
@@ -61,6 +69,167 @@ macro_rules! test_suite {
             ssz_round_trip(AggregateSignature::infinity())
         }
 
+        #[test]
+        fn from_secret_hash_matches_deserialize() {
+            let bytes = [42; 32];
+            let from_hash = SecretKey::from_secret_hash(ZeroizeHash::from(bytes)).unwrap();
+            let deserialized = SecretKey::deserialize(&bytes).unwrap();
+
+            assert_eq!(from_hash.public_key(), deserialized.public_key());
+            assert_eq!(from_hash.serialize().as_bytes(), &bytes[..]);
+        }
+
+        #[test]
+        fn from_secret_hash_rejects_invalid_scalars() {
+            let mut max = [0xff; 32];
+            let mut order_plus_one = CURVE_ORDER;
+            order_plus_one[31] += 1;
+            let mut order_minus_one = CURVE_ORDER;
+            order_minus_one[31] -= 1;
+
+            for invalid in &[[0; 32], CURVE_ORDER, order_plus_one, max] {
+                assert_eq!(
+                    SecretKey::from_secret_hash(ZeroizeHash::from(*invalid)).err(),
+                    Some(bls::Error::InvalidSecretKey)
+                );
+            }
+
+            max[0] = 0;
+            for valid in &[order_minus_one, max] {
+                assert!(SecretKey::from_secret_hash(ZeroizeHash::from(*valid)).is_ok());
+            }
+        }
+
+        #[test]
+        fn secret_hash_round_trip() {
+            use std::convert::TryFrom;
+
+            let secret = secret_from_u64(42);
+            let hash = secret.to_secret_hash();
+            assert_eq!(hash.as_bytes(), secret.serialize().as_bytes());
+
+            let decoded = SecretKey::try_from(hash).unwrap();
+            assert_eq!(decoded.public_key(), secret.public_key());
+            assert_eq!(
+                decoded.to_secret_hash().as_bytes(),
+                secret.serialize().as_bytes()
+            );
+
+            assert_eq!(
+                SecretKey::try_from(ZeroizeHash::from(CURVE_ORDER)).err(),
+                Some(bls::Error::InvalidSecretKey)
+            );
+            assert_eq!(
+                SecretKey::try_from(ZeroizeHash::zero()).err(),
+                Some(bls::Error::InvalidSecretKey)
+            );
+        }
+
+        #[test]
+        fn secret_key_debug_is_fingerprint() {
+            let secret = secret_from_u64(42);
+            let fingerprint = secret.serialize().fingerprint();
+
+            assert_eq!(
+                format!("{:?}", secret),
+                format!("SecretKey(fp={})", fingerprint)
+            );
+            assert_ne!(
+                format!("{:?}", secret),
+                format!("{:?}", secret_from_u64(43))
+            );
+        }
+
+        #[test]
+        fn secret_key_deserialize_known_vector() {
+            let secret: SecretKey = serde_json::from_str(&format!("\"{}\"", INTEROP_SECRET_KEY))
+                .expect("should deserialize secret key");
+
+            assert_eq!(secret.public_key().to_hex_string(), INTEROP_PUBLIC_KEY);
+        }
+
+        #[test]
+        fn secret_key_deserialize_invalid() {
+            let zero = format!("0x{}", "00".repeat(32));
+            let order = format!("0x{}", hex::encode(CURVE_ORDER));
+
+            for invalid in &[
+                &INTEROP_SECRET_KEY[2..],
+                &INTEROP_SECRET_KEY[..64],
+                "0x25295f0d1d592a90b333e26e85149708208e9f8e8bc18f6c77bd62f8ad7a68zz",
+                zero.as_str(),
+                order.as_str(),
+            ] {
+                assert!(serde_json::from_str::<SecretKey>(&format!("\"{}\"", invalid)).is_err());
+            }
+        }
+
+        #[test]
+        fn secret_key_from_ikm() {
+            let ikm = [42; bls::MIN_IKM_BYTES_LEN];
+            let hash = bls::generate_secret_key_from_ikm(&ikm, &[]).unwrap();
+            let secret = SecretKey::from_ikm(&ikm, &[]).unwrap();
+
+            assert_eq!(secret.serialize().as_bytes(), hash.as_bytes());
+            assert_eq!(
+                SecretKey::from_ikm(&ikm[1..], &[]).err(),
+                Some(bls::Error::IkmTooShort {
+                    got: bls::MIN_IKM_BYTES_LEN - 1,
+                    min: bls::MIN_IKM_BYTES_LEN
+                })
+            );
+        }
+
+        #[test]
+        fn public_key_hex_round_trip() {
+            let pubkey = secret_from_u64(42).public_key();
+            let hex = pubkey.to_hex_string();
+
+            assert!(hex.starts_with("0x"));
+            assert_eq!(hex, hex.to_lowercase());
+            assert_eq!(PublicKey::from_hex_str(&hex).unwrap(), pubkey);
+            assert_eq!(PublicKey::from_hex_str(&hex[2..]).unwrap(), pubkey);
+            assert_eq!(
+                PublicKey::from_hex_str(&hex.to_uppercase().replace("0X", "0x")).unwrap(),
+                pubkey
+            );
+        }
+
+        #[test]
+        fn public_key_bytes_hex_matches_public_key() {
+            let pubkey = secret_from_u64(42).public_key();
+            let bytes = PublicKeyBytes::from(pubkey.clone());
+            let hex = bytes.to_hex_string();
+
+            assert_eq!(hex, pubkey.to_hex_string());
+            assert_eq!(PublicKeyBytes::from_hex_str(&hex).unwrap(), bytes);
+            assert_eq!(PublicKeyBytes::from_hex_str(&hex[2..]).unwrap(), bytes);
+        }
+
+        #[test]
+        fn public_key_hex_invalid() {
+            let hex = secret_from_u64(42).public_key().to_hex_string();
+
+            assert_eq!(
+                PublicKey::from_hex_str(&hex[..hex.len() - 2]).err(),
+                Some(bls::Error::InvalidHexLength {
+                    got: 94,
+                    expected: 96
+                })
+            );
+            assert_eq!(
+                PublicKeyBytes::from_hex_str(&format!("{}00", hex)).err(),
+                Some(bls::Error::InvalidHexLength {
+                    got: 98,
+                    expected: 96
+                })
+            );
+            assert_eq!(
+                PublicKey::from_hex_str(&hex.replace("0x", "0xz")[..98]).err(),
+                Some(bls::Error::InvalidHexCharacter { index: 0 })
+            );
+        }
+
         #[test]
         fn partial_eq_empty_sig() {
             assert_eq!(Signature::empty(), Signature::empty())
@@ -392,6 +561,53 @@ macro_rules! test_suite {
                 .assert_single_message_verify(true)
         }
 
+        #[test]
+        fn aggregate_public_key_empty() {
+            assert_eq!(
+                AggregatePublicKey::aggregate(&[]).err(),
+                Some(bls::Error::EmptyAggregate)
+            );
+        }
+
+        #[test]
+        fn aggregate_public_key_single() {
+            let pubkey = secret_from_u64(0).public_key();
+            let aggregate = AggregatePublicKey::aggregate(&[&pubkey]).unwrap();
+            assert_eq!(aggregate.to_public_key(), pubkey);
+        }
+
+        #[test]
+        fn aggregate_public_key_verifies_aggregate_signature() {
+            for num_pubkeys in &[1, 2, 128] {
+                let tester = AggregateSignatureTester::new_with_single_msg(*num_pubkeys);
+                let msg = tester.msgs[0];
+                let pubkeys = tester.pubkeys.iter().collect::<Vec<_>>();
+
+                let aggregate = AggregatePublicKey::aggregate(&pubkeys)
+                    .unwrap()
+                    .to_public_key();
+
+                assert!(tester.sig.fast_aggregate_verify(msg, &pubkeys));
+                assert!(tester.sig.fast_aggregate_verify(msg, &[&aggregate]));
+                assert!(!tester
+                    .sig
+                    .fast_aggregate_verify(Hash256::from_low_u64_be(1), &[&aggregate]));
+            }
+        }
+
+        #[test]
+        fn aggregate_public_key_missing_signer_fails() {
+            let tester = AggregateSignatureTester::new_with_single_msg(4);
+            let pubkeys = tester.pubkeys.iter().skip(1).collect::<Vec<_>>();
+            let aggregate = AggregatePublicKey::aggregate(&pubkeys)
+                .unwrap()
+                .to_public_key();
+
+            assert!(!tester
+                .sig
+                .fast_aggregate_verify(tester.msgs[0], &[&aggregate]));
+        }
+
         /// A helper struct to make it easer to deal with `SignatureSet` lifetimes.
         struct OwnedSignatureSet {
             signature: AggregateSignature,
@@ -517,6 +733,26 @@ macro_rules! test_suite {
                     verify_signature_sets(signature_sets.iter()),
                     should_be_valid
                 );
+
+                assert_eq!(
+                    verify_signature_sets_parallel(signature_sets.clone().into_iter()),
+                    should_be_valid,
+                    "parallel verification expected {} but got {}",
+                    should_be_valid,
+                    !should_be_valid
+                );
+
+                let expected_per_set = self
+                    .owned_sets
+                    .iter()
+                    .map(|owned_set| owned_set.should_be_valid)
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    verify_signature_sets_parallel_per_set(signature_sets.into_iter()),
+                    expected_per_set,
+                    "parallel per-set verification results are incorrect"
+                );
             }
         }
 
@@ -593,6 +829,38 @@ macro_rules! test_suite {
                 .run_checks()
         }
 
+        #[test]
+        fn signature_set_parallel_64_valid_sets() {
+            let mut tester = SignatureSetTester::default();
+            for _ in 0..64 {
+                tester = tester.push_valid_set(2);
+            }
+            tester.run_checks()
+        }
+
+        #[test]
+        fn signature_set_parallel_with_corrupted_sets() {
+            // Place a corrupted set at the start, middle and end of a large batch so that it
+            // lands in different chunks of the thread pool.
+            for corrupt_index in &[0, 31, 63] {
+                let mut tester = SignatureSetTester::default();
+                for i in 0..64 {
+                    tester = if i == *corrupt_index {
+                        tester.push_invalid_set()
+                    } else {
+                        tester.push_valid_set(2)
+                    };
+                }
+                tester.run_checks()
+            }
+        }
+
+        #[test]
+        fn signature_set_parallel_empty() {
+            assert!(!verify_signature_sets_parallel(std::iter::empty()));
+            assert!(verify_signature_sets_parallel_per_set(std::iter::empty()).is_empty());
+        }
+
         #[test]
         fn signature_set_3_sets_with_one_invalid_sig_infinity_set() {
             SignatureSetTester::default()
@@ -604,11 +872,392 @@ macro_rules! test_suite {
     };
 }
 
+#[test]
+fn serde_zeroize_hash_helper() {
+    #[derive(serde_derive::Deserialize)]
+    struct Secret {
+        #[serde(with = "bls::serde_zeroize_hash")]
+        secret: ZeroizeHash,
+    }
+
+    let json = format!("{{\"secret\": \"{}\"}}", INTEROP_SECRET_KEY);
+    let decoded: Secret = serde_json::from_str(&json).expect("should deserialize");
+
+    assert_eq!(
+        decoded.secret.as_bytes(),
+        &hex::decode(&INTEROP_SECRET_KEY[2..]).unwrap()[..]
+    );
+}
+
+#[test]
+fn zeroize_hash_debug_is_fingerprint() {
+    let hash = ZeroizeHash::from_hex(INTEROP_SECRET_KEY).unwrap();
+    let debug = format!("{:?}", hash);
+
+    assert_eq!(hash.fingerprint().len(), FINGERPRINT_BYTES_LEN * 2);
+    assert_eq!(
+        debug.len(),
+        "ZeroizeHash(fp=)".len() + FINGERPRINT_BYTES_LEN * 2
+    );
+    assert_eq!(debug, format!("ZeroizeHash(fp={})", hash.fingerprint()));
+    assert!(!debug.contains(&INTEROP_SECRET_KEY[2..]));
+}
+
+#[test]
+fn zeroize_hash_fingerprints_differ() {
+    let fingerprints = (0..=255)
+        .map(|byte| ZeroizeHash::from([byte; 32]).fingerprint())
+        .collect::<std::collections::HashSet<_>>();
+
+    assert_eq!(fingerprints.len(), 256);
+    // The fingerprint is deterministic.
+    assert_eq!(
+        ZeroizeHash::from([1; 32]).fingerprint(),
+        ZeroizeHash::from([1; 32]).fingerprint()
+    );
+}
+
+#[test]
+fn zeroize_string_debug_is_redacted() {
+    let password = ZeroizeString::from("hunter2".to_string());
+    assert_eq!(format!("{:?}", password), "ZeroizeString(<redacted>)");
+}
+
+#[test]
+fn zeroize_hash_from_hex_valid() {
+    let expected = hex::decode(&INTEROP_SECRET_KEY[2..]).unwrap();
+
+    let prefixed = ZeroizeHash::from_hex(INTEROP_SECRET_KEY).unwrap();
+    let unprefixed = ZeroizeHash::from_hex(&INTEROP_SECRET_KEY[2..]).unwrap();
+    let upper = ZeroizeHash::from_hex(&INTEROP_SECRET_KEY[2..].to_uppercase()).unwrap();
+
+    assert_eq!(prefixed.as_bytes(), &expected[..]);
+    assert_eq!(unprefixed.as_bytes(), &expected[..]);
+    assert_eq!(upper.as_bytes(), &expected[..]);
+}
+
+#[test]
+fn zeroize_hash_from_hex_wrong_length() {
+    assert_eq!(
+        ZeroizeHash::from_hex("0x").err(),
+        Some(bls::Error::InvalidHexLength {
+            got: 0,
+            expected: 64
+        })
+    );
+    assert_eq!(
+        ZeroizeHash::from_hex(&format!("{}00", INTEROP_SECRET_KEY)).err(),
+        Some(bls::Error::InvalidHexLength {
+            got: 66,
+            expected: 64
+        })
+    );
+}
+
+#[test]
+fn zeroize_hash_from_hex_odd_length() {
+    assert_eq!(
+        ZeroizeHash::from_hex(&INTEROP_SECRET_KEY[..65]).err(),
+        Some(bls::Error::InvalidHexLength {
+            got: 63,
+            expected: 64
+        })
+    );
+}
+
+#[test]
+fn zeroize_hash_from_hex_invalid_characters() {
+    let mut invalid = INTEROP_SECRET_KEY.to_string();
+    invalid.replace_range(7..8, "g");
+
+    assert_eq!(
+        ZeroizeHash::from_hex(&invalid).err(),
+        Some(bls::Error::InvalidHexCharacter { index: 5 })
+    );
+    assert_eq!(
+        ZeroizeHash::from_hex(&invalid[2..]).err(),
+        Some(bls::Error::InvalidHexCharacter { index: 5 })
+    );
+}
+
+#[test]
+fn zeroize_hash_from_hex_non_ascii() {
+    // 32 two-byte characters, so the byte-length is correct.
+    let non_ascii = "é".repeat(32);
+    assert!(ZeroizeHash::from_hex(&non_ascii).is_err());
+
+    // Multi-byte characters directly after the prefix.
+    let non_ascii = format!("0x{}", "€".repeat(21));
+    assert!(ZeroizeHash::from_hex(&non_ascii).is_err());
+    assert!(ZeroizeHash::from_hex("0xé").is_err());
+}
+
+/// `derive_master_SK` vectors from EIP-2333, which is `KeyGen` with an empty `key_info`.
+#[test]
+fn keygen_eip2333_vectors() {
+    let vectors = [
+        (
+            "0xc55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            "6083874454709270928345386274498605044986640685124978867557563392430687146096",
+        ),
+        (
+            "0x3141592653589793238462643383279502884197169399375105820974944592",
+            "29757020647961307431480504535336562678282505419141012933316116377660817309383",
+        ),
+    ];
+
+    for (ikm, expected) in &vectors {
+        let ikm = hex::decode(&ikm[2..]).unwrap();
+        let expected = num_bigint_dig::BigUint::parse_bytes(expected.as_bytes(), 10)
+            .unwrap()
+            .to_bytes_be();
+
+        let secret = bls::generate_secret_key_from_ikm(&ikm, &[]).unwrap();
+        assert_eq!(secret.as_bytes(), &expected[..]);
+    }
+}
+
+#[test]
+fn keygen_key_info() {
+    let ikm = [42; bls::MIN_IKM_BYTES_LEN];
+
+    let a = bls::generate_secret_key_from_ikm(&ikm, &[]).unwrap();
+    let b = bls::generate_secret_key_from_ikm(&ikm, b"key info").unwrap();
+
+    assert!(a.as_bytes() != b.as_bytes());
+}
+
+#[test]
+fn keygen_short_ikm() {
+    let ikm = [42; bls::MIN_IKM_BYTES_LEN - 1];
+
+    assert_eq!(
+        bls::generate_secret_key_from_ikm(&ikm, &[]).err(),
+        Some(bls::Error::IkmTooShort {
+            got: bls::MIN_IKM_BYTES_LEN - 1,
+            min: bls::MIN_IKM_BYTES_LEN
+        })
+    );
+}
+
 mod blst {
     test_suite!(blst_implementations);
+
+    #[test]
+    fn public_key_subgroup_check() {
+        let pubkey = secret_from_u64(42).public_key();
+        let unchecked = PublicKey::deserialize_unchecked(&pubkey.serialize()).unwrap();
+
+        assert_eq!(unchecked, pubkey);
+        assert!(unchecked.is_in_correct_subgroup());
+        assert!(pubkey.is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn public_key_not_in_subgroup() {
+        // Almost every point on the curve is outside of the subgroup, so take the first one.
+        let (bytes, pubkey) = (1..=255)
+            .find_map(|x| {
+                let mut bytes = [0; bls::PUBLIC_KEY_BYTES_LEN];
+                bytes[0] = 0x80;
+                bytes[bls::PUBLIC_KEY_BYTES_LEN - 1] = x;
+                PublicKey::deserialize_unchecked(&bytes)
+                    .ok()
+                    .map(|pubkey| (bytes, pubkey))
+            })
+            .expect("should find a point on the curve");
+
+        assert!(!pubkey.is_in_correct_subgroup());
+        assert!(PublicKey::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn signature_subgroup_check() {
+        let sig = secret_from_u64(42).sign(Hash256::from_low_u64_be(42));
+        let unchecked = Signature::deserialize_unchecked(&sig.serialize()).unwrap();
+
+        assert_eq!(unchecked, sig);
+        assert!(unchecked.is_in_correct_subgroup());
+        assert!(sig.is_in_correct_subgroup());
+    }
+
+    #[test]
+    fn signature_not_in_subgroup() {
+        // Almost every point on the curve is outside of the subgroup, so take the first one.
+        let (bytes, sig) = (1..=255)
+            .find_map(|x| {
+                let mut bytes = [0; bls::SIGNATURE_BYTES_LEN];
+                bytes[0] = 0x80;
+                bytes[bls::SIGNATURE_BYTES_LEN - 1] = x;
+                Signature::deserialize_unchecked(&bytes)
+                    .ok()
+                    .map(|sig| (bytes, sig))
+            })
+            .expect("should find a point on the curve");
+
+        assert!(!sig.is_in_correct_subgroup());
+        assert!(Signature::deserialize(&bytes).is_err());
+    }
+
+    #[test]
+    fn uncompressed_public_key_round_trip() {
+        let pubkey = secret_from_u64(42).public_key();
+        let uncompressed = pubkey.serialize_uncompressed().unwrap();
+
+        assert_eq!(uncompressed.len(), PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN);
+        assert_eq!(
+            PublicKey::deserialize_uncompressed(&uncompressed).unwrap(),
+            pubkey
+        );
+    }
+
+    #[test]
+    fn uncompressed_public_key_cross_form() {
+        let compressed = secret_from_u64(42).public_key().serialize();
+        let uncompressed = PublicKey::deserialize(&compressed)
+            .unwrap()
+            .serialize_uncompressed()
+            .unwrap();
+        let decoded = PublicKey::deserialize_uncompressed(&uncompressed).unwrap();
+
+        assert_eq!(&decoded.serialize()[..], &compressed[..]);
+    }
+
+    #[test]
+    fn uncompressed_public_key_infinity() {
+        let infinity = PublicKey::deserialize(&INFINITY_PUBLIC_KEY).unwrap();
+        let uncompressed = infinity.serialize_uncompressed().unwrap();
+        let decoded = PublicKey::deserialize_uncompressed(&uncompressed).unwrap();
+
+        assert_eq!(decoded, infinity);
+        assert_eq!(&decoded.serialize()[..], &INFINITY_PUBLIC_KEY[..]);
+    }
+
+    #[test]
+    fn uncompressed_aggregate_public_key_round_trip() {
+        let pubkeys = (0..4)
+            .map(|i| secret_from_u64(i).public_key())
+            .collect::<Vec<_>>();
+        let pubkey_refs = pubkeys.iter().collect::<Vec<_>>();
+        let aggregate = AggregatePublicKey::aggregate(&pubkey_refs).unwrap();
+        let uncompressed = aggregate.serialize_uncompressed().unwrap();
+
+        assert_eq!(
+            AggregatePublicKey::deserialize_uncompressed(&uncompressed).unwrap(),
+            aggregate
+        );
+    }
+
+    #[test]
+    fn uncompressed_signature_round_trip() {
+        let sig = secret_from_u64(42).sign(Hash256::from_low_u64_be(42));
+        let uncompressed = sig.serialize_uncompressed().unwrap();
+
+        assert_eq!(uncompressed.len(), SIGNATURE_UNCOMPRESSED_BYTES_LEN);
+        assert_eq!(
+            Signature::deserialize_uncompressed(&uncompressed).unwrap(),
+            sig
+        );
+    }
+
+    #[test]
+    fn uncompressed_signature_cross_form() {
+        let compressed = secret_from_u64(42)
+            .sign(Hash256::from_low_u64_be(42))
+            .serialize();
+        let uncompressed = Signature::deserialize(&compressed)
+            .unwrap()
+            .serialize_uncompressed()
+            .unwrap();
+        let decoded = Signature::deserialize_uncompressed(&uncompressed).unwrap();
+
+        assert_eq!(&decoded.serialize()[..], &compressed[..]);
+    }
+
+    #[test]
+    fn uncompressed_signature_empty() {
+        let uncompressed = Signature::empty().serialize_uncompressed().unwrap();
+
+        assert!(Signature::deserialize_uncompressed(&uncompressed)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn uncompressed_aggregate_signature_round_trip() {
+        let msg = Hash256::from_low_u64_be(42);
+        let mut agg_sig = AggregateSignature::infinity();
+        for i in 0..4 {
+            agg_sig.add_assign(&secret_from_u64(i).sign(msg));
+        }
+        let uncompressed = agg_sig.serialize_uncompressed().unwrap();
+
+        assert_eq!(
+            AggregateSignature::deserialize_uncompressed(&uncompressed).unwrap(),
+            agg_sig
+        );
+
+        let infinity = AggregateSignature::infinity();
+        let uncompressed = infinity.serialize_uncompressed().unwrap();
+        assert_eq!(
+            AggregateSignature::deserialize_uncompressed(&uncompressed).unwrap(),
+            infinity
+        );
+    }
+
+    #[test]
+    fn uncompressed_malformed_input() {
+        let pubkey = secret_from_u64(42).public_key();
+        let sig = secret_from_u64(42).sign(Hash256::from_low_u64_be(42));
+
+        // Incorrect length.
+        assert!(PublicKey::deserialize_uncompressed(&pubkey.serialize()).is_err());
+        assert!(Signature::deserialize_uncompressed(&sig.serialize()).is_err());
+
+        // Infinity flag set with non-zero coordinates.
+        let mut bad_infinity = [0; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN];
+        bad_infinity[0] = 0x40;
+        bad_infinity[PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN - 1] = 1;
+        assert!(PublicKey::deserialize_uncompressed(&bad_infinity).is_err());
+
+        let mut bad_infinity = [0; SIGNATURE_UNCOMPRESSED_BYTES_LEN];
+        bad_infinity[0] = 0x40;
+        bad_infinity[SIGNATURE_UNCOMPRESSED_BYTES_LEN - 1] = 1;
+        assert!(Signature::deserialize_uncompressed(&bad_infinity).is_err());
+
+        // Off-curve points.
+        let mut off_curve = pubkey.serialize_uncompressed().unwrap();
+        off_curve[PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN - 1] ^= 1;
+        assert!(PublicKey::deserialize_uncompressed(&off_curve).is_err());
+        assert!(AggregatePublicKey::deserialize_uncompressed(&off_curve).is_err());
+
+        let mut off_curve = sig.serialize_uncompressed().unwrap();
+        off_curve[SIGNATURE_UNCOMPRESSED_BYTES_LEN - 1] ^= 1;
+        assert!(Signature::deserialize_uncompressed(&off_curve).is_err());
+        assert!(AggregateSignature::deserialize_uncompressed(&off_curve).is_err());
+    }
 }
 
 #[cfg(not(debug_assertions))]
 mod milagro {
     test_suite!(milagro_implementations);
+
+    #[test]
+    fn uncompressed_unsupported() {
+        let secret = secret_from_u64(42);
+        let sig = secret.sign(Hash256::from_low_u64_be(42));
+
+        assert_eq!(
+            secret.public_key().serialize_uncompressed().err(),
+            Some(bls::Error::Unsupported)
+        );
+        assert_eq!(
+            sig.serialize_uncompressed().err(),
+            Some(bls::Error::Unsupported)
+        );
+        assert_eq!(
+            PublicKey::deserialize_uncompressed(&[0; PUBLIC_KEY_UNCOMPRESSED_BYTES_LEN]).err(),
+            Some(bls::Error::Unsupported)
+        );
+    }
 }
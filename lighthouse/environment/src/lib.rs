@@ -240,11 +240,13 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
         mut self,
         eth2_testnet_config: Eth2TestnetConfig<E>,
     ) -> Result<Self, String> {
-        // Create a new chain spec from the default configuration.
-        self.eth2_config.spec = eth2_testnet_config
+        let yaml_config = eth2_testnet_config
             .yaml_config
             .as_ref()
-            .ok_or_else(|| "The testnet directory must contain a spec config".to_string())?
+            .ok_or_else(|| "The testnet directory must contain a spec config".to_string())?;
+
+        // Create a new chain spec from the default configuration.
+        self.eth2_config.spec = yaml_config
             .apply_to_chain_spec::<E>(&self.eth2_config.spec)
             .ok_or_else(|| {
                 format!(
@@ -252,6 +254,7 @@ impl<E: EthSpec> EnvironmentBuilder<E> {
                     &self.eth2_config.spec_constants
                 )
             })?;
+        self.eth2_config.config_name = Some(yaml_config.config_name.clone());
 
         self.testnet = Some(eth2_testnet_config);
 
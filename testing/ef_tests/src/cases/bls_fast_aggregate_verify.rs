@@ -1,7 +1,7 @@
 use super::*;
 use crate::case_result::compare_result;
 use crate::cases::common::BlsCase;
-use bls::{AggregateSignature, PublicKey, PublicKeyBytes};
+use bls::{AggregatePublicKey, AggregateSignature, PublicKey, PublicKeyBytes};
 use serde_derive::Deserialize;
 use std::convert::TryInto;
 use types::Hash256;
@@ -38,12 +38,29 @@ impl Case for BlsFastAggregateVerify {
 
         let pubkey_refs = pubkeys.iter().collect::<Vec<_>>();
 
-        let signature_ok = hex::decode(&self.input.signature[2..])
+        let signature = hex::decode(&self.input.signature[2..])
             .ok()
-            .and_then(|bytes: Vec<u8>| AggregateSignature::deserialize(&bytes).ok())
+            .and_then(|bytes: Vec<u8>| AggregateSignature::deserialize(&bytes).ok());
+
+        let signature_ok = signature
+            .as_ref()
             .map(|signature| signature.fast_aggregate_verify(message, &pubkey_refs))
             .unwrap_or(false);
 
-        compare_result::<bool, ()>(&Ok(signature_ok), &Some(self.output))
+        compare_result::<bool, ()>(&Ok(signature_ok), &Some(self.output))?;
+
+        // Verifying against the aggregated public key must give the same result as
+        // `fast_aggregate_verify`.
+        let aggregate_ok = AggregatePublicKey::aggregate(&pubkey_refs)
+            .ok()
+            .and_then(|aggregate| {
+                let aggregate = aggregate.to_public_key();
+                signature
+                    .as_ref()
+                    .map(|signature| signature.fast_aggregate_verify(message, &[&aggregate]))
+            })
+            .unwrap_or(false);
+
+        compare_result::<bool, ()>(&Ok(aggregate_ok), &Some(self.output))
     }
 }
@@ -57,11 +57,7 @@ impl<E: EthSpec> LocalBeaconNode<E> {
             .client
             .http_listen_addr()
             .ok_or_else(|| "A remote beacon node must have a http server".to_string())?;
-        Ok(RemoteBeaconNode::new(format!(
-            "http://{}:{}",
-            socket_addr.ip(),
-            socket_addr.port()
-        ))?)
+        Ok(RemoteBeaconNode::new(format!("http://{}", socket_addr))?)
     }
 }
 
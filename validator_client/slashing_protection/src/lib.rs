@@ -8,8 +8,9 @@ mod test_utils;
 
 pub use crate::signed_attestation::{InvalidAttestation, SignedAttestation};
 pub use crate::signed_block::{InvalidBlock, SignedBlock};
-pub use crate::slashing_database::SlashingDatabase;
+pub use crate::slashing_database::{AutoVacuum, DatabaseOptions, SlashingDatabase};
 use rusqlite::Error as SQLError;
+pub use rusqlite::Transaction;
 use std::io::{Error as IOError, ErrorKind};
 use std::string::ToString;
 use types::{Hash256, PublicKey};
@@ -25,6 +26,7 @@ pub enum NotSafe {
     IOError(ErrorKind),
     SQLError(String),
     SQLPoolError(String),
+    InvalidValidatorIndex(u64),
 }
 
 /// The attestation or block is safe to sign, and will not cause the signer to be slashed.
@@ -52,6 +54,19 @@ fn hash256_from_row(column: usize, row: &rusqlite::Row) -> rusqlite::Result<Hash
     }
 }
 
+/// Safely parse an optional validator index from the given `column` of an SQLite `row`.
+fn validator_index_from_row(column: usize, row: &rusqlite::Row) -> rusqlite::Result<Option<u64>> {
+    use std::convert::TryFrom;
+
+    let index: Option<i64> = row.get(column)?;
+    index
+        .map(|index| {
+            u64::try_from(index)
+                .map_err(|_| rusqlite::Error::IntegralValueOutOfRange(column, index))
+        })
+        .transpose()
+}
+
 impl From<IOError> for NotSafe {
     fn from(error: IOError) -> NotSafe {
         NotSafe::IOError(error.kind())
@@ -1,10 +1,14 @@
 use crate::signed_attestation::InvalidAttestation;
 use crate::signed_block::InvalidBlock;
-use crate::{NotSafe, Safe, SignedAttestation, SignedBlock};
+use crate::{validator_index_from_row, NotSafe, Safe, SignedAttestation, SignedBlock};
+use parking_lot::RwLock;
 use r2d2_sqlite::SqliteConnectionManager;
 use rusqlite::{params, OptionalExtension, Transaction, TransactionBehavior};
+use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fs::{File, OpenOptions};
 use std::path::Path;
+use std::sync::Arc;
 use std::time::Duration;
 use types::{AttestationData, BeaconBlockHeader, Hash256, PublicKey, SignedRoot};
 
@@ -20,9 +24,53 @@ pub const CONNECTION_TIMEOUT: Duration = Duration::from_secs(5);
 #[cfg(test)]
 pub const CONNECTION_TIMEOUT: Duration = Duration::from_millis(100);
 
+/// Number of prepared statements cached by each connection, which must exceed the number of
+/// queries used when checking and inserting signed data.
+pub const STATEMENT_CACHE_CAPACITY: usize = 32;
+
+/// The SQLite `auto_vacuum` mode of a database, which can only be chosen when it is created.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AutoVacuum {
+    /// Free pages are only returned to the filesystem by `SlashingDatabase::vacuum`.
+    None,
+    /// Free pages are returned to the filesystem on every commit.
+    Full,
+    /// Free pages are returned to the filesystem by `SlashingDatabase::incremental_vacuum`, which
+    /// is cheaper than a full `vacuum`.
+    Incremental,
+}
+
+impl AutoVacuum {
+    fn pragma_value(self) -> &'static str {
+        match self {
+            AutoVacuum::None => "NONE",
+            AutoVacuum::Full => "FULL",
+            AutoVacuum::Incremental => "INCREMENTAL",
+        }
+    }
+}
+
+impl Default for AutoVacuum {
+    fn default() -> Self {
+        AutoVacuum::None
+    }
+}
+
+/// Options which apply when a database is created.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct DatabaseOptions {
+    pub auto_vacuum: AutoVacuum,
+}
+
 #[derive(Debug, Clone)]
 pub struct SlashingDatabase {
     conn_pool: Pool,
+    /// Cache of database-internal validator IDs, shared between clones of the database.
+    ///
+    /// Only ever holds IDs of rows which exist in the database, so a miss must still be checked
+    /// against the `validators` table. Any operation which deletes validators must remove their
+    /// entries.
+    validator_ids: Arc<RwLock<HashMap<PublicKey, i64>>>,
 }
 
 impl SlashingDatabase {
@@ -35,10 +83,17 @@ impl SlashingDatabase {
         }
     }
 
-    /// Create a slashing database at the given path.
+    /// Create a slashing database at the given path, with the default options.
     ///
     /// Error if a database (or any file) already exists at `path`.
     pub fn create(path: &Path) -> Result<Self, NotSafe> {
+        Self::create_with_options(path, DatabaseOptions::default())
+    }
+
+    /// Create a slashing database at the given path, with the given `options`.
+    ///
+    /// Error if a database (or any file) already exists at `path`.
+    pub fn create_with_options(path: &Path, options: DatabaseOptions) -> Result<Self, NotSafe> {
         let file = OpenOptions::new()
             .write(true)
             .read(true)
@@ -49,10 +104,14 @@ impl SlashingDatabase {
         let conn_pool = Self::open_conn_pool(path)?;
         let conn = conn_pool.get()?;
 
+        // Must be set before any tables are created.
+        conn.pragma_update(None, "auto_vacuum", &options.auto_vacuum.pragma_value())?;
+
         conn.execute(
             "CREATE TABLE validators (
                 id INTEGER PRIMARY KEY,
-                public_key BLOB NOT NULL
+                public_key BLOB NOT NULL,
+                beacon_index INTEGER
             )",
             params![],
         )?;
@@ -80,13 +139,21 @@ impl SlashingDatabase {
             params![],
         )?;
 
-        Ok(Self { conn_pool })
+        Ok(Self::from_conn_pool(conn_pool))
     }
 
     /// Open an existing `SlashingDatabase` from disk.
     pub fn open(path: &Path) -> Result<Self, NotSafe> {
         let conn_pool = Self::open_conn_pool(&path)?;
-        Ok(Self { conn_pool })
+        Self::add_beacon_index_column(&conn_pool.get()?)?;
+        Ok(Self::from_conn_pool(conn_pool))
+    }
+
+    fn from_conn_pool(conn_pool: Pool) -> Self {
+        Self {
+            conn_pool,
+            validator_ids: Arc::new(RwLock::new(HashMap::new())),
+        }
     }
 
     /// Open a new connection pool with all of the necessary settings and tweaks.
@@ -111,6 +178,7 @@ impl SlashingDatabase {
     fn apply_pragmas(conn: &mut rusqlite::Connection) -> Result<(), rusqlite::Error> {
         conn.pragma_update(None, "foreign_keys", &true)?;
         conn.pragma_update(None, "locking_mode", &"EXCLUSIVE")?;
+        conn.set_prepared_statement_cache_capacity(STATEMENT_CACHE_CAPACITY);
         Ok(())
     }
 
@@ -129,6 +197,31 @@ impl SlashingDatabase {
     #[cfg(windows)]
     fn set_db_file_permissions(file: &File) -> Result<(), NotSafe> {}
 
+    /// Rebuild the database file, returning the space freed by deleted records to the filesystem.
+    ///
+    /// `VACUUM` cannot run inside a transaction, so this waits for the pooled connection and
+    /// fails with `NotSafe::SQLPoolError` if it remains in use for longer than the connection
+    /// timeout.
+    pub fn vacuum(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        conn.execute("VACUUM", params![])?;
+        Ok(())
+    }
+
+    /// Return the free pages of a database created with `AutoVacuum::Incremental` to the
+    /// filesystem, without rebuilding the file. Has no effect in other modes.
+    ///
+    /// As for `vacuum`, this fails with `NotSafe::SQLPoolError` if the pooled connection remains
+    /// in use for longer than the connection timeout.
+    pub fn incremental_vacuum(&self) -> Result<(), NotSafe> {
+        let conn = self.conn_pool.get()?;
+        // Step the pragma to completion, whether or not it returns rows.
+        let mut stmt = conn.prepare("PRAGMA incremental_vacuum")?;
+        let mut rows = stmt.query(params![])?;
+        while rows.next()?.is_some() {}
+        Ok(())
+    }
+
     /// Register a validator with the slashing protection database.
     ///
     /// This allows the validator to record their signatures in the database, and check
@@ -142,46 +235,197 @@ impl SlashingDatabase {
         &self,
         public_keys: impl Iterator<Item = &'a PublicKey>,
     ) -> Result<(), NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction()?;
-        {
-            let mut stmt = txn.prepare("INSERT INTO validators (public_key) VALUES (?1)")?;
+        self.with_transaction(|txn| self.register_validators_in_txn(public_keys, txn))
+    }
 
-            for pubkey in public_keys {
-                stmt.execute(&[pubkey.to_hex_string()])?;
-            }
+    /// Register multiple validators within an existing transaction, e.g., one opened by
+    /// `with_transaction`.
+    pub fn register_validators_in_txn<'a>(
+        &self,
+        public_keys: impl Iterator<Item = &'a PublicKey>,
+        txn: &Transaction,
+    ) -> Result<(), NotSafe> {
+        let mut stmt = txn.prepare_cached("INSERT INTO validators (public_key) VALUES (?1)")?;
+
+        for pubkey in public_keys {
+            stmt.execute(&[pubkey.to_hex_string()])?;
+            // Cache the ID that lookups will return, which belongs to the first registration of
+            // a key that is registered more than once.
+            self.get_validator_id_in_txn(txn, pubkey)?;
         }
-        txn.commit()?;
 
         Ok(())
     }
 
+    /// Run `f` within an exclusive transaction, committing if it returns `Ok` and rolling back
+    /// all of its changes if it returns `Err`.
+    ///
+    /// This allows several operations to be applied atomically. When checking and inserting
+    /// signed data, a check is only valid for an insert made in the *same* transaction, otherwise
+    /// a concurrent insert could make the checked data slashable.
+    pub fn with_transaction<T, F>(&self, f: F) -> Result<T, NotSafe>
+    where
+        F: FnOnce(&Transaction) -> Result<T, NotSafe>,
+    {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+
+        // Dropping the transaction without committing rolls it back.
+        let result = f(&txn).and_then(|value| {
+            txn.commit()?;
+            Ok(value)
+        });
+
+        // IDs of validators registered in a rolled back transaction no longer exist.
+        if result.is_err() {
+            self.validator_ids.write().clear();
+        }
+
+        result
+    }
+
+    /// Get the public keys of all registered validators, in the order they were first registered.
+    ///
+    /// Keys registered more than once are only listed once.
+    pub fn registered_validators(&self) -> Result<Vec<PublicKey>, NotSafe> {
+        let conn = self.conn_pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT public_key FROM validators
+             GROUP BY public_key
+             ORDER BY MIN(id)",
+        )?;
+
+        let public_keys = stmt
+            .query_map(params![], |row| {
+                let hex: String = row.get(0)?;
+                PublicKey::from_hex_str(&hex).map_err(|e| {
+                    rusqlite::Error::FromSqlConversionFailure(
+                        0,
+                        rusqlite::types::Type::Text,
+                        Box::from(format!("Invalid public key: {:?}", e)),
+                    )
+                })
+            })?
+            .collect::<Result<_, _>>()?;
+
+        Ok(public_keys)
+    }
+
+    /// Check whether a validator is registered with the slashing protection database.
+    pub fn contains_validator(&self, public_key: &PublicKey) -> Result<bool, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+
+        match self.get_validator_id_in_txn(&txn, public_key) {
+            Ok(_) => Ok(true),
+            Err(NotSafe::UnregisteredValidator(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
     /// Get the database-internal ID for a validator.
     ///
     /// This is NOT the same as a validator index, and depends on the ordering that validators
     /// are registered with the slashing protection database (and may vary between machines).
-    fn get_validator_id(txn: &Transaction, public_key: &PublicKey) -> Result<i64, NotSafe> {
-        txn.query_row(
-            "SELECT id FROM validators WHERE public_key = ?1",
-            params![&public_key.to_hex_string()],
-            |row| row.get(0),
-        )
-        .optional()?
-        .ok_or_else(|| NotSafe::UnregisteredValidator(public_key.clone()))
+    ///
+    /// IDs are cached after the first lookup, but a validator missing from the cache is always
+    /// looked up in the database.
+    fn get_validator_id_in_txn(
+        &self,
+        txn: &Transaction,
+        public_key: &PublicKey,
+    ) -> Result<i64, NotSafe> {
+        if let Some(&id) = self.validator_ids.read().get(public_key) {
+            return Ok(id);
+        }
+
+        let id = txn
+            .prepare_cached("SELECT id FROM validators WHERE public_key = ?1")?
+            .query_row(params![&public_key.to_hex_string()], |row| row.get(0))
+            .optional()?
+            .ok_or_else(|| NotSafe::UnregisteredValidator(public_key.clone()))?;
+
+        self.validator_ids.write().insert(public_key.clone(), id);
+        Ok(id)
+    }
+
+    /// Add the nullable `beacon_index` column to the `validators` table of a database created
+    /// before the column existed.
+    fn add_beacon_index_column(conn: &rusqlite::Connection) -> Result<(), NotSafe> {
+        let exists = conn
+            .prepare("SELECT 1 FROM pragma_table_info('validators') WHERE name = 'beacon_index'")?
+            .exists(params![])?;
+
+        if !exists {
+            conn.execute(
+                "ALTER TABLE validators ADD COLUMN beacon_index INTEGER",
+                params![],
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Record the beacon chain index of a registered validator.
+    ///
+    /// The index is purely informational, e.g., for correlating records with on-chain data.
+    /// Slashing protection is always keyed by public key and never consults the index.
+    ///
+    /// If a different index was already recorded for the validator it is overwritten, and the
+    /// previous index is returned so that the caller can warn about the conflict.
+    pub fn set_validator_index(
+        &self,
+        public_key: &PublicKey,
+        index: u64,
+    ) -> Result<Option<u64>, NotSafe> {
+        let stored_index =
+            i64::try_from(index).map_err(|_| NotSafe::InvalidValidatorIndex(index))?;
+
+        self.with_transaction(|txn| {
+            let validator_id = self.get_validator_id_in_txn(txn, public_key)?;
+
+            let previous = txn.query_row(
+                "SELECT beacon_index FROM validators WHERE id = ?1",
+                params![validator_id],
+                |row| validator_index_from_row(0, row),
+            )?;
+            txn.execute(
+                "UPDATE validators SET beacon_index = ?1 WHERE id = ?2",
+                params![stored_index, validator_id],
+            )?;
+
+            Ok(previous.filter(|&previous| previous != index))
+        })
+    }
+
+    /// Get the beacon chain index recorded for a registered validator, if any.
+    pub fn validator_index(&self, public_key: &PublicKey) -> Result<Option<u64>, NotSafe> {
+        let mut conn = self.conn_pool.get()?;
+        let txn = conn.transaction()?;
+        let validator_id = self.get_validator_id_in_txn(&txn, public_key)?;
+
+        let index = txn.query_row(
+            "SELECT beacon_index FROM validators WHERE id = ?1",
+            params![validator_id],
+            |row| validator_index_from_row(0, row),
+        )?;
+        Ok(index)
     }
 
     /// Check a block proposal from `validator_pubkey` for slash safety.
-    fn check_block_proposal(
+    ///
+    /// If the proposal is safe, it must be inserted with `insert_block_proposal` in the same
+    /// transaction before it is signed.
+    pub fn check_block_proposal(
         &self,
         txn: &Transaction,
         validator_pubkey: &PublicKey,
         block_header: &BeaconBlockHeader,
         domain: Hash256,
     ) -> Result<Safe, NotSafe> {
-        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let validator_id = self.get_validator_id_in_txn(txn, validator_pubkey)?;
 
         let existing_block = txn
-            .prepare(
+            .prepare_cached(
                 "SELECT slot, signing_root
                  FROM signed_blocks
                  WHERE validator_id = ?1 AND slot = ?2",
@@ -208,7 +452,10 @@ impl SlashingDatabase {
     }
 
     /// Check an attestation from `validator_pubkey` for slash safety.
-    fn check_attestation(
+    ///
+    /// If the attestation is safe, it must be inserted with `insert_attestation` in the same
+    /// transaction before it is signed.
+    pub fn check_attestation(
         &self,
         txn: &Transaction,
         validator_pubkey: &PublicKey,
@@ -226,12 +473,12 @@ impl SlashingDatabase {
             ));
         }
 
-        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let validator_id = self.get_validator_id_in_txn(txn, validator_pubkey)?;
 
         // 1. Check for a double vote. Namely, an existing attestation with the same target epoch,
         //    and a different signing root.
         let same_target_att = txn
-            .prepare(
+            .prepare_cached(
                 "SELECT source_epoch, target_epoch, signing_root
                  FROM signed_attestations
                  WHERE validator_id = ?1 AND target_epoch = ?2",
@@ -258,7 +505,7 @@ impl SlashingDatabase {
         // 2. Check that no previous vote is surrounding `attestation`.
         // If there is a surrounding attestation, we only return the most recent one.
         let surrounding_attestation = txn
-            .prepare(
+            .prepare_cached(
                 "SELECT source_epoch, target_epoch, signing_root
                  FROM signed_attestations
                  WHERE validator_id = ?1 AND source_epoch < ?2 AND target_epoch > ?3
@@ -280,7 +527,7 @@ impl SlashingDatabase {
         // 3. Check that no previous vote is surrounded by `attestation`.
         // If there is a surrounded attestation, we only return the most recent one.
         let surrounded_attestation = txn
-            .prepare(
+            .prepare_cached(
                 "SELECT source_epoch, target_epoch, signing_root
                  FROM signed_attestations
                  WHERE validator_id = ?1 AND source_epoch > ?2 AND target_epoch < ?3
@@ -307,24 +554,24 @@ impl SlashingDatabase {
     ///
     /// This should *only* be called in the same (exclusive) transaction as `check_block_proposal`
     /// so that the check isn't invalidated by a concurrent mutation.
-    fn insert_block_proposal(
+    pub fn insert_block_proposal(
         &self,
         txn: &Transaction,
         validator_pubkey: &PublicKey,
         block_header: &BeaconBlockHeader,
         domain: Hash256,
     ) -> Result<(), NotSafe> {
-        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let validator_id = self.get_validator_id_in_txn(txn, validator_pubkey)?;
 
-        txn.execute(
+        txn.prepare_cached(
             "INSERT INTO signed_blocks (validator_id, slot, signing_root)
              VALUES (?1, ?2, ?3)",
-            params![
-                validator_id,
-                block_header.slot,
-                block_header.signing_root(domain).as_bytes()
-            ],
-        )?;
+        )?
+        .execute(params![
+            validator_id,
+            block_header.slot,
+            block_header.signing_root(domain).as_bytes()
+        ])?;
         Ok(())
     }
 
@@ -332,25 +579,25 @@ impl SlashingDatabase {
     ///
     /// This should *only* be called in the same (exclusive) transaction as `check_attestation`
     /// so that the check isn't invalidated by a concurrent mutation.
-    fn insert_attestation(
+    pub fn insert_attestation(
         &self,
         txn: &Transaction,
         validator_pubkey: &PublicKey,
         attestation: &AttestationData,
         domain: Hash256,
     ) -> Result<(), NotSafe> {
-        let validator_id = Self::get_validator_id(txn, validator_pubkey)?;
+        let validator_id = self.get_validator_id_in_txn(txn, validator_pubkey)?;
 
-        txn.execute(
+        txn.prepare_cached(
             "INSERT INTO signed_attestations (validator_id, source_epoch, target_epoch, signing_root)
              VALUES (?1, ?2, ?3, ?4)",
-            params![
-                validator_id,
-                attestation.source.epoch,
-                attestation.target.epoch,
-                attestation.signing_root(domain).as_bytes()
-            ],
-        )?;
+        )?
+        .execute(params![
+            validator_id,
+            attestation.source.epoch,
+            attestation.target.epoch,
+            attestation.signing_root(domain).as_bytes()
+        ])?;
         Ok(())
     }
 
@@ -366,17 +613,15 @@ impl SlashingDatabase {
         block_header: &BeaconBlockHeader,
         domain: Hash256,
     ) -> Result<Safe, NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        self.with_transaction(|txn| {
+            let safe = self.check_block_proposal(txn, validator_pubkey, block_header, domain)?;
 
-        let safe = self.check_block_proposal(&txn, validator_pubkey, block_header, domain)?;
-
-        if safe != Safe::SameData {
-            self.insert_block_proposal(&txn, validator_pubkey, block_header, domain)?;
-        }
+            if safe != Safe::SameData {
+                self.insert_block_proposal(txn, validator_pubkey, block_header, domain)?;
+            }
 
-        txn.commit()?;
-        Ok(safe)
+            Ok(safe)
+        })
     }
 
     /// Check an attestation for slash safety, and if it is safe, record it in the database.
@@ -391,24 +636,24 @@ impl SlashingDatabase {
         attestation: &AttestationData,
         domain: Hash256,
     ) -> Result<Safe, NotSafe> {
-        let mut conn = self.conn_pool.get()?;
-        let txn = conn.transaction_with_behavior(TransactionBehavior::Exclusive)?;
+        self.with_transaction(|txn| {
+            let safe = self.check_attestation(txn, validator_pubkey, attestation, domain)?;
 
-        let safe = self.check_attestation(&txn, validator_pubkey, attestation, domain)?;
-
-        if safe != Safe::SameData {
-            self.insert_attestation(&txn, validator_pubkey, attestation, domain)?;
-        }
+            if safe != Safe::SameData {
+                self.insert_attestation(txn, validator_pubkey, attestation, domain)?;
+            }
 
-        txn.commit()?;
-        Ok(safe)
+            Ok(safe)
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::pubkey;
+    use crate::attestation_tests::attestation_data_builder;
+    use crate::block_tests::block;
+    use crate::test_utils::{pubkey, DEFAULT_DOMAIN};
     use tempfile::tempdir;
 
     #[test]
@@ -425,8 +670,8 @@ mod tests {
         let file = dir.path().join("db.sqlite");
         let _db1 = SlashingDatabase::create(&file).unwrap();
 
-        let db2 = SlashingDatabase::open(&file).unwrap();
-        db2.register_validator(&pubkey(0)).unwrap_err();
+        // Opening reads the schema, which requires the lock.
+        SlashingDatabase::open(&file).unwrap_err();
     }
 
     // Attempting to create the same database twice should error.
@@ -439,6 +684,390 @@ mod tests {
         SlashingDatabase::create(&file).unwrap_err();
     }
 
+    // Public keys are stored in their canonical hex form, which can be parsed back out.
+    #[test]
+    fn stored_public_key_round_trip() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let conn = db.conn_pool.get().unwrap();
+        let stored: String = conn
+            .query_row("SELECT public_key FROM validators", params![], |row| {
+                row.get(0)
+            })
+            .unwrap();
+
+        assert_eq!(stored, pubkey(0).to_hex_string());
+        assert_eq!(PublicKey::from_hex_str(&stored).unwrap(), pubkey(0));
+    }
+
+    #[test]
+    fn set_validator_index() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+        db.register_validator(&pubkey(1)).unwrap();
+
+        assert_eq!(db.validator_index(&pubkey(0)).unwrap(), None);
+
+        // Setting an index, or re-setting the same index, reports no conflict.
+        assert_eq!(db.set_validator_index(&pubkey(0), 42).unwrap(), None);
+        assert_eq!(db.set_validator_index(&pubkey(0), 42).unwrap(), None);
+        assert_eq!(db.validator_index(&pubkey(0)).unwrap(), Some(42));
+        assert_eq!(db.validator_index(&pubkey(1)).unwrap(), None);
+
+        // A conflicting index overwrites the old one, which is returned.
+        assert_eq!(db.set_validator_index(&pubkey(0), 43).unwrap(), Some(42));
+        assert_eq!(db.validator_index(&pubkey(0)).unwrap(), Some(43));
+
+        assert_eq!(
+            db.set_validator_index(&pubkey(2), 0),
+            Err(NotSafe::UnregisteredValidator(pubkey(2)))
+        );
+        assert_eq!(
+            db.set_validator_index(&pubkey(1), u64::max_value()),
+            Err(NotSafe::InvalidValidatorIndex(u64::max_value()))
+        );
+        assert_eq!(db.validator_index(&pubkey(1)).unwrap(), None);
+    }
+
+    #[test]
+    fn registered_validators() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+
+        assert!(db.registered_validators().unwrap().is_empty());
+        assert!(!db.contains_validator(&pubkey(0)).unwrap());
+
+        db.register_validator(&pubkey(2)).unwrap();
+        db.register_validators([pubkey(0), pubkey(1), pubkey(2)].iter())
+            .unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        assert_eq!(
+            db.registered_validators().unwrap(),
+            vec![pubkey(2), pubkey(0), pubkey(1)]
+        );
+        for i in 0..3 {
+            assert!(db.contains_validator(&pubkey(i)).unwrap());
+        }
+        assert!(!db.contains_validator(&pubkey(3)).unwrap());
+    }
+
+    /// Inserts and then deletes a large batch of blocks, leaving free pages in the file.
+    fn insert_and_delete_blocks(db: &SlashingDatabase) {
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let mut conn = db.conn_pool.get().unwrap();
+        let txn = conn.transaction().unwrap();
+        for slot in 0..10_000i64 {
+            txn.execute(
+                "INSERT INTO signed_blocks (validator_id, slot, signing_root) VALUES (1, ?1, ?2)",
+                params![slot, Hash256::repeat_byte(1).as_bytes()],
+            )
+            .unwrap();
+        }
+        txn.commit().unwrap();
+        conn.execute("DELETE FROM signed_blocks", params![])
+            .unwrap();
+    }
+
+    #[test]
+    fn vacuum_shrinks_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        insert_and_delete_blocks(&db);
+
+        let size_before = std::fs::metadata(&file).unwrap().len();
+        db.vacuum().unwrap();
+        let size_after = std::fs::metadata(&file).unwrap().len();
+
+        assert!(
+            size_after * 4 < size_before,
+            "file should shrink after vacuum: {} -> {}",
+            size_before,
+            size_after
+        );
+    }
+
+    #[test]
+    fn incremental_vacuum_shrinks_file() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let options = DatabaseOptions {
+            auto_vacuum: AutoVacuum::Incremental,
+        };
+        let db = SlashingDatabase::create_with_options(&file, options).unwrap();
+        insert_and_delete_blocks(&db);
+
+        let conn = db.conn_pool.get().unwrap();
+        assert_eq!(
+            conn.pragma_query_value(None, "auto_vacuum", |row| row.get::<_, i64>(0))
+                .unwrap(),
+            2,
+            "should be INCREMENTAL"
+        );
+        drop(conn);
+
+        let size_before = std::fs::metadata(&file).unwrap().len();
+        db.incremental_vacuum().unwrap();
+        let size_after = std::fs::metadata(&file).unwrap().len();
+
+        assert!(
+            size_after * 4 < size_before,
+            "file should shrink after incremental vacuum: {} -> {}",
+            size_before,
+            size_after
+        );
+    }
+
+    // Vacuuming while another operation holds the only connection should error, not block.
+    #[test]
+    fn vacuum_while_in_use_error() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+
+        let mut conn = db.conn_pool.get().unwrap();
+        let _txn = conn.transaction().unwrap();
+
+        assert!(matches!(db.vacuum(), Err(NotSafe::SQLPoolError(_))));
+    }
+
+    // Operations composed with `with_transaction` are committed or rolled back together.
+    #[test]
+    fn with_transaction_atomic() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        let pubkeys = [pubkey(0), pubkey(1)];
+        let blocks = [block(0), block(1)];
+
+        let register_and_insert = |fail: bool| {
+            db.with_transaction(|txn| {
+                db.register_validators_in_txn(pubkeys.iter(), txn)?;
+                for (pubkey, block) in pubkeys.iter().zip(blocks.iter()) {
+                    assert_eq!(
+                        db.check_block_proposal(txn, pubkey, block, DEFAULT_DOMAIN)?,
+                        Safe::Valid
+                    );
+                    db.insert_block_proposal(txn, pubkey, block, DEFAULT_DOMAIN)?;
+                }
+                if fail {
+                    Err(NotSafe::SQLError("injected failure".to_string()))
+                } else {
+                    Ok(())
+                }
+            })
+        };
+
+        assert_eq!(
+            register_and_insert(true),
+            Err(NotSafe::SQLError("injected failure".to_string()))
+        );
+        assert!(db.registered_validators().unwrap().is_empty());
+        let conn = db.conn_pool.get().unwrap();
+        let num_blocks: i64 = conn
+            .query_row("SELECT COUNT(*) FROM signed_blocks", params![], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(num_blocks, 0);
+        drop(conn);
+
+        register_and_insert(false).unwrap();
+        assert_eq!(db.registered_validators().unwrap(), pubkeys.to_vec());
+        for (pubkey, block) in pubkeys.iter().zip(blocks.iter()) {
+            assert_eq!(
+                db.check_and_insert_block_proposal(pubkey, block, DEFAULT_DOMAIN),
+                Ok(Safe::SameData)
+            );
+        }
+    }
+
+    // Validators registered within a transaction are cached with the ID stored in the database.
+    #[test]
+    fn validator_id_cached_on_registration_in_txn() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        let pubkeys = [pubkey(0), pubkey(1), pubkey(0)];
+
+        db.with_transaction(|txn| db.register_validators_in_txn(pubkeys.iter(), txn))
+            .unwrap();
+        assert_eq!(db.validator_ids.read().len(), 2);
+
+        let conn = db.conn_pool.get().unwrap();
+        for pubkey in &pubkeys[..2] {
+            let id: i64 = conn
+                .query_row(
+                    "SELECT MIN(id) FROM validators WHERE public_key = ?1",
+                    params![pubkey.to_hex_string()],
+                    |row| row.get(0),
+                )
+                .unwrap();
+            assert_eq!(db.validator_ids.read().get(pubkey), Some(&id));
+        }
+        drop(conn);
+
+        let header = block(1);
+        assert_eq!(
+            db.check_and_insert_block_proposal(&pubkey(0), &header, DEFAULT_DOMAIN),
+            Ok(Safe::Valid)
+        );
+        assert_eq!(
+            db.check_and_insert_block_proposal(&pubkey(0), &header, DEFAULT_DOMAIN),
+            Ok(Safe::SameData)
+        );
+    }
+
+    // A rolled back transaction invalidates the cache, and misses fall back to the database.
+    #[test]
+    fn validator_id_cache_invalidated() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        let db = SlashingDatabase::create(&file).unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+
+        let result: Result<(), _> = db.with_transaction(|txn| {
+            db.register_validators_in_txn(std::iter::once(&pubkey(1)), txn)?;
+            Err(NotSafe::SQLError("injected failure".to_string()))
+        });
+        assert!(result.is_err());
+        assert!(db.validator_ids.read().is_empty());
+
+        // The validator from the rolled back transaction is not found, even though it was
+        // briefly cached.
+        assert!(!db.contains_validator(&pubkey(1)).unwrap());
+        assert_eq!(
+            db.check_and_insert_block_proposal(&pubkey(1), &block(1), DEFAULT_DOMAIN),
+            Err(NotSafe::UnregisteredValidator(pubkey(1)))
+        );
+
+        // The committed validator is looked up in the database and cached again.
+        assert!(db.contains_validator(&pubkey(0)).unwrap());
+        assert_eq!(db.validator_ids.read().get(&pubkey(0)), Some(&1));
+        assert_eq!(
+            db.check_and_insert_block_proposal(&pubkey(0), &block(1), DEFAULT_DOMAIN),
+            Ok(Safe::Valid)
+        );
+    }
+
+    // Caching prepared statements does not change the result of any check.
+    #[test]
+    fn cached_statements_same_results() {
+        let dir = tempdir().unwrap();
+        // Pairs of conflicting blocks at each slot, shared so both runs sign identical data.
+        let blocks = (0..50).map(|i| block(i / 2)).collect::<Vec<_>>();
+        let run = |name: &str, cache_capacity: usize| {
+            let db = SlashingDatabase::create(&dir.path().join(name)).unwrap();
+            db.conn_pool
+                .get()
+                .unwrap()
+                .set_prepared_statement_cache_capacity(cache_capacity);
+            db.register_validators([pubkey(0), pubkey(1)].iter())
+                .unwrap();
+
+            let mut results = vec![];
+            for i in 0..2 {
+                let pubkey = pubkey(i);
+                for epoch in 1..50 {
+                    // Alternate between valid votes, double votes and surround votes.
+                    let (source, target) = match epoch % 3 {
+                        0 => (epoch - 1, epoch),
+                        1 => (epoch - 1, epoch - 1),
+                        _ => (0, epoch + 1),
+                    };
+                    let attestation = attestation_data_builder(source, target);
+                    results.push(db.check_and_insert_attestation(
+                        &pubkey,
+                        &attestation,
+                        DEFAULT_DOMAIN,
+                    ));
+                }
+                for block in &blocks {
+                    results.push(db.check_and_insert_block_proposal(
+                        &pubkey,
+                        block,
+                        DEFAULT_DOMAIN,
+                    ));
+                }
+            }
+            results
+        };
+
+        let cached = run("cached.sqlite", STATEMENT_CACHE_CAPACITY);
+        let uncached = run("uncached.sqlite", 0);
+        assert!(cached.iter().any(|result| result.is_ok()));
+        assert!(cached.iter().any(|result| result.is_err()));
+        assert_eq!(cached, uncached);
+    }
+
+    // Compare the time taken to check and insert attestations with and without the statement
+    // cache. Timings vary too much between machines to assert on, so they are only printed.
+    //
+    // Run with `cargo test --release -p slashing_protection -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn cached_statements_timing() {
+        use std::time::Instant;
+
+        let dir = tempdir().unwrap();
+        let num_attestations = 2_000;
+        let run = |name: &str, cache_capacity: usize| {
+            let db = SlashingDatabase::create(&dir.path().join(name)).unwrap();
+            db.conn_pool
+                .get()
+                .unwrap()
+                .set_prepared_statement_cache_capacity(cache_capacity);
+            db.register_validator(&pubkey(0)).unwrap();
+
+            let start = Instant::now();
+            for epoch in 1..=num_attestations {
+                let attestation = attestation_data_builder(epoch - 1, epoch);
+                db.check_and_insert_attestation(&pubkey(0), &attestation, DEFAULT_DOMAIN)
+                    .unwrap();
+            }
+            start.elapsed()
+        };
+
+        let uncached = run("uncached.sqlite", 0);
+        let cached = run("cached.sqlite", STATEMENT_CACHE_CAPACITY);
+        println!(
+            "{} attestations: uncached {:?}, cached {:?} (capacity {})",
+            num_attestations, uncached, cached, STATEMENT_CACHE_CAPACITY
+        );
+    }
+
+    // Databases created before the `beacon_index` column existed gain it when opened.
+    #[test]
+    fn set_validator_index_legacy_schema() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("db.sqlite");
+        {
+            let conn = rusqlite::Connection::open(&file).unwrap();
+            conn.execute(
+                "CREATE TABLE validators (
+                    id INTEGER PRIMARY KEY,
+                    public_key BLOB NOT NULL
+                )",
+                params![],
+            )
+            .unwrap();
+        }
+
+        let db = SlashingDatabase::open(&file).unwrap();
+        db.register_validator(&pubkey(0)).unwrap();
+        assert_eq!(db.validator_index(&pubkey(0)).unwrap(), None);
+        assert_eq!(db.set_validator_index(&pubkey(0), 7).unwrap(), None);
+        assert_eq!(db.validator_index(&pubkey(0)).unwrap(), Some(7));
+    }
+
     // Check that both `open` and `create` apply the same connection settings.
     #[test]
     fn connection_settings_applied() {
@@ -460,6 +1089,12 @@ mod tests {
                     .to_uppercase(),
                 "EXCLUSIVE"
             );
+            // 0 is `NONE`, the default.
+            assert_eq!(
+                conn.pragma_query_value(None, "auto_vacuum", |row| { row.get::<_, i64>(0) })
+                    .unwrap(),
+                0
+            );
         };
 
         let db1 = SlashingDatabase::create(&file).unwrap();
@@ -630,6 +630,11 @@ impl<T: SlotClock + 'static, E: EthSpec> DutiesService<T, E> {
                             "validator" => format!("{:?}", &remote_duties.validator_pubkey)
                         );
                         new_validator += 1;
+
+                        if let Some(validator_index) = remote_duties.validator_index {
+                            self.validator_store
+                                .record_validator_index(&validator_pubkey, validator_index);
+                        }
                     }
                     InsertOutcome::NewProposalSlots => new_proposal_slots += 1,
                     InsertOutcome::NewEpoch => new_epoch += 1,
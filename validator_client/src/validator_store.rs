@@ -93,6 +93,31 @@ impl<T: SlotClock + 'static, E: EthSpec> ValidatorStore<T, E> {
             .map_err(|e| format!("Error while registering validators: {:?}", e))
     }
 
+    /// Record the beacon chain index of a validator with the slashing protection database,
+    /// warning if it differs from the index previously recorded for that validator.
+    pub fn record_validator_index(&self, validator_pubkey: &PublicKey, validator_index: u64) {
+        match self
+            .slashing_protection
+            .set_validator_index(validator_pubkey, validator_index)
+        {
+            Ok(None) => (),
+            Ok(Some(previous_index)) => warn!(
+                self.log,
+                "Validator index changed";
+                "msg" => "The slashing protection database may be from another network",
+                "previous_index" => previous_index,
+                "new_index" => validator_index,
+                "public_key" => format!("{:?}", validator_pubkey)
+            ),
+            Err(e) => error!(
+                self.log,
+                "Unable to record validator index";
+                "error" => format!("{:?}", e),
+                "public_key" => format!("{:?}", validator_pubkey)
+            ),
+        }
+    }
+
     pub fn voting_pubkeys(&self) -> Vec<PublicKey> {
         self.validators
             .read()